@@ -333,6 +333,14 @@ export declare class JsCompiler {
   setNonSkippableRegisters(kinds: Array<RegisterJsTapKind>): void
   /** Build with the given option passed to the constructor */
   build(callback: (err: null | Error) => void): void
+  /**
+   * Load the clock saved by the native watcher, the files changed since then are used
+   * as the modified and removed files of the next build instead of validating the whole
+   * persistent cache snapshot.
+   *
+   * Returns false if the clock file does not exist or is invalid.
+   */
+  loadWatcherClock(clockFile: string): boolean
   /** Rebuild with the given option passed to the constructor */
   rebuild(changed_files: string[], removed_files: string[], callback: (err: null | Error) => void): void
   close(): Promise<void>
@@ -430,6 +438,8 @@ export declare class JsResolverFactory {
 export declare class JsStats {
   toJson(jsOptions: JsStatsOptions): JsStatsCompilation
   getLogging(acceptedTypes: number): Array<JsStatsLogging>
+  /** The paths which invalidated the persistent cache on the cold start. */
+  getCacheInvalidation(): JsStatsCacheInvalidation | undefined
 }
 
 export declare class KnownBuildInfo {
@@ -557,10 +567,12 @@ export declare enum BuiltinPluginName {
   NamedModuleIdsPlugin = 'NamedModuleIdsPlugin',
   NaturalModuleIdsPlugin = 'NaturalModuleIdsPlugin',
   DeterministicModuleIdsPlugin = 'DeterministicModuleIdsPlugin',
+  HashedModuleIdsPlugin = 'HashedModuleIdsPlugin',
   NaturalChunkIdsPlugin = 'NaturalChunkIdsPlugin',
   NamedChunkIdsPlugin = 'NamedChunkIdsPlugin',
   DeterministicChunkIdsPlugin = 'DeterministicChunkIdsPlugin',
   OccurrenceChunkIdsPlugin = 'OccurrenceChunkIdsPlugin',
+  RecordIdsPlugin = 'RecordIdsPlugin',
   RealContentHashPlugin = 'RealContentHashPlugin',
   RemoveEmptyChunksPlugin = 'RemoveEmptyChunksPlugin',
   EnsureChunkConditionsPlugin = 'EnsureChunkConditionsPlugin',
@@ -606,6 +618,7 @@ export declare enum BuiltinPluginName {
   RslibPlugin = 'RslibPlugin',
   CircularDependencyRspackPlugin = 'CircularDependencyRspackPlugin',
   URLPlugin = 'URLPlugin',
+  HookTimingPlugin = 'HookTimingPlugin',
   JsLoaderRspackPlugin = 'JsLoaderRspackPlugin',
   LazyCompilationPlugin = 'LazyCompilationPlugin',
   ModuleInfoHeaderPlugin = 'ModuleInfoHeaderPlugin',
@@ -1311,6 +1324,11 @@ export interface JsStatsAssetsByChunkName {
   files: Array<string>
 }
 
+export interface JsStatsCacheInvalidation {
+  buildDependencies: Array<JsStatsInvalidatedPath>
+  paths: Array<JsStatsInvalidatedPath>
+}
+
 export interface JsStatsChildGroupChildAssets {
   preload?: Array<string>
   prefetch?: Array<string>
@@ -1394,6 +1412,14 @@ export interface JsStatsGetAssets {
   assetsByChunkName: Array<JsStatsAssetsByChunkName>
 }
 
+export interface JsStatsInvalidatedPath {
+  path: string
+  scope: 'file' | 'context' | 'missing' | 'build'
+  strategy: 'packageVersion' | 'fileHash' | 'dirHash' | 'missing' | 'failed' | 'fileContentHash'
+  kind: 'modified' | 'deleted'
+  modules: Array<string>
+}
+
 export interface JsStatsLogging {
   name: string
   type: string
@@ -1510,6 +1536,8 @@ export interface JsStatsSize {
 export interface JsTap {
   function: (...args: any[]) => any
   stage: number
+  /** The names of the JavaScript plugins called by this tap, used in the hook timings */
+  name?: string
 }
 
 export interface JsVirtualFile {
@@ -1736,11 +1764,30 @@ export interface NativeWatcherOptions {
   followSymlinks?: boolean
   pollInterval?: number
   aggregateTimeout?: number
+  /** The aggregate timeout of the generated files, defaults to `aggregateTimeout`. */
+  generatedAggregateTimeout?: number
+  /** The poll interval of the paths matching the poll rules. */
+  fallbackPollInterval?: number
+  /** The include, exclude, poll and generated rules of watched paths. */
+  rules?: NativeWatcherRules
   /**
    * The ignored paths for the watcher.
    * It can be a single path, an array of paths, or a regular expression.
    */
   ignored?: string | string[] | RegExp
+  /** The file to save the clock of watched paths to each time watching starts. */
+  clockFile?: string
+}
+
+export interface NativeWatcherRules {
+  /** Only the files and missing paths matching one of them are watched. */
+  include?: Array<string | RegExp>
+  /** The paths matching one of them are not watched. */
+  exclude?: Array<string | RegExp>
+  /** The paths matching one of them are watched by polling, e.g. the paths on network mounts. */
+  poll?: Array<string | RegExp>
+  /** The paths matching one of them are generated files which use `generatedAggregateTimeout`. */
+  generated?: Array<string | RegExp>
 }
 
 export interface NodeFsStats {
@@ -1856,6 +1903,7 @@ export interface RawCacheGroupOptions {
 
 export interface RawCacheOptionsMemory {
   maxGenerations?: number
+  maxSize?: number
 }
 
 export interface RawCacheOptionsPersistent {
@@ -1866,6 +1914,13 @@ export interface RawCacheOptionsPersistent {
   portable?: boolean
 }
 
+export interface RawChunkCostModel {
+  type: 'size' | 'loadTime'
+  compressionRatio?: number
+  requestLatency?: number
+  bandwidth?: number
+}
+
 export interface RawCircularDependencyRspackPluginOptions {
   failOnError?: boolean
   exclude?: RegExp
@@ -1876,6 +1931,12 @@ export interface RawCircularDependencyRspackPluginOptions {
   onEnd?: () => void
 }
 
+export interface RawCoLoadingOptions {
+  loadSets: Array<RawLoadSet>
+  /** The cost of one more request in a navigation, in bytes */
+  requestCost?: number
+}
+
 export interface RawCompilerPlatform {
   web?: boolean | null
   browser?: boolean | null
@@ -2050,6 +2111,7 @@ export interface RawCssAutoParserOptions {
 
 export interface RawCssChunkingPluginOptions {
   strict?: boolean
+  alignWithJs?: boolean
   minSize?: number
   maxSize?: number
   exclude?: RegExp
@@ -2238,6 +2300,17 @@ export interface RawGeneratorOptions {
   json?: RawJsonGeneratorOptions
 }
 
+export interface RawHashedModuleIdsPluginOptions {
+  context?: string
+  hashFunction?: string
+  hashDigest?: string
+  hashDigestLength?: number
+}
+
+export interface RawHookTimingPluginOptions {
+  filename?: string
+}
+
 export interface RawHtmlRspackPluginBaseOptions {
   href?: string
   target?: "_self" | "_blank" | "_parent" | "_top"
@@ -2464,6 +2537,14 @@ export interface RawLimitChunkCountPluginOptions {
   chunkOverhead?: number
   entryChunkMultiplicator?: number
   maxChunks: number
+  maxChunksPerRoute?: number
+  costModel?: RawChunkCostModel
+}
+
+export interface RawLoadSet {
+  chunks?: Array<string>
+  modules?: Array<string>
+  frequency?: number
 }
 
 export interface RawManifestExposeOption {
@@ -2676,6 +2757,7 @@ export interface RawOutputOptions {
   scriptType: "module" | "text/javascript" | false
   environment: RawEnvironment
   compareBeforeEmit: boolean
+  atomicEmit: boolean
 }
 
 export interface RawParserOptions {
@@ -2714,6 +2796,10 @@ export interface RawProvideOptions {
   strictVersion?: boolean
 }
 
+export interface RawRecordIdsPluginOptions {
+  path: string
+}
+
 export interface RawRelated {
   sourceMap?: string
 }
@@ -2857,6 +2943,7 @@ export interface RawSnapshotOptions {
   immutablePaths: Array<string|RegExp>
   unmanagedPaths: Array<string|RegExp>
   managedPaths: Array<string|RegExp>
+  contentHashOnly?: boolean
 }
 
 export interface RawSplitChunkSizes {
@@ -2884,6 +2971,7 @@ export interface RawSplitChunksOptions {
   maxSize?: number | RawSplitChunkSizes
   maxAsyncSize?: number | RawSplitChunkSizes
   maxInitialSize?: number | RawSplitChunkSizes
+  coLoading?: RawCoLoadingOptions
 }
 
 export interface RawStatsBuildInfo {
@@ -2896,8 +2984,21 @@ export interface RawStatsOptions {
 }
 
 export interface RawStorageOptions {
-  type: "filesystem"
-  directory: string
+  type: "filesystem" | "remote"
+  /** The cache directory of the filesystem storage */
+  directory?: string
+  /** The endpoint of the remote storage, either an http:// cache server or a shared directory */
+  endpoint?: string
+  /** The max size of a pack in bytes */
+  packSize?: number
+  /** The expiration of the cache in milliseconds */
+  expire?: number
+  /** The compression of the pack files of the filesystem storage */
+  compression?: "none" | "zstd" | "lz4"
+  /** The level of the zstd compression, 0 means the default level */
+  compressionLevel?: number
+  /** The access mode of the filesystem storage */
+  mode?: "read-write" | "read-only" | "write-only"
 }
 
 export interface RawSubresourceIntegrityPluginOptions {
@@ -3136,6 +3237,7 @@ export interface ThreadsafeNodeFS {
   readUntil: (fd: number, code: number, position: number) => Promise<Buffer | void>
   readToEnd: (fd: number, position: number) => Promise<Buffer | void>
   chmod?: (name: string, mode: number) => Promise<void>
+  fsync?: (name: string) => Promise<void>
 }
 
 export declare function transform(source: string, options: string): Promise<TransformOutput>
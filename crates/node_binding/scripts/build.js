@@ -85,6 +85,9 @@ async function build() {
 		if (process.env.TRACY) {
 			features.push("tracy-client");
 		}
+		if (process.env.MEMORY_COUNTERS) {
+			features.push("memory-counters");
+		}
 		if (values.profile === "release") {
 			features.push("info-level");
 			if (process.env.RUST_TARGET && !process.env.RUST_TARGET.includes("windows-msvc")) {
@@ -936,7 +936,10 @@ impl CompilerOptionsBuilder {
     let bail = d!(self.bail.take(), false);
     let cache = d!(self.cache.take(), {
       if development {
-        CacheOptions::Memory { max_generations: 1 }
+        CacheOptions::Memory {
+          max_generations: 1,
+          max_size: None,
+        }
       } else {
         CacheOptions::Disabled
       }
@@ -2212,6 +2215,8 @@ pub struct OutputOptionsBuilder {
   environment: Option<Environment>,
   /// Set the compare before emit.
   compare_before_emit: Option<bool>,
+  /// Set the atomic emit.
+  atomic_emit: Option<bool>,
 }
 
 impl From<OutputOptions> for OutputOptionsBuilder {
@@ -2261,6 +2266,7 @@ impl From<OutputOptions> for OutputOptionsBuilder {
       devtool_fallback_module_filename_template: None,
       environment: Some(value.environment),
       compare_before_emit: Some(value.compare_before_emit),
+      atomic_emit: Some(value.atomic_emit),
       enabled_chunk_loading_types: None,
       enabled_wasm_loading_types: None,
     }
@@ -2318,6 +2324,7 @@ impl From<&mut OutputOptionsBuilder> for OutputOptionsBuilder {
         .take(),
       environment: value.environment.take(),
       compare_before_emit: value.compare_before_emit.take(),
+      atomic_emit: value.atomic_emit.take(),
     }
   }
 }
@@ -2626,6 +2633,12 @@ impl OutputOptionsBuilder {
     self
   }
 
+  /// Set whether to emit into a staging directory and rename it over the output path.
+  pub fn atomic_emit(&mut self, atomic: bool) -> &mut Self {
+    self.atomic_emit = Some(atomic);
+    self
+  }
+
   /// Build [`OutputOptions`] from builder.
   ///
   /// [`OutputOptions`]: rspack_core::options::OutputOptions
@@ -3165,6 +3178,7 @@ impl OutputOptionsBuilder {
       script_type,
       environment,
       compare_before_emit: self.compare_before_emit.take().unwrap_or(true),
+      atomic_emit: self.atomic_emit.take().unwrap_or(false),
     })
   }
 }
@@ -52,4 +52,93 @@ mod tests {
     })
     .await;
   }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn basic_overlay_filesystem() {
+    use std::sync::Arc;
+
+    use rspack_fs::{NativeFileSystem, OverlayFileSystem, ReadableFileSystem, WritableFileSystem};
+    use rspack_tasks::within_compiler_context_for_testing;
+    within_compiler_context_for_testing(async {
+      // the whole project only exists in the overlay
+      let context = Utf8Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/overlay");
+      let fs = Arc::new(OverlayFileSystem::new(Arc::new(NativeFileSystem::new(
+        false,
+      ))));
+      WritableFileSystem::create_dir_all(fs.as_ref(), &context.join("src"))
+        .await
+        .unwrap();
+      WritableFileSystem::write(
+        fs.as_ref(),
+        &context.join("src/index.js"),
+        b"console.log(789);",
+      )
+      .await
+      .unwrap();
+
+      let mut compiler = Compiler::builder()
+        .context(context.clone())
+        .entry("main", "./src/index.js")
+        .input_filesystem(fs.clone())
+        .output_filesystem(fs.clone())
+        .build()
+        .unwrap();
+
+      compiler.build().await.unwrap();
+
+      let errors: Vec<_> = compiler.compilation.get_errors().collect();
+      assert!(errors.is_empty());
+
+      let output = ReadableFileSystem::read(fs.as_ref(), &context.join("dist/main.js"))
+        .await
+        .unwrap();
+      assert_eq!(output, b"console.log(789);");
+      assert!(!context.as_std_path().exists());
+    })
+    .await;
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn basic_replay_filesystem() {
+    use std::sync::Arc;
+
+    use rspack_fs::{
+      FsArchive, MemoryFileSystem, NativeFileSystem, RecordingFileSystem, ReplayFileSystem,
+    };
+    use rspack_tasks::within_compiler_context_for_testing;
+    within_compiler_context_for_testing(async {
+      let context = Utf8Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/basic");
+      let recording = Arc::new(RecordingFileSystem::new(Arc::new(NativeFileSystem::new(
+        false,
+      ))));
+      let mut compiler = Compiler::builder()
+        .context(context.clone())
+        .entry("main", "./src/index.js")
+        .input_filesystem(recording.clone())
+        .output_filesystem(Arc::new(MemoryFileSystem::default()))
+        .build()
+        .unwrap();
+      compiler.build().await.unwrap();
+      let archive = FsArchive::from_bytes(&recording.archive().to_bytes()).unwrap();
+      assert!(archive.paths().any(|p| p == context.join("src/index.js")));
+
+      let mut compiler = Compiler::builder()
+        .context(context)
+        .entry("main", "./src/index.js")
+        .input_filesystem(Arc::new(ReplayFileSystem::new(archive)))
+        .output_filesystem(Arc::new(MemoryFileSystem::default()))
+        .build()
+        .unwrap();
+      compiler.build().await.unwrap();
+
+      let errors: Vec<_> = compiler.compilation.get_errors().collect();
+      assert!(errors.is_empty());
+      let asset = &compiler.compilation.assets().get("main.js").unwrap();
+      assert_eq!(
+        asset.source.as_ref().unwrap().source().into_string_lossy(),
+        "console.log(123);"
+      );
+    })
+    .await;
+  }
 }
@@ -0,0 +1,67 @@
+use std::{
+  alloc::{GlobalAlloc, Layout},
+  sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The bytes currently allocated
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+/// The bytes allocated since the process started, the freed bytes are not subtracted
+static TOTAL_ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+/// The bytes currently allocated by the global allocator
+pub fn allocated_bytes() -> usize {
+  ALLOCATED.load(Ordering::Relaxed)
+}
+
+/// The bytes allocated by the global allocator since the process started
+pub fn total_allocated_bytes() -> usize {
+  TOTAL_ALLOCATED.load(Ordering::Relaxed)
+}
+
+fn record_alloc(size: usize) {
+  ALLOCATED.fetch_add(size, Ordering::Relaxed);
+  TOTAL_ALLOCATED.fetch_add(size, Ordering::Relaxed);
+}
+
+fn record_dealloc(size: usize) {
+  ALLOCATED.fetch_sub(size, Ordering::Relaxed);
+}
+
+/// A global allocator counting the allocated bytes of the inner allocator
+pub struct CountingAllocator<A>(pub A);
+
+// SAFETY: all the calls are forwarded to the inner allocator, only the counters are updated
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAllocator<A> {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    let ptr = unsafe { self.0.alloc(layout) };
+    if !ptr.is_null() {
+      record_alloc(layout.size());
+    }
+    ptr
+  }
+
+  unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+    let ptr = unsafe { self.0.alloc_zeroed(layout) };
+    if !ptr.is_null() {
+      record_alloc(layout.size());
+    }
+    ptr
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    unsafe { self.0.dealloc(ptr, layout) };
+    record_dealloc(layout.size());
+  }
+
+  unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+    let new_ptr = unsafe { self.0.realloc(ptr, layout, new_size) };
+    if !new_ptr.is_null() {
+      if new_size > layout.size() {
+        record_alloc(new_size - layout.size());
+      } else {
+        record_dealloc(layout.size() - new_size);
+      }
+    }
+    new_ptr
+  }
+}
@@ -1,6 +1,16 @@
+#[cfg(feature = "memory-counters")]
+mod counter;
+
+#[cfg(feature = "memory-counters")]
+pub use counter::{CountingAllocator, allocated_bytes, total_allocated_bytes};
+
 #[global_allocator]
 #[cfg(not(any(miri, target_family = "wasm")))]
-#[cfg(not(any(feature = "sftrace-setup", feature = "tracy-client")))]
+#[cfg(not(any(
+  feature = "sftrace-setup",
+  feature = "tracy-client",
+  feature = "memory-counters"
+)))]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
 #[global_allocator]
@@ -14,3 +24,11 @@ static GLOBAL: sftrace_setup::SftraceAllocator<mimalloc::MiMalloc> =
 #[cfg(all(feature = "tracy-client", not(feature = "sftrace-setup")))]
 static GLOBAL: tracy_client::ProfiledAllocator<std::alloc::System> =
   tracy_client::ProfiledAllocator::new(std::alloc::System, 10); // adjust callstack_depth if needed with performance cost
+
+#[global_allocator]
+#[cfg(not(any(miri, target_family = "wasm")))]
+#[cfg(all(
+  feature = "memory-counters",
+  not(any(feature = "sftrace-setup", feature = "tracy-client"))
+))]
+static GLOBAL: CountingAllocator<mimalloc::MiMalloc> = CountingAllocator(mimalloc::MiMalloc);
@@ -55,6 +55,8 @@ pub struct ThreadsafeNodeFS {
   // The following functions are not supported by webpack, so they are optional
   #[napi(ts_type = "(name: string, mode: number) => Promise<void>")]
   pub chmod: Option<Chmod>,
+  #[napi(ts_type = "(name: string) => Promise<void>")]
+  pub fsync: Option<ThreadsafeFunction<String, Promise<()>>>,
 }
 
 #[napi(object, object_to_js = false)]
@@ -57,6 +57,60 @@ impl WritableFileSystem for NodeFileSystem {
       .to_fs_result()
   }
 
+  async fn create_new(&self, file: &Utf8Path, data: &[u8]) -> Result<()> {
+    let res = self
+      .0
+      .open
+      .call_with_promise((file.as_str().to_string(), "wx".to_string()).into())
+      .await
+      .map_err(|e| {
+        // the error of node is converted to a message, recover the kind for the callers
+        if e.to_string().contains("EEXIST") {
+          Error::new(std::io::ErrorKind::AlreadyExists, &e.to_string())
+        } else {
+          Error::new(std::io::ErrorKind::Other, &e.to_string())
+        }
+      })?;
+    let Either::A(fd) = res else {
+      return Err(Error::new(
+        std::io::ErrorKind::Other,
+        "file system call exclusive open failed:",
+      ));
+    };
+    let res = self
+      .0
+      .write_all
+      .call_with_promise((fd, data.to_vec().into()).into())
+      .await
+      .to_fs_result();
+    self.0.close.call_with_promise(fd).await.to_fs_result()?;
+    res
+  }
+
+  async fn rename(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
+    let from = from.as_str().to_string();
+    let to = to.as_str().to_string();
+    self
+      .0
+      .rename
+      .call_with_promise((from, to).into())
+      .await
+      .to_fs_result()
+  }
+
+  async fn sync(&self, path: &Utf8Path) -> Result<()> {
+    let Some(fsync) = &self.0.fsync else {
+      return Err(Error::new(
+        std::io::ErrorKind::Unsupported,
+        &format!("output file system does not support fsync, {path} can not be flushed"),
+      ));
+    };
+    fsync
+      .call_with_promise(path.as_str().to_string())
+      .await
+      .to_fs_result()
+  }
+
   async fn remove_file(&self, file: &Utf8Path) -> Result<()> {
     let file = file.as_str().to_string();
     self
@@ -145,17 +199,6 @@ impl WritableFileSystem for NodeFileSystem {
 
 #[async_trait]
 impl IntermediateFileSystemExtras for NodeFileSystem {
-  async fn rename(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
-    let from = from.as_str().to_string();
-    let to = to.as_str().to_string();
-    self
-      .0
-      .rename
-      .call_with_promise((from, to).into())
-      .await
-      .to_fs_result()
-  }
-
   async fn create_read_stream(&self, file: &Utf8Path) -> Result<Box<dyn ReadStream>> {
     let reader = NodeReadStream::try_new(file, self.0.clone()).await?;
     Ok(Box::new(reader))
@@ -104,6 +104,7 @@ mod virtual_modules;
 use std::{
   cell::RefCell,
   mem::ManuallyDrop,
+  path::Path,
   sync::{Arc, RwLock},
 };
 
@@ -112,11 +113,13 @@ pub use raw_options::{CustomPluginBuilder, register_custom_plugin};
 use rspack_collections::UkeyMap;
 use rspack_core::{
   BoxDependency, Compilation, CompilerId, CompilerPlatform, EntryOptions, ModuleIdentifier,
-  PluginExt,
+  PluginExt, cache::persistent::snapshot::KnownPathChanges,
 };
 use rspack_error::Diagnostic;
 use rspack_fs::{IntermediateFileSystem, NativeFileSystem, ReadableFileSystem};
+use rspack_paths::ArcPath;
 use rspack_tasks::{CURRENT_COMPILER_CONTEXT, CompilerContext, within_compiler_context_sync};
+use rspack_watcher::FsWatcherClock;
 use rustc_hash::FxHashMap;
 use swc_core::common::util::take::Take;
 
@@ -196,6 +199,11 @@ impl JsCompiler {
   ) -> Result<Self> {
     tracing::info!(name:"rspack_version", version = rspack_workspace::rspack_pkg_version!());
     tracing::info!(name:"raw_options", options=?&options);
+    #[cfg(feature = "memory-counters")]
+    rspack_core::register_allocation_counters(|| rspack_core::AllocationCounters {
+      allocated: rspack_allocator::allocated_bytes(),
+      total_allocated: rspack_allocator::total_allocated_bytes(),
+    });
     let compiler_context = Arc::new(CompilerContext::new());
     CURRENT_COMPILER_CONTEXT.sync_scope(compiler_context.clone(), || {
       let mut plugins = Vec::with_capacity(builtin_plugins.len());
@@ -364,6 +372,36 @@ impl JsCompiler {
     }
   }
 
+  /// Load the clock saved by the native watcher, the files changed since then are used
+  /// as the modified and removed files of the next build instead of validating the whole
+  /// persistent cache snapshot.
+  ///
+  /// Returns false if the clock file does not exist or is invalid.
+  #[napi]
+  pub fn load_watcher_clock(&mut self, clock_file: String) -> Result<bool, ErrorCode> {
+    if self.state.running() {
+      return Err(concurrent_compiler_error());
+    }
+    let Ok(clock) = FsWatcherClock::load(Path::new(&clock_file)) else {
+      return Ok(false);
+    };
+    let changes = clock.changes();
+    self.compiler.cache.set_known_changes(KnownPathChanges {
+      watched: clock.paths().map(ArcPath::from).collect(),
+      modified: changes
+        .changed_files
+        .iter()
+        .map(|file| ArcPath::from(file.as_str()))
+        .collect(),
+      removed: changes
+        .deleted_files
+        .iter()
+        .map(|file| ArcPath::from(file.as_str()))
+        .collect(),
+    });
+    Ok(true)
+  }
+
   /// Rebuild with the given option passed to the constructor
   #[napi(
     ts_args_type = "changed_files: string[], removed_files: string[], callback: (err: null | Error) => void"
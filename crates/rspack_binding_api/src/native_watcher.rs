@@ -8,9 +8,12 @@ use napi::bindgen_prelude::*;
 use napi_derive::*;
 use rspack_paths::ArcPath;
 use rspack_regex::RspackRegex;
-use rspack_watcher::{FsEventKind, FsWatcher, FsWatcherIgnored, FsWatcherOptions};
+use rspack_watcher::{
+  FsEventKind, FsWatcher, FsWatcherIgnored, FsWatcherMatcher, FsWatcherOptions, FsWatcherRules,
+};
 
 type JsWatcherIgnored = Either3<String, Vec<String>, RspackRegex>;
+type JsWatcherMatcher = Either<String, RspackRegex>;
 
 fn to_fs_watcher_ignored(ignored: Option<JsWatcherIgnored>) -> FsWatcherIgnored {
   if let Some(ignored) = ignored {
@@ -24,6 +27,47 @@ fn to_fs_watcher_ignored(ignored: Option<JsWatcherIgnored>) -> FsWatcherIgnored
   }
 }
 
+fn to_fs_watcher_matchers(matchers: Option<Vec<JsWatcherMatcher>>) -> Vec<FsWatcherMatcher> {
+  matchers
+    .unwrap_or_default()
+    .into_iter()
+    .map(|matcher| match matcher {
+      Either::A(glob) => FsWatcherMatcher::Glob(glob),
+      Either::B(regex) => FsWatcherMatcher::Regex(regex),
+    })
+    .collect()
+}
+
+#[napi(object, object_to_js = false)]
+pub struct NativeWatcherRules {
+  #[napi(ts_type = "Array<string | RegExp>")]
+  /// Only the files and missing paths matching one of them are watched.
+  pub include: Option<Vec<JsWatcherMatcher>>,
+
+  #[napi(ts_type = "Array<string | RegExp>")]
+  /// The paths matching one of them are not watched.
+  pub exclude: Option<Vec<JsWatcherMatcher>>,
+
+  #[napi(ts_type = "Array<string | RegExp>")]
+  /// The paths matching one of them are watched by polling, e.g. the paths on network mounts.
+  pub poll: Option<Vec<JsWatcherMatcher>>,
+
+  #[napi(ts_type = "Array<string | RegExp>")]
+  /// The paths matching one of them are generated files which use `generatedAggregateTimeout`.
+  pub generated: Option<Vec<JsWatcherMatcher>>,
+}
+
+impl From<NativeWatcherRules> for FsWatcherRules {
+  fn from(value: NativeWatcherRules) -> Self {
+    Self {
+      include: to_fs_watcher_matchers(value.include),
+      exclude: to_fs_watcher_matchers(value.exclude),
+      poll: to_fs_watcher_matchers(value.poll),
+      generated: to_fs_watcher_matchers(value.generated),
+    }
+  }
+}
+
 #[napi(object, object_to_js = false)]
 pub struct NativeWatcherOptions {
   pub follow_symlinks: Option<bool>,
@@ -32,10 +76,22 @@ pub struct NativeWatcherOptions {
 
   pub aggregate_timeout: Option<u32>,
 
+  /// The aggregate timeout of the generated files, defaults to `aggregateTimeout`.
+  pub generated_aggregate_timeout: Option<u32>,
+
+  /// The poll interval of the paths matching the poll rules.
+  pub fallback_poll_interval: Option<u32>,
+
+  /// The include, exclude, poll and generated rules of watched paths.
+  pub rules: Option<NativeWatcherRules>,
+
   #[napi(ts_type = "string | string[] | RegExp")]
   /// The ignored paths for the watcher.
   /// It can be a single path, an array of paths, or a regular expression.
   pub ignored: Option<JsWatcherIgnored>,
+
+  /// The file to save the clock of watched paths to each time watching starts.
+  pub clock_file: Option<String>,
 }
 
 #[napi]
@@ -63,6 +119,10 @@ impl NativeWatcher {
         follow_symlinks: options.follow_symlinks.unwrap_or(false),
         poll_interval: options.poll_interval,
         aggregate_timeout: options.aggregate_timeout,
+        generated_aggregate_timeout: options.generated_aggregate_timeout,
+        fallback_poll_interval: options.fallback_poll_interval,
+        rules: options.rules.map(Into::into).unwrap_or_default(),
+        clock_file: options.clock_file.map(PathBuf::from),
       },
       to_fs_watcher_ignored(options.ignored.clone()),
     );
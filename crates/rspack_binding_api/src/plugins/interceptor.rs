@@ -2,7 +2,7 @@ use std::{
   collections::HashMap,
   hash::Hash,
   ptr::NonNull,
-  sync::{Arc, RwLock},
+  sync::{Arc, LazyLock, Mutex, RwLock},
 };
 
 use async_trait::async_trait;
@@ -102,6 +102,27 @@ use crate::{
   source::JsSourceToJs,
 };
 
+/// The name of the JavaScript taps in the hook timings if the names of the JavaScript plugins are unknown.
+const JS_TAP_NAME: &str = "JavaScript";
+
+/// Intern the name of a JavaScript tap, the hook timings are keyed by `&'static str`.
+///
+/// The names are leaked, there are only a few distinct names of the JavaScript plugins.
+fn intern_js_tap_name(name: Option<String>) -> &'static str {
+  static NAMES: LazyLock<Mutex<rustc_hash::FxHashSet<&'static str>>> =
+    LazyLock::new(Default::default);
+  let Some(name) = name.filter(|name| !name.is_empty()) else {
+    return JS_TAP_NAME;
+  };
+  let mut names = NAMES.lock().expect("should lock js tap names");
+  if let Some(name) = names.get(name.as_str()) {
+    return name;
+  }
+  let name: &'static str = Box::leak(name.into_boxed_str());
+  names.insert(name);
+  name
+}
+
 #[napi(object)]
 pub struct JsModuleForIds {
   pub identifier: String,
@@ -135,11 +156,14 @@ pub struct JsTap<'f> {
   #[napi(ts_type = "(...args: any[]) => any")]
   pub function: Function<'f>,
   pub stage: i32,
+  /// The names of the JavaScript plugins called by this tap, used in the hook timings
+  pub name: Option<String>,
 }
 
 pub struct ThreadsafeJsTap<T: 'static + JsValuesTupleIntoVec, R> {
   pub function: ThreadsafeFunction<T, R>,
   pub stage: i32,
+  pub name: &'static str,
 }
 
 impl<T: 'static + JsValuesTupleIntoVec, R> Clone for ThreadsafeJsTap<T, R> {
@@ -147,6 +171,7 @@ impl<T: 'static + JsValuesTupleIntoVec, R> Clone for ThreadsafeJsTap<T, R> {
     Self {
       function: self.function.clone(),
       stage: self.stage,
+      name: self.name,
     }
   }
 }
@@ -160,6 +185,7 @@ impl<T: 'static + ToNapiValue + JsValuesTupleIntoVec, R: 'static + FromNapiValue
     Ok(Self {
       function,
       stage: js_tap.stage,
+      name: intern_js_tap_name(js_tap.name),
     })
   }
 }
@@ -311,6 +337,7 @@ macro_rules! define_register {
     struct $tap_name {
       function: ThreadsafeFunction<$arg, $ret>,
       stage: i32,
+      name: &'static str,
     }
 
     impl $tap_name {
@@ -318,6 +345,7 @@ macro_rules! define_register {
         Self {
           function: tap.function,
           stage: tap.stage,
+          name: tap.name,
         }
       }
     }
@@ -1057,6 +1085,10 @@ impl CompilerThisCompilation for CompilerThisCompilationTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1073,6 +1105,10 @@ impl CompilerCompilation for CompilerCompilationTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1085,6 +1121,10 @@ impl CompilerMake for CompilerMakeTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1097,6 +1137,10 @@ impl CompilerFinishMake for CompilerFinishMakeTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1109,6 +1153,10 @@ impl CompilerShouldEmit for CompilerShouldEmitTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1120,6 +1168,10 @@ impl CompilerEmit for CompilerEmitTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1131,6 +1183,10 @@ impl CompilerAfterEmit for CompilerAfterEmitTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1154,6 +1210,10 @@ impl CompilerAssetEmitted for CompilerAssetEmittedTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1178,6 +1238,10 @@ impl CompilationBuildModule for CompilationBuildModuleTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1202,6 +1266,10 @@ impl CompilationStillValidModule for CompilationStillValidModuleTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1226,6 +1294,10 @@ impl CompilationSucceedModule for CompilationSucceedModuleTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1251,6 +1323,10 @@ impl CompilationExecuteModule for CompilationExecuteModuleTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1267,6 +1343,10 @@ impl CompilationFinishModules for CompilationFinishModulesTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1282,6 +1362,10 @@ impl CompilationOptimizeModules for CompilationOptimizeModulesTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1293,6 +1377,10 @@ impl CompilationAfterOptimizeModules for CompilationAfterOptimizeModulesTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1304,6 +1392,10 @@ impl CompilationOptimizeTree for CompilationOptimizeTreeTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1315,6 +1407,10 @@ impl CompilationOptimizeChunkModules for CompilationOptimizeChunkModulesTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1339,6 +1435,10 @@ impl CompilationBeforeModuleIds for CompilationBeforeModuleIdsTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1370,6 +1470,10 @@ impl CompilationAdditionalTreeRuntimeRequirements
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1402,6 +1506,10 @@ impl CompilationRuntimeRequirementInTree for CompilationRuntimeRequirementInTree
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1451,6 +1559,10 @@ impl CompilationRuntimeModule for CompilationRuntimeModuleTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1472,6 +1584,10 @@ impl CompilationChunkHash for CompilationChunkHashTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1494,6 +1610,10 @@ impl CompilationChunkAsset for CompilationChunkAssetTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1506,6 +1626,10 @@ impl CompilationProcessAssets for CompilationProcessAssetsTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1522,6 +1646,10 @@ impl CompilationAfterProcessAssets for CompilationAfterProcessAssetsTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1533,6 +1661,10 @@ impl CompilationSeal for CompilationSealTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1544,6 +1676,10 @@ impl CompilationAfterSeal for CompilationAfterSealTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1565,6 +1701,10 @@ impl NormalModuleFactoryBeforeResolve for NormalModuleFactoryBeforeResolveTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1589,6 +1729,10 @@ impl NormalModuleFactoryFactorize for NormalModuleFactoryFactorizeTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1613,6 +1757,10 @@ impl NormalModuleFactoryResolve for NormalModuleFactoryResolveTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1640,6 +1788,10 @@ impl NormalModuleFactoryResolveForScheme for NormalModuleFactoryResolveForScheme
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1665,6 +1817,10 @@ impl NormalModuleFactoryAfterResolve for NormalModuleFactoryAfterResolveTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1690,6 +1846,10 @@ impl NormalModuleFactoryCreateModule for NormalModuleFactoryCreateModuleTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1713,6 +1873,10 @@ impl ContextModuleFactoryBeforeResolve for ContextModuleFactoryBeforeResolveTap
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1733,6 +1897,10 @@ impl ContextModuleFactoryAfterResolve for ContextModuleFactoryAfterResolveTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1754,6 +1922,10 @@ impl JavascriptModulesChunkHash for JavascriptModulesChunkHashTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1772,6 +1944,10 @@ impl HtmlPluginBeforeAssetTagGeneration for HtmlPluginBeforeAssetTagGenerationTa
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1787,6 +1963,10 @@ impl HtmlPluginAlterAssetTags for HtmlPluginAlterAssetTagsTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1805,6 +1985,10 @@ impl HtmlPluginAlterAssetTagGroups for HtmlPluginAlterAssetTagGroupsTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1823,6 +2007,10 @@ impl HtmlPluginAfterTemplateExecution for HtmlPluginAfterTemplateExecutionTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1838,6 +2026,10 @@ impl HtmlPluginBeforeEmit for HtmlPluginBeforeEmitTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1853,6 +2045,10 @@ impl HtmlPluginAfterEmit for HtmlPluginAfterEmitTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1871,6 +2067,10 @@ impl RuntimePluginCreateScript for RuntimePluginCreateScriptTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1889,6 +2089,10 @@ impl RuntimePluginCreateLink for RuntimePluginCreateLinkTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1907,6 +2111,10 @@ impl RuntimePluginLinkPreload for RuntimePluginLinkPreloadTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1925,6 +2133,10 @@ impl RuntimePluginLinkPrefetch for RuntimePluginLinkPrefetchTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1941,6 +2153,10 @@ impl RsdoctorPluginModuleGraph for RsdoctorPluginModuleGraphTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1957,6 +2173,10 @@ impl RsdoctorPluginChunkGraph for RsdoctorPluginChunkGraphTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1973,6 +2193,10 @@ impl RsdoctorPluginModuleIds for RsdoctorPluginModuleIdsTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -1989,6 +2213,10 @@ impl RsdoctorPluginModuleSources for RsdoctorPluginModuleSourcesTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
 
 #[async_trait]
@@ -2004,4 +2232,8 @@ impl RsdoctorPluginAssets for RsdoctorPluginAssetsTap {
   fn stage(&self) -> i32 {
     self.stage
   }
+
+  fn tap_name(&self) -> &'static str {
+    self.name
+  }
 }
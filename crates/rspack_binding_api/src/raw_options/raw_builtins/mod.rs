@@ -7,6 +7,7 @@ mod raw_css_chunking;
 mod raw_css_extract;
 mod raw_dll;
 mod raw_esm_lib;
+mod raw_hook_timing;
 mod raw_html;
 mod raw_http_uri;
 mod raw_ids;
@@ -30,15 +31,18 @@ use napi::{
 };
 use napi_derive::napi;
 use raw_dll::{RawDllReferenceAgencyPluginOptions, RawFlagAllModulesAsUsedPluginOptions};
-use raw_ids::RawOccurrenceChunkIdsPluginOptions;
+use raw_ids::{
+  RawHashedModuleIdsPluginOptions, RawOccurrenceChunkIdsPluginOptions, RawRecordIdsPluginOptions,
+};
 use raw_lightning_css_minimizer::RawLightningCssMinimizerRspackPluginOptions;
 use raw_mf::{RawModuleFederationManifestPluginOptions, RawModuleFederationRuntimePluginOptions};
 use raw_sri::RawSubresourceIntegrityPluginOptions;
 use rspack_core::{BoxPlugin, Plugin, PluginExt};
 use rspack_error::{Result, ToStringResultToRspackResultExt};
 use rspack_ids::{
-  DeterministicChunkIdsPlugin, DeterministicModuleIdsPlugin, NamedChunkIdsPlugin,
-  NamedModuleIdsPlugin, NaturalChunkIdsPlugin, NaturalModuleIdsPlugin, OccurrenceChunkIdsPlugin,
+  DeterministicChunkIdsPlugin, DeterministicModuleIdsPlugin, HashedModuleIdsPlugin,
+  NamedChunkIdsPlugin, NamedModuleIdsPlugin, NaturalChunkIdsPlugin, NaturalModuleIdsPlugin,
+  OccurrenceChunkIdsPlugin, RecordIdsPlugin,
 };
 use rspack_plugin_asset::AssetPlugin;
 use rspack_plugin_banner::BannerPlugin;
@@ -62,6 +66,7 @@ use rspack_plugin_externals::{
   ExternalsPlugin, electron_target_plugin, http_externals_rspack_plugin, node_target_plugin,
 };
 use rspack_plugin_hmr::HotModuleReplacementPlugin;
+use rspack_plugin_hook_timing::HookTimingPlugin;
 use rspack_plugin_html::HtmlRspackPlugin;
 use rspack_plugin_ignore::IgnorePlugin;
 use rspack_plugin_javascript::{
@@ -112,6 +117,7 @@ use self::{
   raw_css_chunking::RawCssChunkingPluginOptions,
   raw_css_extract::RawCssExtractPluginOption,
   raw_dll::{RawDllEntryPluginOptions, RawLibManifestPluginOptions},
+  raw_hook_timing::RawHookTimingPluginOptions,
   raw_html::RawHtmlRspackPluginOptions,
   raw_ignore::RawIgnorePluginOptions,
   raw_lazy_compilation::{JsBackend, RawLazyCompilationOption},
@@ -180,10 +186,12 @@ pub enum BuiltinPluginName {
   NamedModuleIdsPlugin,
   NaturalModuleIdsPlugin,
   DeterministicModuleIdsPlugin,
+  HashedModuleIdsPlugin,
   NaturalChunkIdsPlugin,
   NamedChunkIdsPlugin,
   DeterministicChunkIdsPlugin,
   OccurrenceChunkIdsPlugin,
+  RecordIdsPlugin,
   RealContentHashPlugin,
   RemoveEmptyChunksPlugin,
   EnsureChunkConditionsPlugin,
@@ -232,6 +240,7 @@ pub enum BuiltinPluginName {
   RslibPlugin,
   CircularDependencyRspackPlugin,
   URLPlugin,
+  HookTimingPlugin,
 
   // rspack js adapter plugins
   // naming format follow XxxRspackPlugin
@@ -440,7 +449,7 @@ impl<'a> BuiltinPlugin<'a> {
         let plugin = LimitChunkCountPlugin::new(
           downcast_into::<RawLimitChunkCountPluginOptions>(self.options)
             .map_err(|report| napi::Error::from_reason(report.to_string()))?
-            .into(),
+            .try_into()?,
         )
         .boxed();
         plugins.push(plugin);
@@ -544,6 +553,23 @@ impl<'a> BuiltinPlugin<'a> {
         )
         .boxed(),
       ),
+      BuiltinPluginName::HashedModuleIdsPlugin => plugins.push(
+        HashedModuleIdsPlugin::new(
+          downcast_into::<RawHashedModuleIdsPluginOptions>(self.options)
+            .map_err(|report| napi::Error::from_reason(report.to_string()))?
+            .try_into()
+            .map_err(|report: rspack_error::Error| napi::Error::from_reason(report.to_string()))?,
+        )
+        .boxed(),
+      ),
+      BuiltinPluginName::RecordIdsPlugin => plugins.push(
+        RecordIdsPlugin::new(
+          downcast_into::<RawRecordIdsPluginOptions>(self.options)
+            .map_err(|report| napi::Error::from_reason(report.to_string()))?
+            .into(),
+        )
+        .boxed(),
+      ),
       BuiltinPluginName::RealContentHashPlugin => {
         plugins.push(RealContentHashPlugin::default().boxed())
       }
@@ -733,6 +759,14 @@ impl<'a> BuiltinPlugin<'a> {
         )
         .boxed(),
       ),
+      BuiltinPluginName::HookTimingPlugin => plugins.push(
+        HookTimingPlugin::new(
+          downcast_into::<RawHookTimingPluginOptions>(self.options)
+            .map_err(|report| napi::Error::from_reason(report.to_string()))?
+            .into(),
+        )
+        .boxed(),
+      ),
       BuiltinPluginName::JsLoaderRspackPlugin => {
         // Set the compiler._runLoader property on the JsObject to ensure that the runLoader
         // is not garbage collected by JS while the stats Object holds a reference to JsLoaderPlugin.
@@ -3,6 +3,7 @@ use rspack_regex::RspackRegex;
 #[napi(object, object_to_js = false)]
 pub struct RawCssChunkingPluginOptions {
   pub strict: Option<bool>,
+  pub align_with_js: Option<bool>,
   pub min_size: Option<f64>,
   pub max_size: Option<f64>,
   #[napi(ts_type = "RegExp")]
@@ -13,6 +14,7 @@ impl From<RawCssChunkingPluginOptions> for rspack_plugin_css_chunking::CssChunki
   fn from(options: RawCssChunkingPluginOptions) -> Self {
     Self {
       strict: options.strict.unwrap_or(false),
+      align_with_js: options.align_with_js.unwrap_or(false),
       min_size: options.min_size,
       max_size: options.max_size,
       exclude: options.exclude,
@@ -0,0 +1,16 @@
+use napi_derive::napi;
+use rspack_plugin_hook_timing::HookTimingPluginOptions;
+
+#[derive(Debug)]
+#[napi(object, object_to_js = false)]
+pub struct RawHookTimingPluginOptions {
+  pub filename: Option<String>,
+}
+
+impl From<RawHookTimingPluginOptions> for HookTimingPluginOptions {
+  fn from(value: RawHookTimingPluginOptions) -> Self {
+    Self {
+      filename: value.filename,
+    }
+  }
+}
@@ -1,5 +1,7 @@
 use napi_derive::napi;
-use rspack_ids::OccurrenceChunkIdsPluginOptions;
+use rspack_ids::{
+  HashedModuleIdsPluginOptions, OccurrenceChunkIdsPluginOptions, RecordIdsPluginOptions,
+};
 
 #[derive(Debug)]
 #[napi(object, object_to_js = false)]
@@ -14,3 +16,48 @@ impl From<RawOccurrenceChunkIdsPluginOptions> for OccurrenceChunkIdsPluginOption
     }
   }
 }
+
+#[derive(Debug)]
+#[napi(object, object_to_js = false)]
+pub struct RawRecordIdsPluginOptions {
+  pub path: String,
+}
+
+impl From<RawRecordIdsPluginOptions> for RecordIdsPluginOptions {
+  fn from(value: RawRecordIdsPluginOptions) -> Self {
+    Self {
+      path: value.path.into(),
+    }
+  }
+}
+
+#[derive(Debug)]
+#[napi(object, object_to_js = false)]
+pub struct RawHashedModuleIdsPluginOptions {
+  pub context: Option<String>,
+  pub hash_function: Option<String>,
+  pub hash_digest: Option<String>,
+  pub hash_digest_length: Option<u32>,
+}
+
+impl TryFrom<RawHashedModuleIdsPluginOptions> for HashedModuleIdsPluginOptions {
+  type Error = rspack_error::Error;
+
+  fn try_from(value: RawHashedModuleIdsPluginOptions) -> rspack_error::Result<Self> {
+    let default = Self::default();
+    Ok(Self {
+      context: value.context,
+      hash_function: match value.hash_function {
+        Some(hash_function) => hash_function.as_str().try_into()?,
+        None => default.hash_function,
+      },
+      hash_digest: match value.hash_digest {
+        Some(hash_digest) => hash_digest.as_str().try_into()?,
+        None => default.hash_digest,
+      },
+      hash_digest_length: value
+        .hash_digest_length
+        .map_or(default.hash_digest_length, |length| length as usize),
+    })
+  }
+}
@@ -1,5 +1,39 @@
 use napi_derive::napi;
-use rspack_plugin_limit_chunk_count::LimitChunkCountPluginOptions;
+use rspack_error::error;
+use rspack_plugin_limit_chunk_count::{
+  ChunkCostModel, LimitChunkCountPluginOptions, LoadTimeCostOptions,
+};
+
+#[derive(Debug, Clone)]
+#[napi(object)]
+pub struct RawChunkCostModel {
+  #[napi(ts_type = "'size' | 'loadTime'")]
+  pub r#type: String,
+  pub compression_ratio: Option<f64>,
+  pub request_latency: Option<f64>,
+  pub bandwidth: Option<f64>,
+}
+
+impl TryFrom<RawChunkCostModel> for ChunkCostModel {
+  type Error = rspack_error::Error;
+
+  fn try_from(value: RawChunkCostModel) -> Result<Self, Self::Error> {
+    match value.r#type.as_str() {
+      "size" => Ok(Self::Size),
+      "loadTime" => {
+        let default = LoadTimeCostOptions::default();
+        Ok(Self::LoadTime(LoadTimeCostOptions {
+          compression_ratio: value.compression_ratio.unwrap_or(default.compression_ratio),
+          request_latency: value.request_latency.unwrap_or(default.request_latency),
+          bandwidth: value.bandwidth.unwrap_or(default.bandwidth),
+        }))
+      }
+      ty => Err(error!(
+        "Invalid cost model type \"{ty}\" of LimitChunkCountPlugin, expected \"size\" or \"loadTime\""
+      )),
+    }
+  }
+}
 
 #[derive(Debug, Clone)]
 #[napi(object)]
@@ -10,14 +44,25 @@ pub struct RawLimitChunkCountPluginOptions {
   pub entry_chunk_multiplicator: Option<f64>,
   // Limit the maximum number of chunks using a value greater greater than or equal to 1.
   pub max_chunks: f64,
+  // Limit the maximum number of chunks loaded by a chunk group.
+  pub max_chunks_per_route: Option<f64>,
+  pub cost_model: Option<RawChunkCostModel>,
 }
 
-impl From<RawLimitChunkCountPluginOptions> for LimitChunkCountPluginOptions {
-  fn from(value: RawLimitChunkCountPluginOptions) -> Self {
-    Self {
+impl TryFrom<RawLimitChunkCountPluginOptions> for LimitChunkCountPluginOptions {
+  type Error = rspack_error::Error;
+
+  fn try_from(value: RawLimitChunkCountPluginOptions) -> Result<Self, Self::Error> {
+    Ok(Self {
       chunk_overhead: value.chunk_overhead,
       entry_chunk_multiplicator: value.entry_chunk_multiplicator,
       max_chunks: value.max_chunks as usize,
-    }
+      max_chunks_per_route: value.max_chunks_per_route.map(|max| max as usize),
+      cost_model: value
+        .cost_model
+        .map(TryInto::try_into)
+        .transpose()?
+        .unwrap_or_default(),
+    })
   }
 }
@@ -22,9 +22,11 @@ pub struct RawCacheOptionsPersistent {
   pub portable: Option<bool>,
 }
 
-impl From<RawCacheOptionsPersistent> for PersistentCacheOptions {
-  fn from(value: RawCacheOptionsPersistent) -> Self {
-    Self {
+impl TryFrom<RawCacheOptionsPersistent> for PersistentCacheOptions {
+  type Error = rspack_error::Error;
+
+  fn try_from(value: RawCacheOptionsPersistent) -> Result<Self, Self::Error> {
+    Ok(Self {
       build_dependencies: value
         .build_dependencies
         .unwrap_or_default()
@@ -33,9 +35,9 @@ impl From<RawCacheOptionsPersistent> for PersistentCacheOptions {
         .collect(),
       version: value.version.unwrap_or_default(),
       snapshot: value.snapshot.unwrap_or_default().into(),
-      storage: value.storage.unwrap_or_default().into(),
+      storage: value.storage.unwrap_or_default().try_into()?,
       portable: value.portable.unwrap_or_default(),
-    }
+    })
   }
 }
 
@@ -43,6 +45,7 @@ impl From<RawCacheOptionsPersistent> for PersistentCacheOptions {
 #[napi(object)]
 pub struct RawCacheOptionsMemory {
   pub max_generations: Option<u32>,
+  pub max_size: Option<f64>,
 }
 
 #[derive(Debug)]
@@ -91,20 +94,24 @@ impl FromNapiValue for InnerCacheOptions {
 
 pub type RawCacheOptions = Either<bool, InnerCacheOptions>;
 
-pub fn normalize_raw_cache(options: RawCacheOptions) -> CacheOptions {
-  match options {
+pub fn normalize_raw_cache(options: RawCacheOptions) -> rspack_error::Result<CacheOptions> {
+  Ok(match options {
     Either::A(options) => {
       if options {
-        CacheOptions::Memory { max_generations: 1 }
+        CacheOptions::Memory {
+          max_generations: 1,
+          max_size: None,
+        }
       } else {
         CacheOptions::Disabled
       }
     }
     Either::B(options) => match options {
-      InnerCacheOptions::Persistent(options) => CacheOptions::Persistent(options.into()),
+      InnerCacheOptions::Persistent(options) => CacheOptions::Persistent(options.try_into()?),
       InnerCacheOptions::Memory(options) => CacheOptions::Memory {
         max_generations: options.max_generations.unwrap_or(1),
+        max_size: options.max_size.map(|size| size as usize),
       },
     },
-  }
+  })
 }
@@ -21,6 +21,7 @@ pub struct RawSnapshotOptions {
   pub unmanaged_paths: Vec<RawPathMatcher>,
   #[napi(ts_type = r#"Array<string|RegExp>"#)]
   pub managed_paths: Vec<RawPathMatcher>,
+  pub content_hash_only: Option<bool>,
 }
 
 impl From<RawSnapshotOptions> for SnapshotOptions {
@@ -42,5 +43,6 @@ impl From<RawSnapshotOptions> for SnapshotOptions {
         .map(normalize_raw_path_matcher)
         .collect(),
     )
+    .with_content_hash_only(value.content_hash_only.unwrap_or_default())
   }
 }
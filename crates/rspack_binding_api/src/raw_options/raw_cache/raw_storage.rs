@@ -1,21 +1,85 @@
 use napi_derive::napi;
-use rspack_core::cache::persistent::storage::StorageOptions;
+use rspack_core::cache::persistent::storage::{
+  DEFAULT_EXPIRE, DEFAULT_PACK_SIZE, StorageCompression, StorageMode, StorageOptions,
+};
+use rspack_error::error;
 
 #[derive(Debug, Default)]
 #[napi(object)]
 pub struct RawStorageOptions {
-  #[napi(ts_type = r#""filesystem""#)]
+  #[napi(ts_type = r#""filesystem" | "remote""#)]
   pub r#type: String,
-  pub directory: String,
+  /// The cache directory of the filesystem storage
+  pub directory: Option<String>,
+  /// The endpoint of the remote storage, either an http:// cache server or a shared directory
+  pub endpoint: Option<String>,
+  /// The max size of a pack in bytes
+  pub pack_size: Option<u32>,
+  /// The expiration of the cache in milliseconds
+  pub expire: Option<f64>,
+  /// The compression of the pack files of the filesystem storage
+  #[napi(ts_type = r#""none" | "zstd" | "lz4""#)]
+  pub compression: Option<String>,
+  /// The level of the zstd compression, 0 means the default level
+  pub compression_level: Option<i32>,
+  /// The access mode of the filesystem storage
+  #[napi(ts_type = r#""read-write" | "read-only" | "write-only""#)]
+  pub mode: Option<String>,
 }
 
-impl From<RawStorageOptions> for StorageOptions {
-  fn from(value: RawStorageOptions) -> Self {
-    match value.r#type.as_str() {
+impl TryFrom<RawStorageOptions> for StorageOptions {
+  type Error = rspack_error::Error;
+
+  fn try_from(value: RawStorageOptions) -> Result<Self, Self::Error> {
+    let pack_size = value
+      .pack_size
+      .map_or(DEFAULT_PACK_SIZE, |size| size as usize);
+    let expire = value.expire.map_or(DEFAULT_EXPIRE, |expire| expire as u64);
+    let options = match value.r#type.as_str() {
       "filesystem" => StorageOptions::FileSystem {
-        directory: value.directory.into(),
+        directory: value
+          .directory
+          .ok_or_else(|| error!("`directory` is required by the filesystem storage"))?
+          .into(),
+        pack_size,
+        expire,
+        compression: match value.compression.as_deref() {
+          None | Some("none") => StorageCompression::None,
+          Some("zstd") => StorageCompression::Zstd {
+            level: value.compression_level.unwrap_or_default(),
+          },
+          Some("lz4") => StorageCompression::Lz4,
+          Some(s) => {
+            return Err(error!(
+              "unsupported storage compression {s}, expected 'none', 'zstd' or 'lz4'"
+            ));
+          }
+        },
+        mode: match value.mode.as_deref() {
+          None | Some("read-write") => StorageMode::ReadWrite,
+          Some("read-only") => StorageMode::ReadOnly,
+          Some("write-only") => StorageMode::WriteOnly,
+          Some(s) => {
+            return Err(error!(
+              "unsupported storage mode {s}, expected 'read-write', 'read-only' or 'write-only'"
+            ));
+          }
+        },
+      },
+      "remote" => StorageOptions::Remote {
+        endpoint: value
+          .endpoint
+          .ok_or_else(|| error!("`endpoint` is required by the remote storage"))?,
+        pack_size,
+        expire,
       },
-      s => panic!("unsupported storage type {s}"),
-    }
+      s => {
+        return Err(error!(
+          "unsupported storage type {s}, expected 'filesystem' or 'remote'"
+        ));
+      }
+    };
+    options.validate()?;
+    Ok(options)
   }
 }
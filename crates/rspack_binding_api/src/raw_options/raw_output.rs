@@ -137,6 +137,7 @@ pub struct RawOutputOptions {
   pub script_type: WithFalse<String>,
   pub environment: RawEnvironment,
   pub compare_before_emit: bool,
+  pub atomic_emit: bool,
 }
 
 pub type RawWasmLoading = WithFalse<String>;
@@ -203,8 +204,8 @@ impl TryFrom<RawOutputOptions> for OutputOptions {
       module: value.module,
       trusted_types: value.trusted_types.map(Into::into),
       source_map_filename: value.source_map_filename.into(),
-      hash_function: value.hash_function.as_str().into(),
-      hash_digest: value.hash_digest.as_str().into(),
+      hash_function: value.hash_function.as_str().try_into()?,
+      hash_digest: value.hash_digest.as_str().try_into()?,
       hash_digest_length: value.hash_digest_length as usize,
       hash_salt: value.hash_salt.into(),
       async_chunks: value.async_chunks,
@@ -218,6 +219,7 @@ impl TryFrom<RawOutputOptions> for OutputOptions {
       environment: value.environment.into(),
       chunk_load_timeout: value.chunk_load_timeout,
       compare_before_emit: value.compare_before_emit,
+      atomic_emit: value.atomic_emit,
     })
   }
 }
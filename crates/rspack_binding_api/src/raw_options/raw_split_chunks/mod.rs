@@ -24,6 +24,9 @@ use self::{
 };
 use crate::filename::JsFilename;
 
+/// Same as the default `minSize`, a request is worth about as many bytes as the smallest split chunk
+const DEFAULT_REQUEST_COST: f64 = 20000.0;
+
 #[napi(object, object_to_js = false)]
 #[derive(Debug)]
 pub struct RawSplitChunksOptions<'a> {
@@ -53,6 +56,23 @@ pub struct RawSplitChunksOptions<'a> {
   pub max_size: Option<Either<f64, RawSplitChunkSizes>>,
   pub max_async_size: Option<Either<f64, RawSplitChunkSizes>>,
   pub max_initial_size: Option<Either<f64, RawSplitChunkSizes>>,
+  pub co_loading: Option<RawCoLoadingOptions>,
+}
+
+#[napi(object, object_to_js = false)]
+#[derive(Debug)]
+pub struct RawCoLoadingOptions {
+  pub load_sets: Vec<RawLoadSet>,
+  /// The cost of one more request in a navigation, in bytes
+  pub request_cost: Option<f64>,
+}
+
+#[napi(object, object_to_js = false)]
+#[derive(Debug)]
+pub struct RawLoadSet {
+  pub chunks: Option<Vec<String>>,
+  pub modules: Option<Vec<String>>,
+  pub frequency: Option<f64>,
 }
 
 #[napi(object, object_to_js = false)]
@@ -261,6 +281,26 @@ impl<'a> From<RawSplitChunksOptions<'a>> for rspack_plugin_split_chunks::PluginO
       .merge(&overall_max_initial_size)
       .merge(&overall_max_size);
 
+    let co_loading =
+      raw_opts
+        .co_loading
+        .map(|co_loading| rspack_plugin_split_chunks::CoLoadingOptions {
+          load_sets: co_loading
+            .load_sets
+            .into_iter()
+            .map(|load_set| rspack_plugin_split_chunks::LoadSet {
+              chunks: load_set.chunks.unwrap_or_default(),
+              modules: load_set.modules.unwrap_or_default(),
+              frequency: load_set.frequency.unwrap_or(1.0),
+            })
+            .collect(),
+          request_cost: co_loading.request_cost.unwrap_or(DEFAULT_REQUEST_COST),
+          min_size: overall_min_size,
+          max_async_size: overall_max_async_size,
+          max_async_requests: raw_opts.max_async_requests.unwrap_or(f64::INFINITY),
+          max_initial_requests: raw_opts.max_initial_requests.unwrap_or(f64::INFINITY),
+        });
+
     rspack_plugin_split_chunks::PluginOptions {
       cache_groups,
       fallback_cache_group: rspack_plugin_split_chunks::FallbackCacheGroup {
@@ -277,6 +317,7 @@ impl<'a> From<RawSplitChunksOptions<'a>> for rspack_plugin_split_chunks::PluginO
           .unwrap_or(overall_automatic_name_delimiter.clone()),
       },
       hide_path_info: raw_opts.hide_path_info,
+      co_loading,
     }
   }
 }
@@ -952,6 +952,51 @@ impl<'a> From<rspack_core::StatsAssetsByChunkName<'a>> for JsStatsAssetsByChunkN
   }
 }
 
+#[napi(object, object_from_js = false)]
+pub struct JsStatsCacheInvalidation<'a> {
+  pub build_dependencies: Vec<JsStatsInvalidatedPath<'a>>,
+  pub paths: Vec<JsStatsInvalidatedPath<'a>>,
+}
+
+impl<'a> From<rspack_core::StatsCacheInvalidation<'a>> for JsStatsCacheInvalidation<'a> {
+  fn from(stats: rspack_core::StatsCacheInvalidation<'a>) -> Self {
+    Self {
+      build_dependencies: stats
+        .build_dependencies
+        .into_iter()
+        .map(Into::into)
+        .collect(),
+      paths: stats.paths.into_iter().map(Into::into).collect(),
+    }
+  }
+}
+
+#[napi(object, object_from_js = false)]
+pub struct JsStatsInvalidatedPath<'a> {
+  pub path: String,
+  #[napi(ts_type = "'file' | 'context' | 'missing' | 'build'")]
+  pub scope: &'a str,
+  #[napi(
+    ts_type = "'packageVersion' | 'fileHash' | 'dirHash' | 'missing' | 'failed' | 'fileContentHash'"
+  )]
+  pub strategy: &'a str,
+  #[napi(ts_type = "'modified' | 'deleted'")]
+  pub kind: &'a str,
+  pub modules: Vec<&'a str>,
+}
+
+impl<'a> From<rspack_core::StatsInvalidatedPath<'a>> for JsStatsInvalidatedPath<'a> {
+  fn from(stats: rspack_core::StatsInvalidatedPath<'a>) -> Self {
+    Self {
+      path: stats.path,
+      scope: stats.scope,
+      strategy: stats.strategy,
+      kind: stats.kind,
+      modules: stats.modules,
+    }
+  }
+}
+
 #[napi(object, object_to_js = false)]
 pub struct JsStatsOptions {
   pub assets: bool,
@@ -1225,6 +1270,12 @@ impl JsStats {
       .collect()
   }
 
+  /// The paths which invalidated the persistent cache on the cold start.
+  #[napi]
+  pub fn get_cache_invalidation(&self) -> Option<JsStatsCacheInvalidation<'_>> {
+    self.inner.get_cache_invalidation().map(Into::into)
+  }
+
   fn hash(&self) -> Option<&str> {
     self.inner.get_hash()
   }
@@ -2,7 +2,7 @@ use std::cell::RefCell;
 
 use napi::bindgen_prelude::BigInt;
 use napi_derive::napi;
-use rspack_tracing::{PerfettoTracer, StdoutTracer, TraceEvent, Tracer};
+use rspack_tracing::{ChromeTracer, OtlpTracer, PerfettoTracer, StdoutTracer, TraceEvent, Tracer};
 use rspack_util::tracing_preset::{
   TRACING_ALL_PRESET, TRACING_BENCH_TARGET, TRACING_OVERVIEW_PRESET,
 };
@@ -65,8 +65,10 @@ pub(super) fn register_global_trace(
       let mut tracer: Box<dyn Tracer> = match layer.as_str() {
         "logger" => Box::new(StdoutTracer::default()),
         "perfetto" => Box::new(PerfettoTracer::default()),
+        "otlp" => Box::new(OtlpTracer::new(&output).map_err(anyhow::Error::msg)?),
+        "chrome" => Box::new(ChromeTracer::default()),
         _ => anyhow::bail!(
-          "Unexpected layer: {}, supported layers:'logger', 'perfetto' ",
+          "Unexpected layer: {}, supported layers:'logger', 'perfetto', 'otlp', 'chrome' ",
           layer
         ),
       };
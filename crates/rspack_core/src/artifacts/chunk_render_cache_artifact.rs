@@ -1,10 +1,11 @@
 use futures::Future;
 use rspack_collections::Identifier;
 use rspack_error::{Diagnostic, Result};
+use rspack_hash::RspackHashDigest;
 use rspack_sources::BoxSource;
 
 use crate::{
-  ArtifactExt, Chunk, Compilation, MemoryGCStorage, SourceType,
+  ArtifactExt, Chunk, Compilation, MemoryCacheStats, MemoryGCEvict, MemoryGCStorage, SourceType,
   incremental::{Incremental, IncrementalPasses},
 };
 
@@ -33,6 +34,35 @@ impl ChunkRenderCacheArtifact {
       storage.start_next_generation();
     }
   }
+  pub(crate) fn evictable_storage(&self) -> Option<&dyn MemoryGCEvict> {
+    self
+      .storage
+      .as_ref()
+      .map(|storage| storage as &dyn MemoryGCEvict)
+  }
+  pub fn stats(&self) -> MemoryCacheStats {
+    self
+      .storage
+      .as_ref()
+      .map(|storage| storage.stats())
+      .unwrap_or_default()
+  }
+  pub(crate) fn cache_key(content_hash: &RspackHashDigest) -> Identifier {
+    Identifier::from(content_hash.encoded())
+  }
+
+  /// Get the cached source, used by persistent cache to save sources
+  pub(crate) fn get(&self, key: &Identifier) -> Option<BoxSource> {
+    self.storage.as_ref()?.peek(key)
+  }
+
+  /// Set the cached source, used by persistent cache to recover sources
+  pub(crate) fn set(&self, key: Identifier, value: BoxSource) {
+    if let Some(storage) = &self.storage {
+      storage.set(key, value);
+    }
+  }
+
   pub async fn use_cache<G, F>(
     &self,
     compilation: &Compilation,
@@ -52,7 +82,7 @@ impl ChunkRenderCacheArtifact {
     else {
       return generator().await;
     };
-    let cache_key = Identifier::from(content_hash.encoded());
+    let cache_key = Self::cache_key(content_hash);
     if let Some(value) = storage.get(&cache_key) {
       Ok((value, Vec::new()))
     } else {
@@ -1,10 +1,11 @@
 use futures::Future;
 use rspack_collections::Identifier;
 use rspack_error::Result;
+use rspack_hash::RspackHashDigest;
 
 use crate::{
   ArtifactExt, CacheOptions, CodeGenerationJob, CodeGenerationResult, CompilerOptions,
-  MemoryGCStorage,
+  MemoryCacheStats, MemoryGCEvict, MemoryGCStorage, ModuleIdentifier,
   incremental::{Incremental, IncrementalPasses},
 };
 
@@ -26,7 +27,9 @@ impl CodeGenerateCacheArtifact {
   pub fn new(options: &CompilerOptions) -> Self {
     Self {
       storage: match &options.cache {
-        CacheOptions::Memory { max_generations } => Some(MemoryGCStorage::new(*max_generations)),
+        CacheOptions::Memory {
+          max_generations, ..
+        } => Some(MemoryGCStorage::new(*max_generations)),
         CacheOptions::Persistent(_) => Some(MemoryGCStorage::new(1)),
         CacheOptions::Disabled => None,
       },
@@ -39,6 +42,37 @@ impl CodeGenerateCacheArtifact {
     }
   }
 
+  pub(crate) fn evictable_storage(&self) -> Option<&dyn MemoryGCEvict> {
+    self
+      .storage
+      .as_ref()
+      .map(|storage| storage as &dyn MemoryGCEvict)
+  }
+
+  pub fn stats(&self) -> MemoryCacheStats {
+    self
+      .storage
+      .as_ref()
+      .map(|storage| storage.stats())
+      .unwrap_or_default()
+  }
+
+  pub(crate) fn cache_key(module: &ModuleIdentifier, hash: &RspackHashDigest) -> Identifier {
+    Identifier::from(format!("{}|{}", module, hash.encoded()))
+  }
+
+  /// Get the cached result, used by persistent cache to save results
+  pub(crate) fn get(&self, key: &Identifier) -> Option<CodeGenerationResult> {
+    self.storage.as_ref()?.peek(key)
+  }
+
+  /// Set the cached result, used by persistent cache to recover results
+  pub(crate) fn set(&self, key: Identifier, value: CodeGenerationResult) {
+    if let Some(storage) = &self.storage {
+      storage.set(key, value);
+    }
+  }
+
   pub async fn use_cache<G, F>(
     &self,
     job: &CodeGenerationJob,
@@ -53,7 +87,7 @@ impl CodeGenerateCacheArtifact {
       return (res, false);
     };
 
-    let cache_key = Identifier::from(format!("{}|{}", job.module, job.hash.encoded()));
+    let cache_key = Self::cache_key(&job.module, &job.hash);
     if let Some(value) = storage.get(&cache_key) {
       (Ok(value), true)
     } else {
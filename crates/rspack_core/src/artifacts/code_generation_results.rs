@@ -14,8 +14,9 @@ use rustc_hash::{FxHashMap as HashMap, FxHashSet};
 use serde::Serialize;
 
 use crate::{
-  ArtifactExt, AssetInfo, BindingCell, ChunkInitFragments, ConcatenationScope, ModuleIdentifier,
-  RuntimeGlobals, RuntimeSpec, RuntimeSpecMap, SourceType, incremental::IncrementalPasses,
+  ArtifactExt, AssetInfo, BindingCell, ChunkInitFragments, ConcatenationScope, MemorySize,
+  ModuleIdentifier, RuntimeGlobals, RuntimeSpec, RuntimeSpecMap, SourceType, entries_memory_size,
+  incremental::IncrementalPasses,
 };
 
 #[derive(Clone, Debug)]
@@ -196,6 +197,20 @@ impl ArtifactExt for CodeGenerationResults {
   const PASS: IncrementalPasses = IncrementalPasses::MODULES_CODEGEN;
 }
 
+impl MemorySize for CodeGenerationResults {
+  fn memory_size(&self) -> usize {
+    self
+      .module_generation_result_map
+      .values()
+      .map(|result| result.memory_size())
+      .sum::<usize>()
+      + entries_memory_size::<(CodeGenResultId, BindingCell<CodeGenerationResult>)>(
+        self.module_generation_result_map.len(),
+      )
+      + entries_memory_size::<(ModuleIdentifier, RuntimeSpecMap<CodeGenResultId>)>(self.map.len())
+  }
+}
+
 impl CodeGenerationResults {
   pub fn is_empty(&self) -> bool {
     self.module_generation_result_map.is_empty() && self.map.is_empty()
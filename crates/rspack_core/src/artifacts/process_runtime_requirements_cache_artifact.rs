@@ -3,8 +3,8 @@ use rspack_collections::Identifier;
 use rspack_error::Result;
 
 use crate::{
-  ArtifactExt, CacheOptions, ChunkGraph, Compilation, CompilerOptions, MemoryGCStorage,
-  ModuleIdentifier, RuntimeGlobals, RuntimeSpec, get_runtime_key,
+  ArtifactExt, CacheOptions, ChunkGraph, Compilation, CompilerOptions, MemoryCacheStats,
+  MemoryGCEvict, MemoryGCStorage, ModuleIdentifier, RuntimeGlobals, RuntimeSpec, get_runtime_key,
   incremental::{Incremental, IncrementalPasses},
 };
 
@@ -26,7 +26,9 @@ impl ProcessRuntimeRequirementsCacheArtifact {
   pub fn new(options: &CompilerOptions) -> Self {
     Self {
       storage: match &options.cache {
-        CacheOptions::Memory { max_generations } => Some(MemoryGCStorage::new(*max_generations)),
+        CacheOptions::Memory {
+          max_generations, ..
+        } => Some(MemoryGCStorage::new(*max_generations)),
         CacheOptions::Persistent(_) => Some(MemoryGCStorage::new(1)),
         CacheOptions::Disabled => None,
       },
@@ -39,6 +41,21 @@ impl ProcessRuntimeRequirementsCacheArtifact {
     }
   }
 
+  pub(crate) fn evictable_storage(&self) -> Option<&dyn MemoryGCEvict> {
+    self
+      .storage
+      .as_ref()
+      .map(|storage| storage as &dyn MemoryGCEvict)
+  }
+
+  pub fn stats(&self) -> MemoryCacheStats {
+    self
+      .storage
+      .as_ref()
+      .map(|storage| storage.stats())
+      .unwrap_or_default()
+  }
+
   pub async fn use_cache<G, F>(
     &self,
     module: ModuleIdentifier,
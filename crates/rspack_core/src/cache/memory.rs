@@ -1,7 +1,8 @@
 use super::Cache;
 use crate::{
-  Compilation, compilation::build_module_graph::BuildModuleGraphArtifact, incremental::Incremental,
-  recover_artifact,
+  Compilation, Logger, MemoryCacheStats, ModuleGraph,
+  compilation::build_module_graph::BuildModuleGraphArtifact, evict_least_recently_used,
+  incremental::Incremental, recover_artifact,
 };
 
 /// Memory cache implementation
@@ -11,15 +12,90 @@ use crate::{
 #[derive(Debug, Default)]
 pub struct MemoryCache {
   old_compilation: Option<Box<Compilation>>,
+  /// The maximum approximate bytes of the module graph and cache storages in old compilation
+  max_size: Option<usize>,
+  /// Counters of the old compilation, the size is the module graph size
+  stats: MemoryCacheStats,
+}
+
+impl MemoryCache {
+  pub fn new(max_size: Option<usize>) -> Self {
+    Self {
+      old_compilation: None,
+      max_size,
+      stats: Default::default(),
+    }
+  }
+
+  /// Approximate module graph size by the original source of modules
+  fn module_graph_size(module_graph: &ModuleGraph) -> usize {
+    module_graph
+      .modules()
+      .values()
+      .map(|module| {
+        module.source().map_or(0, |source| source.size()) + std::mem::size_of_val(module)
+      })
+      .sum()
+  }
 }
 
 #[async_trait::async_trait]
 impl Cache for MemoryCache {
   async fn before_compile(&mut self, _compilation: &mut Compilation) -> bool {
-    self.old_compilation.is_some()
+    if self.old_compilation.is_some() {
+      self.stats.hits += 1;
+      true
+    } else {
+      self.stats.misses += 1;
+      false
+    }
+  }
+
+  async fn after_compile(&mut self, compilation: &Compilation) {
+    let logger = compilation.get_logger("rspack.memoryCache");
+    for (name, stats) in [
+      ("module graph", self.stats),
+      (
+        "code generation",
+        compilation.code_generate_cache_artifact.stats(),
+      ),
+      (
+        "runtime requirements",
+        compilation
+          .process_runtime_requirements_cache_artifact
+          .stats(),
+      ),
+      (
+        "chunk render",
+        compilation.chunk_render_cache_artifact.stats(),
+      ),
+    ] {
+      logger.debug(format!(
+        "{name}: {} hits, {} misses, {} evictions, {} items with {} bytes",
+        stats.hits, stats.misses, stats.evictions, stats.count, stats.size
+      ));
+    }
   }
 
   fn store_old_compilation(&mut self, compilation: Box<Compilation>) {
+    let size = Self::module_graph_size(compilation.get_module_graph());
+    if let Some(max_size) = self.max_size {
+      // the module graph is required by incremental rebuild, so it takes its part of the budget
+      // and the other storages evict their least recently used items to fit the rest.
+      let storages = [
+        compilation.code_generate_cache_artifact.evictable_storage(),
+        compilation
+          .process_runtime_requirements_cache_artifact
+          .evictable_storage(),
+        compilation.chunk_render_cache_artifact.evictable_storage(),
+      ]
+      .into_iter()
+      .flatten()
+      .collect::<Vec<_>>();
+      evict_least_recently_used(&storages, max_size.saturating_sub(size));
+    }
+    self.stats.count = compilation.get_module_graph().modules().len();
+    self.stats.size = size;
     self.old_compilation = Some(compilation);
   }
 
@@ -1,4 +1,8 @@
-use super::{Cache, memory::MemoryCache, persistent::PersistentCache};
+use super::{
+  Cache,
+  memory::MemoryCache,
+  persistent::{PersistentCache, snapshot::KnownPathChanges},
+};
 use crate::{
   Compilation, compilation::build_module_graph::BuildModuleGraphArtifact, incremental::Incremental,
 };
@@ -49,6 +53,10 @@ impl Cache for MixedCache {
     self.memory.store_old_compilation(compilation);
   }
 
+  fn set_known_changes(&mut self, changes: KnownPathChanges) {
+    self.persistent.set_known_changes(changes);
+  }
+
   // BUILD_MODULE_GRAPH hooks
   async fn before_build_module_graph(
     &mut self,
@@ -8,7 +8,10 @@ use std::{fmt::Debug, sync::Arc};
 use rspack_fs::{IntermediateFileSystem, ReadableFileSystem};
 
 use self::{
-  disable::DisableCache, memory::MemoryCache, mixed::MixedCache, persistent::PersistentCache,
+  disable::DisableCache,
+  memory::MemoryCache,
+  mixed::MixedCache,
+  persistent::{PersistentCache, snapshot::KnownPathChanges},
 };
 use crate::{
   CacheOptions, Compilation, CompilerOptions,
@@ -94,6 +97,9 @@ pub trait Cache: Debug + Send + Sync {
 
   /// Store old compilation for artifact recovery (used by MemoryCache)
   fn store_old_compilation(&mut self, _compilation: Box<Compilation>) {}
+
+  /// Set the path changes known before the first build (used by PersistentCache)
+  fn set_known_changes(&mut self, _changes: KnownPathChanges) {}
 }
 
 pub fn new_cache(
@@ -104,7 +110,7 @@ pub fn new_cache(
 ) -> Box<dyn Cache> {
   match &compiler_option.cache {
     CacheOptions::Disabled => Box::new(DisableCache),
-    CacheOptions::Memory { .. } => Box::<MemoryCache>::default(),
+    CacheOptions::Memory { max_size, .. } => Box::new(MemoryCache::new(*max_size)),
     CacheOptions::Persistent(option) => {
       let persistent = PersistentCache::new(
         compiler_path,
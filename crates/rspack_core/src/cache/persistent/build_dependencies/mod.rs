@@ -9,6 +9,7 @@ use rustc_hash::FxHashSet as HashSet;
 
 use self::helper::{Helper, is_node_package_path};
 use super::{
+  invalidation::InvalidatedPath,
   snapshot::{Snapshot, SnapshotScope},
   storage::Storage,
 };
@@ -85,36 +86,40 @@ impl BuildDeps {
 
   /// Validate build dependencies
   ///
-  /// If any build dependencies have changed, this method will reset storage.
-  pub async fn validate(&mut self) -> Result<()> {
-    let (_, modified_files, removed_files, no_changed_files) = self
+  /// If any build dependencies have changed, this method will reset storage
+  /// and return the invalidated build dependencies.
+  pub async fn validate(&mut self) -> Result<Vec<InvalidatedPath>> {
+    let (_, invalidated_paths, no_changed_files) = self
       .snapshot
-      .calc_modified_paths(SnapshotScope::BUILD)
+      .calc_invalidated_paths(SnapshotScope::BUILD, None)
       .await?;
 
-    if !modified_files.is_empty() || !removed_files.is_empty() {
+    if !invalidated_paths.is_empty() {
       self.storage.reset().await;
 
-      tracing::info!(
-        "BuildDependencies: cache invalidate by modified_files {modified_files:?} and removed_files {removed_files:?}"
-      );
-      return Ok(());
+      tracing::info!("BuildDependencies: cache invalidate by {invalidated_paths:?}");
+      return Ok(invalidated_paths);
     }
     self.added = no_changed_files;
-    Ok(())
+    Ok(vec![])
   }
 }
 
 #[cfg(test)]
 mod test {
-  use std::{path::PathBuf, sync::Arc};
+  use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+  };
 
   use rspack_fs::{MemoryFileSystem, WritableFileSystem};
+  use rspack_paths::ArcPath;
   use rspack_storage::Storage;
 
   use super::{
     super::{
       codec::CacheCodec,
+      invalidation::InvalidationStrategy,
       snapshot::{Snapshot, SnapshotOptions, SnapshotScope},
       storage::MemoryStorage,
     },
@@ -175,10 +180,13 @@ mod test {
     fs.write("/b.js".into(), r#"require("./c")"#.as_bytes())
       .await
       .unwrap();
-    build_deps
+    let invalidated_paths = build_deps
       .validate()
       .await
       .expect("should validate success");
+    assert_eq!(invalidated_paths.len(), 1);
+    assert_eq!(invalidated_paths[0].path, ArcPath::from(Path::new("/b.js")));
+    assert_eq!(invalidated_paths[0].strategy, InvalidationStrategy::DirHash);
 
     let data = storage.load(scope).await.expect("should load success");
     assert_eq!(data.len(), 0);
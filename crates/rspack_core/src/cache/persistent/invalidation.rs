@@ -0,0 +1,154 @@
+use rspack_paths::ArcPath;
+
+use super::snapshot::{SnapshotScope, Strategy};
+use crate::{BuildModuleGraphArtifact, Logger, ModuleIdentifier, ResourceId};
+
+/// The snapshot strategy which decided that a path is invalidated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidationStrategy {
+  PackageVersion,
+  FileHash,
+  DirHash,
+  Missing,
+  Failed,
+  FileContentHash,
+}
+
+impl From<&Strategy> for InvalidationStrategy {
+  fn from(value: &Strategy) -> Self {
+    match value {
+      Strategy::PackageVersion(_) => Self::PackageVersion,
+      Strategy::FileHash { .. } => Self::FileHash,
+      Strategy::DirHash { .. } => Self::DirHash,
+      Strategy::Missing => Self::Missing,
+      Strategy::Failed => Self::Failed,
+      Strategy::FileContentHash { .. } => Self::FileContentHash,
+    }
+  }
+}
+
+impl InvalidationStrategy {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::PackageVersion => "packageVersion",
+      Self::FileHash => "fileHash",
+      Self::DirHash => "dirHash",
+      Self::Missing => "missing",
+      Self::Failed => "failed",
+      Self::FileContentHash => "fileContentHash",
+    }
+  }
+}
+
+/// How the path is invalidated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidationKind {
+  Modified,
+  Deleted,
+}
+
+impl InvalidationKind {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Modified => "modified",
+      Self::Deleted => "deleted",
+    }
+  }
+}
+
+/// A path which is invalidated by snapshot
+#[derive(Debug, Clone)]
+pub struct InvalidatedPath {
+  pub path: ArcPath,
+  pub scope: SnapshotScope,
+  pub strategy: InvalidationStrategy,
+  pub kind: InvalidationKind,
+  /// The modules rebuilt because of this path
+  pub modules: Vec<ModuleIdentifier>,
+}
+
+impl InvalidatedPath {
+  pub fn new(
+    path: ArcPath,
+    scope: SnapshotScope,
+    strategy: InvalidationStrategy,
+    kind: InvalidationKind,
+  ) -> Self {
+    Self {
+      path,
+      scope,
+      strategy,
+      kind,
+      modules: vec![],
+    }
+  }
+}
+
+/// Invalidation report of persistent cache
+///
+/// This report explains why the modules are rebuilt after a cold start.
+#[derive(Debug, Default, Clone)]
+pub struct InvalidationReport {
+  /// The invalidated build dependencies.
+  ///
+  /// Any of them will reset the whole cache, so all of modules will be rebuilt.
+  pub build_dependencies: Vec<InvalidatedPath>,
+  /// The invalidated file, context and missing dependencies.
+  pub paths: Vec<InvalidatedPath>,
+}
+
+impl InvalidationReport {
+  pub fn is_empty(&self) -> bool {
+    self.build_dependencies.is_empty() && self.paths.is_empty()
+  }
+
+  /// Fill the modules which depend on the invalidated paths from the recovered artifact.
+  pub fn collect_modules(&mut self, artifact: &BuildModuleGraphArtifact) {
+    for item in &mut self.paths {
+      let counter = match item.scope {
+        SnapshotScope::FILE => &artifact.file_dependencies,
+        SnapshotScope::CONTEXT => &artifact.context_dependencies,
+        SnapshotScope::MISSING => &artifact.missing_dependencies,
+        SnapshotScope::BUILD => &artifact.build_dependencies,
+      };
+      let Some(resource_ids) = counter.related_resource_ids(&item.path) else {
+        continue;
+      };
+      item.modules = resource_ids
+        .iter()
+        .filter_map(|id| match id {
+          ResourceId::Module(mid) => Some(*mid),
+          ResourceId::Dependency(_) => None,
+        })
+        .collect();
+      item.modules.sort_unstable();
+    }
+  }
+
+  /// Write the report to logger, it will be shown in the logging of stats.
+  ///
+  /// The structured report is available in `stats.cacheInvalidation`.
+  pub fn log(&self, logger: &impl Logger) {
+    if self.is_empty() {
+      return;
+    }
+    logger.group_collapsed(format!(
+      "cache invalidated by {} build dependencies and {} paths",
+      self.build_dependencies.len(),
+      self.paths.len()
+    ));
+    for item in self.build_dependencies.iter().chain(&self.paths) {
+      logger.log(format!(
+        "{:?} {} by {:?}, {} modules rebuilt",
+        item.kind,
+        item.path.display(),
+        item.strategy,
+        item.modules.len()
+      ));
+      for module in &item.modules {
+        logger.debug(format!("  {module}"));
+      }
+    }
+    logger.group_end();
+  }
+}
@@ -1,5 +1,6 @@
 pub mod build_dependencies;
 pub mod codec;
+pub mod invalidation;
 pub mod occasion;
 pub mod snapshot;
 pub mod storage;
@@ -21,9 +22,10 @@ use rspack_workspace::rspack_pkg_version;
 use self::{
   build_dependencies::{BuildDeps, BuildDepsOptions},
   codec::CacheCodec,
-  occasion::{MakeOccasion, MetaOccasion},
-  snapshot::{Snapshot, SnapshotOptions, SnapshotScope},
-  storage::{Storage, StorageOptions, create_storage},
+  invalidation::{InvalidationKind, InvalidationReport},
+  occasion::{ChunkRenderOccasion, CodeGenerationOccasion, MakeOccasion, MetaOccasion},
+  snapshot::{KnownPathChanges, Snapshot, SnapshotOptions, SnapshotScope},
+  storage::{MemoryStorage, Storage, StorageOptions, create_storage},
 };
 use super::Cache;
 use crate::{
@@ -51,8 +53,14 @@ pub struct PersistentCache {
   snapshot: Arc<Snapshot>,
   make_occasion: MakeOccasion,
   meta_occasion: MetaOccasion,
+  code_generation_occasion: CodeGenerationOccasion,
+  chunk_render_occasion: ChunkRenderOccasion,
   async_mode: bool,
   storage: Arc<dyn Storage>,
+  /// The paths which invalidated the cache on the cold start
+  invalidation_report: InvalidationReport,
+  /// The path changes known before the first build, e.g. from the clock of a watcher
+  known_changes: Option<Arc<KnownPathChanges>>,
   // TODO replace to logger and output warnings directly.
   warnings: Vec<String>,
 }
@@ -75,7 +83,10 @@ impl PersistentCache {
     // use codec.encode to transform the absolute path in option,
     // it will ensure that same project in different directory have the same version.
     let option_bytes = codec
-      .encode(option)
+      .encode(&PersistentCacheOptions {
+        storage: option.storage.version_options(),
+        ..option.clone()
+      })
       .expect("should persistent cache options can be serialized");
     let version = {
       let mut hasher = DefaultHasher::new();
@@ -86,7 +97,13 @@ impl PersistentCache {
       compiler_options.mode.hash(&mut hasher);
       hex::encode(hasher.finish().to_ne_bytes())
     };
-    let storage = create_storage(option.storage.clone(), version, intermediate_filesystem);
+    let mut warnings = vec![];
+    let storage = create_storage(option.storage.clone(), version, intermediate_filesystem)
+      .unwrap_or_else(|err| {
+        // the options from js are validated, fall back to a cold build for the invalid options from rust api
+        warnings.push(err.to_string());
+        Arc::new(MemoryStorage::default())
+      });
     let snapshot = Arc::new(Snapshot::new(
       option.snapshot.clone(),
       input_filesystem.clone(),
@@ -104,8 +121,12 @@ impl PersistentCache {
       ),
       snapshot,
       make_occasion: MakeOccasion::new(storage.clone(), codec.clone()),
-      meta_occasion: MetaOccasion::new(storage.clone(), codec),
-      warnings: Default::default(),
+      meta_occasion: MetaOccasion::new(storage.clone(), codec.clone()),
+      code_generation_occasion: CodeGenerationOccasion::new(storage.clone(), codec.clone()),
+      chunk_render_occasion: ChunkRenderOccasion::new(storage.clone(), codec),
+      invalidation_report: Default::default(),
+      known_changes: None,
+      warnings,
       async_mode,
       storage,
     }
@@ -117,8 +138,9 @@ impl PersistentCache {
     }
     self.initialized = true;
 
-    if let Err(err) = self.build_deps.validate().await {
-      self.warnings.push(err.to_string());
+    match self.build_deps.validate().await {
+      Ok(invalidated_paths) => self.invalidation_report.build_dependencies = invalidated_paths,
+      Err(err) => self.warnings.push(err.to_string()),
     }
     if let Err(err) = self.meta_occasion.recovery().await {
       self.warnings.push(err.to_string());
@@ -148,6 +170,10 @@ impl PersistentCache {
 
 #[async_trait::async_trait]
 impl Cache for PersistentCache {
+  fn set_known_changes(&mut self, changes: KnownPathChanges) {
+    self.known_changes = Some(Arc::new(changes));
+  }
+
   async fn before_compile(&mut self, compilation: &mut Compilation) -> bool {
     self.initialize().await;
 
@@ -157,23 +183,37 @@ impl Cache for PersistentCache {
       let mut is_hot_start = false;
       let mut modified_paths = ArcPathSet::default();
       let mut removed_paths = ArcPathSet::default();
+      let known = self.known_changes.take();
+      if let Some(known) = &known {
+        // the created and removed entries of watched directories are not in the snapshot
+        modified_paths.extend(known.modified.iter().cloned());
+        removed_paths.extend(known.removed.iter().cloned());
+      }
       let data = vec![
-        self.snapshot.calc_modified_paths(SnapshotScope::FILE).await,
         self
           .snapshot
-          .calc_modified_paths(SnapshotScope::CONTEXT)
+          .calc_invalidated_paths(SnapshotScope::FILE, known.as_ref())
+          .await,
+        self
+          .snapshot
+          .calc_invalidated_paths(SnapshotScope::CONTEXT, known.as_ref())
           .await,
         self
           .snapshot
-          .calc_modified_paths(SnapshotScope::MISSING)
+          .calc_invalidated_paths(SnapshotScope::MISSING, known.as_ref())
           .await,
       ];
       for item in data {
         match item {
-          Ok((a, b, c, _)) => {
+          Ok((a, invalidated_paths, _)) => {
             is_hot_start = is_hot_start || a;
-            modified_paths.extend(b);
-            removed_paths.extend(c);
+            for item in &invalidated_paths {
+              match item.kind {
+                InvalidationKind::Modified => modified_paths.insert(item.path.clone()),
+                InvalidationKind::Deleted => removed_paths.insert(item.path.clone()),
+              };
+            }
+            self.invalidation_report.paths.extend(invalidated_paths);
           }
           Err(err) => {
             self.warnings.push(err.to_string());
@@ -253,7 +293,10 @@ impl Cache for PersistentCache {
       BuildModuleGraphArtifactState::Uninitialized
     ) {
       match self.make_occasion.recovery().await {
-        Ok(artifact) => *make_artifact = artifact,
+        Ok(artifact) => {
+          *make_artifact = artifact;
+          self.invalidation_report.collect_modules(make_artifact);
+        }
         Err(err) => self.warnings.push(err.to_string()),
       }
     }
@@ -262,4 +305,57 @@ impl Cache for PersistentCache {
   async fn after_build_module_graph(&self, make_artifact: &BuildModuleGraphArtifact) {
     self.make_occasion.save(make_artifact);
   }
+
+  async fn before_finish_modules(&mut self, compilation: &mut Compilation) {
+    // the report only describes the cold start, rebuild will not generate it again
+    if !compilation.is_rebuild {
+      let report = std::mem::take(&mut self.invalidation_report);
+      report.log(&compilation.get_logger("rspack.persistentCache"));
+      compilation.cache_invalidation_report = Some(report);
+    }
+  }
+
+  async fn before_modules_hashes(&mut self, compilation: &mut Compilation) {
+    // the hashes of rebuild are recovered by memory cache
+    if !compilation.is_rebuild
+      && let Err(err) = self
+        .code_generation_occasion
+        .recovery_module_hashes(compilation)
+        .await
+    {
+      self.warnings.push(err.to_string());
+    }
+  }
+
+  async fn before_modules_codegen(&mut self, compilation: &mut Compilation) {
+    // the results of rebuild are recovered by memory cache
+    if !compilation.is_rebuild
+      && let Err(err) = self
+        .code_generation_occasion
+        .recovery(&compilation.code_generate_cache_artifact)
+        .await
+    {
+      self.warnings.push(err.to_string());
+    }
+  }
+
+  async fn after_modules_codegen(&self, compilation: &Compilation) {
+    self.code_generation_occasion.save(compilation);
+  }
+
+  async fn before_chunk_asset(&mut self, compilation: &mut Compilation) {
+    // the sources of rebuild are recovered by memory cache
+    if !compilation.is_rebuild
+      && let Err(err) = self
+        .chunk_render_occasion
+        .recovery(&compilation.chunk_render_cache_artifact)
+        .await
+    {
+      self.warnings.push(err.to_string());
+    }
+  }
+
+  async fn after_chunk_asset(&self, compilation: &Compilation) {
+    self.chunk_render_occasion.save(compilation);
+  }
 }
@@ -0,0 +1,88 @@
+use std::sync::{Arc, Mutex};
+
+use rspack_cacheable::{cacheable, with::AsPreset};
+use rspack_collections::{Identifier, IdentifierSet};
+use rspack_error::Result;
+use rspack_sources::BoxSource;
+
+use super::super::{Storage, codec::CacheCodec};
+use crate::{ChunkRenderCacheArtifact, Compilation};
+
+pub const SCOPE: &str = "chunk_render";
+
+/// The rendered source of a chunk.
+#[cacheable]
+struct Entry {
+  #[cacheable(with=AsPreset)]
+  source: BoxSource,
+}
+
+/// Chunk Render Occasion is used to save ChunkRenderCacheArtifact
+///
+/// The sources are keyed by the content hash of chunks, so a chunk with the
+/// same content hash can skip rendering after a cold start.
+#[derive(Debug)]
+pub struct ChunkRenderOccasion {
+  storage: Arc<dyn Storage>,
+  codec: Arc<CacheCodec>,
+  /// The keys which have been saved to storage
+  saved_keys: Mutex<IdentifierSet>,
+}
+
+impl ChunkRenderOccasion {
+  pub fn new(storage: Arc<dyn Storage>, codec: Arc<CacheCodec>) -> Self {
+    Self {
+      storage,
+      codec,
+      saved_keys: Default::default(),
+    }
+  }
+
+  #[tracing::instrument("Cache::Occasion::ChunkRender::save", skip_all)]
+  pub fn save(&self, compilation: &Compilation) {
+    let artifact = &compilation.chunk_render_cache_artifact;
+    let current_keys = compilation
+      .chunk_by_ukey
+      .values()
+      .filter_map(|chunk| chunk.content_hash(&compilation.chunk_hashes_artifact))
+      .flat_map(|content_hash| content_hash.values())
+      .map(ChunkRenderCacheArtifact::cache_key)
+      .collect::<IdentifierSet>();
+
+    let mut saved_keys = self.saved_keys.lock().expect("should get lock");
+    for key in saved_keys.difference(&current_keys) {
+      self.storage.remove(SCOPE, key.as_bytes());
+    }
+    saved_keys.retain(|key| current_keys.contains(key));
+    for key in current_keys {
+      if saved_keys.contains(&key) {
+        continue;
+      }
+      let Some(source) = artifact.get(&key) else {
+        continue;
+      };
+      self.storage.set(
+        SCOPE,
+        key.as_bytes().to_vec(),
+        self
+          .codec
+          .encode(&Entry { source })
+          .expect("should encode success"),
+      );
+      saved_keys.insert(key);
+    }
+  }
+
+  #[tracing::instrument("Cache::Occasion::ChunkRender::recovery", skip_all)]
+  pub async fn recovery(&self, artifact: &ChunkRenderCacheArtifact) -> Result<()> {
+    let data = self.storage.load(SCOPE).await?;
+    let mut saved_keys = self.saved_keys.lock().expect("should get lock");
+    for (key, value) in data {
+      let key = Identifier::from(String::from_utf8_lossy(&key).as_ref());
+      let entry: Entry = self.codec.decode(&value)?;
+      artifact.set(key, entry.source);
+      saved_keys.insert(key);
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,398 @@
+use std::sync::{Arc, Mutex};
+
+use rspack_cacheable::{
+  cacheable,
+  with::{AsCacheable, AsPreset, AsTuple2, AsVec},
+};
+use rspack_collections::{Identifier, IdentifierMap, IdentifierSet};
+use rspack_error::Result;
+use rspack_hash::RspackHashDigest;
+use rspack_sources::BoxSource;
+
+use super::super::{Storage, codec::CacheCodec};
+use crate::{
+  ChunkGraph, CodeGenerateCacheArtifact, CodeGenerationResult, Compilation, Module, RuntimeGlobals,
+  RuntimeSpecMap, SourceType, get_runtime_key,
+  incremental::compute_affected_modules_with_module_graph,
+};
+
+pub const SCOPE: &str = "code_generation";
+pub const MODULE_HASHES_SCOPE: &str = "module_hashes";
+
+/// The cacheable part of CodeGenerationResult.
+#[cacheable]
+struct Entry {
+  #[cacheable(with=AsVec<AsTuple2<AsCacheable, AsPreset>>)]
+  sources: Vec<(SourceType, BoxSource)>,
+  runtime_requirements: RuntimeGlobals,
+  hash: Option<RspackHashDigest>,
+}
+
+impl Entry {
+  /// Results with code generation data, chunk init fragments or concatenation scope
+  /// can not be serialized, they will be generated again.
+  fn from_result(result: &CodeGenerationResult) -> Option<Self> {
+    if !result.data.is_empty()
+      || !result.chunk_init_fragments.is_empty()
+      || result.concatenation_scope.is_some()
+    {
+      return None;
+    }
+    Some(Self {
+      sources: result
+        .inner()
+        .iter()
+        .map(|(source_type, source)| (*source_type, source.clone()))
+        .collect(),
+      runtime_requirements: result.runtime_requirements,
+      hash: result.hash.clone(),
+    })
+  }
+
+  fn into_result(self) -> CodeGenerationResult {
+    let mut result = CodeGenerationResult::default();
+    for (source_type, source) in self.sources {
+      result.add(source_type, source);
+    }
+    result.runtime_requirements = self.runtime_requirements;
+    result.hash = self.hash;
+    result
+  }
+}
+
+/// The module hash of a runtime.
+#[cacheable]
+#[derive(Debug, PartialEq, Eq)]
+struct RuntimeHash {
+  runtime: String,
+  /// The module graph hash is a part of the module hash which may change without rebuilding
+  /// the module, e.g. the module ids, async flags and exports info of the module and its references.
+  graph_hash: u64,
+  hash: RspackHashDigest,
+}
+
+/// The module hashes of all runtimes.
+#[cacheable]
+#[derive(Debug, PartialEq, Eq)]
+struct ModuleHashesEntry {
+  /// The chunk ids loaded by each async block of the module.
+  block_chunk_ids: Vec<Vec<String>>,
+  /// Sorted by runtime key.
+  hashes: Vec<RuntimeHash>,
+}
+
+impl ModuleHashesEntry {
+  fn block_chunk_ids(compilation: &Compilation, module: &dyn Module) -> Vec<Vec<String>> {
+    module
+      .get_blocks()
+      .iter()
+      .map(|block| {
+        compilation
+          .chunk_graph
+          .get_block_chunk_group(block, &compilation.chunk_group_by_ukey)
+          .map(|group| {
+            group
+              .chunks
+              .iter()
+              .filter_map(|chunk| {
+                compilation
+                  .chunk_by_ukey
+                  .expect_get(chunk)
+                  .id()
+                  .map(|id| id.as_str().to_string())
+              })
+              .collect()
+          })
+          .unwrap_or_default()
+      })
+      .collect()
+  }
+
+  /// Concatenated modules are skipped, their hashes depend on the inner modules.
+  fn from_compilation(
+    compilation: &Compilation,
+    module: &dyn Module,
+    saved: Option<&Self>,
+  ) -> Option<Self> {
+    if module.as_concatenated_module().is_some() {
+      return None;
+    }
+    let identifier = module.identifier();
+    let mut runtime_hashes = compilation
+      .chunk_graph
+      .get_module_runtimes(identifier, &compilation.chunk_by_ukey)
+      .values()
+      .map(|runtime| {
+        let hash = compilation.cgm_hash_artifact.get(&identifier, runtime)?;
+        Some((runtime, hash))
+      })
+      .collect::<Option<Vec<_>>>()?;
+    runtime_hashes.sort_unstable_by(|(a, _), (b, _)| get_runtime_key(a).cmp(get_runtime_key(b)));
+    let block_chunk_ids = Self::block_chunk_ids(compilation, module);
+
+    // the graph hash is a part of the module hash, reuse it when the module hash does not change
+    if let Some(saved) = saved
+      && saved.block_chunk_ids == block_chunk_ids
+      && saved.hashes.len() == runtime_hashes.len()
+      && saved
+        .hashes
+        .iter()
+        .zip(&runtime_hashes)
+        .all(|(saved, (runtime, hash))| {
+          &saved.runtime == get_runtime_key(runtime) && &saved.hash == *hash
+        })
+    {
+      return None;
+    }
+
+    Some(Self {
+      block_chunk_ids,
+      hashes: runtime_hashes
+        .into_iter()
+        .map(|(runtime, hash)| RuntimeHash {
+          runtime: get_runtime_key(runtime).clone(),
+          graph_hash: compilation.chunk_graph.get_module_graph_hash(
+            module,
+            compilation,
+            Some(runtime),
+          ),
+          hash: hash.clone(),
+        })
+        .collect(),
+    })
+  }
+
+  /// Returns the module hashes if the module is still placed in the same runtimes
+  /// and the parts of the hash outside the module do not change.
+  fn to_hashes(
+    &self,
+    compilation: &Compilation,
+    module: &dyn Module,
+  ) -> Option<RuntimeSpecMap<RspackHashDigest>> {
+    if Self::block_chunk_ids(compilation, module) != self.block_chunk_ids {
+      return None;
+    }
+    let runtimes = compilation
+      .chunk_graph
+      .get_module_runtimes(module.identifier(), &compilation.chunk_by_ukey);
+    if runtimes.len() != self.hashes.len() {
+      return None;
+    }
+    let mut hashes = RuntimeSpecMap::new();
+    for item in &self.hashes {
+      let runtime = runtimes
+        .values()
+        .find(|runtime| get_runtime_key(runtime) == &item.runtime)?;
+      if compilation
+        .chunk_graph
+        .get_module_graph_hash(module, compilation, Some(runtime))
+        != item.graph_hash
+      {
+        return None;
+      }
+      hashes.set(runtime.clone(), item.hash.clone());
+    }
+    Some(hashes)
+  }
+}
+
+/// Code Generation Occasion is used to save CodeGenerateCacheArtifact
+///
+/// The results are keyed by module identifier and module hash of each runtime,
+/// so a module with the same hash can skip code generation after a cold start.
+/// The module hashes are saved along with them, so the unaffected modules can
+/// skip hashing as well.
+#[derive(Debug)]
+pub struct CodeGenerationOccasion {
+  storage: Arc<dyn Storage>,
+  codec: Arc<CacheCodec>,
+  /// The keys which have been saved to storage
+  saved_keys: Mutex<IdentifierSet>,
+  /// The module hashes which have been saved to storage
+  saved_module_hashes: Mutex<IdentifierMap<ModuleHashesEntry>>,
+}
+
+impl CodeGenerationOccasion {
+  pub fn new(storage: Arc<dyn Storage>, codec: Arc<CacheCodec>) -> Self {
+    Self {
+      storage,
+      codec,
+      saved_keys: Default::default(),
+      saved_module_hashes: Default::default(),
+    }
+  }
+
+  #[tracing::instrument("Cache::Occasion::CodeGeneration::save", skip_all)]
+  pub fn save(&self, compilation: &Compilation) {
+    let artifact = &compilation.code_generate_cache_artifact;
+    let mut current_keys = IdentifierSet::default();
+    for module in compilation.code_generation_results.inner().0.keys() {
+      for runtime in compilation
+        .chunk_graph
+        .get_module_runtimes_iter(*module, &compilation.chunk_by_ukey)
+      {
+        if let Some(hash) = ChunkGraph::get_module_hash(compilation, *module, runtime) {
+          current_keys.insert(CodeGenerateCacheArtifact::cache_key(module, hash));
+        }
+      }
+    }
+
+    let mut saved_keys = self.saved_keys.lock().expect("should get lock");
+    for key in saved_keys.difference(&current_keys) {
+      self.storage.remove(SCOPE, key.as_bytes());
+    }
+    saved_keys.retain(|key| current_keys.contains(key));
+    for key in current_keys {
+      if saved_keys.contains(&key) {
+        continue;
+      }
+      let Some(entry) = artifact
+        .get(&key)
+        .and_then(|result| Entry::from_result(&result))
+      else {
+        continue;
+      };
+      self.storage.set(
+        SCOPE,
+        key.as_bytes().to_vec(),
+        self.codec.encode(&entry).expect("should encode success"),
+      );
+      saved_keys.insert(key);
+    }
+    drop(saved_keys);
+
+    self.save_module_hashes(compilation);
+  }
+
+  fn save_module_hashes(&self, compilation: &Compilation) {
+    let modules = compilation.get_module_graph().modules();
+    let mut saved = self.saved_module_hashes.lock().expect("should get lock");
+    saved.retain(|identifier, _| {
+      let exist = modules.contains_key(identifier);
+      if !exist {
+        self
+          .storage
+          .remove(MODULE_HASHES_SCOPE, identifier.as_bytes());
+      }
+      exist
+    });
+    for (identifier, module) in modules {
+      let Some(entry) =
+        ModuleHashesEntry::from_compilation(compilation, module.as_ref(), saved.get(&identifier))
+      else {
+        continue;
+      };
+      self.storage.set(
+        MODULE_HASHES_SCOPE,
+        identifier.as_bytes().to_vec(),
+        self.codec.encode(&entry).expect("should encode success"),
+      );
+      saved.insert(identifier, entry);
+    }
+  }
+
+  #[tracing::instrument("Cache::Occasion::CodeGeneration::recovery", skip_all)]
+  pub async fn recovery(&self, artifact: &CodeGenerateCacheArtifact) -> Result<()> {
+    let data = self.storage.load(SCOPE).await?;
+    let mut saved_keys = self.saved_keys.lock().expect("should get lock");
+    for (key, value) in data {
+      let key = Identifier::from(String::from_utf8_lossy(&key).as_ref());
+      let entry: Entry = self.codec.decode(&value)?;
+      artifact.set(key, entry.into_result());
+      saved_keys.insert(key);
+    }
+    Ok(())
+  }
+
+  /// Recover the hashes of the modules which are not affected by the modified files.
+  #[tracing::instrument("Cache::Occasion::CodeGeneration::recovery_module_hashes", skip_all)]
+  pub async fn recovery_module_hashes(&self, compilation: &mut Compilation) -> Result<()> {
+    let data = self.storage.load(MODULE_HASHES_SCOPE).await?;
+    let mg = compilation.get_module_graph();
+    let make_artifact = &compilation.build_module_graph_artifact;
+    let affected_modules = compute_affected_modules_with_module_graph(
+      mg,
+      make_artifact.built_modules().copied().collect(),
+      make_artifact
+        .affected_dependencies
+        .active()
+        .copied()
+        .collect(),
+    );
+
+    let mut recovered = vec![];
+    let mut saved = self.saved_module_hashes.lock().expect("should get lock");
+    for (key, value) in data {
+      let identifier = Identifier::from(String::from_utf8_lossy(&key).as_ref());
+      let entry: ModuleHashesEntry = self.codec.decode(&value)?;
+      if !affected_modules.contains(&identifier)
+        && let Some(module) = mg.module_by_identifier(&identifier)
+        && module.as_concatenated_module().is_none()
+        && let Some(hashes) = entry.to_hashes(compilation, module.as_ref())
+      {
+        recovered.push((identifier, hashes));
+      }
+      saved.insert(identifier, entry);
+    }
+    drop(saved);
+
+    for (identifier, hashes) in recovered {
+      ChunkGraph::set_module_hashes(compilation, identifier, hashes);
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rspack_sources::{RawStringSource, SourceExt};
+
+  use super::*;
+
+  #[test]
+  fn entry_round_trip() {
+    let codec = CacheCodec::new(None);
+    let mut result = CodeGenerationResult::default()
+      .with_javascript(RawStringSource::from_static("console.log(1)").boxed());
+    result.runtime_requirements = RuntimeGlobals::REQUIRE;
+    result.hash = Some(RspackHashDigest::from("abc"));
+
+    let entry = Entry::from_result(&result).expect("should be cacheable");
+    let bytes = codec.encode(&entry).expect("should encode success");
+    let entry: Entry = codec.decode(&bytes).expect("should decode success");
+    let recovered = entry.into_result();
+    assert_eq!(
+      recovered
+        .get(&SourceType::JavaScript)
+        .expect("should have javascript source")
+        .source()
+        .into_string_lossy(),
+      "console.log(1)"
+    );
+    assert_eq!(recovered.runtime_requirements, RuntimeGlobals::REQUIRE);
+    assert_eq!(recovered.hash, result.hash);
+  }
+
+  #[test]
+  fn module_hashes_entry_round_trip() {
+    let codec = CacheCodec::new(None);
+    let entry = ModuleHashesEntry {
+      block_chunk_ids: vec![vec!["a".into(), "b".into()], vec![]],
+      hashes: vec![
+        RuntimeHash {
+          runtime: "main".into(),
+          graph_hash: 1,
+          hash: RspackHashDigest::from("abc"),
+        },
+        RuntimeHash {
+          runtime: "other".into(),
+          graph_hash: 2,
+          hash: RspackHashDigest::from("def"),
+        },
+      ],
+    };
+    let bytes = codec.encode(&entry).expect("should encode success");
+    let recovered: ModuleHashesEntry = codec.decode(&bytes).expect("should decode success");
+    assert_eq!(recovered, entry);
+  }
+}
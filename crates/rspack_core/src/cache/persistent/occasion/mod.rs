@@ -1,5 +1,9 @@
+pub mod chunk_render;
+pub mod code_generation;
 pub mod make;
 pub mod meta;
 
+pub use chunk_render::ChunkRenderOccasion;
+pub use code_generation::CodeGenerationOccasion;
 pub use make::MakeOccasion;
 pub use meta::MetaOccasion;
@@ -14,9 +14,36 @@ pub use self::{
   scope::SnapshotScope,
   strategy::Strategy,
 };
-use super::{codec::CacheCodec, storage::Storage};
+use super::{
+  codec::CacheCodec,
+  invalidation::{InvalidatedPath, InvalidationKind},
+  storage::Storage,
+};
 use crate::FutureConsumer;
 
+/// The path changes known without validating the snapshot, e.g. from the clock saved by a watcher.
+#[derive(Debug, Default)]
+pub struct KnownPathChanges {
+  /// The paths whose changes are known, the other paths are still validated by their strategy.
+  pub watched: ArcPathSet,
+  pub modified: ArcPathSet,
+  pub removed: ArcPathSet,
+}
+
+impl KnownPathChanges {
+  fn validate(&self, path: &ArcPath) -> Option<ValidateResult> {
+    if self.removed.contains(path) {
+      Some(ValidateResult::Deleted)
+    } else if self.modified.contains(path) {
+      Some(ValidateResult::Modified)
+    } else if self.watched.contains(path) {
+      Some(ValidateResult::NoChanged)
+    } else {
+      None
+    }
+  }
+}
+
 /// Snapshot is used to check if files have been modified or deleted.
 ///
 /// Snapshot will generate `Strategy` for target file, and check the modification
@@ -60,6 +87,7 @@ impl Snapshot {
       return Some(v);
     }
     Some(match scope {
+      SnapshotScope::FILE if options.is_content_hash_only() => helper.file_content_hash(path).await,
       SnapshotScope::FILE => helper.file_hash(path).await,
       SnapshotScope::MISSING => Strategy::Missing,
       SnapshotScope::CONTEXT | SnapshotScope::BUILD => helper.dir_hash(path).await,
@@ -100,14 +128,36 @@ impl Snapshot {
     }
   }
 
+  #[cfg(test)]
   #[allow(clippy::type_complexity)]
   #[tracing::instrument("Cache::Snapshot::calc_modified_path", skip_all)]
   pub async fn calc_modified_paths(
     &self,
     scope: SnapshotScope,
   ) -> Result<(bool, ArcPathSet, ArcPathSet, ArcPathSet)> {
+    let (is_hot_start, invalidated_paths, no_change_path) =
+      self.calc_invalidated_paths(scope, None).await?;
     let mut modified_path = ArcPathSet::default();
     let mut deleted_path = ArcPathSet::default();
+    for item in invalidated_paths {
+      match item.kind {
+        InvalidationKind::Modified => modified_path.insert(item.path),
+        InvalidationKind::Deleted => deleted_path.insert(item.path),
+      };
+    }
+    Ok((is_hot_start, modified_path, deleted_path, no_change_path))
+  }
+
+  /// Calculate the invalidated paths with the strategy which decided it.
+  ///
+  /// The paths in `known` changes are not validated again.
+  #[tracing::instrument("Cache::Snapshot::calc_invalidated_paths", skip_all)]
+  pub async fn calc_invalidated_paths(
+    &self,
+    scope: SnapshotScope,
+    known: Option<&Arc<KnownPathChanges>>,
+  ) -> Result<(bool, Vec<InvalidatedPath>, ArcPathSet)> {
+    let mut invalidated_paths = vec![];
     let mut no_change_path = ArcPathSet::default();
     let helper = Arc::new(StrategyHelper::new(self.fs.clone()));
     let codec = self.codec.clone();
@@ -119,27 +169,31 @@ impl Snapshot {
       .map(|(key, value)| {
         let helper = helper.clone();
         let codec = codec.clone();
+        let known = known.cloned();
         async move {
           let path: ArcPath = codec.decode(&key).expect("should decode success");
           let strategy: Strategy = codec.decode(&value).expect("should decode success");
-          let validate = helper.validate(&path, &strategy).await;
-          (path, validate)
+          let validate = match known.and_then(|known| known.validate(&path)) {
+            Some(validate) => validate,
+            None => helper.validate(&path, &strategy).await,
+          };
+          (path, strategy, validate)
         }
       })
-      .fut_consume(|(path, validate)| match validate {
-        ValidateResult::Modified => {
-          modified_path.insert(path);
-        }
-        ValidateResult::Deleted => {
-          deleted_path.insert(path);
-        }
-        ValidateResult::NoChanged => {
-          no_change_path.insert(path);
-        }
+      .fut_consume(|(path, strategy, validate)| {
+        let kind = match validate {
+          ValidateResult::Modified => InvalidationKind::Modified,
+          ValidateResult::Deleted => InvalidationKind::Deleted,
+          ValidateResult::NoChanged => {
+            no_change_path.insert(path);
+            return;
+          }
+        };
+        invalidated_paths.push(InvalidatedPath::new(path, scope, (&strategy).into(), kind));
       })
       .await;
 
-    Ok((is_hot_start, modified_path, deleted_path, no_change_path))
+    Ok((is_hot_start, invalidated_paths, no_change_path))
   }
 }
 
@@ -148,11 +202,11 @@ mod tests {
   use std::sync::Arc;
 
   use rspack_fs::{MemoryFileSystem, WritableFileSystem};
-  use rspack_paths::ArcPath;
+  use rspack_paths::{ArcPath, ArcPathSet};
 
   use super::{
     super::{codec::CacheCodec, storage::MemoryStorage},
-    PathMatcher, Snapshot, SnapshotOptions, SnapshotScope,
+    KnownPathChanges, PathMatcher, Snapshot, SnapshotOptions, SnapshotScope,
   };
 
   macro_rules! p {
@@ -258,4 +312,46 @@ mod tests {
     assert!(modified_paths.contains(&p!("/node_modules/lib/file1")));
     assert_eq!(no_change_paths.len(), 1);
   }
+
+  #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+  async fn should_trust_known_changes() {
+    let fs = Arc::new(MemoryFileSystem::default());
+    let storage = Arc::new(MemoryStorage::default());
+    let codec = Arc::new(CacheCodec::new(None));
+    let options = SnapshotOptions::new(vec![], vec![], vec![]);
+    for file in ["/file1", "/file2", "/file3"] {
+      fs.write(file.into(), "abc".as_bytes()).await.unwrap();
+    }
+
+    let snapshot = Snapshot::new(options, fs.clone(), storage, codec);
+    snapshot
+      .add(
+        SnapshotScope::FILE,
+        [p!("/file1"), p!("/file2"), p!("/file3")].into_iter(),
+      )
+      .await;
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    fs.write("/file1".into(), "abcd".as_bytes()).await.unwrap();
+    fs.write("/file3".into(), "abcd".as_bytes()).await.unwrap();
+
+    let known = Arc::new(KnownPathChanges {
+      watched: [p!("/file1"), p!("/file2")].into_iter().collect(),
+      modified: [p!("/file2")].into_iter().collect(),
+      removed: Default::default(),
+    });
+    let (is_hot_start, invalidated_paths, no_change_paths) = snapshot
+      .calc_invalidated_paths(SnapshotScope::FILE, Some(&known))
+      .await
+      .unwrap();
+    assert!(is_hot_start);
+    let invalidated_paths = invalidated_paths
+      .into_iter()
+      .map(|item| item.path)
+      .collect::<ArcPathSet>();
+    // file1 is trusted as unchanged, file2 is trusted as modified and file3 is not watched
+    assert_eq!(invalidated_paths.len(), 2);
+    assert!(invalidated_paths.contains(&p!("/file2")));
+    assert!(invalidated_paths.contains(&p!("/file3")));
+    assert!(no_change_paths.contains(&p!("/file1")));
+  }
 }
@@ -29,6 +29,9 @@ pub struct SnapshotOptions {
   unmanaged_paths: Vec<PathMatcher>,
   /// managed_paths, snapshot will use lib version strategy
   managed_paths: Vec<PathMatcher>,
+  /// snapshot will only compare the content hash of files and ignore
+  /// the modified time, the hashes are read from git index if possible
+  content_hash_only: bool,
 }
 
 impl SnapshotOptions {
@@ -41,9 +44,19 @@ impl SnapshotOptions {
       immutable_paths,
       unmanaged_paths,
       managed_paths,
+      content_hash_only: false,
     }
   }
 
+  pub fn with_content_hash_only(mut self, content_hash_only: bool) -> Self {
+    self.content_hash_only = content_hash_only;
+    self
+  }
+
+  pub fn is_content_hash_only(&self) -> bool {
+    self.content_hash_only
+  }
+
   pub fn is_immutable_path(&self, path_str: &str) -> bool {
     for item in &self.immutable_paths {
       if item.try_match(path_str) {
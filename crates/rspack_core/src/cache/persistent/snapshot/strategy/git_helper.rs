@@ -0,0 +1,251 @@
+use std::{path::Path, process::Command, sync::Arc};
+
+use rspack_fs::ReadableFileSystem;
+use rspack_paths::{ArcPath, ArcPathDashMap, AssertUtf8};
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use sha1::{Digest, Sha1};
+use tokio::sync::OnceCell;
+
+type GitIndex = HashMap<ArcPath, String>;
+
+/// Calculate the git blob object id of the content.
+///
+/// It is the same as the output of `git hash-object`, so the object ids in
+/// the git index can be used directly.
+pub fn git_blob_hash(content: &[u8]) -> String {
+  let mut hasher = Sha1::new();
+  hasher.update(format!("blob {}\0", content.len()).as_bytes());
+  hasher.update(content);
+  hex::encode(hasher.finalize())
+}
+
+/// Parse the output of `git ls-files -s -z` and `git ls-files -m -z`.
+///
+/// Files modified or deleted in the work tree, symlinks, submodules and
+/// conflicted files are skipped, they should be hashed from the content.
+fn parse_git_index(root: &Path, staged: &[u8], modified: &[u8]) -> GitIndex {
+  let modified = modified.split(|c| *c == 0).collect::<HashSet<_>>();
+  let mut index = GitIndex::default();
+  for entry in staged.split(|c| *c == 0) {
+    // <mode> SP <object> SP <stage> TAB <file>
+    let Some(tab_pos) = entry.iter().position(|c| *c == b'\t') else {
+      continue;
+    };
+    let (info, file) = (&entry[..tab_pos], &entry[tab_pos + 1..]);
+    if modified.contains(file) {
+      continue;
+    }
+    let mut info = info.split(|c| *c == b' ');
+    let (Some(mode), Some(oid), Some(stage)) = (info.next(), info.next(), info.next()) else {
+      continue;
+    };
+    if !matches!(mode, b"100644" | b"100755") || stage != b"0" {
+      continue;
+    }
+    let (Ok(file), Ok(oid)) = (std::str::from_utf8(file), std::str::from_utf8(oid)) else {
+      continue;
+    };
+    // git always uses `/` as separator
+    let path = file
+      .split('/')
+      .fold(root.to_path_buf(), |path, item| path.join(item));
+    index.insert(ArcPath::from(path), oid.to_string());
+  }
+  index
+}
+
+fn read_git_index(root: &Path) -> Option<GitIndex> {
+  let run = |args: &[&str]| {
+    let output = Command::new("git")
+      .arg("-C")
+      .arg(root)
+      .args(args)
+      .output()
+      .ok()?;
+    output.status.success().then_some(output.stdout)
+  };
+  let staged = run(&["ls-files", "-s", "-z"])?;
+  let modified = run(&["ls-files", "-m", "-z"])?;
+  Some(parse_git_index(root, &staged, &modified))
+}
+
+/// A helper for computing git blob object ids of files.
+///
+/// The object ids of files which are not modified in the work tree can be
+/// read from the git index, so that a fresh clone with new mtimes does not
+/// need to read and hash all of files. Files affected by git filters such as
+/// `core.autocrlf` may get a different object id from the content, they
+/// will be treated as modified.
+#[derive(Debug)]
+pub struct GitHelper {
+  /// File system abstraction for reading file contents.
+  fs: Arc<dyn ReadableFileSystem>,
+
+  /// Cache for the repository root of directories.
+  root_cache: ArcPathDashMap<Option<ArcPath>>,
+  /// Cache for the git index of repository roots.
+  index_cache: ArcPathDashMap<Arc<OnceCell<Option<GitIndex>>>>,
+  /// Cache for object ids calculated from file contents.
+  file_cache: ArcPathDashMap<Option<String>>,
+}
+
+impl GitHelper {
+  /// Creates a new GitHelper instance with the given file system.
+  pub fn new(fs: Arc<dyn ReadableFileSystem>) -> Self {
+    Self {
+      fs,
+      root_cache: Default::default(),
+      index_cache: Default::default(),
+      file_cache: Default::default(),
+    }
+  }
+
+  /// Find the repository root which contains the directory.
+  #[async_recursion::async_recursion]
+  async fn repository_root(&self, dir: &ArcPath) -> Option<ArcPath> {
+    if let Some(root) = self.root_cache.get(dir) {
+      return root.clone();
+    }
+
+    let root = if self
+      .fs
+      .metadata(&dir.join(".git").assert_utf8())
+      .await
+      .is_ok()
+    {
+      Some(dir.clone())
+    } else if let Some(parent) = dir.parent() {
+      self.repository_root(&ArcPath::from(parent)).await
+    } else {
+      None
+    };
+    self.root_cache.insert(dir.clone(), root.clone());
+    root
+  }
+
+  /// Get the object id from the git index, return None if the file is not indexed.
+  async fn indexed_object_id(&self, path: &ArcPath) -> Option<String> {
+    let root = self.repository_root(&ArcPath::from(path.parent()?)).await?;
+    let cell = self.index_cache.entry(root.clone()).or_default().clone();
+    let index = cell
+      .get_or_init(|| async move {
+        tokio::task::spawn_blocking(move || read_git_index(&root))
+          .await
+          .ok()
+          .flatten()
+      })
+      .await;
+    index.as_ref()?.get(path).cloned()
+  }
+
+  /// Get the object id calculated from the file content.
+  ///
+  /// Return an empty string for directories and symlinks, return None if
+  /// the file does not exist.
+  pub async fn object_id(&self, path: &ArcPath) -> Option<String> {
+    if let Some(hash) = self.file_cache.get(path) {
+      return hash.clone();
+    }
+
+    let utf8_path = path.assert_utf8();
+    let hash = match self.fs.metadata(utf8_path).await {
+      Ok(metadata) if metadata.is_file && !metadata.is_symlink => self
+        .fs
+        .read(utf8_path)
+        .await
+        .ok()
+        .map(|c| git_blob_hash(&c)),
+      Ok(_) => Some(String::new()),
+      Err(_) => None,
+    };
+    self.file_cache.insert(path.clone(), hash.clone());
+    hash
+  }
+
+  /// Get the object id from the git index first, and fall back to the file content.
+  pub async fn current_object_id(&self, path: &ArcPath) -> Option<String> {
+    if let Some(hash) = self.indexed_object_id(path).await {
+      return Some(hash);
+    }
+    self.object_id(path).await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{path::Path, sync::Arc};
+
+  use rspack_fs::{MemoryFileSystem, WritableFileSystem};
+  use rspack_paths::ArcPath;
+
+  use super::{GitHelper, git_blob_hash, parse_git_index};
+
+  #[test]
+  fn blob_hash() {
+    assert_eq!(
+      git_blob_hash("abc".as_bytes()),
+      "f2ba8f84ab5c1bce84a7b441cb1959cfc7093b7f"
+    );
+    assert_eq!(
+      git_blob_hash("abcd".as_bytes()),
+      "85df50785d62d3b05ab03d9cbf7e4a0b49449730"
+    );
+  }
+
+  #[test]
+  fn parse_index() {
+    let staged = [
+      "100644 f2ba8f84ab5c1bce84a7b441cb1959cfc7093b7f 0\tsrc/a.js",
+      "100755 85df50785d62d3b05ab03d9cbf7e4a0b49449730 0\tbin/run",
+      "100644 85df50785d62d3b05ab03d9cbf7e4a0b49449730 0\tsrc/modified.js",
+      "120000 85df50785d62d3b05ab03d9cbf7e4a0b49449730 0\tlink",
+      "160000 85df50785d62d3b05ab03d9cbf7e4a0b49449730 0\tsubmodule",
+      "100644 85df50785d62d3b05ab03d9cbf7e4a0b49449730 1\tconflict.js",
+      "",
+    ]
+    .join("\0");
+    let modified = ["src/modified.js", ""].join("\0");
+    let index = parse_git_index(Path::new("/root"), staged.as_bytes(), modified.as_bytes());
+    assert_eq!(index.len(), 2);
+    assert_eq!(
+      index.get(&ArcPath::from("/root/src/a.js")).unwrap(),
+      "f2ba8f84ab5c1bce84a7b441cb1959cfc7093b7f"
+    );
+    assert_eq!(
+      index.get(&ArcPath::from("/root/bin/run")).unwrap(),
+      "85df50785d62d3b05ab03d9cbf7e4a0b49449730"
+    );
+  }
+
+  #[tokio::test]
+  async fn object_id() {
+    let fs = Arc::new(MemoryFileSystem::default());
+    fs.create_dir_all("/a".into()).await.unwrap();
+    fs.write("/a/file.js".into(), "abc".as_bytes())
+      .await
+      .unwrap();
+
+    // no repository in memory fs, fall back to the file content
+    let helper = GitHelper::new(fs.clone());
+    assert_eq!(
+      helper
+        .current_object_id(&ArcPath::from("/a/file.js"))
+        .await
+        .unwrap(),
+      "f2ba8f84ab5c1bce84a7b441cb1959cfc7093b7f"
+    );
+    assert_eq!(
+      helper
+        .current_object_id(&ArcPath::from("/a"))
+        .await
+        .unwrap(),
+      ""
+    );
+    assert!(
+      helper
+        .current_object_id(&ArcPath::from("/a/not_exist.js"))
+        .await
+        .is_none()
+    );
+  }
+}
@@ -1,3 +1,4 @@
+mod git_helper;
 mod hash_helper;
 mod package_helper;
 
@@ -8,6 +9,7 @@ use rspack_fs::ReadableFileSystem;
 use rspack_paths::{ArcPath, AssertUtf8};
 
 use self::{
+  git_helper::GitHelper,
   hash_helper::{ContentHash, HashHelper},
   package_helper::PackageHelper,
 };
@@ -44,6 +46,13 @@ pub enum Strategy {
   /// This strategy represents a snapshot that could not be created or
   /// validated correctly and should be treated as invalid.
   Failed,
+
+  /// Check by file content hash only
+  ///
+  /// This strategy ignores the modified time and compares the git blob
+  /// object id of the file, which can be read from the git index without
+  /// hashing the file content.
+  FileContentHash { hash: String },
 }
 
 impl PartialEq for Strategy {
@@ -54,6 +63,7 @@ impl PartialEq for Strategy {
       (Self::DirHash { hash: h1, .. }, Self::DirHash { hash: h2, .. }) => h1 == h2,
       (Self::Missing, Self::Missing) => true,
       (Self::Failed, Self::Failed) => true,
+      (Self::FileContentHash { hash: h1 }, Self::FileContentHash { hash: h2 }) => h1 == h2,
       _ => false,
     }
   }
@@ -74,6 +84,7 @@ pub struct StrategyHelper {
   fs: Arc<dyn ReadableFileSystem>,
   package_helper: PackageHelper,
   hash_helper: HashHelper,
+  git_helper: GitHelper,
 }
 
 impl StrategyHelper {
@@ -81,7 +92,8 @@ impl StrategyHelper {
     Self {
       fs: fs.clone(),
       package_helper: PackageHelper::new(fs.clone()),
-      hash_helper: HashHelper::new(fs),
+      hash_helper: HashHelper::new(fs.clone()),
+      git_helper: GitHelper::new(fs),
     }
   }
 
@@ -117,6 +129,15 @@ impl StrategyHelper {
     }
   }
 
+  /// get path file content hash strategy
+  pub async fn file_content_hash(&self, path: &ArcPath) -> Strategy {
+    if let Some(hash) = self.git_helper.object_id(path).await {
+      Strategy::FileContentHash { hash }
+    } else {
+      Strategy::Failed
+    }
+  }
+
   /// get path context hash strategy
   pub async fn dir_hash(&self, path: &ArcPath) -> Strategy {
     if let Some(ContentHash { hash, .. }) = self.hash_helper.dir_hash(path).await {
@@ -174,6 +195,16 @@ impl StrategyHelper {
         }
       }
       Strategy::Failed => ValidateResult::Modified,
+      Strategy::FileContentHash { hash } => {
+        let Some(cur_hash) = self.git_helper.current_object_id(path).await else {
+          return ValidateResult::Deleted;
+        };
+        if &cur_hash == hash {
+          ValidateResult::NoChanged
+        } else {
+          ValidateResult::Modified
+        }
+      }
     }
   }
 }
@@ -289,6 +320,57 @@ mod tests {
     ));
   }
 
+  #[tokio::test]
+  async fn validate_file_content_hash() {
+    let fs = Arc::new(MemoryFileSystem::default());
+    fs.create_dir_all("/".into()).await.unwrap();
+    fs.write("/file1.js".into(), "abc".as_bytes())
+      .await
+      .unwrap();
+
+    let helper = StrategyHelper::new(fs.clone());
+    let strategy = helper.file_content_hash(&ArcPath::from("/file1.js")).await;
+    assert_eq!(
+      strategy,
+      Strategy::FileContentHash {
+        hash: "f2ba8f84ab5c1bce84a7b441cb1959cfc7093b7f".into()
+      }
+    );
+
+    // rewrite with the same content, mtime is ignored
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    let helper = StrategyHelper::new(fs.clone());
+    fs.write("/file1.js".into(), "abc".as_bytes())
+      .await
+      .unwrap();
+    assert!(matches!(
+      helper
+        .validate(&ArcPath::from("/file1.js"), &strategy)
+        .await,
+      ValidateResult::NoChanged
+    ));
+
+    let helper = StrategyHelper::new(fs.clone());
+    fs.write("/file1.js".into(), "abcd".as_bytes())
+      .await
+      .unwrap();
+    assert!(matches!(
+      helper
+        .validate(&ArcPath::from("/file1.js"), &strategy)
+        .await,
+      ValidateResult::Modified
+    ));
+
+    let helper = StrategyHelper::new(fs.clone());
+    fs.remove_file("/file1.js".into()).await.unwrap();
+    assert!(matches!(
+      helper
+        .validate(&ArcPath::from("/file1.js"), &strategy)
+        .await,
+      ValidateResult::Deleted
+    ));
+  }
+
   #[tokio::test]
   async fn validate_missing() {
     let fs = Arc::new(MemoryFileSystem::default());
@@ -1,13 +1,70 @@
-// TODO add #[cfg(test)]
 mod memory;
 
-use std::{path::PathBuf, sync::Arc};
+use std::{path::PathBuf, sync::Arc, time::Duration};
 
 pub use memory::MemoryStorage;
 use rspack_cacheable::{cacheable, utils::PortablePath, with::As};
+use rspack_error::{Result, error};
 use rspack_fs::IntermediateFileSystem;
+use rspack_paths::AssertUtf8;
 pub use rspack_storage::Storage;
-use rspack_storage::{BridgeFileSystem, PackStorage, PackStorageOptions};
+use rspack_storage::{
+  BlobStore, BridgeFileSystem, HttpBlobStore, LocalBlobStore, PackCompression, PackStorage,
+  PackStorageMode, PackStorageOptions, RemotePackStorage, RemotePackStorageOptions,
+};
+
+/// The default max size of a pack in bytes
+pub const DEFAULT_PACK_SIZE: usize = 500 * 1024;
+/// The default expiration of the cache in milliseconds
+pub const DEFAULT_EXPIRE: u64 = 7 * 24 * 60 * 60 * 1000;
+/// The timeout of the requests to the http cache server
+const REMOTE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Compression of the pack files of the filesystem storage
+#[cacheable]
+#[derive(Debug, Default, Clone, Copy, Hash)]
+pub enum StorageCompression {
+  #[default]
+  None,
+  /// zstd with a level from 1 (fastest) to 22 (smallest), 0 means the default level
+  Zstd {
+    level: i32,
+  },
+  Lz4,
+}
+
+impl From<StorageCompression> for PackCompression {
+  fn from(value: StorageCompression) -> Self {
+    match value {
+      StorageCompression::None => Self::None,
+      StorageCompression::Zstd { level } => Self::Zstd { level },
+      StorageCompression::Lz4 => Self::Lz4,
+    }
+  }
+}
+
+/// Access mode of the filesystem storage
+#[cacheable]
+#[derive(Debug, Default, Clone, Copy, Hash)]
+pub enum StorageMode {
+  /// Load the existing contents and save updates
+  #[default]
+  ReadWrite,
+  /// Load the existing contents, but never write, clean or expire anything
+  ReadOnly,
+  /// Ignore the existing contents and save a fresh storage
+  WriteOnly,
+}
+
+impl From<StorageMode> for PackStorageMode {
+  fn from(value: StorageMode) -> Self {
+    match value {
+      StorageMode::ReadWrite => Self::ReadWrite,
+      StorageMode::ReadOnly => Self::ReadOnly,
+      StorageMode::WriteOnly => Self::WriteOnly,
+    }
+  }
+}
 
 /// Storage Options
 ///
@@ -19,29 +76,173 @@ pub enum StorageOptions {
   FileSystem {
     #[cacheable(with=As<PortablePath>)]
     directory: PathBuf,
+    pack_size: usize,
+    expire: u64,
+    compression: StorageCompression,
+    mode: StorageMode,
+  },
+  /// A content-addressed store shared by multiple machines.
+  ///
+  /// The endpoint is either an `http://` cache server or a shared directory.
+  Remote {
+    endpoint: String,
+    pack_size: usize,
+    expire: u64,
   },
 }
 
+impl StorageOptions {
+  /// The options deciding the cache version.
+  ///
+  /// The mode and compression are excluded, so a read-only process shares the storage seeded by
+  /// a write-only one, and the packs written with another compression are loaded by their recorded codec.
+  pub fn version_options(&self) -> Self {
+    let mut options = self.clone();
+    if let Self::FileSystem {
+      mode, compression, ..
+    } = &mut options
+    {
+      *mode = StorageMode::default();
+      *compression = StorageCompression::default();
+    }
+    options
+  }
+
+  /// Validate the options from users, e.g. the endpoint of the remote storage.
+  pub fn validate(&self) -> Result<()> {
+    match self {
+      Self::FileSystem { .. } => Ok(()),
+      Self::Remote { endpoint, .. } => remote_endpoint(endpoint).map(|_| ()),
+    }
+  }
+}
+
+/// Parse the http cache server of the remote endpoint, `None` means the endpoint is a shared directory.
+fn remote_endpoint(endpoint: &str) -> Result<Option<HttpBlobStore>> {
+  if endpoint.starts_with("http://") {
+    return HttpBlobStore::new(endpoint, REMOTE_TIMEOUT)
+      .map(Some)
+      .map_err(|e| error!(e.to_string()));
+  }
+  if let Some((scheme, _)) = endpoint.split_once("://") {
+    return Err(error!(
+      "unsupported scheme `{scheme}` of remote storage endpoint `{endpoint}`, only http:// endpoints and directories are supported"
+    ));
+  }
+  Ok(None)
+}
+
 pub fn create_storage(
   options: StorageOptions,
   version: String,
   fs: Arc<dyn IntermediateFileSystem>,
-) -> Arc<dyn Storage> {
-  match options {
-    StorageOptions::FileSystem { directory } => {
+) -> Result<Arc<dyn Storage>> {
+  Ok(match options {
+    StorageOptions::FileSystem {
+      directory,
+      pack_size,
+      expire,
+      compression,
+      mode,
+    } => {
       let option = PackStorageOptions {
         temp_root: directory.join(".temp"),
         root: directory,
         clean: true,
         bucket_size: 20,
-        pack_size: 500 * 1024,
-        expire: 7 * 24 * 60 * 60 * 1000,
+        pack_size,
+        expire,
         fs: Arc::new(BridgeFileSystem(fs)),
         fresh_generation: Some(1),
         release_generation: Some(2),
+        compression: compression.into(),
+        mode: mode.into(),
         version,
       };
       Arc::new(PackStorage::new(option))
     }
+    StorageOptions::Remote {
+      endpoint,
+      pack_size,
+      expire,
+    } => {
+      let store: Arc<dyn BlobStore> = match remote_endpoint(&endpoint)? {
+        Some(store) => Arc::new(store),
+        None => Arc::new(LocalBlobStore::new(
+          PathBuf::from(endpoint).assert_utf8(),
+          Arc::new(BridgeFileSystem(fs)),
+        )),
+      };
+      Arc::new(RemotePackStorage::new(RemotePackStorageOptions {
+        store,
+        version,
+        pack_size,
+        expire,
+      }))
+    }
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    path::PathBuf,
+    sync::Arc,
+  };
+
+  use rspack_fs::MemoryFileSystem;
+
+  use super::{StorageCompression, StorageMode, StorageOptions, create_storage};
+
+  fn create_options(compression: StorageCompression) -> StorageOptions {
+    StorageOptions::FileSystem {
+      directory: PathBuf::from("/rspack_test/storage/version_options"),
+      pack_size: 100,
+      expire: 7 * 24 * 60 * 60 * 1000,
+      compression,
+      mode: StorageMode::ReadWrite,
+    }
+  }
+
+  fn get_version(options: &StorageOptions) -> String {
+    let mut hasher = DefaultHasher::new();
+    options.version_options().hash(&mut hasher);
+    hex::encode(hasher.finish().to_ne_bytes())
+  }
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn should_load_uncompressed_packs_with_compression() {
+    let fs = Arc::new(MemoryFileSystem::default());
+
+    let uncompressed = create_options(StorageCompression::None);
+    let storage = create_storage(uncompressed.clone(), get_version(&uncompressed), fs.clone())
+      .expect("should create storage");
+    for i in 0..100 {
+      storage.set(
+        "test",
+        format!("key_{i}").as_bytes().to_vec(),
+        format!("val_{i}").as_bytes().to_vec(),
+      );
+    }
+    storage
+      .trigger_save()
+      .expect("should trigger save")
+      .await
+      .expect("should save")
+      .expect("should save");
+
+    let compressed = create_options(StorageCompression::Zstd { level: 3 });
+    assert_eq!(get_version(&uncompressed), get_version(&compressed));
+    let storage = create_storage(compressed.clone(), get_version(&compressed), fs)
+      .expect("should create storage");
+    let data = storage.load("test").await.expect("should load");
+    assert_eq!(data.len(), 100);
+    assert!(
+      data
+        .iter()
+        .any(|(key, value)| key.as_slice() == b"key_99" && value.as_slice() == b"val_99")
+    );
   }
 }
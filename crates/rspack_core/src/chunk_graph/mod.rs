@@ -7,7 +7,8 @@ use rspack_util::env::has_query;
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 
 use crate::{
-  AsyncDependenciesBlockIdentifier, ChunkGroupUkey, ChunkUkey, Compilation, ModuleIdentifier,
+  AsyncDependenciesBlockIdentifier, ChunkGroupUkey, ChunkUkey, Compilation, MemorySize,
+  ModuleIdentifier, entries_memory_size,
 };
 
 pub mod chunk_graph_chunk;
@@ -32,6 +33,39 @@ impl ChunkGraph {
     !cgm.entry_in_chunks.is_empty()
   }
 }
+
+impl MemorySize for ChunkGraph {
+  fn memory_size(&self) -> usize {
+    let modules = self
+      .chunk_graph_module_by_module_identifier
+      .values()
+      .map(|cgm| {
+        entries_memory_size::<ChunkUkey>(
+          cgm.entry_in_chunks.len() + cgm.chunks.len() + cgm.runtime_in_chunks.len(),
+        )
+      })
+      .sum::<usize>();
+    let chunks = self
+      .chunk_graph_chunk_by_chunk_ukey
+      .values()
+      .map(|cgc| {
+        entries_memory_size::<(ModuleIdentifier, ChunkGroupUkey)>(cgc.entry_modules.len())
+          + entries_memory_size::<ModuleIdentifier>(cgc.modules.len() + cgc.runtime_modules.len())
+      })
+      .sum::<usize>();
+    modules
+      + chunks
+      + entries_memory_size::<(ModuleIdentifier, ChunkGraphModule)>(
+        self.chunk_graph_module_by_module_identifier.len(),
+      )
+      + entries_memory_size::<(ChunkUkey, ChunkGraphChunk)>(
+        self.chunk_graph_chunk_by_chunk_ukey.len(),
+      )
+      + entries_memory_size::<(AsyncDependenciesBlockIdentifier, ChunkGroupUkey)>(
+        self.block_to_chunk_group_ukey.len(),
+      )
+  }
+}
 static INDENT: &str = "    ";
 
 impl ChunkGraph {
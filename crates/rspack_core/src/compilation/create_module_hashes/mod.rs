@@ -109,10 +109,17 @@ async fn create_module_hashes_pass_impl(compilation: &mut Compilation) -> Result
 
     modules
   } else {
+    // the hashes recovered by persistent cache are kept
     compilation
       .get_module_graph()
       .modules()
       .keys()
+      .filter(|module| {
+        compilation
+          .cgm_hash_artifact
+          .get_runtime_map(module)
+          .is_none()
+      })
       .copied()
       .collect()
   };
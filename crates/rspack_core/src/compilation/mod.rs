@@ -79,6 +79,7 @@ use crate::{
   ProcessRuntimeRequirementsCacheArtifact, ResolverFactory, RuntimeGlobals, RuntimeKeyMap,
   RuntimeMode, RuntimeModule, RuntimeSpec, RuntimeSpecMap, RuntimeTemplate, SharedPluginDriver,
   SideEffectsOptimizeArtifact, SourceType, Stats, ValueCacheVersions,
+  cache::persistent::invalidation::InvalidationReport,
   compilation::build_module_graph::{
     BuildModuleGraphArtifact, ModuleExecutor, UpdateParam, update_module_graph,
   },
@@ -281,6 +282,8 @@ pub struct Compilation {
 
   pub modified_files: ArcPathSet,
   pub removed_files: ArcPathSet,
+  /// The paths which invalidated the persistent cache, only available on the cold start.
+  pub cache_invalidation_report: Option<InvalidationReport>,
   pub build_module_graph_artifact: DerefOption<BuildModuleGraphArtifact>,
   pub input_filesystem: Arc<dyn ReadableFileSystem>,
 
@@ -385,13 +388,13 @@ impl Compilation {
       module_graph_cache_artifact: Default::default(),
       module_static_cache: Default::default(),
       code_generated_modules: Default::default(),
-      chunk_render_cache_artifact: ChunkRenderCacheArtifact::new(MemoryGCStorage::new(
-        match &options.cache {
-          CacheOptions::Disabled => 0, // FIXME: this should be removed in future
-          CacheOptions::Memory { max_generations } => *max_generations,
-          CacheOptions::Persistent(_) => 1,
-        },
-      )),
+      chunk_render_cache_artifact: ChunkRenderCacheArtifact::new(match &options.cache {
+        CacheOptions::Disabled => MemoryGCStorage::new(0), // FIXME: this should be removed in future
+        CacheOptions::Memory {
+          max_generations, ..
+        } => MemoryGCStorage::new(*max_generations),
+        CacheOptions::Persistent(_) => MemoryGCStorage::new(1),
+      }),
       code_generate_cache_artifact: CodeGenerateCacheArtifact::new(&options),
       process_runtime_requirements_cache_artifact: ProcessRuntimeRequirementsCacheArtifact::new(
         &options,
@@ -417,6 +420,7 @@ impl Compilation {
       build_module_graph_artifact: DerefOption::new(BuildModuleGraphArtifact::default()),
       modified_files,
       removed_files,
+      cache_invalidation_report: None,
       input_filesystem,
 
       intermediate_filesystem,
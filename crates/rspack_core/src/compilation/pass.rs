@@ -1,7 +1,9 @@
 use async_trait::async_trait;
 use rspack_error::Result;
 
-use crate::{Compilation, cache::Cache, logger::Logger};
+use crate::{
+  Compilation, allocation_counters, cache::Cache, logger::Logger, report_phase_memory_usage,
+};
 
 /// A compilation pass that transforms the compilation state.
 ///
@@ -38,12 +40,15 @@ pub trait PassExt: Send + Sync {
   }
 
   /// Unified entry point: check enabled → log → before_pass → run_pass → after_pass
+  ///
+  /// The allocation counters are sampled at the boundaries of the pass if they are registered.
   async fn run(&self, compilation: &mut Compilation, cache: &mut dyn Cache) -> Result<()> {
     if !self.is_enabled(compilation) {
       return Ok(());
     }
     let logger = compilation.get_logger("rspack.Compilation");
     let start = logger.time(self.name());
+    let counters = allocation_counters();
 
     self.before_pass(compilation, cache).await;
     let result = self.run_pass_with_cache(compilation, cache).await;
@@ -52,6 +57,11 @@ pub trait PassExt: Send + Sync {
     }
 
     logger.time_end(start);
+    if let Some(before) = counters
+      && let Some(after) = allocation_counters()
+    {
+      report_phase_memory_usage(&logger, self.name(), before, after);
+    }
     result
   }
 }
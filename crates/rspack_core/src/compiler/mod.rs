@@ -4,7 +4,10 @@ use std::sync::{Arc, atomic::AtomicU32};
 use futures::future::join_all;
 use rspack_cacheable::cacheable;
 use rspack_error::Result;
-use rspack_fs::{IntermediateFileSystem, NativeFileSystem, ReadableFileSystem, WritableFileSystem};
+use rspack_fs::{
+  EmitFileSystem, EmitFileSystemOptions, IntermediateFileSystem, NativeFileSystem,
+  ReadableFileSystem, WritableFileSystem,
+};
 use rspack_hook::define_hook;
 use rspack_paths::{Utf8Path, Utf8PathBuf};
 use rspack_sources::BoxSource;
@@ -17,13 +20,13 @@ pub use self::rebuild::CompilationRecords;
 use crate::{
   BoxPlugin, CleanOptions, Compilation, CompilationAsset, CompilerOptions, CompilerPlatform,
   ContextModuleFactory, Filename, KeepPattern, NormalModuleFactory, PluginDriver, ResolverFactory,
-  SharedPluginDriver,
+  SharedPluginDriver, allocation_counters,
   cache::{Cache, new_cache},
   compilation::build_module_graph::ModuleExecutor,
   fast_set, include_hash,
   incremental::{Incremental, IncrementalPasses},
   logger::Logger,
-  trim_dir,
+  report_artifacts_memory_usage, report_phase_memory_usage, trim_dir,
 };
 
 // should be SyncHook, but rspack need call js hook
@@ -99,6 +102,8 @@ pub struct Compiler {
   pub emitted_asset_versions: HashMap<String, String>,
   pub platform: Arc<CompilerPlatform>,
   compiler_context: Arc<CompilerContext>,
+  /// the wrapper of output_filesystem used for emitting assets
+  emit_filesystem: Arc<EmitFileSystem>,
 }
 
 impl Compiler {
@@ -128,8 +133,15 @@ impl Compiler {
     // pnp is only meaningful for input_filesystem, so disable it for intermediate_filesystem and output_filesystem
     let input_filesystem = input_filesystem.unwrap_or_else(|| Arc::new(NativeFileSystem::new(pnp)));
 
-    let output_filesystem =
-      output_filesystem.unwrap_or_else(|| Arc::new(NativeFileSystem::new(false)));
+    let emit_filesystem = Arc::new(EmitFileSystem::new(
+      output_filesystem.unwrap_or_else(|| Arc::new(NativeFileSystem::new(false))),
+      EmitFileSystemOptions {
+        skip_unchanged: options.output.compare_before_emit,
+        staging: options.output.atomic_emit,
+        fsync: options.output.atomic_emit,
+      },
+    ));
+    let output_filesystem: Arc<dyn WritableFileSystem> = emit_filesystem.clone();
     let intermediate_filesystem =
       intermediate_filesystem.unwrap_or_else(|| Arc::new(NativeFileSystem::new(false)));
 
@@ -195,6 +207,7 @@ impl Compiler {
       input_filesystem,
       platform,
       compiler_context,
+      emit_filesystem,
     }
   }
 
@@ -300,6 +313,9 @@ impl Compiler {
       .run_passes(self.plugin_driver.clone(), &mut *self.cache)
       .await?;
     logger.time_end(start);
+    if allocation_counters().is_some() {
+      report_artifacts_memory_usage(&logger, &self.compilation);
+    }
 
     // Consume plugin driver diagnostic
     let plugin_driver_diagnostics = self.plugin_driver.take_diagnostic();
@@ -327,8 +343,14 @@ impl Compiler {
     }
 
     let start = logger.time("emitAssets");
+    let counters = allocation_counters();
     self.emit_assets().await?;
     logger.time_end(start);
+    if let Some(before) = counters
+      && let Some(after) = allocation_counters()
+    {
+      report_phase_memory_usage(&logger, "emitAssets", before, after);
+    }
 
     Ok(())
   }
@@ -343,6 +365,29 @@ impl Compiler {
       )
       .await?;
     let output_path = Utf8Path::new(&output_path_str);
+    self.emit_filesystem.begin(output_path).await?;
+    if let Err(err) = self.write_assets(output_path).await {
+      // the staging directory is left to be removed by the next emit
+      self.emit_filesystem.abort();
+      return Err(err);
+    }
+
+    let stats = self.emit_filesystem.finish().await?;
+    let logger = self.compilation.get_logger("rspack.Compiler");
+    logger.debug(format!(
+      "{} assets written, {} unchanged assets skipped",
+      stats.written, stats.skipped
+    ));
+
+    self
+      .plugin_driver
+      .compiler_hooks
+      .after_emit
+      .call(&mut self.compilation)
+      .await
+  }
+
+  async fn write_assets(&mut self, output_path: &Utf8Path) -> Result<()> {
     self.run_clean_options(output_path).await?;
 
     self
@@ -388,14 +433,9 @@ impl Compiler {
     .await;
 
     self.emitted_asset_versions = new_emitted_asset_versions;
-
-    self
-      .plugin_driver
-      .compiler_hooks
-      .after_emit
-      .call(&mut self.compilation)
-      .await
+    Ok(())
   }
+
   async fn emit_asset(
     &self,
     output_path: &Utf8Path,
@@ -424,39 +464,22 @@ impl Compiler {
             || include_hash(target_file, &asset.info.full_hash));
       }
 
-      let stat = self
-        .output_filesystem
-        .stat(file_path.as_path().as_ref())
-        .await
-        .ok();
-
-      let need_write = if !self.options.output.compare_before_emit {
-        // write when compare_before_emit is false
-        true
-      } else if !stat.as_ref().is_some_and(|stat| stat.is_file) {
-        // write when not exists or not a file
-        true
-      } else if immutable {
-        // do not write when asset is immutable and the file exists
-        false
-      } else if (content.len() as u64) == stat.as_ref().unwrap_or_else(|| unreachable!()).size {
-        match self
+      // do not write when asset is immutable and the file exists,
+      // the content comparison of other assets is done by the emit filesystem
+      let skip = self.emit_filesystem.options().skip_unchanged
+        && immutable
+        && self
           .output_filesystem
-          .read_file(file_path.as_path().as_ref())
+          .stat(file_path.as_path().as_ref())
           .await
-        {
-          // write when content is different
-          Ok(c) => content != c,
-          // write when file can not be read
-          Err(_) => true,
-        }
-      } else {
-        // write if content length is different
-        true
-      };
-
-      if need_write {
-        self.output_filesystem.write(&file_path, &content).await?;
+          .is_ok_and(|stat| stat.is_file);
+
+      if !skip
+        && self
+          .emit_filesystem
+          .write_if_changed(&file_path, &content)
+          .await?
+      {
         self.compilation.emitted_assets.insert(filename.to_string());
       }
 
@@ -9,6 +9,7 @@ use std::{
 };
 
 use bitflags::bitflags;
+pub(crate) use mutations::compute_affected_modules_with_module_graph;
 pub use mutations::{Mutation, Mutations};
 use rspack_error::{Diagnostic, Error};
 
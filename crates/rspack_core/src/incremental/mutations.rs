@@ -237,7 +237,7 @@ impl Mutations {
 }
 
 #[tracing::instrument(skip_all)]
-fn compute_affected_modules_with_module_graph(
+pub(crate) fn compute_affected_modules_with_module_graph(
   module_graph: &ModuleGraph,
   built_modules: IdentifierSet,
   built_dependencies: UkeySet<DependencyId>,
@@ -22,7 +22,7 @@ mod exports_info;
 
 use crate::{
   BoxDependency, BoxModule, DependencyCondition, DependencyId, ExportsInfo, ExportsInfoData,
-  ModuleIdentifier,
+  MemorySize, ModuleIdentifier, entries_memory_size,
 };
 
 // TODO Here request can be used Atom
@@ -138,6 +138,37 @@ impl ModuleGraph {
   }
 }
 
+impl MemorySize for ModuleGraph {
+  /// The approximate size of the entries in the graph and the original sources of modules,
+  /// the data owned by modules and dependencies such as ASTs are not counted.
+  fn memory_size(&self) -> usize {
+    let inner = &self.inner;
+    let sources = inner
+      .modules
+      .iter()
+      .filter_map(|(_, module)| module.source())
+      .map(MemorySize::memory_size)
+      .sum::<usize>();
+    sources
+      + entries_memory_size::<(ModuleIdentifier, BoxModule)>(inner.modules.iter().count())
+      + entries_memory_size::<(DependencyId, BoxDependency)>(inner.dependencies.len())
+      + entries_memory_size::<(AsyncDependenciesBlockIdentifier, AsyncDependenciesBlock)>(
+        inner.blocks.len(),
+      )
+      + entries_memory_size::<(DependencyId, DependencyParents)>(
+        inner.dependency_id_to_parents.len(),
+      )
+      + entries_memory_size::<(ModuleIdentifier, ModuleGraphModule)>(
+        inner.module_graph_modules.iter().count(),
+      )
+      + entries_memory_size::<(DependencyId, ModuleGraphConnection)>(
+        inner.connections.iter().count(),
+      )
+      + entries_memory_size::<(ExportsInfo, ExportsInfoData)>(inner.exports_info_map.iter().count())
+      + entries_memory_size::<(DependencyId, DependencyExtraMeta)>(inner.dep_meta_map.len())
+  }
+}
+
 impl ModuleGraph {
   /// Return an unordered iterator of modules
   pub fn modules(&self) -> IdentifierMap<&BoxModule> {
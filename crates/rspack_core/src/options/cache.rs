@@ -9,6 +9,12 @@ pub enum CacheOptions {
     /// For example, if `max_generations` is set to 1,
     /// the cache will be removed if it's not accessed for 1 compilation generation.
     max_generations: u32,
+    /// The maximum approximate bytes of the memory cache in total.
+    ///
+    /// The module graph is counted in but never evicted, the least recently used
+    /// code generation, runtime requirements and chunk render items will be evicted
+    /// after each compilation when it's exceeded, `None` means there is no limit.
+    max_size: Option<usize>,
   },
   Persistent(PersistentCacheOptions),
 }
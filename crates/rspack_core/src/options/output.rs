@@ -70,6 +70,7 @@ pub struct OutputOptions {
   pub script_type: String,
   pub environment: Environment,
   pub compare_before_emit: bool,
+  pub atomic_emit: bool,
 }
 
 impl From<&OutputOptions> for RspackHash {
@@ -17,6 +17,7 @@ use crate::{
   BoxModule, BoxRuntimeModule, Chunk, ChunkGraph, ChunkGroupOrderKey, ChunkGroupUkey, ChunkUkey,
   Compilation, LogType, ModuleGraph, ModuleGraphCacheArtifact, ModuleIdentifier,
   PrefetchExportsInfoMode, ProvidedExports, RuntimeSpec, SourceType, UsedExports,
+  cache::persistent::{invalidation::InvalidatedPath, snapshot::SnapshotScope},
   compilation::build_module_graph::ExecutedRuntimeModule,
 };
 
@@ -728,6 +729,29 @@ impl Stats<'_> {
     self.compilation.get_hash()
   }
 
+  pub fn get_cache_invalidation(&self) -> Option<StatsCacheInvalidation<'_>> {
+    fn to_stats(item: &InvalidatedPath) -> StatsInvalidatedPath<'_> {
+      StatsInvalidatedPath {
+        path: item.path.to_string_lossy().into_owned(),
+        scope: match item.scope {
+          SnapshotScope::FILE => "file",
+          SnapshotScope::CONTEXT => "context",
+          SnapshotScope::MISSING => "missing",
+          SnapshotScope::BUILD => "build",
+        },
+        strategy: item.strategy.as_str(),
+        kind: item.kind.as_str(),
+        modules: item.modules.iter().map(|module| module.as_str()).collect(),
+      }
+    }
+
+    let report = self.compilation.cache_invalidation_report.as_ref()?;
+    Some(StatsCacheInvalidation {
+      build_dependencies: report.build_dependencies.iter().map(to_stats).collect(),
+      paths: report.paths.iter().map(to_stats).collect(),
+    })
+  }
+
   #[allow(clippy::too_many_arguments)]
   fn get_module<'a>(
     &'a self,
@@ -121,6 +121,21 @@ pub struct StatsAsset<'a> {
   pub auxiliary_chunks: Vec<Option<&'a str>>,
 }
 
+#[derive(Debug)]
+pub struct StatsCacheInvalidation<'a> {
+  pub build_dependencies: Vec<StatsInvalidatedPath<'a>>,
+  pub paths: Vec<StatsInvalidatedPath<'a>>,
+}
+
+#[derive(Debug)]
+pub struct StatsInvalidatedPath<'a> {
+  pub path: String,
+  pub scope: &'static str,
+  pub strategy: &'static str,
+  pub kind: &'static str,
+  pub modules: Vec<&'a str>,
+}
+
 #[derive(Debug)]
 pub struct StatsAssetsByChunkName<'a> {
   pub name: &'a str,
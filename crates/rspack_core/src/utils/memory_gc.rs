@@ -1,24 +1,91 @@
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
 
 use dashmap::DashMap;
 use rspack_collections::{Identifier, IdentifierDashMap};
+use rspack_sources::BoxSource;
+
+use crate::{CodeGenerationResult, RuntimeGlobals};
+
+/// The access tick shared by all storages, so the least recently used items
+/// can be compared across storages.
+static ACCESS_TICK: AtomicU64 = AtomicU64::new(0);
+
+fn next_access_tick() -> u64 {
+  ACCESS_TICK.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Approximate memory size of cache items in bytes
+pub trait MemorySize {
+  fn memory_size(&self) -> usize;
+}
+
+impl MemorySize for BoxSource {
+  fn memory_size(&self) -> usize {
+    self.size()
+  }
+}
+
+impl MemorySize for RuntimeGlobals {
+  fn memory_size(&self) -> usize {
+    std::mem::size_of::<Self>()
+  }
+}
+
+impl MemorySize for CodeGenerationResult {
+  fn memory_size(&self) -> usize {
+    self
+      .inner()
+      .values()
+      .map(MemorySize::memory_size)
+      .sum::<usize>()
+      + std::mem::size_of::<Self>()
+  }
+}
+
+/// Counters of memory cache
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryCacheStats {
+  pub hits: usize,
+  pub misses: usize,
+  pub evictions: usize,
+  /// The number of items in cache
+  pub count: usize,
+  /// The approximate bytes of items in cache
+  pub size: usize,
+}
 
 #[derive(Debug, Hash, PartialEq, Eq)]
 struct CacheData<Item> {
   item: Item,
   generation: u32,
+  /// The access tick of the item, the smallest one is the least recently used
+  last_access: u64,
+  size: usize,
 }
 
 impl<Item> CacheData<Item> {
-  fn new(item: Item, generation: u32) -> Self {
-    Self { item, generation }
+  fn new(item: Item, generation: u32, last_access: u64, size: usize) -> Self {
+    Self {
+      item,
+      generation,
+      last_access,
+      size,
+    }
   }
 }
+
 /// memory storage with garbage collection based on generations
+///
+/// The least recently used items of storages can also be evicted together
+/// by [`evict_least_recently_used`] to keep them in a size budget.
 #[derive(Debug)]
 pub struct MemoryGCStorage<Item> {
   generation: AtomicU32,
   max_generations: u32,
+  size: AtomicUsize,
+  hits: AtomicUsize,
+  misses: AtomicUsize,
+  evictions: AtomicUsize,
   data: IdentifierDashMap<CacheData<Item>>,
 }
 
@@ -27,27 +94,62 @@ impl<Item> MemoryGCStorage<Item> {
     Self {
       generation: AtomicU32::new(0),
       max_generations,
+      size: AtomicUsize::new(0),
+      hits: AtomicUsize::new(0),
+      misses: AtomicUsize::new(0),
+      evictions: AtomicUsize::new(0),
       data: DashMap::default(),
     }
   }
+
+  pub fn stats(&self) -> MemoryCacheStats {
+    MemoryCacheStats {
+      hits: self.hits.load(Ordering::Relaxed),
+      misses: self.misses.load(Ordering::Relaxed),
+      evictions: self.evictions.load(Ordering::Relaxed),
+      count: self.data.len(),
+      size: self.size.load(Ordering::Relaxed),
+    }
+  }
 }
 
 impl<Item> MemoryGCStorage<Item>
 where
-  Item: Clone + std::fmt::Debug + Send + Sync,
+  Item: Clone + std::fmt::Debug + Send + Sync + MemorySize,
 {
   pub(crate) fn get(&self, id: &Identifier) -> Option<Item> {
-    self.data.get_mut(id).map(|mut item| {
+    let item = self.data.get_mut(id).map(|mut item| {
       // Reset the generation to the current generation if the item is accessed
       item.generation = self.generation.load(Ordering::Relaxed);
+      item.last_access = next_access_tick();
       item.item.clone()
-    })
+    });
+    if item.is_some() {
+      self.hits.fetch_add(1, Ordering::Relaxed);
+    } else {
+      self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+    item
+  }
+  /// Get the item without refreshing its generation
+  pub(crate) fn peek(&self, id: &Identifier) -> Option<Item> {
+    self.data.get(id).map(|item| item.item.clone())
   }
   pub(crate) fn set(&self, id: Identifier, data: Item) {
-    self.data.insert(
+    let size = data.memory_size();
+    let old = self.data.insert(
       id,
-      CacheData::new(data, self.generation.load(Ordering::Relaxed)),
+      CacheData::new(
+        data,
+        self.generation.load(Ordering::Relaxed),
+        next_access_tick(),
+        size,
+      ),
     );
+    if let Some(old) = old {
+      self.size.fetch_sub(old.size, Ordering::Relaxed);
+    }
+    self.size.fetch_add(size, Ordering::Relaxed);
   }
   /// notify storage that the current generation is over and start a new one
   pub(crate) fn start_next_generation(&self) {
@@ -59,7 +161,131 @@ where
       // For example:
       // Cache created on generation 0 will be removed on generation 2 with `max_generations` set to 1,
       // If it's not accessed on generation 1.
-      cache_data.generation.saturating_add(self.max_generations) >= generation
+      let keep = cache_data.generation.saturating_add(self.max_generations) >= generation;
+      if !keep {
+        self.size.fetch_sub(cache_data.size, Ordering::Relaxed);
+        self.evictions.fetch_add(1, Ordering::Relaxed);
+      }
+      keep
     });
   }
 }
+
+/// A storage whose items can be evicted by [`evict_least_recently_used`]
+pub(crate) trait MemoryGCEvict {
+  /// The approximate bytes of items
+  fn size(&self) -> usize;
+  /// The last access tick and the identifier of each item
+  fn access_ticks(&self) -> Vec<(u64, Identifier)>;
+  fn evict(&self, id: &Identifier);
+}
+
+impl<Item> MemoryGCEvict for MemoryGCStorage<Item> {
+  fn size(&self) -> usize {
+    self.size.load(Ordering::Relaxed)
+  }
+
+  fn access_ticks(&self) -> Vec<(u64, Identifier)> {
+    self
+      .data
+      .iter()
+      .map(|item| (item.last_access, *item.key()))
+      .collect()
+  }
+
+  fn evict(&self, id: &Identifier) {
+    if let Some((_, cache_data)) = self.data.remove(id) {
+      self.size.fetch_sub(cache_data.size, Ordering::Relaxed);
+      self.evictions.fetch_add(1, Ordering::Relaxed);
+    }
+  }
+}
+
+/// Evict the least recently used items of all storages until their total size fits `max_size`.
+pub(crate) fn evict_least_recently_used(storages: &[&dyn MemoryGCEvict], max_size: usize) {
+  let mut size = storages.iter().map(|storage| storage.size()).sum::<usize>();
+  if size <= max_size {
+    return;
+  }
+  let mut items = storages
+    .iter()
+    .enumerate()
+    .flat_map(|(index, storage)| {
+      storage
+        .access_ticks()
+        .into_iter()
+        .map(move |(last_access, id)| (last_access, index, id))
+    })
+    .collect::<Vec<_>>();
+  items.sort_unstable_by_key(|(last_access, ..)| *last_access);
+  for (_, index, id) in items {
+    if size <= max_size {
+      break;
+    }
+    let storage = storages[index];
+    let before = storage.size();
+    storage.evict(&id);
+    size -= before - storage.size();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rspack_collections::Identifier;
+
+  use super::{MemoryGCStorage, MemorySize, evict_least_recently_used};
+
+  #[derive(Debug, Clone)]
+  struct Item(usize);
+
+  impl MemorySize for Item {
+    fn memory_size(&self) -> usize {
+      self.0
+    }
+  }
+
+  #[test]
+  fn should_evict_least_recently_used_across_storages() {
+    let storage1 = MemoryGCStorage::new(1);
+    let storage2 = MemoryGCStorage::new(1);
+    storage1.set(Identifier::from("a"), Item(20));
+    storage2.set(Identifier::from("b"), Item(40));
+    storage1.set(Identifier::from("c"), Item(30));
+    assert!(storage1.get(&Identifier::from("a")).is_some());
+    storage2.set(Identifier::from("d"), Item(20));
+
+    // "b" in storage2 and "c" in storage1 are the least recently used
+    evict_least_recently_used(&[&storage1, &storage2], 50);
+    assert!(storage1.peek(&Identifier::from("a")).is_some());
+    assert!(storage2.peek(&Identifier::from("b")).is_none());
+    assert!(storage1.peek(&Identifier::from("c")).is_none());
+    assert!(storage2.peek(&Identifier::from("d")).is_some());
+    assert!(storage2.get(&Identifier::from("b")).is_none());
+
+    let stats = storage1.stats();
+    assert_eq!(stats.hits, 1);
+    assert_eq!(stats.evictions, 1);
+    assert_eq!(stats.count, 1);
+    assert_eq!(stats.size, 20);
+    let stats = storage2.stats();
+    assert_eq!(stats.misses, 1);
+    assert_eq!(stats.evictions, 1);
+    assert_eq!(stats.size, 20);
+  }
+
+  #[test]
+  fn should_evict_by_generations() {
+    let storage = MemoryGCStorage::new(1);
+    storage.set(Identifier::from("a"), Item(10));
+    storage.set(Identifier::from("b"), Item(10));
+    storage.start_next_generation();
+    assert!(storage.get(&Identifier::from("a")).is_some());
+    storage.start_next_generation();
+    assert!(storage.peek(&Identifier::from("a")).is_some());
+    assert!(storage.peek(&Identifier::from("b")).is_none());
+
+    let stats = storage.stats();
+    assert_eq!(stats.evictions, 1);
+    assert_eq!(stats.size, 10);
+  }
+}
@@ -0,0 +1,106 @@
+use std::sync::OnceLock;
+
+use crate::{Compilation, Logger, MemorySize};
+
+/// Counters of the global allocator
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AllocationCounters {
+  /// The bytes currently allocated
+  pub allocated: usize,
+  /// The bytes allocated since the process started, the freed bytes are not subtracted
+  pub total_allocated: usize,
+}
+
+static ALLOCATION_COUNTERS: OnceLock<fn() -> AllocationCounters> = OnceLock::new();
+
+/// Register the function reading the counters of the global allocator.
+///
+/// The memory usage of compilation phases and artifacts is only reported after it is registered,
+/// the counters are provided by `rspack_allocator` with the `memory-counters` feature.
+pub fn register_allocation_counters(read: fn() -> AllocationCounters) {
+  let _ = ALLOCATION_COUNTERS.set(read);
+}
+
+/// Sample the counters of the global allocator, `None` if they are not registered.
+pub fn allocation_counters() -> Option<AllocationCounters> {
+  ALLOCATION_COUNTERS.get().map(|read| read())
+}
+
+/// Approximate memory size of the entries of a map or a set
+pub(crate) fn entries_memory_size<T>(len: usize) -> usize {
+  len * std::mem::size_of::<T>()
+}
+
+fn format_bytes(bytes: usize) -> String {
+  const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+  let mut size = bytes as f64;
+  let mut unit = 0;
+  while size >= 1024.0 && unit < UNITS.len() - 1 {
+    size /= 1024.0;
+    unit += 1;
+  }
+  if unit == 0 {
+    format!("{bytes} B")
+  } else {
+    format!("{size:.2} {}", UNITS[unit])
+  }
+}
+
+/// Report the memory usage of a phase sampled at its boundaries, to the tracing counters
+/// and the stats logging.
+pub(crate) fn report_phase_memory_usage(
+  logger: &impl Logger,
+  phase: &'static str,
+  before: AllocationCounters,
+  after: AllocationCounters,
+) {
+  let phase_allocated = after.total_allocated.saturating_sub(before.total_allocated);
+  let retained = after.allocated as i64 - before.allocated as i64;
+  tracing::info!(
+    name: "memory_usage",
+    phase,
+    counter.allocated = after.allocated as u64,
+    counter.phase_allocated = phase_allocated as u64
+  );
+  logger.log(format!(
+    "{phase}: {} allocated, {} during the phase, {}{} retained",
+    format_bytes(after.allocated),
+    format_bytes(phase_allocated),
+    if retained < 0 { "-" } else { "+" },
+    format_bytes(retained.unsigned_abs() as usize)
+  ));
+}
+
+/// Report the approximate memory size of the artifacts after the seal, to the tracing counters
+/// and the stats logging.
+pub(crate) fn report_artifacts_memory_usage(logger: &impl Logger, compilation: &Compilation) {
+  let module_graph = compilation.get_module_graph().memory_size();
+  let chunk_graph = compilation.chunk_graph.memory_size();
+  let code_generation_results = compilation.code_generation_results.memory_size();
+  tracing::info!(
+    name: "artifacts_memory_usage",
+    counter.module_graph = module_graph as u64,
+    counter.chunk_graph = chunk_graph as u64,
+    counter.code_generation_results = code_generation_results as u64
+  );
+  logger.group("artifacts memory usage");
+  logger.log(format!("module graph: {}", format_bytes(module_graph)));
+  logger.log(format!("chunk graph: {}", format_bytes(chunk_graph)));
+  logger.log(format!(
+    "code generation results: {}",
+    format_bytes(code_generation_results)
+  ));
+  logger.group_end();
+}
+
+#[cfg(test)]
+mod tests {
+  use super::format_bytes;
+
+  #[test]
+  fn should_format_bytes() {
+    assert_eq!(format_bytes(512), "512 B");
+    assert_eq!(format_bytes(1536), "1.50 KiB");
+    assert_eq!(format_bytes(3 * 1024 * 1024), "3.00 MiB");
+  }
+}
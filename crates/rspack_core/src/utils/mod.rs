@@ -25,6 +25,7 @@ mod hash;
 mod identifier;
 mod iterator_consumer;
 mod memory_gc;
+mod memory_usage;
 mod module_rules;
 mod property_access;
 mod property_name;
@@ -38,7 +39,12 @@ pub use compile_boolean_matcher::*;
 pub use concatenated_module_visitor::*;
 pub use concatenation_scope::*;
 pub use deref_option::DerefOption;
-pub use memory_gc::MemoryGCStorage;
+pub use memory_gc::{MemoryCacheStats, MemoryGCStorage, MemorySize};
+pub(crate) use memory_gc::{MemoryGCEvict, evict_least_recently_used};
+pub use memory_usage::{AllocationCounters, allocation_counters, register_allocation_counters};
+pub(crate) use memory_usage::{
+  entries_memory_size, report_artifacts_memory_usage, report_phase_memory_usage,
+};
 
 pub use self::{
   comment::*,
@@ -0,0 +1,793 @@
+use std::{
+  collections::HashSet,
+  sync::{Arc, Mutex},
+};
+
+use rspack_paths::{Utf8Path, Utf8PathBuf};
+
+use crate::{Error, FileMetadata, FilePermissions, Result, WritableFileSystem};
+
+fn new_error(msg: &str) -> Error {
+  Error::Io(std::io::Error::other(msg))
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EmitFileSystemOptions {
+  /// Skip the write when the file on disk already has the same bytes,
+  /// which keeps the mtime of unchanged files stable.
+  pub skip_unchanged: bool,
+  /// Write into a staging directory next to the output path, and rename it over
+  /// the output path when the emit finishes.
+  pub staging: bool,
+  /// Flush the written files and directories to the storage before the emit finishes.
+  pub fsync: bool,
+}
+
+/// Counters of an emit, returned by [`EmitFileSystem::finish`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EmitStats {
+  pub written: usize,
+  pub skipped: usize,
+}
+
+#[derive(Debug)]
+struct Staging {
+  output_path: Utf8PathBuf,
+  staging_path: Utf8PathBuf,
+  /// Whether the output path existed when the emit began
+  has_output: bool,
+}
+
+#[derive(Debug, Default)]
+struct EmitState {
+  staging: Option<Staging>,
+  /// Paths under the output path removed during the emit, their previous contents are not carried over
+  removed: HashSet<Utf8PathBuf>,
+  created_dirs: HashSet<Utf8PathBuf>,
+  written_files: Vec<Utf8PathBuf>,
+  stats: EmitStats,
+}
+
+/// Where a path is read from during a staged emit
+enum Located {
+  /// The path is outside of the output path
+  Plain(Utf8PathBuf),
+  /// The path is inside of the output path, it is read from the staging directory first,
+  /// and then from the previous output unless it has been removed in this emit.
+  Staged {
+    staged: Utf8PathBuf,
+    previous: Option<Utf8PathBuf>,
+  },
+}
+
+fn is_unsupported(err: &Error) -> bool {
+  matches!(err, Error::Io(e) if e.kind() == std::io::ErrorKind::Unsupported)
+}
+
+/// An output file system wrapper used for asset emitting.
+///
+/// Between [`EmitFileSystem::begin`] and [`EmitFileSystem::finish`], the writes are batched:
+/// - directories are only created once,
+/// - files with unchanged bytes are not written again,
+/// - with staging enabled, every write under the output path is redirected to the staging directory,
+///   reads fall back to the previous output, and the files which are not emitted again are linked
+///   into the staging directory before it is swapped with the output path at the end,
+///   so a watching consumer never sees a half emitted output,
+/// - with fsync enabled, the written files and directories are flushed at the end.
+///
+/// Outside of an emit, it forwards every operation to the wrapped file system.
+#[derive(Debug)]
+pub struct EmitFileSystem {
+  inner: Arc<dyn WritableFileSystem>,
+  options: EmitFileSystemOptions,
+  state: Mutex<EmitState>,
+}
+
+impl EmitFileSystem {
+  pub fn new(inner: Arc<dyn WritableFileSystem>, options: EmitFileSystemOptions) -> Self {
+    Self {
+      inner,
+      options,
+      state: Default::default(),
+    }
+  }
+
+  pub fn options(&self) -> &EmitFileSystemOptions {
+    &self.options
+  }
+
+  fn staging_sibling(output_path: &Utf8Path, suffix: &str) -> Result<Utf8PathBuf> {
+    let (Some(parent), Some(name)) = (output_path.parent(), output_path.file_name()) else {
+      return Err(new_error(&format!(
+        "can not emit to {output_path} through a staging directory"
+      )));
+    };
+    Ok(parent.join(format!(".{name}.{suffix}")))
+  }
+
+  fn locate(&self, path: &Utf8Path) -> Located {
+    let state = self.state.lock().expect("should get lock");
+    if let Some(staging) = &state.staging
+      && let Ok(relative) = path.strip_prefix(&staging.output_path)
+    {
+      let removed = path
+        .ancestors()
+        .take_while(|ancestor| ancestor.starts_with(&staging.output_path))
+        .any(|ancestor| state.removed.contains(ancestor));
+      return Located::Staged {
+        staged: staging.staging_path.join(relative),
+        previous: (staging.has_output && !removed).then(|| path.to_path_buf()),
+      };
+    }
+    Located::Plain(path.to_path_buf())
+  }
+
+  /// Redirect the path to the staging directory when it is inside the output path.
+  fn resolve(&self, path: &Utf8Path) -> Utf8PathBuf {
+    match self.locate(path) {
+      Located::Plain(path) => path,
+      Located::Staged { staged, .. } => staged,
+    }
+  }
+
+  /// Start an emit into the output path.
+  pub async fn begin(&self, output_path: &Utf8Path) -> Result<()> {
+    *self.state.lock().expect("should get lock") = EmitState::default();
+    if !self.options.staging {
+      return Ok(());
+    }
+
+    let staging_path = Self::staging_sibling(output_path, "staging")?;
+    // a staging directory left by a failed emit
+    if self.inner.stat(&staging_path).await.is_ok() {
+      self.inner.remove_dir_all(&staging_path).await?;
+    }
+    let has_output = match self.inner.stat(output_path).await {
+      Ok(metadata) if metadata.is_directory => true,
+      Ok(_) => {
+        return Err(new_error(&format!(
+          "output path {output_path} is not a directory"
+        )));
+      }
+      Err(_) => false,
+    };
+    // the staging directory starts empty, the previous output is only read through
+    self.inner.create_dir_all(&staging_path).await?;
+
+    self.state.lock().expect("should get lock").staging = Some(Staging {
+      output_path: output_path.to_path_buf(),
+      staging_path,
+      has_output,
+    });
+    Ok(())
+  }
+
+  async fn create_staged_parent(&self, staged: &Utf8Path) -> Result<()> {
+    if let Some(parent) = staged.parent() {
+      WritableFileSystem::create_dir_all(self, parent).await?;
+    }
+    Ok(())
+  }
+
+  /// Link the files of the previous output which are neither emitted again nor removed
+  /// into the staging directory, a hard link keeps the inode and the mtime of the file.
+  async fn carry_over(&self, output_path: &Utf8Path, staging_path: &Utf8Path) -> Result<()> {
+    let mut pending = vec![output_path.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+      for name in self.inner.read_dir(&dir).await? {
+        let path = dir.join(&name);
+        let Located::Staged {
+          staged,
+          previous: Some(previous),
+        } = self.locate(&path)
+        else {
+          continue;
+        };
+        if self.inner.stat(&previous).await?.is_directory {
+          pending.push(previous);
+        } else if self.inner.stat(&staged).await.is_err() {
+          self.create_staged_parent(&staged).await?;
+          self.inner.hard_link(&previous, &staged).await?;
+        }
+      }
+    }
+    // the staging directory is removed when `output.clean` cleans everything and nothing is emitted
+    if self.inner.stat(staging_path).await.is_err() {
+      self.inner.create_dir_all(staging_path).await?;
+    }
+    Ok(())
+  }
+
+  /// Write the file unless its bytes on disk already match, returns whether it was written.
+  pub async fn write_if_changed(&self, file: &Utf8Path, data: &[u8]) -> Result<bool> {
+    if self.options.skip_unchanged && self.is_unchanged(file, data).await {
+      self.state.lock().expect("should get lock").stats.skipped += 1;
+      return Ok(false);
+    }
+
+    let file = match self.locate(file) {
+      Located::Plain(file) => file,
+      Located::Staged { staged, .. } => {
+        self.create_staged_parent(&staged).await?;
+        staged
+      }
+    };
+    self.inner.write(&file, data).await?;
+    let mut state = self.state.lock().expect("should get lock");
+    state.stats.written += 1;
+    if self.options.fsync {
+      state.written_files.push(file);
+    }
+    Ok(true)
+  }
+
+  async fn is_unchanged(&self, file: &Utf8Path, data: &[u8]) -> bool {
+    // the unchanged file of the previous output is linked into the staging directory at the end
+    match WritableFileSystem::stat(self, file).await {
+      // compare the bytes only when the size matches
+      Ok(metadata) if metadata.is_file && metadata.size == data.len() as u64 => {
+        WritableFileSystem::read_file(self, file)
+          .await
+          .is_ok_and(|content| content == data)
+      }
+      _ => false,
+    }
+  }
+
+  /// Abort the emit, the following operations are forwarded to the wrapped file system again.
+  pub fn abort(&self) {
+    *self.state.lock().expect("should get lock") = EmitState::default();
+  }
+
+  /// Replace the output path with the staging directory.
+  ///
+  /// The paths are exchanged atomically when the file system supports it. Otherwise the output path
+  /// is moved aside first, and moved back if the staging directory can not be moved into place.
+  async fn swap(&self, output_path: &Utf8Path, staging_path: &Utf8Path) -> Result<()> {
+    if self.inner.stat(output_path).await.is_err() {
+      return self.inner.rename(staging_path, output_path).await;
+    }
+
+    match self.inner.exchange(staging_path, output_path).await {
+      // the staging path holds the previous output now
+      Ok(()) => return self.inner.remove_dir_all(staging_path).await,
+      Err(err) if !is_unsupported(&err) => return Err(err),
+      Err(_) => {}
+    }
+
+    let old_path = Self::staging_sibling(output_path, "old")?;
+    if self.inner.stat(&old_path).await.is_ok() {
+      self.inner.remove_dir_all(&old_path).await?;
+    }
+    self.inner.rename(output_path, &old_path).await?;
+    if let Err(err) = self.inner.rename(staging_path, output_path).await {
+      self.inner.rename(&old_path, output_path).await?;
+      return Err(err);
+    }
+    self.inner.remove_dir_all(&old_path).await
+  }
+
+  /// Finish the emit, flushes the written files and swaps the staging directory into place.
+  pub async fn finish(&self) -> Result<EmitStats> {
+    let (staging, stats) = {
+      let state = self.state.lock().expect("should get lock");
+      (
+        state
+          .staging
+          .as_ref()
+          .map(|staging| (staging.output_path.clone(), staging.staging_path.clone())),
+        state.stats,
+      )
+    };
+
+    if let Some((output_path, staging_path)) = &staging {
+      self.carry_over(output_path, staging_path).await?;
+    }
+
+    let EmitState {
+      created_dirs,
+      written_files,
+      ..
+    } = std::mem::take(&mut *self.state.lock().expect("should get lock"));
+
+    if self.options.fsync {
+      for file in &written_files {
+        self.inner.sync(file).await?;
+      }
+      for dir in &created_dirs {
+        self.inner.sync(dir).await?;
+      }
+    }
+
+    let Some((output_path, staging_path)) = staging else {
+      return Ok(stats);
+    };
+
+    if self.options.fsync {
+      self.inner.sync(&staging_path).await?;
+    }
+    self.swap(&output_path, &staging_path).await?;
+    if self.options.fsync
+      && let Some(parent) = output_path.parent()
+    {
+      self.inner.sync(parent).await?;
+    }
+
+    Ok(stats)
+  }
+}
+
+#[async_trait::async_trait]
+impl WritableFileSystem for EmitFileSystem {
+  async fn create_dir(&self, dir: &Utf8Path) -> Result<()> {
+    self.inner.create_dir(&self.resolve(dir)).await
+  }
+
+  async fn create_dir_all(&self, dir: &Utf8Path) -> Result<()> {
+    let dir = self.resolve(dir);
+    if self
+      .state
+      .lock()
+      .expect("should get lock")
+      .created_dirs
+      .contains(&dir)
+    {
+      return Ok(());
+    }
+    self.inner.create_dir_all(&dir).await?;
+    self
+      .state
+      .lock()
+      .expect("should get lock")
+      .created_dirs
+      .insert(dir);
+    Ok(())
+  }
+
+  async fn write(&self, file: &Utf8Path, data: &[u8]) -> Result<()> {
+    self.write_if_changed(file, data).await?;
+    Ok(())
+  }
+
+  async fn create_new(&self, file: &Utf8Path, data: &[u8]) -> Result<()> {
+    match self.locate(file) {
+      Located::Plain(file) => self.inner.create_new(&file, data).await,
+      Located::Staged { staged, previous } => {
+        if let Some(previous) = previous
+          && self.inner.stat(&previous).await.is_ok()
+        {
+          return Err(Error::Io(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            "file already exists",
+          )));
+        }
+        self.create_staged_parent(&staged).await?;
+        self.inner.create_new(&staged, data).await
+      }
+    }
+  }
+
+  async fn rename(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
+    let Located::Staged { staged, previous } = self.locate(from) else {
+      return self.inner.rename(from, &self.resolve(to)).await;
+    };
+    let to = self.resolve(to);
+    if self.inner.stat(&staged).await.is_ok() {
+      self.inner.rename(&staged, &to).await?;
+    } else if let Some(previous) = previous {
+      // the previous output is left untouched until the swap, link the file instead of moving it
+      if self.inner.stat(&previous).await?.is_directory {
+        return Err(new_error(&format!(
+          "can not rename the directory {from} of the previous output during a staged emit"
+        )));
+      }
+      if let Some(parent) = to.parent() {
+        WritableFileSystem::create_dir_all(self, parent).await?;
+      }
+      self.inner.hard_link(&previous, &to).await?;
+    } else {
+      return Err(new_error(&format!("{from} does not exist")));
+    }
+    self
+      .state
+      .lock()
+      .expect("should get lock")
+      .removed
+      .insert(from.to_path_buf());
+    Ok(())
+  }
+
+  async fn exchange(&self, a: &Utf8Path, b: &Utf8Path) -> Result<()> {
+    self
+      .inner
+      .exchange(&self.resolve(a), &self.resolve(b))
+      .await
+  }
+
+  async fn hard_link(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
+    let from = match self.locate(from) {
+      Located::Plain(from) => from,
+      Located::Staged { staged, previous } => match previous {
+        Some(previous) if self.inner.stat(&staged).await.is_err() => previous,
+        _ => staged,
+      },
+    };
+    self.inner.hard_link(&from, &self.resolve(to)).await
+  }
+
+  async fn sync(&self, path: &Utf8Path) -> Result<()> {
+    self.inner.sync(&self.resolve(path)).await
+  }
+
+  async fn remove_file(&self, file: &Utf8Path) -> Result<()> {
+    let Located::Staged { staged, .. } = self.locate(file) else {
+      return self.inner.remove_file(file).await;
+    };
+    if self.inner.stat(&staged).await.is_ok() {
+      self.inner.remove_file(&staged).await?;
+    }
+    self
+      .state
+      .lock()
+      .expect("should get lock")
+      .removed
+      .insert(file.to_path_buf());
+    Ok(())
+  }
+
+  async fn remove_dir_all(&self, dir: &Utf8Path) -> Result<()> {
+    let staged = match self.locate(dir) {
+      Located::Plain(dir) => {
+        self.inner.remove_dir_all(&dir).await?;
+        dir
+      }
+      Located::Staged { staged, .. } => {
+        if self.inner.stat(&staged).await.is_ok() {
+          self.inner.remove_dir_all(&staged).await?;
+        }
+        self
+          .state
+          .lock()
+          .expect("should get lock")
+          .removed
+          .insert(dir.to_path_buf());
+        staged
+      }
+    };
+    self
+      .state
+      .lock()
+      .expect("should get lock")
+      .created_dirs
+      .retain(|created| !created.starts_with(&staged));
+    Ok(())
+  }
+
+  async fn read_dir(&self, dir: &Utf8Path) -> Result<Vec<String>> {
+    let (staged, previous) = match self.locate(dir) {
+      Located::Plain(dir) => return self.inner.read_dir(&dir).await,
+      Located::Staged { staged, previous } => (staged, previous),
+    };
+    let staged_entries = self.inner.read_dir(&staged).await;
+    let previous_entries = match &previous {
+      Some(previous) => Some(self.inner.read_dir(previous).await),
+      None => None,
+    };
+    let (staged_entries, previous_entries) = match (staged_entries, previous_entries) {
+      // the directory exists in neither of them
+      (Err(err), None | Some(Err(_))) => return Err(err),
+      (staged_entries, previous_entries) => (
+        staged_entries.unwrap_or_default(),
+        previous_entries.and_then(Result::ok).unwrap_or_default(),
+      ),
+    };
+
+    let state = self.state.lock().expect("should get lock");
+    let mut entries = staged_entries;
+    for name in previous_entries {
+      if !entries.contains(&name) && !state.removed.contains(&dir.join(&name)) {
+        entries.push(name);
+      }
+    }
+    Ok(entries)
+  }
+
+  async fn read_file(&self, file: &Utf8Path) -> Result<Vec<u8>> {
+    match self.locate(file) {
+      Located::Plain(file) => self.inner.read_file(&file).await,
+      Located::Staged { staged, previous } => {
+        let res = self.inner.read_file(&staged).await;
+        match previous {
+          Some(previous) if res.is_err() => self.inner.read_file(&previous).await,
+          _ => res,
+        }
+      }
+    }
+  }
+
+  async fn stat(&self, file: &Utf8Path) -> Result<FileMetadata> {
+    match self.locate(file) {
+      Located::Plain(file) => self.inner.stat(&file).await,
+      Located::Staged { staged, previous } => {
+        let res = self.inner.stat(&staged).await;
+        match previous {
+          Some(previous) if res.is_err() => self.inner.stat(&previous).await,
+          _ => res,
+        }
+      }
+    }
+  }
+
+  async fn set_permissions(&self, path: &Utf8Path, perm: FilePermissions) -> Result<()> {
+    self.inner.set_permissions(&self.resolve(path), perm).await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use rspack_paths::Utf8Path;
+
+  use super::{EmitFileSystem, EmitFileSystemOptions, EmitStats};
+  use crate::{
+    FileMetadata, FilePermissions, MemoryFileSystem, ReadableFileSystem, Result, WritableFileSystem,
+  };
+
+  async fn create_output() -> Result<MemoryFileSystem> {
+    let fs = MemoryFileSystem::default();
+    fs.create_dir_all(Utf8Path::new("/dist/static")).await?;
+    fs.write(Utf8Path::new("/dist/main.js"), b"main").await?;
+    fs.write(Utf8Path::new("/dist/static/a.js"), b"a").await?;
+    Ok(fs)
+  }
+
+  #[tokio::test]
+  async fn should_skip_unchanged_writes() -> Result<()> {
+    let inner = create_output().await?;
+    let fs = EmitFileSystem::new(
+      Arc::new(inner.clone()),
+      EmitFileSystemOptions {
+        skip_unchanged: true,
+        ..Default::default()
+      },
+    );
+    let mtime = ReadableFileSystem::metadata(&inner, Utf8Path::new("/dist/main.js"))
+      .await?
+      .mtime_ms;
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+    fs.begin(Utf8Path::new("/dist")).await?;
+    assert!(
+      !fs
+        .write_if_changed(Utf8Path::new("/dist/main.js"), b"main")
+        .await?
+    );
+    assert!(
+      fs.write_if_changed(Utf8Path::new("/dist/static/a.js"), b"b")
+        .await?
+    );
+    assert_eq!(
+      fs.finish().await?,
+      EmitStats {
+        written: 1,
+        skipped: 1
+      }
+    );
+
+    assert_eq!(
+      ReadableFileSystem::metadata(&inner, Utf8Path::new("/dist/main.js"))
+        .await?
+        .mtime_ms,
+      mtime
+    );
+    assert_eq!(
+      ReadableFileSystem::read(&inner, Utf8Path::new("/dist/static/a.js")).await?,
+      b"b"
+    );
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn should_swap_staging_dir_on_finish() -> Result<()> {
+    let inner = create_output().await?;
+    let fs = EmitFileSystem::new(
+      Arc::new(inner.clone()),
+      EmitFileSystemOptions {
+        staging: true,
+        ..Default::default()
+      },
+    );
+
+    fs.begin(Utf8Path::new("/dist")).await?;
+    fs.create_dir_all(Utf8Path::new("/dist/chunks")).await?;
+    fs.write(Utf8Path::new("/dist/chunks/b.js"), b"b").await?;
+    fs.write(Utf8Path::new("/dist/main.js"), b"main2").await?;
+    fs.remove_file(Utf8Path::new("/dist/static/a.js")).await?;
+
+    // the output is untouched until the emit finishes
+    assert_eq!(
+      ReadableFileSystem::read(&inner, Utf8Path::new("/dist/main.js")).await?,
+      b"main"
+    );
+    assert!(
+      ReadableFileSystem::read(&inner, Utf8Path::new("/dist/chunks/b.js"))
+        .await
+        .is_err()
+    );
+    assert_eq!(
+      fs.read_file(Utf8Path::new("/dist/main.js")).await?,
+      b"main2"
+    );
+
+    fs.finish().await?;
+    assert_eq!(
+      ReadableFileSystem::read(&inner, Utf8Path::new("/dist/main.js")).await?,
+      b"main2"
+    );
+    assert_eq!(
+      ReadableFileSystem::read(&inner, Utf8Path::new("/dist/chunks/b.js")).await?,
+      b"b"
+    );
+    assert!(
+      ReadableFileSystem::read(&inner, Utf8Path::new("/dist/static/a.js"))
+        .await
+        .is_err()
+    );
+    let mut children = ReadableFileSystem::read_dir(&inner, Utf8Path::new("/")).await?;
+    children.sort();
+    assert_eq!(children, vec!["dist"]);
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn should_link_files_not_emitted_again_into_staging() -> Result<()> {
+    let inner = create_output().await?;
+    let fs = EmitFileSystem::new(
+      Arc::new(inner.clone()),
+      EmitFileSystemOptions {
+        skip_unchanged: true,
+        staging: true,
+        ..Default::default()
+      },
+    );
+    let mtime = ReadableFileSystem::metadata(&inner, Utf8Path::new("/dist/static/a.js"))
+      .await?
+      .mtime_ms;
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+    fs.begin(Utf8Path::new("/dist")).await?;
+    // the staging directory is not seeded with a copy of the previous output
+    assert!(
+      ReadableFileSystem::read_dir(&inner, Utf8Path::new("/.dist.staging"))
+        .await?
+        .is_empty()
+    );
+    assert!(
+      !fs
+        .write_if_changed(Utf8Path::new("/dist/static/a.js"), b"a")
+        .await?
+    );
+    assert!(
+      fs.write_if_changed(Utf8Path::new("/dist/main.js"), b"main2")
+        .await?
+    );
+    let mut children = fs.read_dir(Utf8Path::new("/dist")).await?;
+    children.sort();
+    assert_eq!(children, vec!["main.js", "static"]);
+
+    assert_eq!(
+      fs.finish().await?,
+      EmitStats {
+        written: 1,
+        skipped: 1
+      }
+    );
+    assert_eq!(
+      ReadableFileSystem::metadata(&inner, Utf8Path::new("/dist/static/a.js"))
+        .await?
+        .mtime_ms,
+      mtime
+    );
+    assert_eq!(
+      ReadableFileSystem::read(&inner, Utf8Path::new("/dist/main.js")).await?,
+      b"main2"
+    );
+    Ok(())
+  }
+
+  /// Supports neither exchanging nor renaming the staging directory into place
+  #[derive(Debug)]
+  struct NoSwapFileSystem(MemoryFileSystem);
+
+  #[async_trait::async_trait]
+  impl WritableFileSystem for NoSwapFileSystem {
+    async fn create_dir(&self, dir: &Utf8Path) -> Result<()> {
+      self.0.create_dir(dir).await
+    }
+    async fn create_dir_all(&self, dir: &Utf8Path) -> Result<()> {
+      self.0.create_dir_all(dir).await
+    }
+    async fn write(&self, file: &Utf8Path, data: &[u8]) -> Result<()> {
+      self.0.write(file, data).await
+    }
+    async fn rename(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
+      if from.as_str().ends_with(".staging") {
+        return Err(crate::Error::new(
+          std::io::ErrorKind::PermissionDenied,
+          "rename failed",
+        ));
+      }
+      self.0.rename(from, to).await
+    }
+    async fn sync(&self, path: &Utf8Path) -> Result<()> {
+      self.0.sync(path).await
+    }
+    async fn remove_file(&self, file: &Utf8Path) -> Result<()> {
+      self.0.remove_file(file).await
+    }
+    async fn remove_dir_all(&self, dir: &Utf8Path) -> Result<()> {
+      self.0.remove_dir_all(dir).await
+    }
+    async fn read_dir(&self, dir: &Utf8Path) -> Result<Vec<String>> {
+      WritableFileSystem::read_dir(&self.0, dir).await
+    }
+    async fn read_file(&self, file: &Utf8Path) -> Result<Vec<u8>> {
+      self.0.read_file(file).await
+    }
+    async fn stat(&self, file: &Utf8Path) -> Result<FileMetadata> {
+      self.0.stat(file).await
+    }
+    async fn set_permissions(&self, path: &Utf8Path, perm: FilePermissions) -> Result<()> {
+      self.0.set_permissions(path, perm).await
+    }
+  }
+
+  #[tokio::test]
+  async fn should_restore_output_when_swap_fails() -> Result<()> {
+    let inner = create_output().await?;
+    let fs = EmitFileSystem::new(
+      Arc::new(NoSwapFileSystem(inner.clone())),
+      EmitFileSystemOptions {
+        staging: true,
+        ..Default::default()
+      },
+    );
+
+    fs.begin(Utf8Path::new("/dist")).await?;
+    fs.write(Utf8Path::new("/dist/main.js"), b"main2").await?;
+    assert!(fs.finish().await.is_err());
+    assert_eq!(
+      ReadableFileSystem::read(&inner, Utf8Path::new("/dist/main.js")).await?,
+      b"main"
+    );
+    assert_eq!(
+      ReadableFileSystem::read(&inner, Utf8Path::new("/dist/static/a.js")).await?,
+      b"a"
+    );
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn should_emit_through_staging_without_previous_output() -> Result<()> {
+    let inner = MemoryFileSystem::default();
+    inner.create_dir_all(Utf8Path::new("/")).await?;
+    let fs = EmitFileSystem::new(
+      Arc::new(inner.clone()),
+      EmitFileSystemOptions {
+        staging: true,
+        fsync: true,
+        ..Default::default()
+      },
+    );
+
+    fs.begin(Utf8Path::new("/dist")).await?;
+    fs.write(Utf8Path::new("/dist/main.js"), b"main").await?;
+    fs.finish().await?;
+    assert_eq!(
+      ReadableFileSystem::read(&inner, Utf8Path::new("/dist/main.js")).await?,
+      b"main"
+    );
+    Ok(())
+  }
+}
@@ -19,6 +19,15 @@ pub use native_fs::{NativeFileSystem, NativeReadStream, NativeWriteStream};
 mod memory_fs;
 pub use memory_fs::{MemoryFileSystem, MemoryReadStream, MemoryWriteStream};
 
+mod overlay_fs;
+pub use overlay_fs::{OverlayFileSystem, OverlayWriteStream};
+
+mod emit_fs;
+pub use emit_fs::{EmitFileSystem, EmitFileSystemOptions, EmitStats};
+
+mod replay_fs;
+pub use replay_fs::{FsArchive, RecordingFileSystem, ReplayFileSystem};
+
 mod error;
 pub use error::{
   Error, FsResultToIoResultExt, IoResultToFsResultExt, Result, RspackResultToFsResultExt,
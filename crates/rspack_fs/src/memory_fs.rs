@@ -132,13 +132,27 @@ impl MemoryFileSystem {
     Ok(res.into_iter().collect())
   }
 
-  fn _rename_file(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
-    if !self.contains_file(from)? {
-      return Err(new_error("from dir not exist"));
-    }
+  fn _rename(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
     let mut files = self.files.lock().expect("should get lock");
-    let file = files.remove(from).expect("should have file");
-    files.insert(to.into(), file);
+    if !files.contains_key(from) {
+      return Err(new_error("from path not exist"));
+    }
+    if from == to {
+      return Ok(());
+    }
+
+    // replace the target and move the entry together with its children
+    files.retain(|path, _| !path.starts_with(to));
+    let moved = files
+      .keys()
+      .filter(|path| path.starts_with(from))
+      .cloned()
+      .collect::<Vec<_>>();
+    for path in moved {
+      let entry = files.remove(&path).expect("should have entry");
+      let relative = path.strip_prefix(from).expect("should be child of from");
+      files.insert(to.join(relative), entry);
+    }
 
     Ok(())
   }
@@ -203,6 +217,77 @@ impl WritableFileSystem for MemoryFileSystem {
     Ok(())
   }
 
+  async fn create_new(&self, file: &Utf8Path, data: &[u8]) -> Result<()> {
+    let p = file.parent().expect("should have parent dir");
+    if !self.contains_dir(p)? {
+      return Err(new_error("parent dir not exist"));
+    }
+
+    let mut files = self.files.lock().expect("should get lock");
+    if files.contains_key(file) {
+      return Err(Error::Io(std::io::Error::new(
+        std::io::ErrorKind::AlreadyExists,
+        "file already exists",
+      )));
+    }
+    files.insert(file.to_path_buf(), FileType::new_file(data.to_vec()));
+    Ok(())
+  }
+
+  async fn rename(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
+    self._rename(from, to)
+  }
+
+  async fn exchange(&self, a: &Utf8Path, b: &Utf8Path) -> Result<()> {
+    let mut files = self.files.lock().expect("should get lock");
+    if !files.contains_key(a) || !files.contains_key(b) {
+      return Err(new_error("exchanged path not exist"));
+    }
+    if a.starts_with(b) || b.starts_with(a) {
+      return Err(new_error("can not exchange a path with its ancestor"));
+    }
+    let moved = files
+      .keys()
+      .filter(|path| path.starts_with(a) || path.starts_with(b))
+      .cloned()
+      .collect::<Vec<_>>();
+    let entries = moved
+      .into_iter()
+      .map(|path| {
+        let entry = files.remove(&path).expect("should have entry");
+        let target = match path.strip_prefix(a) {
+          Ok(relative) => b.join(relative),
+          Err(_) => a.join(path.strip_prefix(b).expect("should be child of b")),
+        };
+        (target, entry)
+      })
+      .collect::<Vec<_>>();
+    files.extend(entries);
+    Ok(())
+  }
+
+  async fn hard_link(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
+    let p = to.parent().expect("should have parent dir");
+    if !self.contains_dir(p)? {
+      return Err(new_error("parent dir not exist"));
+    }
+    let mut files = self.files.lock().expect("should get lock");
+    let Some(FileType::File { content, metadata }) = files.get(from) else {
+      return Err(new_error("invalid file path"));
+    };
+    let entry = FileType::File {
+      content: content.clone(),
+      metadata: metadata.clone(),
+    };
+    files.insert(to.to_path_buf(), entry);
+    Ok(())
+  }
+
+  async fn sync(&self, _path: &Utf8Path) -> Result<()> {
+    // nothing to flush in memory
+    Ok(())
+  }
+
   async fn remove_file(&self, file: &Utf8Path) -> Result<()> {
     self._remove_file(file)
   }
@@ -284,11 +369,6 @@ impl ReadableFileSystem for MemoryFileSystem {
 
 #[async_trait::async_trait]
 impl IntermediateFileSystemExtras for MemoryFileSystem {
-  async fn rename(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
-    self._rename_file(from, to)?;
-    Ok(())
-  }
-
   async fn create_read_stream(&self, file: &Utf8Path) -> Result<Box<dyn ReadStream>> {
     let contents = self.read(file).await?;
     let reader = MemoryReadStream::new(contents);
@@ -31,6 +31,59 @@ impl NativeFileSystem {
   }
 }
 
+/// Swap two paths with `renameat2(RENAME_EXCHANGE)` on linux and `renamex_np(RENAME_SWAP)` on macOS
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn exchange_paths(a: &Utf8Path, b: &Utf8Path) -> Result<()> {
+  use std::ffi::CString;
+
+  let to_c_string = |path: &Utf8Path| {
+    CString::new(path.as_str())
+      .map_err(|e| Error::new(std::io::ErrorKind::InvalidInput, &e.to_string()))
+  };
+  let a = to_c_string(a)?;
+  let b = to_c_string(b)?;
+
+  // SAFETY: both of the paths are valid nul terminated strings
+  #[cfg(target_os = "linux")]
+  let res = unsafe {
+    libc::syscall(
+      libc::SYS_renameat2,
+      libc::AT_FDCWD,
+      a.as_ptr(),
+      libc::AT_FDCWD,
+      b.as_ptr(),
+      libc::RENAME_EXCHANGE,
+    )
+  };
+  // SAFETY: both of the paths are valid nul terminated strings
+  #[cfg(target_os = "macos")]
+  let res = unsafe { libc::renamex_np(a.as_ptr(), b.as_ptr(), libc::RENAME_SWAP) };
+
+  if res == 0 {
+    return Ok(());
+  }
+  let err = std::io::Error::last_os_error();
+  // the kernel or the file system does not support exchanging
+  if matches!(
+    err.raw_os_error(),
+    Some(libc::ENOSYS | libc::EINVAL | libc::ENOTSUP)
+  ) {
+    return Err(Error::new(
+      std::io::ErrorKind::Unsupported,
+      &err.to_string(),
+    ));
+  }
+  Err(err.into())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_family = "wasm")))]
+fn exchange_paths(a: &Utf8Path, _b: &Utf8Path) -> Result<()> {
+  Err(Error::new(
+    std::io::ErrorKind::Unsupported,
+    &format!("the platform does not support exchanging {a} atomically"),
+  ))
+}
+
 #[cfg(not(target_family = "wasm"))]
 #[async_trait::async_trait]
 impl WritableFileSystem for NativeFileSystem {
@@ -46,6 +99,34 @@ impl WritableFileSystem for NativeFileSystem {
   async fn write(&self, file: &Utf8Path, data: &[u8]) -> Result<()> {
     fs::write(file, data).to_fs_result()
   }
+  #[instrument(skip(self, data), level = "debug")]
+  async fn create_new(&self, file: &Utf8Path, data: &[u8]) -> Result<()> {
+    File::create_new(file)
+      .and_then(|mut f| f.write_all(data))
+      .to_fs_result()
+  }
+  #[instrument(skip(self), level = "debug")]
+  async fn rename(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
+    tokio::fs::rename(from, to).await.to_fs_result()
+  }
+  #[instrument(skip(self), level = "debug")]
+  async fn exchange(&self, a: &Utf8Path, b: &Utf8Path) -> Result<()> {
+    exchange_paths(a, b)
+  }
+  #[instrument(skip(self), level = "debug")]
+  async fn hard_link(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
+    tokio::fs::hard_link(from, to).await.to_fs_result()
+  }
+  #[instrument(skip(self), level = "debug")]
+  async fn sync(&self, path: &Utf8Path) -> Result<()> {
+    // directories can only be opened for syncing on unix
+    if cfg!(not(unix)) && fs::metadata(path).to_fs_result()?.is_dir() {
+      return Ok(());
+    }
+    File::open(path)
+      .and_then(|file| file.sync_all())
+      .to_fs_result()
+  }
   #[instrument(skip(self), level = "debug")]
   async fn remove_file(&self, file: &Utf8Path) -> Result<()> {
     tokio::fs::remove_file(file).await.to_fs_result()
@@ -98,6 +179,29 @@ impl WritableFileSystem for NativeFileSystem {
   async fn write(&self, file: &Utf8Path, data: &[u8]) -> Result<()> {
     fs::write(file, data).to_fs_result()
   }
+  #[instrument(skip(self, data), level = "debug")]
+  async fn create_new(&self, file: &Utf8Path, data: &[u8]) -> Result<()> {
+    File::create_new(file)
+      .and_then(|mut f| f.write_all(data))
+      .to_fs_result()
+  }
+  #[instrument(skip(self), level = "debug")]
+  async fn rename(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
+    fs::rename(from, to).to_fs_result()
+  }
+  #[instrument(skip(self), level = "debug")]
+  async fn hard_link(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
+    fs::hard_link(from, to).to_fs_result()
+  }
+  #[instrument(skip(self), level = "debug")]
+  async fn sync(&self, path: &Utf8Path) -> Result<()> {
+    if fs::metadata(path).to_fs_result()?.is_dir() {
+      return Ok(());
+    }
+    File::open(path)
+      .and_then(|file| file.sync_all())
+      .to_fs_result()
+  }
   #[instrument(skip(self), level = "debug")]
   async fn remove_file(&self, file: &Utf8Path) -> Result<()> {
     fs::remove_file(file).to_fs_result()
@@ -346,10 +450,6 @@ impl ReadableFileSystem for NativeFileSystem {
 
 #[async_trait::async_trait]
 impl IntermediateFileSystemExtras for NativeFileSystem {
-  #[instrument(skip(self), level = "debug")]
-  async fn rename(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
-    fs::rename(from, to).to_fs_result()
-  }
   #[instrument(skip(self), level = "debug")]
   async fn create_read_stream(&self, file: &Utf8Path) -> Result<Box<dyn ReadStream>> {
     let reader = NativeReadStream::try_new(file)?;
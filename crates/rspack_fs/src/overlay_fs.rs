@@ -0,0 +1,601 @@
+use std::{
+  collections::{HashMap, HashSet},
+  sync::{Arc, Mutex},
+};
+
+use rspack_paths::{Utf8Path, Utf8PathBuf};
+use rspack_util::current_time;
+
+use crate::{
+  Error, FileMetadata, FilePermissions, IntermediateFileSystem, IntermediateFileSystemExtras,
+  MemoryReadStream, ReadStream, ReadableFileSystem, Result, WritableFileSystem, WriteStream,
+};
+
+fn new_error(msg: &str) -> Error {
+  Error::Io(std::io::Error::other(msg))
+}
+
+fn not_found() -> Error {
+  Error::new(std::io::ErrorKind::NotFound, "file not exist")
+}
+
+fn new_metadata(is_directory: bool, size: u64) -> FileMetadata {
+  let now = current_time();
+  FileMetadata {
+    is_file: !is_directory,
+    is_directory,
+    is_symlink: false,
+    atime_ms: now,
+    mtime_ms: now,
+    ctime_ms: now,
+    size,
+  }
+}
+
+#[derive(Debug, Clone)]
+enum Entry {
+  Dir {
+    metadata: FileMetadata,
+    /// An opaque dir hides the lower dir at the same path,
+    /// it is created after the lower dir is deleted.
+    opaque: bool,
+  },
+  File {
+    content: Vec<u8>,
+    metadata: FileMetadata,
+  },
+  /// The path is deleted, the lower file or dir is hidden.
+  Whiteout,
+}
+
+/// Where a path is resolved from
+#[derive(Debug)]
+enum Layer {
+  Upper(Entry),
+  Lower,
+  Deleted,
+}
+
+/// A writable copy-on-write file system
+///
+/// All of the changes are kept in memory and shadow the files of the lower file system,
+/// which is never written. Deletions and renames are recorded as whiteouts, so the
+/// deleted lower files disappear from `read`, `metadata` and `read_dir`.
+#[derive(Debug, Clone)]
+pub struct OverlayFileSystem {
+  lower: Arc<dyn ReadableFileSystem>,
+  upper: Arc<Mutex<HashMap<Utf8PathBuf, Entry>>>,
+}
+
+impl OverlayFileSystem {
+  pub fn new(lower: Arc<dyn ReadableFileSystem>) -> Self {
+    Self {
+      lower,
+      upper: Default::default(),
+    }
+  }
+
+  /// Drop all of the changes and expose the lower file system again.
+  pub fn clear(&self) {
+    let mut upper = self.upper.lock().expect("should get lock");
+    upper.clear();
+  }
+
+  fn layer(&self, path: &Utf8Path) -> Layer {
+    let upper = self.upper.lock().expect("should get lock");
+    match upper.get(path) {
+      Some(Entry::Whiteout) => return Layer::Deleted,
+      Some(entry) => return Layer::Upper(entry.clone()),
+      None => {}
+    }
+    for ancestor in path.ancestors().skip(1) {
+      match upper.get(ancestor) {
+        Some(Entry::Dir { opaque: false, .. }) | None => {}
+        Some(_) => return Layer::Deleted,
+      }
+    }
+    Layer::Lower
+  }
+
+  fn lower_exists(&self, path: &Utf8Path) -> bool {
+    self.lower.metadata_sync(path).is_ok()
+  }
+
+  fn upper_children(&self, dir: &Utf8Path) -> Vec<String> {
+    let upper = self.upper.lock().expect("should get lock");
+    upper
+      .iter()
+      .filter(|(path, entry)| !matches!(entry, Entry::Whiteout) && path.parent() == Some(dir))
+      .filter_map(|(path, _)| path.file_name().map(ToString::to_string))
+      .collect()
+  }
+
+  fn merge_dir(&self, dir: &Utf8Path, lower: Vec<String>) -> Vec<String> {
+    let mut res = lower
+      .into_iter()
+      .filter(|name| !matches!(self.layer(&dir.join(name)), Layer::Deleted))
+      .collect::<Vec<_>>();
+    let mut exists = res.iter().cloned().collect::<HashSet<_>>();
+    for name in self.upper_children(dir) {
+      if exists.insert(name.clone()) {
+        res.push(name);
+      }
+    }
+    res
+  }
+
+  fn dir_lower(&self, dir: &Utf8Path) -> Result<Option<bool>> {
+    // returns whether the lower dir should be merged, `None` means that the dir is not in upper
+    match self.layer(dir) {
+      Layer::Upper(Entry::Dir { opaque, .. }) => Ok(Some(!opaque)),
+      Layer::Upper(_) => Err(new_error("invalid dir path")),
+      Layer::Deleted => Err(not_found()),
+      Layer::Lower => Ok(None),
+    }
+  }
+
+  fn insert(&self, path: &Utf8Path, entry: Entry) {
+    let mut upper = self.upper.lock().expect("should get lock");
+    upper.insert(path.to_path_buf(), entry);
+  }
+
+  /// Remove the path from upper, and hide the lower one if it exists.
+  fn delete(&self, path: &Utf8Path) {
+    let whiteout = self.lower_exists(path);
+    let mut upper = self.upper.lock().expect("should get lock");
+    upper.retain(|p, _| !p.starts_with(path));
+    if whiteout {
+      upper.insert(path.to_path_buf(), Entry::Whiteout);
+    }
+  }
+
+  async fn is_dir(&self, path: &Utf8Path) -> bool {
+    ReadableFileSystem::metadata(self, path)
+      .await
+      .is_ok_and(|meta| meta.is_directory)
+  }
+
+  async fn copy(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
+    let mut queue = vec![(from.to_path_buf(), to.to_path_buf())];
+    while let Some((from, to)) = queue.pop() {
+      if self.is_dir(&from).await {
+        WritableFileSystem::create_dir(self, &to).await?;
+        for name in ReadableFileSystem::read_dir(self, &from).await? {
+          queue.push((from.join(&name), to.join(&name)));
+        }
+      } else {
+        let content = ReadableFileSystem::read(self, &from).await?;
+        WritableFileSystem::write(self, &to, &content).await?;
+      }
+    }
+    Ok(())
+  }
+}
+
+#[async_trait::async_trait]
+impl ReadableFileSystem for OverlayFileSystem {
+  async fn read(&self, path: &Utf8Path) -> Result<Vec<u8>> {
+    match self.layer(path) {
+      Layer::Upper(Entry::File { content, .. }) => Ok(content),
+      Layer::Upper(_) => Err(new_error("invalid file path")),
+      Layer::Deleted => Err(not_found()),
+      Layer::Lower => self.lower.read(path).await,
+    }
+  }
+
+  fn read_sync(&self, path: &Utf8Path) -> Result<Vec<u8>> {
+    match self.layer(path) {
+      Layer::Upper(Entry::File { content, .. }) => Ok(content),
+      Layer::Upper(_) => Err(new_error("invalid file path")),
+      Layer::Deleted => Err(not_found()),
+      Layer::Lower => self.lower.read_sync(path),
+    }
+  }
+
+  async fn metadata(&self, path: &Utf8Path) -> Result<FileMetadata> {
+    match self.layer(path) {
+      Layer::Upper(Entry::File { metadata, .. } | Entry::Dir { metadata, .. }) => Ok(metadata),
+      Layer::Upper(Entry::Whiteout) | Layer::Deleted => Err(not_found()),
+      Layer::Lower => self.lower.metadata(path).await,
+    }
+  }
+
+  fn metadata_sync(&self, path: &Utf8Path) -> Result<FileMetadata> {
+    match self.layer(path) {
+      Layer::Upper(Entry::File { metadata, .. } | Entry::Dir { metadata, .. }) => Ok(metadata),
+      Layer::Upper(Entry::Whiteout) | Layer::Deleted => Err(not_found()),
+      Layer::Lower => self.lower.metadata_sync(path),
+    }
+  }
+
+  async fn symlink_metadata(&self, path: &Utf8Path) -> Result<FileMetadata> {
+    match self.layer(path) {
+      Layer::Upper(Entry::File { metadata, .. } | Entry::Dir { metadata, .. }) => Ok(metadata),
+      Layer::Upper(Entry::Whiteout) | Layer::Deleted => Err(not_found()),
+      Layer::Lower => self.lower.symlink_metadata(path).await,
+    }
+  }
+
+  async fn canonicalize(&self, path: &Utf8Path) -> Result<Utf8PathBuf> {
+    match self.layer(path) {
+      Layer::Upper(_) => {
+        // upper files have no links, resolve the nearest ancestor which comes from lower
+        for ancestor in path.ancestors().skip(1) {
+          if matches!(self.layer(ancestor), Layer::Lower)
+            && let Ok(base) = self.lower.canonicalize(ancestor).await
+          {
+            let relative = path.strip_prefix(ancestor).expect("should be ancestor");
+            return Ok(base.join(relative));
+          }
+        }
+        Ok(path.to_path_buf())
+      }
+      Layer::Deleted => Err(not_found()),
+      Layer::Lower => {
+        let res = self.lower.canonicalize(path).await?;
+        if matches!(self.layer(&res), Layer::Deleted) {
+          return Err(not_found());
+        }
+        Ok(res)
+      }
+    }
+  }
+
+  async fn read_dir(&self, dir: &Utf8Path) -> Result<Vec<String>> {
+    let lower = match self.dir_lower(dir)? {
+      Some(true) => self.lower.read_dir(dir).await.unwrap_or_default(),
+      Some(false) => vec![],
+      None => self.lower.read_dir(dir).await?,
+    };
+    Ok(self.merge_dir(dir, lower))
+  }
+
+  fn read_dir_sync(&self, dir: &Utf8Path) -> Result<Vec<String>> {
+    let lower = match self.dir_lower(dir)? {
+      Some(true) => self.lower.read_dir_sync(dir).unwrap_or_default(),
+      Some(false) => vec![],
+      None => self.lower.read_dir_sync(dir)?,
+    };
+    Ok(self.merge_dir(dir, lower))
+  }
+
+  async fn permissions(&self, path: &Utf8Path) -> Result<Option<FilePermissions>> {
+    match self.layer(path) {
+      Layer::Upper(_) => Ok(None),
+      Layer::Deleted => Err(not_found()),
+      Layer::Lower => self.lower.permissions(path).await,
+    }
+  }
+}
+
+#[async_trait::async_trait]
+impl WritableFileSystem for OverlayFileSystem {
+  async fn create_dir(&self, dir: &Utf8Path) -> Result<()> {
+    let opaque = match self.layer(dir) {
+      Layer::Upper(Entry::Dir { .. }) => return Ok(()),
+      Layer::Upper(_) => return Err(new_error("invalid dir path")),
+      Layer::Deleted => true,
+      Layer::Lower => match self.lower.metadata(dir).await {
+        Ok(meta) if meta.is_directory => return Ok(()),
+        Ok(_) => return Err(new_error("invalid dir path")),
+        Err(_) => false,
+      },
+    };
+
+    if let Some(p) = dir.parent()
+      && !self.is_dir(p).await
+    {
+      return Err(new_error("parent directory not exist"));
+    }
+
+    self.insert(
+      dir,
+      Entry::Dir {
+        metadata: new_metadata(true, 0),
+        opaque,
+      },
+    );
+    Ok(())
+  }
+
+  async fn create_dir_all(&self, dir: &Utf8Path) -> Result<()> {
+    let mut missing = vec![];
+    for ancestor in dir.ancestors() {
+      if self.is_dir(ancestor).await {
+        break;
+      }
+      missing.push(ancestor);
+    }
+    for dir in missing.into_iter().rev() {
+      WritableFileSystem::create_dir(self, dir).await?;
+    }
+    Ok(())
+  }
+
+  async fn write(&self, file: &Utf8Path, data: &[u8]) -> Result<()> {
+    let ctime_ms = match ReadableFileSystem::metadata(self, file).await {
+      Ok(meta) if meta.is_directory => return Err(new_error("invalid file path")),
+      Ok(meta) => Some(meta.ctime_ms),
+      Err(_) => None,
+    };
+
+    let p = file.parent().expect("should have parent dir");
+    if !self.is_dir(p).await {
+      return Err(new_error("parent dir not exist"));
+    }
+
+    let mut metadata = new_metadata(false, data.len() as u64);
+    if let Some(ctime_ms) = ctime_ms {
+      metadata.ctime_ms = ctime_ms;
+    }
+    self.insert(
+      file,
+      Entry::File {
+        content: data.to_vec(),
+        metadata,
+      },
+    );
+    Ok(())
+  }
+
+  /// The layers only live in the current process, so checking and writing is enough.
+  async fn create_new(&self, file: &Utf8Path, data: &[u8]) -> Result<()> {
+    if ReadableFileSystem::metadata(self, file).await.is_ok() {
+      return Err(Error::Io(std::io::Error::new(
+        std::io::ErrorKind::AlreadyExists,
+        "file already exists",
+      )));
+    }
+    WritableFileSystem::write(self, file, data).await
+  }
+
+  /// Copy the file or dir to the new path and leave a whiteout at the old path.
+  async fn rename(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
+    if ReadableFileSystem::metadata(self, from).await.is_err() {
+      return Err(new_error("from path not exist"));
+    }
+    if ReadableFileSystem::metadata(self, to).await.is_ok() {
+      self.delete(to);
+    }
+    self.copy(from, to).await?;
+    self.delete(from);
+    Ok(())
+  }
+
+  async fn sync(&self, _path: &Utf8Path) -> Result<()> {
+    // the changes only live in memory until they are applied
+    Ok(())
+  }
+
+  async fn remove_file(&self, file: &Utf8Path) -> Result<()> {
+    match ReadableFileSystem::metadata(self, file).await {
+      Ok(meta) if meta.is_directory => Err(new_error("invalid file path")),
+      Ok(_) => {
+        self.delete(file);
+        Ok(())
+      }
+      Err(_) => Ok(()),
+    }
+  }
+
+  async fn remove_dir_all(&self, dir: &Utf8Path) -> Result<()> {
+    match ReadableFileSystem::metadata(self, dir).await {
+      Ok(meta) if !meta.is_directory => Err(new_error("invalid dir path")),
+      Ok(_) => {
+        self.delete(dir);
+        Ok(())
+      }
+      Err(_) => Ok(()),
+    }
+  }
+
+  async fn read_dir(&self, dir: &Utf8Path) -> Result<Vec<String>> {
+    ReadableFileSystem::read_dir(self, dir).await
+  }
+
+  async fn read_file(&self, file: &Utf8Path) -> Result<Vec<u8>> {
+    ReadableFileSystem::read(self, file).await
+  }
+
+  async fn stat(&self, file: &Utf8Path) -> Result<FileMetadata> {
+    ReadableFileSystem::metadata(self, file).await
+  }
+
+  async fn set_permissions(&self, _path: &Utf8Path, _perm: FilePermissions) -> Result<()> {
+    Ok(())
+  }
+}
+
+#[async_trait::async_trait]
+impl IntermediateFileSystemExtras for OverlayFileSystem {
+  async fn create_read_stream(&self, file: &Utf8Path) -> Result<Box<dyn ReadStream>> {
+    let contents = ReadableFileSystem::read(self, file).await?;
+    Ok(Box::new(MemoryReadStream::new(contents)))
+  }
+
+  async fn create_write_stream(&self, file: &Utf8Path) -> Result<Box<dyn WriteStream>> {
+    Ok(Box::new(OverlayWriteStream {
+      file: file.to_path_buf(),
+      contents: vec![],
+      fs: self.clone(),
+    }))
+  }
+}
+
+impl IntermediateFileSystem for OverlayFileSystem {}
+
+#[derive(Debug)]
+pub struct OverlayWriteStream {
+  file: Utf8PathBuf,
+  contents: Vec<u8>,
+  fs: OverlayFileSystem,
+}
+
+#[async_trait::async_trait]
+impl WriteStream for OverlayWriteStream {
+  async fn write(&mut self, buf: &[u8]) -> Result<usize> {
+    self.contents.extend(buf);
+    Ok(buf.len())
+  }
+  async fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+    self.contents = buf.to_vec();
+    Ok(())
+  }
+  async fn flush(&mut self) -> Result<()> {
+    WritableFileSystem::write(&self.fs, &self.file, &self.contents).await
+  }
+  async fn close(&mut self) -> Result<()> {
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use rspack_paths::Utf8Path;
+
+  use super::OverlayFileSystem;
+  use crate::{MemoryFileSystem, ReadableFileSystem, Result, WritableFileSystem};
+
+  async fn create_lower() -> Result<MemoryFileSystem> {
+    let lower = MemoryFileSystem::default();
+    WritableFileSystem::create_dir_all(&lower, Utf8Path::new("/src/utils")).await?;
+    WritableFileSystem::write(&lower, Utf8Path::new("/src/index.js"), b"index").await?;
+    WritableFileSystem::write(&lower, Utf8Path::new("/src/utils/a.js"), b"a").await?;
+    WritableFileSystem::write(&lower, Utf8Path::new("/src/utils/b.js"), b"b").await?;
+    Ok(lower)
+  }
+
+  fn sorted(mut names: Vec<String>) -> Vec<String> {
+    names.sort();
+    names
+  }
+
+  #[tokio::test]
+  async fn should_shadow_lower_files() -> Result<()> {
+    let lower = create_lower().await?;
+    let fs = OverlayFileSystem::new(Arc::new(lower.clone()));
+
+    WritableFileSystem::write(&fs, Utf8Path::new("/src/index.js"), b"unsaved").await?;
+    WritableFileSystem::create_dir_all(&fs, Utf8Path::new("/src/new/nested")).await?;
+    WritableFileSystem::write(&fs, Utf8Path::new("/src/new/nested/c.js"), b"c").await?;
+
+    assert_eq!(
+      ReadableFileSystem::read(&fs, Utf8Path::new("/src/index.js")).await?,
+      b"unsaved"
+    );
+    assert_eq!(
+      ReadableFileSystem::read(&fs, Utf8Path::new("/src/utils/a.js")).await?,
+      b"a"
+    );
+    assert!(ReadableFileSystem::metadata_sync(&fs, Utf8Path::new("/src/new/nested"))?.is_directory);
+    assert_eq!(
+      sorted(ReadableFileSystem::read_dir(&fs, Utf8Path::new("/src")).await?),
+      vec!["index.js", "new", "utils"]
+    );
+    // lower is never written
+    assert_eq!(
+      ReadableFileSystem::read(&lower, Utf8Path::new("/src/index.js")).await?,
+      b"index"
+    );
+    assert!(ReadableFileSystem::read_sync(&lower, Utf8Path::new("/src/new/nested/c.js")).is_err());
+
+    fs.clear();
+    assert_eq!(
+      ReadableFileSystem::read(&fs, Utf8Path::new("/src/index.js")).await?,
+      b"index"
+    );
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn should_hide_deleted_files() -> Result<()> {
+    let lower = create_lower().await?;
+    let fs = OverlayFileSystem::new(Arc::new(lower.clone()));
+
+    WritableFileSystem::remove_file(&fs, Utf8Path::new("/src/utils/a.js")).await?;
+    assert!(ReadableFileSystem::read_sync(&fs, Utf8Path::new("/src/utils/a.js")).is_err());
+    assert_eq!(
+      ReadableFileSystem::read_dir_sync(&fs, Utf8Path::new("/src/utils"))?,
+      vec!["b.js"]
+    );
+    // write after delete
+    WritableFileSystem::write(&fs, Utf8Path::new("/src/utils/a.js"), b"new a").await?;
+    assert_eq!(
+      ReadableFileSystem::read(&fs, Utf8Path::new("/src/utils/a.js")).await?,
+      b"new a"
+    );
+
+    WritableFileSystem::remove_dir_all(&fs, Utf8Path::new("/src/utils")).await?;
+    assert!(
+      ReadableFileSystem::metadata(&fs, Utf8Path::new("/src/utils/b.js"))
+        .await
+        .is_err()
+    );
+    assert!(
+      ReadableFileSystem::read_dir(&fs, Utf8Path::new("/src/utils"))
+        .await
+        .is_err()
+    );
+    assert_eq!(
+      ReadableFileSystem::read_dir(&fs, Utf8Path::new("/src")).await?,
+      vec!["index.js"]
+    );
+    // the recreated dir does not expose the deleted lower files
+    WritableFileSystem::create_dir(&fs, Utf8Path::new("/src/utils")).await?;
+    assert!(
+      ReadableFileSystem::read_dir(&fs, Utf8Path::new("/src/utils"))
+        .await?
+        .is_empty()
+    );
+    assert!(
+      ReadableFileSystem::read(&fs, Utf8Path::new("/src/utils/b.js"))
+        .await
+        .is_err()
+    );
+    assert!(
+      ReadableFileSystem::read(&lower, Utf8Path::new("/src/utils/b.js"))
+        .await
+        .is_ok()
+    );
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn should_rename_with_whiteout() -> Result<()> {
+    let lower = create_lower().await?;
+    let fs = OverlayFileSystem::new(Arc::new(lower));
+
+    fs.rename(
+      Utf8Path::new("/src/index.js"),
+      Utf8Path::new("/src/main.js"),
+    )
+    .await?;
+    fs.rename(Utf8Path::new("/src/utils"), Utf8Path::new("/src/lib"))
+      .await?;
+
+    assert!(
+      ReadableFileSystem::read(&fs, Utf8Path::new("/src/index.js"))
+        .await
+        .is_err()
+    );
+    assert_eq!(
+      ReadableFileSystem::read(&fs, Utf8Path::new("/src/main.js")).await?,
+      b"index"
+    );
+    assert_eq!(
+      ReadableFileSystem::read(&fs, Utf8Path::new("/src/lib/b.js")).await?,
+      b"b"
+    );
+    assert_eq!(
+      sorted(ReadableFileSystem::read_dir(&fs, Utf8Path::new("/src")).await?),
+      vec!["lib", "main.js"]
+    );
+    assert_eq!(
+      sorted(ReadableFileSystem::read_dir(&fs, Utf8Path::new("/src/lib")).await?),
+      vec!["a.js", "b.js"]
+    );
+    Ok(())
+  }
+}
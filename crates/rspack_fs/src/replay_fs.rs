@@ -0,0 +1,523 @@
+use std::{
+  collections::BTreeMap,
+  io::ErrorKind,
+  sync::{Arc, Mutex},
+};
+
+use rspack_paths::{Utf8Path, Utf8PathBuf};
+
+use crate::{Error, FileMetadata, FilePermissions, ReadableFileSystem, Result};
+
+const MAGIC: &[u8] = b"RSPACKFS";
+const VERSION: u8 = 1;
+
+const OP_END: u8 = 0;
+const OP_READ: u8 = 1;
+const OP_METADATA: u8 = 2;
+const OP_SYMLINK_METADATA: u8 = 3;
+const OP_CANONICALIZE: u8 = 4;
+const OP_READ_DIR: u8 = 5;
+const OP_PERMISSIONS: u8 = 6;
+
+fn invalid_data(msg: &str) -> Error {
+  Error::new(ErrorKind::InvalidData, msg)
+}
+
+/// The recorded result of a file system operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Recorded<T> {
+  Ok(T),
+  NotFound,
+  Failed(String),
+}
+
+impl<T: Clone> Recorded<T> {
+  fn from_result(res: &Result<T>) -> Self {
+    match res {
+      Ok(value) => Self::Ok(value.clone()),
+      Err(Error::Io(e)) if e.kind() == ErrorKind::NotFound => Self::NotFound,
+      Err(e) => Self::Failed(e.to_string()),
+    }
+  }
+
+  fn to_result(&self) -> Result<T> {
+    match self {
+      Self::Ok(value) => Ok(value.clone()),
+      Self::NotFound => Err(Error::new(ErrorKind::NotFound, "file not exist")),
+      Self::Failed(msg) => Err(Error::Io(std::io::Error::other(msg.clone()))),
+    }
+  }
+}
+
+#[derive(Debug, Default, Clone)]
+struct ArchiveEntry {
+  read: Option<Recorded<Vec<u8>>>,
+  metadata: Option<Recorded<FileMetadata>>,
+  symlink_metadata: Option<Recorded<FileMetadata>>,
+  canonicalize: Option<Recorded<Utf8PathBuf>>,
+  read_dir: Option<Recorded<Vec<String>>>,
+  permissions: Option<Recorded<Option<u32>>>,
+}
+
+/// The archive of file system accesses
+///
+/// It is captured by [`RecordingFileSystem`] and served by [`ReplayFileSystem`],
+/// use [`FsArchive::to_bytes`] and [`FsArchive::from_bytes`] to save it as a single file.
+#[derive(Debug, Default, Clone)]
+pub struct FsArchive {
+  entries: BTreeMap<Utf8PathBuf, ArchiveEntry>,
+}
+
+impl FsArchive {
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  /// The recorded paths in order
+  pub fn paths(&self) -> impl Iterator<Item = &Utf8Path> {
+    self.entries.keys().map(|p| p.as_path())
+  }
+
+  fn entry(&mut self, path: &Utf8Path) -> &mut ArchiveEntry {
+    self.entries.entry(path.to_path_buf()).or_default()
+  }
+
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut encoder = Encoder::default();
+    encoder.buf.extend(MAGIC);
+    encoder.put_u8(VERSION);
+    encoder.put_u64(self.entries.len() as u64);
+    for (path, entry) in &self.entries {
+      encoder.put_str(path.as_str());
+      if let Some(r) = &entry.read {
+        encoder.put_recorded(OP_READ, r, |e, v| e.put_bytes(v));
+      }
+      if let Some(r) = &entry.metadata {
+        encoder.put_recorded(OP_METADATA, r, Encoder::put_metadata);
+      }
+      if let Some(r) = &entry.symlink_metadata {
+        encoder.put_recorded(OP_SYMLINK_METADATA, r, Encoder::put_metadata);
+      }
+      if let Some(r) = &entry.canonicalize {
+        encoder.put_recorded(OP_CANONICALIZE, r, |e, v| e.put_str(v.as_str()));
+      }
+      if let Some(r) = &entry.read_dir {
+        encoder.put_recorded(OP_READ_DIR, r, |e, v| {
+          e.put_u64(v.len() as u64);
+          for name in v {
+            e.put_str(name);
+          }
+        });
+      }
+      if let Some(r) = &entry.permissions {
+        encoder.put_recorded(OP_PERMISSIONS, r, |e, v| match v {
+          Some(mode) => {
+            e.put_u8(1);
+            e.put_u64(u64::from(*mode));
+          }
+          None => e.put_u8(0),
+        });
+      }
+      encoder.put_u8(OP_END);
+    }
+    encoder.buf
+  }
+
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+    let mut decoder = Decoder { buf: bytes, pos: 0 };
+    if decoder.take(MAGIC.len())? != MAGIC {
+      return Err(invalid_data("not a file system archive"));
+    }
+    if decoder.get_u8()? != VERSION {
+      return Err(invalid_data("unsupported file system archive version"));
+    }
+    let mut archive = Self::default();
+    for _ in 0..decoder.get_u64()? {
+      let path = Utf8PathBuf::from(decoder.get_str()?);
+      let mut entry = ArchiveEntry::default();
+      loop {
+        match decoder.get_u8()? {
+          OP_END => break,
+          OP_READ => entry.read = Some(decoder.get_recorded(|d| Ok(d.get_bytes()?.to_vec()))?),
+          OP_METADATA => entry.metadata = Some(decoder.get_recorded(Decoder::get_metadata)?),
+          OP_SYMLINK_METADATA => {
+            entry.symlink_metadata = Some(decoder.get_recorded(Decoder::get_metadata)?)
+          }
+          OP_CANONICALIZE => {
+            entry.canonicalize =
+              Some(decoder.get_recorded(|d| Ok(Utf8PathBuf::from(d.get_str()?)))?)
+          }
+          OP_READ_DIR => {
+            entry.read_dir = Some(decoder.get_recorded(|d| {
+              (0..d.get_u64()?)
+                .map(|_| d.get_str())
+                .collect::<Result<Vec<_>>>()
+            })?)
+          }
+          OP_PERMISSIONS => {
+            entry.permissions = Some(decoder.get_recorded(|d| match d.get_u8()? {
+              0 => Ok(None),
+              _ => Ok(Some(
+                u32::try_from(d.get_u64()?).map_err(|_| invalid_data("invalid file mode"))?,
+              )),
+            })?)
+          }
+          _ => return Err(invalid_data("unknown file system operation")),
+        }
+      }
+      archive.entries.insert(path, entry);
+    }
+    Ok(archive)
+  }
+}
+
+#[derive(Debug, Default)]
+struct Encoder {
+  buf: Vec<u8>,
+}
+
+impl Encoder {
+  fn put_u8(&mut self, value: u8) {
+    self.buf.push(value);
+  }
+
+  fn put_u64(&mut self, value: u64) {
+    self.buf.extend(value.to_le_bytes());
+  }
+
+  fn put_bytes(&mut self, value: &[u8]) {
+    self.put_u64(value.len() as u64);
+    self.buf.extend(value);
+  }
+
+  fn put_str(&mut self, value: &str) {
+    self.put_bytes(value.as_bytes());
+  }
+
+  fn put_metadata(&mut self, value: &FileMetadata) {
+    self.put_u8(
+      u8::from(value.is_file)
+        | (u8::from(value.is_directory) << 1)
+        | (u8::from(value.is_symlink) << 2),
+    );
+    self.put_u64(value.atime_ms);
+    self.put_u64(value.mtime_ms);
+    self.put_u64(value.ctime_ms);
+    self.put_u64(value.size);
+  }
+
+  fn put_recorded<T>(&mut self, op: u8, value: &Recorded<T>, put: impl FnOnce(&mut Self, &T)) {
+    self.put_u8(op);
+    match value {
+      Recorded::Ok(value) => {
+        self.put_u8(0);
+        put(self, value);
+      }
+      Recorded::NotFound => self.put_u8(1),
+      Recorded::Failed(msg) => {
+        self.put_u8(2);
+        self.put_str(msg);
+      }
+    }
+  }
+}
+
+struct Decoder<'a> {
+  buf: &'a [u8],
+  pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+  fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+    let end = self
+      .pos
+      .checked_add(len)
+      .filter(|end| *end <= self.buf.len())
+      .ok_or_else(|| invalid_data("unexpected end of file system archive"))?;
+    let res = &self.buf[self.pos..end];
+    self.pos = end;
+    Ok(res)
+  }
+
+  fn get_u8(&mut self) -> Result<u8> {
+    Ok(self.take(1)?[0])
+  }
+
+  fn get_u64(&mut self) -> Result<u64> {
+    let bytes = self.take(8)?.try_into().expect("should be 8 bytes");
+    Ok(u64::from_le_bytes(bytes))
+  }
+
+  fn get_bytes(&mut self) -> Result<&'a [u8]> {
+    let len = usize::try_from(self.get_u64()?).map_err(|_| invalid_data("invalid length"))?;
+    self.take(len)
+  }
+
+  fn get_str(&mut self) -> Result<String> {
+    String::from_utf8(self.get_bytes()?.to_vec()).map_err(|_| invalid_data("invalid utf8 string"))
+  }
+
+  fn get_metadata(&mut self) -> Result<FileMetadata> {
+    let flags = self.get_u8()?;
+    Ok(FileMetadata {
+      is_file: flags & 1 != 0,
+      is_directory: flags & (1 << 1) != 0,
+      is_symlink: flags & (1 << 2) != 0,
+      atime_ms: self.get_u64()?,
+      mtime_ms: self.get_u64()?,
+      ctime_ms: self.get_u64()?,
+      size: self.get_u64()?,
+    })
+  }
+
+  fn get_recorded<T>(&mut self, get: impl FnOnce(&mut Self) -> Result<T>) -> Result<Recorded<T>> {
+    match self.get_u8()? {
+      0 => Ok(Recorded::Ok(get(self)?)),
+      1 => Ok(Recorded::NotFound),
+      2 => Ok(Recorded::Failed(self.get_str()?)),
+      _ => Err(invalid_data("invalid recorded status")),
+    }
+  }
+}
+
+/// A file system wrapper which records every access to the inner file system
+///
+/// Use it as the input file system of compiler, the resolver shares the same file system,
+/// so all of the reads, stats, read_dirs and canonicalizes of a build are captured.
+#[derive(Debug, Clone)]
+pub struct RecordingFileSystem {
+  inner: Arc<dyn ReadableFileSystem>,
+  archive: Arc<Mutex<FsArchive>>,
+}
+
+impl RecordingFileSystem {
+  pub fn new(inner: Arc<dyn ReadableFileSystem>) -> Self {
+    Self {
+      inner,
+      archive: Default::default(),
+    }
+  }
+
+  /// A snapshot of the accesses recorded so far
+  pub fn archive(&self) -> FsArchive {
+    self.archive.lock().expect("should get lock").clone()
+  }
+
+  fn record(&self, path: &Utf8Path, f: impl FnOnce(&mut ArchiveEntry)) {
+    let mut archive = self.archive.lock().expect("should get lock");
+    f(archive.entry(path));
+  }
+}
+
+#[async_trait::async_trait]
+impl ReadableFileSystem for RecordingFileSystem {
+  async fn read(&self, path: &Utf8Path) -> Result<Vec<u8>> {
+    let res = self.inner.read(path).await;
+    self.record(path, |e| e.read = Some(Recorded::from_result(&res)));
+    res
+  }
+
+  fn read_sync(&self, path: &Utf8Path) -> Result<Vec<u8>> {
+    let res = self.inner.read_sync(path);
+    self.record(path, |e| e.read = Some(Recorded::from_result(&res)));
+    res
+  }
+
+  async fn metadata(&self, path: &Utf8Path) -> Result<FileMetadata> {
+    let res = self.inner.metadata(path).await;
+    self.record(path, |e| e.metadata = Some(Recorded::from_result(&res)));
+    res
+  }
+
+  fn metadata_sync(&self, path: &Utf8Path) -> Result<FileMetadata> {
+    let res = self.inner.metadata_sync(path);
+    self.record(path, |e| e.metadata = Some(Recorded::from_result(&res)));
+    res
+  }
+
+  async fn symlink_metadata(&self, path: &Utf8Path) -> Result<FileMetadata> {
+    let res = self.inner.symlink_metadata(path).await;
+    self.record(path, |e| {
+      e.symlink_metadata = Some(Recorded::from_result(&res));
+    });
+    res
+  }
+
+  async fn canonicalize(&self, path: &Utf8Path) -> Result<Utf8PathBuf> {
+    let res = self.inner.canonicalize(path).await;
+    self.record(path, |e| e.canonicalize = Some(Recorded::from_result(&res)));
+    res
+  }
+
+  async fn read_dir(&self, dir: &Utf8Path) -> Result<Vec<String>> {
+    let res = self.inner.read_dir(dir).await;
+    self.record(dir, |e| e.read_dir = Some(Recorded::from_result(&res)));
+    res
+  }
+
+  fn read_dir_sync(&self, dir: &Utf8Path) -> Result<Vec<String>> {
+    let res = self.inner.read_dir_sync(dir);
+    self.record(dir, |e| e.read_dir = Some(Recorded::from_result(&res)));
+    res
+  }
+
+  async fn permissions(&self, path: &Utf8Path) -> Result<Option<FilePermissions>> {
+    let res = self
+      .inner
+      .permissions(path)
+      .await
+      .map(|perm| perm.and_then(FilePermissions::into_mode));
+    self.record(path, |e| e.permissions = Some(Recorded::from_result(&res)));
+    res.map(|mode| mode.map(FilePermissions::from_mode))
+  }
+}
+
+/// A read-only file system which serves a recorded [`FsArchive`]
+///
+/// The operations which are not recorded are treated as not found,
+/// so the replayed build never touches the disk.
+#[derive(Debug, Clone)]
+pub struct ReplayFileSystem {
+  archive: Arc<FsArchive>,
+}
+
+impl ReplayFileSystem {
+  pub fn new(archive: FsArchive) -> Self {
+    Self {
+      archive: Arc::new(archive),
+    }
+  }
+
+  fn replay<T: Clone>(
+    &self,
+    path: &Utf8Path,
+    f: impl FnOnce(&ArchiveEntry) -> &Option<Recorded<T>>,
+  ) -> Result<T> {
+    self
+      .archive
+      .entries
+      .get(path)
+      .and_then(|entry| f(entry).as_ref())
+      .unwrap_or(&Recorded::NotFound)
+      .to_result()
+  }
+}
+
+#[async_trait::async_trait]
+impl ReadableFileSystem for ReplayFileSystem {
+  async fn read(&self, path: &Utf8Path) -> Result<Vec<u8>> {
+    self.read_sync(path)
+  }
+
+  fn read_sync(&self, path: &Utf8Path) -> Result<Vec<u8>> {
+    self.replay(path, |e| &e.read)
+  }
+
+  async fn metadata(&self, path: &Utf8Path) -> Result<FileMetadata> {
+    self.metadata_sync(path)
+  }
+
+  fn metadata_sync(&self, path: &Utf8Path) -> Result<FileMetadata> {
+    self.replay(path, |e| &e.metadata)
+  }
+
+  async fn symlink_metadata(&self, path: &Utf8Path) -> Result<FileMetadata> {
+    self.replay(path, |e| &e.symlink_metadata)
+  }
+
+  async fn canonicalize(&self, path: &Utf8Path) -> Result<Utf8PathBuf> {
+    self.replay(path, |e| &e.canonicalize)
+  }
+
+  async fn read_dir(&self, dir: &Utf8Path) -> Result<Vec<String>> {
+    self.read_dir_sync(dir)
+  }
+
+  fn read_dir_sync(&self, dir: &Utf8Path) -> Result<Vec<String>> {
+    self.replay(dir, |e| &e.read_dir)
+  }
+
+  async fn permissions(&self, path: &Utf8Path) -> Result<Option<FilePermissions>> {
+    Ok(
+      self
+        .replay(path, |e| &e.permissions)?
+        .map(FilePermissions::from_mode),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use rspack_paths::Utf8Path;
+
+  use super::{FsArchive, RecordingFileSystem, ReplayFileSystem};
+  use crate::{MemoryFileSystem, ReadableFileSystem, Result, WritableFileSystem};
+
+  #[tokio::test]
+  async fn should_replay_recorded_accesses() -> Result<()> {
+    let disk = MemoryFileSystem::default();
+    WritableFileSystem::create_dir_all(&disk, Utf8Path::new("/project/src")).await?;
+    WritableFileSystem::write(&disk, Utf8Path::new("/project/src/index.js"), b"index").await?;
+    WritableFileSystem::write(&disk, Utf8Path::new("/project/src/a.js"), b"a").await?;
+
+    let fs = RecordingFileSystem::new(Arc::new(disk.clone()));
+    let mut children = ReadableFileSystem::read_dir(&fs, Utf8Path::new("/project/src")).await?;
+    children.sort();
+    let index = ReadableFileSystem::read(&fs, Utf8Path::new("/project/src/index.js")).await?;
+    let meta = ReadableFileSystem::metadata_sync(&fs, Utf8Path::new("/project/src/index.js"))?;
+    assert!(
+      ReadableFileSystem::metadata(&fs, Utf8Path::new("/project/src/b.js"))
+        .await
+        .is_err()
+    );
+
+    let archive = FsArchive::from_bytes(&fs.archive().to_bytes())?;
+    assert_eq!(
+      archive.paths().collect::<Vec<_>>(),
+      vec![
+        Utf8Path::new("/project/src"),
+        Utf8Path::new("/project/src/b.js"),
+        Utf8Path::new("/project/src/index.js"),
+      ]
+    );
+
+    // the disk state is changed after recording
+    disk.clear();
+    let replay = ReplayFileSystem::new(archive);
+    let mut replayed_children =
+      ReadableFileSystem::read_dir(&replay, Utf8Path::new("/project/src")).await?;
+    replayed_children.sort();
+    assert_eq!(replayed_children, children);
+    assert_eq!(
+      ReadableFileSystem::read(&replay, Utf8Path::new("/project/src/index.js")).await?,
+      index
+    );
+    let replayed_meta =
+      ReadableFileSystem::metadata(&replay, Utf8Path::new("/project/src/index.js")).await?;
+    assert!(replayed_meta.is_file);
+    assert_eq!(replayed_meta.mtime_ms, meta.mtime_ms);
+    assert_eq!(replayed_meta.size, meta.size);
+
+    // recorded failures and unrecorded accesses are not found
+    let err = ReadableFileSystem::metadata(&replay, Utf8Path::new("/project/src/b.js"))
+      .await
+      .expect_err("should not found");
+    assert!(err.to_string().contains("file not exist"));
+    assert!(ReadableFileSystem::read_sync(&replay, Utf8Path::new("/project/src/a.js")).is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn should_reject_invalid_archive() {
+    assert!(FsArchive::from_bytes(b"invalid").is_err());
+    let mut bytes = FsArchive::default().to_bytes();
+    bytes.push(1);
+    assert!(FsArchive::from_bytes(&bytes[..bytes.len() - 2]).is_err());
+  }
+}
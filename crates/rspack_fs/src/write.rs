@@ -2,9 +2,16 @@ use std::fmt::Debug;
 
 use rspack_paths::Utf8Path;
 
-use super::{FileMetadata, Result};
+use super::{Error, FileMetadata, Result};
 use crate::file_metadata::FilePermissions;
 
+fn unsupported(operation: &str, path: &Utf8Path) -> Error {
+  Error::new(
+    std::io::ErrorKind::Unsupported,
+    &format!("the file system does not support {operation} of {path}"),
+  )
+}
+
 #[async_trait::async_trait]
 pub trait WritableFileSystem: Debug + Send + Sync {
   /// Creates a new, empty directory at the provided path.
@@ -26,6 +33,43 @@ pub trait WritableFileSystem: Debug + Send + Sync {
   /// This function will create a file if it does not exist, and will entirely replace its contents if it does.
   async fn write(&self, file: &Utf8Path, data: &[u8]) -> Result<()>;
 
+  /// Create a file with the contents, fails with [`std::io::ErrorKind::AlreadyExists`] if the file exists.
+  ///
+  /// The check and the creation are supposed to be atomic, so it can be used to take locks between processes.
+  /// File systems which can not guarantee it keep the default implementation, which returns an unsupported error.
+  async fn create_new(&self, file: &Utf8Path, _data: &[u8]) -> Result<()> {
+    Err(unsupported("create_new", file))
+  }
+
+  /// Rename a file or directory to a new name, replacing the original file if `to` already exists.
+  /// The default implementation returns an unsupported error.
+  async fn rename(&self, from: &Utf8Path, _to: &Utf8Path) -> Result<()> {
+    Err(unsupported("rename", from))
+  }
+
+  /// Swap two paths atomically, both of them are supposed to exist.
+  ///
+  /// File systems which can not swap atomically keep the default implementation, which returns an unsupported error.
+  async fn exchange(&self, a: &Utf8Path, _b: &Utf8Path) -> Result<()> {
+    Err(unsupported("exchange", a))
+  }
+
+  /// Create a hard link of the file, which shares the contents and the metadata with the file.
+  ///
+  /// The default implementation copies the contents, so the new file gets its own metadata.
+  async fn hard_link(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
+    let content = self.read_file(from).await?;
+    self.write(to, &content).await
+  }
+
+  /// Flush the contents of a file or directory to the underlying storage.
+  ///
+  /// The default implementation returns an unsupported error, file systems without a durable storage
+  /// are supposed to implement it as a no-op explicitly.
+  async fn sync(&self, path: &Utf8Path) -> Result<()> {
+    Err(unsupported("sync", path))
+  }
+
   /// Removes a file from the filesystem.
   async fn remove_file(&self, file: &Utf8Path) -> Result<()>;
 
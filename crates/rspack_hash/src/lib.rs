@@ -6,22 +6,30 @@ use std::{
 use md4::Digest;
 use rspack_cacheable::{cacheable, with::AsPreset};
 use smol_str::SmolStr;
-use xxhash_rust::xxh64::Xxh64;
+use xxhash_rust::{xxh3::Xxh3, xxh64::Xxh64};
 
 #[derive(Debug, Clone, Copy)]
 pub enum HashFunction {
   Xxhash64,
+  Xxhash3_128,
   MD4,
   SHA256,
+  Blake3,
 }
 
-impl From<&str> for HashFunction {
-  fn from(value: &str) -> Self {
+impl TryFrom<&str> for HashFunction {
+  type Error = rspack_error::Error;
+
+  fn try_from(value: &str) -> rspack_error::Result<Self> {
     match value {
-      "xxhash64" => HashFunction::Xxhash64,
-      "md4" => HashFunction::MD4,
-      "sha256" => HashFunction::SHA256,
-      _ => panic!("Unsupported hash function: '{value}'. Expected one of: xxhash64, md4, sha256"),
+      "xxhash64" => Ok(HashFunction::Xxhash64),
+      "xxhash3-128" => Ok(HashFunction::Xxhash3_128),
+      "md4" => Ok(HashFunction::MD4),
+      "sha256" => Ok(HashFunction::SHA256),
+      "blake3" => Ok(HashFunction::Blake3),
+      _ => Err(rspack_error::error!(
+        "Unsupported hash function: '{value}'. Expected one of: xxhash64, xxhash3-128, md4, sha256, blake3"
+      )),
     }
   }
 }
@@ -29,13 +37,23 @@ impl From<&str> for HashFunction {
 #[derive(Debug, Clone, Copy)]
 pub enum HashDigest {
   Hex,
+  Base64,
+  Base64Url,
+  Base62,
 }
 
-impl From<&str> for HashDigest {
-  fn from(value: &str) -> Self {
+impl TryFrom<&str> for HashDigest {
+  type Error = rspack_error::Error;
+
+  fn try_from(value: &str) -> rspack_error::Result<Self> {
     match value {
-      "hex" => HashDigest::Hex,
-      _ => panic!("Unsupported hash digest: '{value}'. Expected: hex"),
+      "hex" => Ok(HashDigest::Hex),
+      "base64" => Ok(HashDigest::Base64),
+      "base64url" => Ok(HashDigest::Base64Url),
+      "base62" => Ok(HashDigest::Base62),
+      _ => Err(rspack_error::error!(
+        "Unsupported hash digest: '{value}'. Expected one of: hex, base64, base64url, base62"
+      )),
     }
   }
 }
@@ -58,16 +76,20 @@ impl From<Option<String>> for HashSalt {
 #[derive(Clone)]
 pub enum RspackHash {
   Xxhash64(Box<Xxh64>),
+  Xxhash3_128(Box<Xxh3>),
   MD4(Box<md4::Md4>),
   SHA256(Box<sha2::Sha256>),
+  Blake3(Box<blake3::Hasher>),
 }
 
 impl fmt::Debug for RspackHash {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     match self {
       Self::Xxhash64(_) => write!(f, "RspackHash(Xxhash64)"),
+      Self::Xxhash3_128(_) => write!(f, "RspackHash(Xxhash3_128)"),
       Self::MD4(_) => write!(f, "RspackHash(MD4)"),
-      Self::SHA256(_) => write!(f, "RspackHash(SHA256"),
+      Self::SHA256(_) => write!(f, "RspackHash(SHA256)"),
+      Self::Blake3(_) => write!(f, "RspackHash(Blake3)"),
     }
   }
 }
@@ -76,8 +98,10 @@ impl RspackHash {
   pub fn new(function: &HashFunction) -> Self {
     match function {
       HashFunction::Xxhash64 => Self::Xxhash64(Box::new(Xxh64::new(0))),
+      HashFunction::Xxhash3_128 => Self::Xxhash3_128(Box::new(Xxh3::new())),
       HashFunction::MD4 => Self::MD4(Box::new(md4::Md4::new())),
       HashFunction::SHA256 => Self::SHA256(Box::new(sha2::Sha256::new())),
+      HashFunction::Blake3 => Self::Blake3(Box::new(blake3::Hasher::new())),
     }
   }
 
@@ -90,7 +114,7 @@ impl RspackHash {
   }
 
   pub fn digest(self, digest: &HashDigest) -> RspackHashDigest {
-    // The maximum value of sha256, the largest possible hash
+    // The maximum value of sha256 and blake3, the largest possible hash
     let mut result = [0; 32];
     let len;
 
@@ -100,6 +124,11 @@ impl RspackHash {
         len = buf.len();
         result[..len].copy_from_slice(&buf);
       }
+      RspackHash::Xxhash3_128(hasher) => {
+        let buf = hasher.digest128().to_be_bytes();
+        len = buf.len();
+        result[..len].copy_from_slice(&buf);
+      }
       RspackHash::MD4(hash) => {
         let buf = hash.finalize();
         len = buf.len();
@@ -110,6 +139,11 @@ impl RspackHash {
         len = buf.len();
         result[..len].copy_from_slice(&buf);
       }
+      RspackHash::Blake3(hash) => {
+        let buf = hash.finalize();
+        len = buf.as_bytes().len();
+        result[..len].copy_from_slice(buf.as_bytes());
+      }
     }
 
     RspackHashDigest::new(&result[..len], digest)
@@ -120,6 +154,7 @@ impl Hasher for RspackHash {
   fn finish(&self) -> u64 {
     match self {
       RspackHash::Xxhash64(hasher) => hasher.finish(),
+      RspackHash::Xxhash3_128(hasher) => (hasher.digest128() >> 64) as u64,
       RspackHash::MD4(hasher) => {
         // finalize take ownership, so we need to clone it
         let hash = (**hasher).clone().finalize();
@@ -145,14 +180,24 @@ impl Hasher for RspackHash {
           | (hash[7] as u64);
         msb_u64
       }
+      RspackHash::Blake3(hasher) => {
+        let hash = hasher.finalize();
+        let mut msb = [0; 8];
+        msb.copy_from_slice(&hash.as_bytes()[..8]);
+        u64::from_be_bytes(msb)
+      }
     }
   }
 
   fn write(&mut self, bytes: &[u8]) {
     match self {
       RspackHash::Xxhash64(hasher) => hasher.write(bytes),
+      RspackHash::Xxhash3_128(hasher) => hasher.update(bytes),
       RspackHash::MD4(hasher) => hasher.update(bytes),
       RspackHash::SHA256(hasher) => hasher.update(bytes),
+      RspackHash::Blake3(hasher) => {
+        hasher.update(bytes);
+      }
     }
   }
 }
@@ -181,6 +226,21 @@ impl RspackHashDigest {
         let s = hex(inner, &mut buf);
         s.into()
       }
+      HashDigest::Base64 => {
+        let mut buf = [0; 44];
+        let s = base64(inner, BASE64_TABLE, true, &mut buf);
+        s.into()
+      }
+      HashDigest::Base64Url => {
+        let mut buf = [0; 44];
+        let s = base64(inner, BASE64_URL_TABLE, false, &mut buf);
+        s.into()
+      }
+      HashDigest::Base62 => {
+        let mut buf = [0; 43];
+        let s = base62(inner, &mut buf);
+        s.into()
+      }
     };
     Self { encoded }
   }
@@ -189,6 +249,9 @@ impl RspackHashDigest {
     &self.encoded
   }
 
+  /// Returns the first `length` chars of the encoded digest.
+  ///
+  /// All supported digests are ascii, so truncation never splits a char.
   pub fn rendered(&self, length: usize) -> &str {
     let len = self.encoded.len().min(length);
     &self.encoded[..len]
@@ -228,3 +291,120 @@ fn hex<'a>(data: &[u8], output: &'a mut [u8]) -> &'a str {
   // hex is always ascii
   unsafe { std::str::from_utf8_unchecked(&output[..i]) }
 }
+
+const BASE64_TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_URL_TABLE: &[u8; 64] =
+  b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Same as node's `digest("base64")` / `digest("base64url")`, padding is only
+/// emitted for the standard alphabet.
+#[inline]
+fn base64<'a>(data: &[u8], table: &[u8; 64], padding: bool, output: &'a mut [u8]) -> &'a str {
+  assert!(data.len().div_ceil(3) * 4 <= output.len());
+
+  let mut i = 0;
+  for chunk in data.chunks(3) {
+    let b0 = chunk[0] as u32;
+    let b1 = chunk.get(1).copied().unwrap_or_default() as u32;
+    let b2 = chunk.get(2).copied().unwrap_or_default() as u32;
+    let n = (b0 << 16) | (b1 << 8) | b2;
+
+    // a chunk of n bytes is encoded into n + 1 chars
+    for j in 0..=chunk.len() {
+      output[i] = table[((n >> (18 - j * 6)) & 0x3f) as usize];
+      i += 1;
+    }
+    if padding {
+      for _ in chunk.len()..3 {
+        output[i] = b'=';
+        i += 1;
+      }
+    }
+  }
+
+  // # Safety
+  //
+  // base64 is always ascii
+  unsafe { std::str::from_utf8_unchecked(&output[..i]) }
+}
+
+/// Same alphabet as the `base62` digest type of webpack's `loader-utils`.
+const BASE62_TABLE: &[u8; 62] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Encode `data` as a big-endian unsigned integer in base 62.
+///
+/// The length of the output only depends on the length of `data`, so that
+/// digests of the same hash function always have the same length.
+#[inline]
+fn base62<'a>(data: &[u8], output: &'a mut [u8]) -> &'a str {
+  // ceil(bits / log2(62))
+  let len = (data.len() * 8 * 1000).div_ceil(5954);
+  assert!(data.len() <= 32 && len <= output.len());
+
+  let mut num = [0u8; 32];
+  let num = &mut num[..data.len()];
+  num.copy_from_slice(data);
+
+  for i in (0..len).rev() {
+    // long division of `num` by 62, leaving the quotient in `num`
+    let mut rem = 0u32;
+    for byte in num.iter_mut() {
+      let acc = (rem << 8) | *byte as u32;
+      *byte = (acc / 62) as u8;
+      rem = acc % 62;
+    }
+    output[i] = BASE62_TABLE[rem as usize];
+  }
+
+  // # Safety
+  //
+  // base62 is always ascii
+  unsafe { std::str::from_utf8_unchecked(&output[..len]) }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn encode(data: &[u8], digest: &str) -> String {
+    let digest = HashDigest::try_from(digest).expect("should be a supported digest");
+    RspackHashDigest::new(data, &digest).encoded().to_string()
+  }
+
+  #[test]
+  fn should_encode_digests() {
+    assert_eq!(encode(b"\xfb\xff\xbf", "hex"), "fbffbf");
+    assert_eq!(encode(b"\xfb\xff\xbf", "base64"), "+/+/");
+    assert_eq!(encode(b"\xfb\xff\xbf", "base64url"), "-_-_");
+    assert_eq!(encode(b"hello", "base64"), "aGVsbG8=");
+    assert_eq!(encode(b"hello", "base64url"), "aGVsbG8");
+    assert_eq!(encode(&[0, 61], "base62"), "00Z");
+    assert_eq!(encode(&[0, 62], "base62"), "010");
+    assert_eq!(encode(&[0xff; 32], "base62").len(), 43);
+  }
+
+  #[test]
+  fn should_keep_digest_length_stable() {
+    for function in ["xxhash64", "xxhash3-128", "md4", "sha256", "blake3"] {
+      let function = HashFunction::try_from(function).expect("should be a supported function");
+      for digest in ["hex", "base64", "base64url", "base62"] {
+        let digest = HashDigest::try_from(digest).expect("should be a supported digest");
+        let mut a = RspackHash::new(&function);
+        a.write(b"a");
+        let mut b = RspackHash::new(&function);
+        b.write(b"b");
+        let a = a.digest(&digest);
+        let b = b.digest(&digest);
+        assert_ne!(a, b);
+        assert_eq!(a.encoded().len(), b.encoded().len());
+        assert_eq!(a.rendered(8).len(), 8);
+      }
+    }
+  }
+
+  #[test]
+  fn should_reject_unknown_values() {
+    assert!(HashFunction::try_from("md5").is_err());
+    assert!(HashDigest::try_from("latin1").is_err());
+  }
+}
@@ -1,6 +1,9 @@
+mod timing;
+
 use async_trait::async_trait;
 use rspack_error::Result;
 use rustc_hash::FxHashSet;
+pub use timing::{HookTimingReport, HookTimingSession, TapTiming, is_hook_timing_enabled};
 
 #[async_trait]
 pub trait Interceptor<H: Hook> {
@@ -33,6 +36,8 @@ pub mod __macro_helper {
   pub use rspack_error::Result;
   pub use rustc_hash::FxHashSet;
   pub use tracing;
+
+  pub use crate::timing::TapTimer;
 }
 
 pub use rspack_macros::{define_hook, plugin, plugin_hook};
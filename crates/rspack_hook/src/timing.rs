@@ -0,0 +1,200 @@
+use std::{
+  sync::{
+    Arc, LazyLock, Mutex,
+    atomic::{AtomicU64, AtomicUsize, Ordering},
+  },
+  time::{Duration, Instant},
+};
+
+use rustc_hash::FxHashMap;
+
+type TimingKey = (&'static str, &'static str, i32);
+
+type Timings = FxHashMap<TimingKey, TapTiming>;
+
+/// The number of the active sessions, the taps are only timed if there is any.
+static ACTIVE_SESSIONS: AtomicUsize = AtomicUsize::new(0);
+
+static NEXT_SESSION_ID: AtomicU64 = AtomicU64::new(0);
+
+/// The timings recorded by each thread.
+///
+/// A tap call only locks the shard of its own thread, the shards are drained when a session takes the timings.
+static SHARDS: LazyLock<Mutex<Vec<Arc<Mutex<Timings>>>>> = LazyLock::new(Default::default);
+
+/// The timings of the active sessions, which are moved from the shards.
+static SESSIONS: LazyLock<Mutex<FxHashMap<u64, Timings>>> = LazyLock::new(Default::default);
+
+thread_local! {
+  static SHARD: Arc<Mutex<Timings>> = {
+    let shard = Arc::<Mutex<Timings>>::default();
+    SHARDS
+      .lock()
+      .expect("should lock hook timing shards")
+      .push(Arc::clone(&shard));
+    shard
+  };
+}
+
+pub fn is_hook_timing_enabled() -> bool {
+  ACTIVE_SESSIONS.load(Ordering::Relaxed) > 0
+}
+
+/// Move the timings recorded by the threads to all the active sessions.
+fn drain_shards(sessions: &mut FxHashMap<u64, Timings>) {
+  let mut shards = SHARDS.lock().expect("should lock hook timing shards");
+  for shard in shards.iter() {
+    let timings = std::mem::take(&mut *shard.lock().expect("should lock hook timings"));
+    for timing in timings.into_values() {
+      for session in sessions.values_mut() {
+        merge_timing(session, timing.clone());
+      }
+    }
+  }
+  // the shards of the exited threads are no longer written
+  shards.retain(|shard| Arc::strong_count(shard) > 1);
+}
+
+fn merge_timing(timings: &mut Timings, timing: TapTiming) {
+  match timings.get_mut(&(timing.hook, timing.tap, timing.stage)) {
+    Some(existing) => {
+      existing.calls += timing.calls;
+      existing.total += timing.total;
+      existing.max = existing.max.max(timing.max);
+    }
+    None => {
+      timings.insert((timing.hook, timing.tap, timing.stage), timing);
+    }
+  }
+}
+
+/// A session recording the wall time of each tap.
+///
+/// The taps are timed while any session is active, each session receives the timings recorded
+/// during its lifetime, so taking the timings of a session does not drain the other sessions,
+/// e.g. the other compilers of a multi compiler.
+#[derive(Debug)]
+pub struct HookTimingSession {
+  id: u64,
+}
+
+impl HookTimingSession {
+  pub fn start() -> Self {
+    let mut sessions = SESSIONS.lock().expect("should lock hook timing sessions");
+    // the timings recorded before belong to the existing sessions only
+    drain_shards(&mut sessions);
+    let id = NEXT_SESSION_ID.fetch_add(1, Ordering::Relaxed);
+    sessions.insert(id, Timings::default());
+    ACTIVE_SESSIONS.fetch_add(1, Ordering::Relaxed);
+    Self { id }
+  }
+
+  /// Take the timings recorded since the session started or the last take.
+  pub fn take(&self) -> HookTimingReport {
+    let mut sessions = SESSIONS.lock().expect("should lock hook timing sessions");
+    drain_shards(&mut sessions);
+    let timings = sessions
+      .get_mut(&self.id)
+      .map(std::mem::take)
+      .unwrap_or_default();
+    let mut taps = timings.into_values().collect::<Vec<_>>();
+    taps.sort_by(|a, b| {
+      b.total
+        .cmp(&a.total)
+        .then_with(|| a.hook.cmp(b.hook))
+        .then_with(|| a.tap.cmp(b.tap))
+        .then_with(|| a.stage.cmp(&b.stage))
+    });
+    HookTimingReport { taps }
+  }
+}
+
+impl Drop for HookTimingSession {
+  fn drop(&mut self) {
+    let mut sessions = SESSIONS.lock().expect("should lock hook timing sessions");
+    drain_shards(&mut sessions);
+    sessions.remove(&self.id);
+    ACTIVE_SESSIONS.fetch_sub(1, Ordering::Relaxed);
+  }
+}
+
+/// The aggregated wall time of a tap of a hook
+#[derive(Debug, Clone)]
+pub struct TapTiming {
+  pub hook: &'static str,
+  pub tap: &'static str,
+  pub stage: i32,
+  pub calls: u64,
+  pub total: Duration,
+  pub max: Duration,
+}
+
+/// The timings of all taps, sorted by the total wall time in descending order
+#[derive(Debug, Default, Clone)]
+pub struct HookTimingReport {
+  pub taps: Vec<TapTiming>,
+}
+
+impl HookTimingReport {
+  pub fn is_empty(&self) -> bool {
+    self.taps.is_empty()
+  }
+
+  /// The total wall time of all taps
+  pub fn total(&self) -> Duration {
+    self.taps.iter().map(|tap| tap.total).sum()
+  }
+
+  pub fn to_json(&self) -> serde_json::Value {
+    serde_json::json!({
+      "total": self.total().as_secs_f64() * 1000.0,
+      "taps": self
+        .taps
+        .iter()
+        .map(|tap| serde_json::json!({
+          "hook": tap.hook,
+          "tap": tap.tap,
+          "stage": tap.stage,
+          "calls": tap.calls,
+          "total": tap.total.as_secs_f64() * 1000.0,
+          "max": tap.max.as_secs_f64() * 1000.0,
+        }))
+        .collect::<Vec<_>>(),
+    })
+  }
+}
+
+/// Records the wall time of a tap call when it is dropped, so the calls returning errors are also recorded.
+#[doc(hidden)]
+pub struct TapTimer {
+  key: TimingKey,
+  start: Instant,
+}
+
+impl TapTimer {
+  pub fn start(hook: &'static str, tap: &'static str, stage: i32) -> Option<Self> {
+    is_hook_timing_enabled().then(|| Self {
+      key: (hook, tap, stage),
+      start: Instant::now(),
+    })
+  }
+}
+
+impl Drop for TapTimer {
+  fn drop(&mut self) {
+    let elapsed = self.start.elapsed();
+    let (hook, tap, stage) = self.key;
+    let timing = TapTiming {
+      hook,
+      tap,
+      stage,
+      calls: 1,
+      total: elapsed,
+      max: elapsed,
+    };
+    // the thread local is unavailable while the thread is exiting, the timing is dropped then
+    let _ = SHARD.try_with(|shard| {
+      merge_timing(&mut shard.lock().expect("should lock hook timings"), timing);
+    });
+  }
+}
@@ -0,0 +1,354 @@
+//! A blocking HTTP/1.1 client for plain `http://` endpoints.
+//!
+//! It is shared by the remote cache storage and the OTLP trace exporter, which only
+//! send small requests to a local or intranet server and don't need TLS.
+
+use std::{
+  io::{BufRead, BufReader, Error, ErrorKind, Read, Result, Write},
+  net::{TcpStream, ToSocketAddrs},
+  time::Duration,
+};
+
+/// An `http://` endpoint, e.g. `http://localhost:4318/v1/traces`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpEndpoint {
+  pub host: String,
+  pub port: u16,
+  /// The path of the endpoint without the trailing slash, empty for the root
+  pub path: String,
+}
+
+impl HttpEndpoint {
+  pub fn parse(url: &str) -> std::result::Result<Self, String> {
+    let rest = url
+      .strip_prefix("http://")
+      .ok_or_else(|| "only http:// endpoints are supported".to_string())?;
+    let (authority, path) = match rest.split_once('/') {
+      Some((authority, path)) => (authority, path.trim_end_matches('/')),
+      None => (rest, ""),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+      Some((host, port)) => (
+        host,
+        port
+          .parse::<u16>()
+          .map_err(|_| "illegal port".to_string())?,
+      ),
+      None => (authority, 80),
+    };
+    if host.is_empty() {
+      return Err("missing host".to_string());
+    }
+
+    Ok(Self {
+      host: host.to_string(),
+      port,
+      path: if path.is_empty() {
+        String::new()
+      } else {
+        format!("/{path}")
+      },
+    })
+  }
+}
+
+#[derive(Debug)]
+pub struct HttpResponse {
+  pub status: u16,
+  pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+  pub fn is_success(&self) -> bool {
+    (200..300).contains(&self.status)
+  }
+}
+
+/// Sends one request per connection to the endpoint.
+///
+/// The response body must be framed by `Content-Length` or chunked encoding,
+/// so a connection closed in the middle of the body is reported as an error
+/// instead of returning a truncated body.
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+  endpoint: HttpEndpoint,
+  /// The connect, read and write timeout
+  timeout: Duration,
+}
+
+impl HttpClient {
+  pub fn new(endpoint: HttpEndpoint, timeout: Duration) -> Self {
+    Self { endpoint, timeout }
+  }
+
+  pub fn endpoint(&self) -> &HttpEndpoint {
+    &self.endpoint
+  }
+
+  /// The url of the path relative to the endpoint, e.g. `/packs/<hash>`
+  pub fn url(&self, path: &str) -> String {
+    format!(
+      "http://{}:{}{}{path}",
+      self.endpoint.host, self.endpoint.port, self.endpoint.path
+    )
+  }
+
+  fn connect(&self) -> Result<TcpStream> {
+    let mut last_error = None;
+    for addr in (self.endpoint.host.as_str(), self.endpoint.port).to_socket_addrs()? {
+      match TcpStream::connect_timeout(&addr, self.timeout) {
+        Ok(stream) => {
+          stream.set_read_timeout(Some(self.timeout))?;
+          stream.set_write_timeout(Some(self.timeout))?;
+          return Ok(stream);
+        }
+        Err(e) => last_error = Some(e),
+      }
+    }
+    Err(last_error.unwrap_or_else(|| {
+      Error::new(
+        ErrorKind::NotFound,
+        format!("failed to resolve {}", self.endpoint.host),
+      )
+    }))
+  }
+
+  /// Send a request to the path relative to the endpoint.
+  pub fn request(
+    &self,
+    method: &str,
+    path: &str,
+    content_type: Option<&str>,
+    body: &[u8],
+  ) -> Result<HttpResponse> {
+    let mut stream = self.connect()?;
+    let target = format!("{}{path}", self.endpoint.path);
+    let mut request = format!(
+      "{method} {} HTTP/1.1\r\nHost: {}:{}\r\nContent-Length: {}\r\nConnection: close\r\n",
+      if target.is_empty() { "/" } else { &target },
+      self.endpoint.host,
+      self.endpoint.port,
+      body.len()
+    );
+    if let Some(content_type) = content_type {
+      request.push_str(&format!("Content-Type: {content_type}\r\n"));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes())?;
+    stream.write_all(body)?;
+    stream.flush()?;
+
+    read_response(&mut BufReader::new(stream), method == "HEAD")
+  }
+}
+
+fn invalid_data(reason: impl Into<String>) -> Error {
+  Error::new(ErrorKind::InvalidData, reason.into())
+}
+
+/// Read a line without the line break, a missing line break means the connection is closed.
+fn read_line(reader: &mut impl BufRead) -> Result<String> {
+  let mut line = vec![];
+  reader.read_until(b'\n', &mut line)?;
+  if line.pop() != Some(b'\n') {
+    return Err(Error::new(
+      ErrorKind::UnexpectedEof,
+      "connection closed before the end of the response header",
+    ));
+  }
+  if line.last() == Some(&b'\r') {
+    line.pop();
+  }
+  String::from_utf8(line).map_err(|_| invalid_data("illegal response header"))
+}
+
+fn read_exact(reader: &mut impl Read, len: usize) -> Result<Vec<u8>> {
+  let mut buf = vec![0; len];
+  reader.read_exact(&mut buf).map_err(|e| {
+    if e.kind() == ErrorKind::UnexpectedEof {
+      Error::new(
+        ErrorKind::UnexpectedEof,
+        "connection closed before the end of the response body",
+      )
+    } else {
+      e
+    }
+  })?;
+  Ok(buf)
+}
+
+fn read_chunked_body(reader: &mut impl BufRead) -> Result<Vec<u8>> {
+  let mut body = vec![];
+  loop {
+    let line = read_line(reader)?;
+    let size = line.split(';').next().unwrap_or_default().trim();
+    let size = usize::from_str_radix(size, 16)
+      .map_err(|_| invalid_data(format!("illegal chunk size `{size}`")))?;
+    if size == 0 {
+      // skip the trailers
+      while !read_line(reader)?.is_empty() {}
+      return Ok(body);
+    }
+    body.extend(read_exact(reader, size)?);
+    if !read_line(reader)?.is_empty() {
+      return Err(invalid_data("missing line break after chunk"));
+    }
+  }
+}
+
+fn read_response(reader: &mut impl BufRead, is_head: bool) -> Result<HttpResponse> {
+  loop {
+    let status_line = read_line(reader)?;
+    let status = status_line
+      .split(' ')
+      .nth(1)
+      .and_then(|status| status.parse::<u16>().ok())
+      .ok_or_else(|| invalid_data(format!("illegal status line `{status_line}`")))?;
+
+    let mut content_length = None;
+    let mut chunked = false;
+    loop {
+      let line = read_line(reader)?;
+      if line.is_empty() {
+        break;
+      }
+      let (name, value) = line
+        .split_once(':')
+        .ok_or_else(|| invalid_data(format!("illegal header `{line}`")))?;
+      let value = value.trim();
+      if name.eq_ignore_ascii_case("content-length") {
+        content_length = Some(
+          value
+            .parse::<usize>()
+            .map_err(|_| invalid_data(format!("illegal content length `{value}`")))?,
+        );
+      } else if name.eq_ignore_ascii_case("transfer-encoding") {
+        chunked = value
+          .split(',')
+          .any(|coding| coding.trim().eq_ignore_ascii_case("chunked"));
+      }
+    }
+
+    // the interim responses are followed by the final one
+    if (100..200).contains(&status) {
+      continue;
+    }
+    let body = if is_head || status == 204 || status == 304 {
+      vec![]
+    } else if chunked {
+      read_chunked_body(reader)?
+    } else if let Some(len) = content_length {
+      read_exact(reader, len)?
+    } else {
+      return Err(invalid_data("response without content length"));
+    };
+    return Ok(HttpResponse { status, body });
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    time::Duration,
+  };
+
+  use super::{HttpClient, HttpEndpoint};
+
+  /// Serve one connection with the response and return the client of it.
+  fn serve(response: &'static [u8]) -> HttpClient {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("should bind");
+    let port = listener.local_addr().expect("should have addr").port();
+    std::thread::spawn(move || {
+      let (mut stream, _) = listener.accept().expect("should accept");
+      // read the whole request before responding
+      let mut request = vec![];
+      let mut byte = [0; 1];
+      while !request.ends_with(b"\r\n\r\n") && stream.read(&mut byte).unwrap_or_default() == 1 {
+        request.push(byte[0]);
+      }
+      let content_length = String::from_utf8_lossy(&request)
+        .lines()
+        .find_map(|line| line.strip_prefix("Content-Length: ")?.parse::<usize>().ok())
+        .unwrap_or_default();
+      let mut body = vec![0; content_length];
+      let _ = stream.read_exact(&mut body);
+      let _ = stream.write_all(response);
+    });
+    HttpClient::new(
+      HttpEndpoint::parse(&format!("http://127.0.0.1:{port}/cache")).expect("should parse"),
+      Duration::from_secs(5),
+    )
+  }
+
+  #[test]
+  fn should_parse_endpoint() {
+    assert_eq!(
+      HttpEndpoint::parse("http://localhost:4318/v1/traces/"),
+      Ok(HttpEndpoint {
+        host: "localhost".to_string(),
+        port: 4318,
+        path: "/v1/traces".to_string(),
+      })
+    );
+    assert_eq!(
+      HttpEndpoint::parse("http://cache.local"),
+      Ok(HttpEndpoint {
+        host: "cache.local".to_string(),
+        port: 80,
+        path: String::new(),
+      })
+    );
+    assert!(HttpEndpoint::parse("https://cache.local").is_err());
+    assert!(HttpEndpoint::parse("http://cache.local:port").is_err());
+    assert!(HttpEndpoint::parse("http://:80/cache").is_err());
+  }
+
+  #[test]
+  fn should_read_framed_body() {
+    let client = serve(b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello");
+    let response = client
+      .request("GET", "/key", None, &[])
+      .expect("should request");
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body, b"hello");
+
+    let client = serve(
+      b"HTTP/1.1 100 Continue\r\n\r\nHTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3;ext\r\nhel\r\n2\r\nlo\r\n0\r\nTrailer: 1\r\n\r\n",
+    );
+    let response = client
+      .request("PUT", "/key", Some("text/plain"), b"hello")
+      .expect("should request");
+    assert!(response.is_success());
+    assert_eq!(response.body, b"hello");
+
+    let client = serve(b"HTTP/1.1 404 Not Found\r\nContent-Length: 9\r\n\r\n");
+    let response = client
+      .request("HEAD", "/key", None, &[])
+      .expect("should request");
+    assert_eq!(response.status, 404);
+    assert!(response.body.is_empty());
+  }
+
+  #[test]
+  fn should_reject_truncated_body() {
+    let client = serve(b"HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\nhello");
+    let error = client
+      .request("GET", "/key", None, &[])
+      .expect_err("should fail");
+    assert!(error.to_string().contains("end of the response body"));
+
+    let client = serve(b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhel");
+    assert!(client.request("GET", "/key", None, &[]).is_err());
+
+    let client = serve(b"HTTP/1.1 200 OK\r\nConnection: close\r\n\r\nhello");
+    let error = client
+      .request("GET", "/key", None, &[])
+      .expect_err("should fail");
+    assert!(error.to_string().contains("without content length"));
+
+    let client = serve(b"HTTP/1.1 200 OK\r\nContent-Le");
+    assert!(client.request("GET", "/key", None, &[]).is_err());
+  }
+}
@@ -0,0 +1,174 @@
+use std::hash::Hasher;
+
+use rspack_core::{
+  ChunkGraph, Compilation, CompilationModuleIds, ModuleIdsArtifact, Plugin,
+  incremental::IncrementalPasses,
+};
+use rspack_error::{Diagnostic, Result, error};
+use rspack_hash::{HashDigest, HashFunction, RspackHash};
+use rspack_hook::{plugin, plugin_hook};
+use rustc_hash::FxHashSet;
+
+use crate::id_helpers::{
+  compare_modules_by_pre_order_index_or_identifier, get_full_module_name,
+  get_used_module_ids_and_modules_with_artifact,
+};
+
+#[derive(Debug)]
+pub struct HashedModuleIdsPluginOptions {
+  /// The context the module identifiers are made relative to, defaults to `options.context`
+  pub context: Option<String>,
+  pub hash_function: HashFunction,
+  pub hash_digest: HashDigest,
+  /// The length of the ids, which is increased for a module when its id conflicts
+  pub hash_digest_length: usize,
+}
+
+impl Default for HashedModuleIdsPluginOptions {
+  fn default() -> Self {
+    Self {
+      context: None,
+      hash_function: HashFunction::MD4,
+      hash_digest: HashDigest::Base64,
+      hash_digest_length: 4,
+    }
+  }
+}
+
+/// Uses the hash of the context relative module identifier as the module id,
+/// same as the `HashedModuleIdsPlugin` of webpack.
+#[plugin]
+#[derive(Debug)]
+pub struct HashedModuleIdsPlugin {
+  options: HashedModuleIdsPluginOptions,
+}
+
+impl HashedModuleIdsPlugin {
+  pub fn new(options: HashedModuleIdsPluginOptions) -> Self {
+    Self::new_inner(options)
+  }
+}
+
+#[plugin_hook(CompilationModuleIds for HashedModuleIdsPlugin)]
+async fn module_ids(
+  &self,
+  compilation: &Compilation,
+  module_ids: &mut ModuleIdsArtifact,
+  diagnostics: &mut Vec<Diagnostic>,
+) -> Result<()> {
+  if let Some(diagnostic) = compilation.incremental.disable_passes(
+    IncrementalPasses::MODULE_IDS,
+    "HashedModuleIdsPlugin",
+    "it requires calculating the id of all the modules, which is a global effect",
+  ) {
+    if let Some(diagnostic) = diagnostic {
+      diagnostics.push(diagnostic);
+    }
+    module_ids.clear();
+  }
+
+  let (mut used_ids, mut modules) =
+    get_used_module_ids_and_modules_with_artifact(compilation, module_ids, None);
+
+  let context = self
+    .options
+    .context
+    .as_deref()
+    .unwrap_or_else(|| compilation.options.context.as_str());
+  let module_graph = compilation.get_module_graph();
+  modules
+    .sort_unstable_by(|a, b| compare_modules_by_pre_order_index_or_identifier(module_graph, a, b));
+
+  for module in modules
+    .into_iter()
+    .filter_map(|i| module_graph.module_by_identifier(&i))
+  {
+    let ident = get_full_module_name(module, context);
+    let module_id = hashed_module_id(&ident, &used_ids, &self.options)?;
+    ChunkGraph::set_module_id(module_ids, module.identifier(), module_id.as_str().into());
+    used_ids.insert(module_id);
+  }
+
+  Ok(())
+}
+
+/// Hashes the identifier and takes the shortest prefix of the digest, which is at least
+/// `hash_digest_length` long, that isn't used by other modules.
+fn hashed_module_id(
+  ident: &str,
+  used_ids: &FxHashSet<String>,
+  options: &HashedModuleIdsPluginOptions,
+) -> Result<String> {
+  let mut hasher = RspackHash::new(&options.hash_function);
+  hasher.write(ident.as_bytes());
+  let hash = hasher.digest(&options.hash_digest);
+
+  let mut len = options.hash_digest_length;
+  while used_ids.contains(hash.rendered(len)) {
+    if len >= hash.encoded().len() {
+      return Err(error!(
+        "HashedModuleIdsPlugin: the hash of module {ident} conflicts with the id of another module"
+      ));
+    }
+    len += 1;
+  }
+  Ok(hash.rendered(len).to_string())
+}
+
+impl Plugin for HashedModuleIdsPlugin {
+  fn name(&self) -> &'static str {
+    "rspack.HashedModuleIdsPlugin"
+  }
+
+  fn apply(&self, ctx: &mut rspack_core::ApplyContext<'_>) -> Result<()> {
+    ctx.compilation_hooks.module_ids.tap(module_ids::new(self));
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rustc_hash::FxHashSet;
+
+  use super::{HashedModuleIdsPluginOptions, hashed_module_id};
+
+  #[test]
+  fn should_match_webpack_ids() {
+    let options = HashedModuleIdsPluginOptions::default();
+    let used_ids = FxHashSet::default();
+    // md4("./index.js") in base64 is "QfWibhy54BGAD/ioApVcjA=="
+    assert_eq!(
+      hashed_module_id("./index.js", &used_ids, &options).unwrap(),
+      "QfWi"
+    );
+    assert_eq!(
+      hashed_module_id("./a.js", &used_ids, &options).unwrap(),
+      "KpHw"
+    );
+  }
+
+  #[test]
+  fn should_lengthen_conflicting_ids() {
+    let options = HashedModuleIdsPluginOptions::default();
+    let mut used_ids = FxHashSet::default();
+    used_ids.insert("QfWi".to_string());
+    assert_eq!(
+      hashed_module_id("./index.js", &used_ids, &options).unwrap(),
+      "QfWib"
+    );
+    used_ids.insert("QfWib".to_string());
+    assert_eq!(
+      hashed_module_id("./index.js", &used_ids, &options).unwrap(),
+      "QfWibh"
+    );
+  }
+
+  #[test]
+  fn should_error_when_the_whole_hash_conflicts() {
+    let options = HashedModuleIdsPluginOptions::default();
+    let used_ids = (4..=24)
+      .map(|len| "QfWibhy54BGAD/ioApVcjA=="[..len].to_string())
+      .collect::<FxHashSet<_>>();
+    assert!(hashed_module_id("./index.js", &used_ids, &options).is_err());
+  }
+}
@@ -7,9 +7,13 @@ mod named_chunk_ids_plugin;
 pub use named_chunk_ids_plugin::*;
 mod deterministic_chunk_ids_plugin;
 pub use deterministic_chunk_ids_plugin::DeterministicChunkIdsPlugin;
+mod hashed_module_ids_plugin;
+pub use hashed_module_ids_plugin::{HashedModuleIdsPlugin, HashedModuleIdsPluginOptions};
 mod natural_module_ids_plugin;
 pub use natural_module_ids_plugin::NaturalModuleIdsPlugin;
 mod natural_chunk_ids_plugin;
 pub use natural_chunk_ids_plugin::NaturalChunkIdsPlugin;
 mod occurrence_chunk_ids_plugin;
 pub use occurrence_chunk_ids_plugin::*;
+mod record_ids_plugin;
+pub use record_ids_plugin::{RecordIdsPlugin, RecordIdsPluginOptions};
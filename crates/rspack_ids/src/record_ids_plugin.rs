@@ -0,0 +1,411 @@
+use std::{
+  collections::BTreeMap,
+  sync::{Arc, Mutex},
+};
+
+use rayon::prelude::*;
+use rspack_collections::{DatabaseItem, IdentifierMap, UkeyMap};
+use rspack_core::{
+  ChunkByUkey, ChunkGraph, ChunkNamedIdArtifact, Compilation, CompilationChunkIds,
+  CompilationModuleIds, CompilationParams, CompilerEmit, CompilerThisCompilation,
+  ModuleIdsArtifact, Plugin, incremental::IncrementalPasses,
+};
+use rspack_error::{Diagnostic, Result, ToStringResultToRspackResultExt};
+use rspack_hook::{plugin, plugin_hook};
+use rspack_paths::{Utf8Path, Utf8PathBuf};
+use rustc_hash::FxHashSet;
+use serde::{Deserialize, Serialize};
+
+use crate::id_helpers::{
+  assign_deterministic_ids, compare_chunks_natural,
+  compare_modules_by_pre_order_index_or_identifier, get_full_chunk_name, get_full_module_name,
+  get_used_chunk_ids, get_used_module_ids_and_modules_with_artifact,
+};
+
+/// The ids assigned by the previous builds, keyed by the full module name and the full chunk name,
+/// which are relative to the context so the records file can be shared across machines.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+struct IdRecords {
+  modules: BTreeMap<String, String>,
+  chunks: BTreeMap<String, String>,
+}
+
+impl IdRecords {
+  /// Merge the ids of the current compilation into the previous records.
+  ///
+  /// The records of the modules and chunks which are absent in the current compilation are kept,
+  /// so they get the same ids back when they are added again, unless the ids are taken by others.
+  fn merge(mut self, current: IdRecords) -> IdRecords {
+    fn merge_ids(previous: &mut BTreeMap<String, String>, current: BTreeMap<String, String>) {
+      let used_ids = current.values().cloned().collect::<FxHashSet<_>>();
+      previous.retain(|name, id| !current.contains_key(name) && !used_ids.contains(id));
+      previous.extend(current);
+    }
+    merge_ids(&mut self.modules, current.modules);
+    merge_ids(&mut self.chunks, current.chunks);
+    self
+  }
+}
+
+#[derive(Debug)]
+pub struct RecordIdsPluginOptions {
+  /// Path of the records file, relative paths are resolved against the context
+  pub path: Utf8PathBuf,
+}
+
+/// Keeps the module and chunk ids assigned by the previous builds by persisting them to a records
+/// file, the modules and chunks without a recorded id are assigned deterministic ids.
+#[plugin]
+#[derive(Debug)]
+pub struct RecordIdsPlugin {
+  path: Utf8PathBuf,
+  /// The records read at the start of the current compilation
+  records: Mutex<Arc<IdRecords>>,
+}
+
+impl RecordIdsPlugin {
+  pub fn new(options: RecordIdsPluginOptions) -> Self {
+    Self::new_inner(options.path, Default::default())
+  }
+
+  fn records(&self) -> Arc<IdRecords> {
+    self.records.lock().expect("should lock records").clone()
+  }
+
+  fn records_path(&self, compilation: &Compilation) -> Utf8PathBuf {
+    if self.path.is_absolute() {
+      self.path.clone()
+    } else {
+      Utf8Path::new(compilation.options.context.as_str()).join(&self.path)
+    }
+  }
+
+  async fn read_records(&self, compilation: &Compilation) -> Result<IdRecords> {
+    let path = self.records_path(compilation);
+    let fs = compilation.intermediate_filesystem.as_ref();
+    // the records file doesn't exist before the first build
+    if fs.stat(&path).await.is_err() {
+      return Ok(IdRecords::default());
+    }
+    let content = fs.read_file(&path).await?;
+    serde_json::from_slice(&content)
+      .to_rspack_result_with_message(|e| format!("Failed to parse the id records file {path}: {e}"))
+  }
+}
+
+#[plugin_hook(CompilerThisCompilation for RecordIdsPlugin)]
+async fn this_compilation(
+  &self,
+  compilation: &mut Compilation,
+  _params: &mut CompilationParams,
+) -> Result<()> {
+  let records = self.read_records(compilation).await?;
+  *self.records.lock().expect("should lock records") = Arc::new(records);
+  Ok(())
+}
+
+#[plugin_hook(CompilationModuleIds for RecordIdsPlugin, stage = -100)]
+async fn module_ids(
+  &self,
+  compilation: &Compilation,
+  module_ids: &mut ModuleIdsArtifact,
+  diagnostics: &mut Vec<Diagnostic>,
+) -> Result<()> {
+  if let Some(diagnostic) = compilation.incremental.disable_passes(
+    IncrementalPasses::MODULE_IDS,
+    "RecordIdsPlugin",
+    "it requires calculating the id of all the modules, which is a global effect",
+  ) {
+    if let Some(diagnostic) = diagnostic {
+      diagnostics.push(diagnostic);
+    }
+    module_ids.clear();
+  }
+
+  let records = self.records();
+
+  let (mut used_ids, modules) =
+    get_used_module_ids_and_modules_with_artifact(compilation, module_ids, None);
+
+  let mut module_ids_map = std::mem::take(module_ids);
+  let context = compilation.options.context.as_ref();
+  let module_graph = compilation.get_module_graph();
+  let modules = modules
+    .into_iter()
+    .filter_map(|i| module_graph.module_by_identifier(&i))
+    .collect::<Vec<_>>();
+
+  let module_names = modules
+    .par_iter()
+    .map(|m| (m.identifier(), get_full_module_name(m, context)))
+    .collect::<IdentifierMap<String>>();
+
+  // revive the recorded ids first, so the new modules can't take them
+  let modules = modules
+    .into_iter()
+    .filter(|module| {
+      let name = module_names
+        .get(&module.identifier())
+        .expect("should have generated full module name");
+      if let Some(id) = records.modules.get(name)
+        && used_ids.insert(id.clone())
+      {
+        ChunkGraph::set_module_id(&mut module_ids_map, module.identifier(), id.as_str().into());
+        return false;
+      }
+      true
+    })
+    .collect::<Vec<_>>();
+  // the ids recorded for the absent modules are reserved for them
+  used_ids.extend(records.modules.values().cloned());
+  let used_ids_len = used_ids.len();
+
+  assign_deterministic_ids(
+    modules,
+    |m| {
+      module_names
+        .get(&m.identifier())
+        .expect("should have generated full module name")
+        .clone()
+    },
+    |a, b| {
+      compare_modules_by_pre_order_index_or_identifier(
+        module_graph,
+        &a.identifier(),
+        &b.identifier(),
+      )
+    },
+    |module, id| {
+      if !used_ids.insert(id.to_string()) {
+        return false;
+      }
+      ChunkGraph::set_module_id(
+        &mut module_ids_map,
+        module.identifier(),
+        id.to_string().into(),
+      );
+      true
+    },
+    &[usize::pow(10, 3)],
+    10,
+    used_ids_len,
+    0,
+  );
+  *module_ids = module_ids_map;
+  Ok(())
+}
+
+#[plugin_hook(CompilationChunkIds for RecordIdsPlugin, stage = -100)]
+async fn chunk_ids(
+  &self,
+  compilation: &Compilation,
+  chunk_by_ukey: &mut ChunkByUkey,
+  _named_chunk_ids_artifact: &mut ChunkNamedIdArtifact,
+  diagnostics: &mut Vec<Diagnostic>,
+) -> Result<()> {
+  if let Some(diagnostic) = compilation.incremental.disable_passes(
+    IncrementalPasses::CHUNK_IDS,
+    "RecordIdsPlugin",
+    "it requires calculating the id of all the chunks, which is a global effect",
+  ) && let Some(diagnostic) = diagnostic
+  {
+    diagnostics.push(diagnostic);
+  }
+
+  let records = self.records();
+
+  let mut used_ids = get_used_chunk_ids(chunk_by_ukey);
+
+  let chunk_graph = &compilation.chunk_graph;
+  let module_graph = compilation.get_module_graph();
+  let module_graph_cache = &compilation.module_graph_cache_artifact;
+  let context = compilation.options.context.as_str();
+
+  let mut chunks = chunk_by_ukey
+    .values()
+    .filter(|chunk| chunk.id().is_none())
+    .collect::<Vec<_>>();
+
+  let chunk_names = chunks
+    .par_iter()
+    .map(|chunk| {
+      (
+        chunk.ukey(),
+        get_full_chunk_name(
+          chunk,
+          chunk_graph,
+          module_graph,
+          module_graph_cache,
+          context,
+        ),
+      )
+    })
+    .collect::<UkeyMap<_, _>>();
+
+  let mut chunk_key_to_id = UkeyMap::default();
+
+  // revive the recorded ids first, so the new chunks can't take them
+  chunks.sort_unstable_by_key(|chunk| &chunk_names[&chunk.ukey()]);
+  let chunks = chunks
+    .into_iter()
+    .filter(|chunk| {
+      if let Some(id) = records.chunks.get(&chunk_names[&chunk.ukey()])
+        && used_ids.insert(id.clone())
+      {
+        chunk_key_to_id.insert(chunk.ukey(), id.clone());
+        return false;
+      }
+      true
+    })
+    .collect::<Vec<_>>();
+  // the ids recorded for the absent chunks are reserved for them
+  used_ids.extend(records.chunks.values().cloned());
+  let used_ids_len = used_ids.len();
+
+  let mut ordered_chunk_modules_cache = Default::default();
+
+  assign_deterministic_ids(
+    chunks,
+    |chunk| chunk_names[&chunk.ukey()].clone(),
+    |a, b| {
+      compare_chunks_natural(
+        chunk_graph,
+        &compilation.chunk_group_by_ukey,
+        &compilation.module_ids_artifact,
+        a,
+        b,
+        &mut ordered_chunk_modules_cache,
+      )
+    },
+    |chunk, id| {
+      if !used_ids.insert(id.to_string()) {
+        return false;
+      }
+      chunk_key_to_id.insert(chunk.ukey(), id.to_string());
+      true
+    },
+    &[usize::pow(10, 3)],
+    10,
+    used_ids_len,
+    10,
+  );
+
+  for (chunk_ukey, id) in chunk_key_to_id {
+    let chunk = chunk_by_ukey.expect_get_mut(&chunk_ukey);
+    chunk.set_id(id);
+  }
+
+  Ok(())
+}
+
+#[plugin_hook(CompilerEmit for RecordIdsPlugin)]
+async fn emit(&self, compilation: &mut Compilation) -> Result<()> {
+  let context = compilation.options.context.as_str();
+  let module_graph = compilation.get_module_graph();
+  let mut records = IdRecords::default();
+
+  for (identifier, module) in module_graph.modules() {
+    if let Some(id) = ChunkGraph::get_module_id(&compilation.module_ids_artifact, identifier) {
+      records
+        .modules
+        .insert(get_full_module_name(module, context), id.to_string());
+    }
+  }
+
+  for chunk in compilation.chunk_by_ukey.values() {
+    if let Some(id) = chunk.id() {
+      records.chunks.insert(
+        get_full_chunk_name(
+          chunk,
+          &compilation.chunk_graph,
+          module_graph,
+          &compilation.module_graph_cache_artifact,
+          context,
+        ),
+        id.to_string(),
+      );
+    }
+  }
+
+  let records = self.records().as_ref().clone().merge(records);
+  let json = serde_json::to_string_pretty(&records).to_rspack_result()?;
+  let path = self.records_path(compilation);
+  let intermediate_filesystem = compilation.intermediate_filesystem.as_ref();
+  if let Some(dir) = path.parent() {
+    intermediate_filesystem.create_dir_all(dir).await?;
+  }
+  intermediate_filesystem
+    .write(&path, json.as_bytes())
+    .await?;
+
+  Ok(())
+}
+
+impl Plugin for RecordIdsPlugin {
+  fn name(&self) -> &'static str {
+    "rspack.RecordIdsPlugin"
+  }
+
+  fn apply(&self, ctx: &mut rspack_core::ApplyContext<'_>) -> Result<()> {
+    ctx
+      .compiler_hooks
+      .this_compilation
+      .tap(this_compilation::new(self));
+    ctx.compilation_hooks.module_ids.tap(module_ids::new(self));
+    ctx.compilation_hooks.chunk_ids.tap(chunk_ids::new(self));
+    ctx.compiler_hooks.emit.tap(emit::new(self));
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::IdRecords;
+
+  fn records(modules: &[(&str, &str)]) -> IdRecords {
+    IdRecords {
+      modules: modules
+        .iter()
+        .map(|(name, id)| (name.to_string(), id.to_string()))
+        .collect(),
+      chunks: Default::default(),
+    }
+  }
+
+  #[test]
+  fn should_round_trip_records() {
+    let initial = records(&[("./a.js", "100"), ("./b.js", "200")]);
+    let json = serde_json::to_string_pretty(&initial).unwrap();
+    assert_eq!(serde_json::from_str::<IdRecords>(&json).unwrap(), initial);
+    // the records written by older versions may miss fields
+    assert_eq!(
+      serde_json::from_str::<IdRecords>(r#"{"modules":{"./a.js":"100"}}"#).unwrap(),
+      records(&[("./a.js", "100")])
+    );
+  }
+
+  #[test]
+  fn should_keep_ids_of_removed_modules() {
+    let initial = records(&[("./a.js", "100"), ("./b.js", "200")]);
+    // ./b.js is removed and ./c.js is added
+    let removed = initial
+      .clone()
+      .merge(records(&[("./a.js", "100"), ("./c.js", "300")]));
+    assert_eq!(
+      removed,
+      records(&[("./a.js", "100"), ("./b.js", "200"), ("./c.js", "300")])
+    );
+    // ./b.js is added back with the recorded id
+    let readded = removed.merge(records(&[("./a.js", "100"), ("./b.js", "200")]));
+    assert_eq!(readded.modules["./b.js"], "200");
+    assert_eq!(readded.modules["./c.js"], "300");
+  }
+
+  #[test]
+  fn should_drop_records_whose_id_is_taken() {
+    let merged = records(&[("./a.js", "100"), ("./b.js", "200")])
+      .merge(records(&[("./a.js", "101"), ("./c.js", "200")]));
+    assert_eq!(merged, records(&[("./a.js", "101"), ("./c.js", "200")]));
+  }
+}
@@ -93,7 +93,8 @@ impl DefineHookInput {
       .collect::<Result<Punctuated<&Ident, Comma>>>()?;
     let hook_name = Ident::new(&format!("{trait_name}Hook"), trait_name.span());
     let hook_name_lit_str = LitStr::new(&hook_name.to_string(), trait_name.span());
-    let call_body = exec_kind.body(arg_names);
+    let trait_name_lit_str = LitStr::new(&trait_name.to_string(), trait_name.span());
+    let call_body = exec_kind.body(arg_names, &trait_name_lit_str);
     let call_body = if tracing.is_none_or(|bool_lit| bool_lit.value) {
       let tracing_span_name = LitStr::new(&format!("hook:{trait_name}"), trait_name.span());
       quote! {
@@ -117,6 +118,9 @@ impl DefineHookInput {
         fn stage(&self) -> i32 {
           0
         }
+        fn tap_name(&self) -> &'static str {
+          ::std::any::type_name::<Self>()
+        }
       }
 
       pub struct #hook_name {
@@ -210,13 +214,17 @@ impl ExecKind {
     }
   }
 
-  pub fn body(&self, args: Punctuated<&Ident, Comma>) -> TokenStream {
+  pub fn body(&self, args: Punctuated<&Ident, Comma>, hook_name: &LitStr) -> TokenStream {
     let additional_taps = self.additional_taps();
+    let timer = quote! {
+      let _timer = ::rspack_hook::__macro_helper::TapTimer::start(#hook_name, tap.tap_name(), tap.stage());
+    };
     match self {
       Self::Series => {
         quote! {
           #additional_taps
           for tap in all_taps {
+            #timer
             tap.run(#args).await?;
           }
           Ok(())
@@ -226,6 +234,7 @@ impl ExecKind {
         quote! {
           #additional_taps
           for tap in all_taps {
+            #timer
             if let Some(res) = tap.run(#args).await? {
               return Ok(Some(res));
             }
@@ -238,6 +247,7 @@ impl ExecKind {
           #additional_taps
           let mut data = #args;
           for tap in all_taps {
+            #timer
             data = tap.run(data).await?
           }
           Ok(data)
@@ -246,7 +256,10 @@ impl ExecKind {
       Self::Parallel => {
         quote! {
           #additional_taps
-          let futs: std::vec::Vec<_> = all_taps.iter().map(|t| t.run(#args)).collect();
+          let futs: std::vec::Vec<_> = all_taps.iter().map(|tap| async move {
+            #timer
+            tap.run(#args).await
+          }).collect();
           futures_concurrency::vec::TryJoin(futs).await?;
           Ok(())
         }
@@ -246,6 +246,10 @@ pub fn expand_fn(args: HookArgs, input: syn::ItemFn) -> proc_macro::TokenStream
       }
 
       #stage_fn
+
+      fn tap_name(&self) -> &'static str {
+        #plugin_name
+      }
     }
   };
   expanded.into()
@@ -46,3 +46,57 @@ mod simple {
     Ok(())
   }
 }
+
+mod timing {
+  use rspack_hook::HookTimingSession;
+
+  use super::*;
+
+  define_hook!(Process: Series(count: &mut u32));
+
+  #[plugin]
+  #[derive(Default)]
+  struct MyProcessPlugin;
+
+  #[plugin_hook(Process for MyProcessPlugin, stage = 10)]
+  async fn process(&self, count: &mut u32) -> Result<()> {
+    *count += 1;
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test() -> Result<()> {
+    let mut hook = ProcessHook::default();
+    let plugin = MyProcessPlugin::default();
+    hook.tap(process::new(&plugin));
+
+    let session = HookTimingSession::start();
+    let mut count = 0;
+    hook.call(&mut count).await?;
+    let other_session = HookTimingSession::start();
+    hook.call(&mut count).await?;
+    assert_eq!(count, 2);
+
+    // the sessions are independent, the other session only sees the calls after it started
+    let other_report = other_session.take();
+    let report = session.take();
+    drop(other_session);
+    drop(session);
+    let timing = report
+      .taps
+      .iter()
+      .find(|tap| tap.hook == "Process")
+      .expect("should record the tap");
+    assert_eq!(timing.tap, "MyProcessPlugin");
+    assert_eq!(timing.stage, 10);
+    assert_eq!(timing.calls, 2);
+    assert!(timing.max <= timing.total);
+    let other_timing = other_report
+      .taps
+      .iter()
+      .find(|tap| tap.hook == "Process")
+      .expect("should record the tap");
+    assert_eq!(other_timing.calls, 1);
+    Ok(())
+  }
+}
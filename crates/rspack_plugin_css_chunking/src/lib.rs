@@ -11,7 +11,7 @@ use rspack_core::{
   ChunkUkey, Compilation, CompilationOptimizeChunks, CompilationParams, CompilerCompilation,
   Logger, Module, ModuleIdentifier, Plugin, SourceType,
 };
-use rspack_error::Result;
+use rspack_error::{Diagnostic, Result};
 use rspack_hook::{plugin, plugin_hook};
 use rspack_plugin_css::CssPlugin;
 use rspack_regex::RspackRegex;
@@ -28,6 +28,10 @@ fn is_global_css(name_for_condition: &Option<Box<str>>) -> bool {
 #[derive(Debug)]
 pub struct CssChunkingPluginOptions {
   pub strict: bool,
+  /// Only group css modules loaded by the same chunks, so the css chunks keep the load boundaries
+  /// of the chunks owning them, and warn about the conflicting order of css modules in a chunk.
+  /// It implies `strict`.
+  pub align_with_js: bool,
   pub min_size: Option<f64>,
   pub max_size: Option<f64>,
   pub exclude: Option<RspackRegex>,
@@ -38,6 +42,7 @@ pub struct CssChunkingPluginOptions {
 pub struct CssChunkingPlugin {
   once: AtomicBool,
   strict: bool,
+  align_with_js: bool,
   min_size: f64,
   max_size: f64,
   exclude: Option<RspackRegex>,
@@ -48,6 +53,7 @@ impl CssChunkingPlugin {
     Self::new_inner(
       AtomicBool::new(false),
       options.strict,
+      options.align_with_js,
       options.min_size.unwrap_or(MIN_CSS_CHUNK_SIZE),
       options.max_size.unwrap_or(MAX_CSS_CHUNK_SIZE),
       options.exclude,
@@ -65,6 +71,45 @@ async fn compilation(
   Ok(())
 }
 
+/// The chunk ids are not assigned yet when the chunks are optimized, so the unnamed chunks are
+/// named by their root modules.
+fn chunk_display_name(chunk_ukey: &ChunkUkey, compilation: &Compilation) -> String {
+  let chunk = compilation.chunk_by_ukey.expect_get(chunk_ukey);
+  if let Some(name) = chunk.name() {
+    return name.to_string();
+  }
+  let module_graph = compilation.get_module_graph();
+  let root_modules = compilation
+    .chunk_graph
+    .get_chunk_root_modules(
+      chunk_ukey,
+      module_graph,
+      &compilation.module_graph_cache_artifact,
+    )
+    .into_iter()
+    .filter_map(|identifier| module_graph.module_by_identifier(&identifier))
+    .map(|module| {
+      module
+        .readable_identifier(&compilation.options.context)
+        .into_owned()
+    })
+    .collect::<Vec<_>>();
+  if root_modules.is_empty() {
+    format!("#{}", chunk_ukey.as_u32())
+  } else {
+    root_modules.join(" + ")
+  }
+}
+
+/// The conflicting order of two css modules in a chunk
+#[derive(Debug)]
+struct OrderConflict {
+  chunk: ChunkUkey,
+  chunk_name: String,
+  failed_module: ModuleIdentifier,
+  selected_module: ModuleIdentifier,
+}
+
 #[derive(Debug)]
 struct ChunkState {
   chunk: ChunkUkey,
@@ -74,7 +119,7 @@ struct ChunkState {
 
 #[plugin_hook(CompilationOptimizeChunks for CssChunkingPlugin, stage = 5)]
 async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<bool>> {
-  let strict = self.strict;
+  let strict = self.strict || self.align_with_js;
 
   if self.once.load(Ordering::Relaxed) {
     return Ok(None);
@@ -87,6 +132,7 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
   let mut chunk_states: UkeyMap<ChunkUkey, ChunkState> = Default::default();
   let mut chunk_states_by_module: IdentifierIndexMap<UkeyMap<ChunkUkey, usize>> =
     Default::default();
+  let mut order_conflicts: Vec<OrderConflict> = vec![];
 
   // Collect all css modules in chunks and the execpted order of them
   let chunk_graph = &compilation.chunk_graph;
@@ -118,7 +164,18 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
       continue;
     }
     let chunk = compilation.chunk_by_ukey.expect_get(chunk_ukey);
-    let (ordered_modules, _) = CssPlugin::get_modules_in_order(chunk, modules, compilation);
+    let (ordered_modules, conflicts) = CssPlugin::get_modules_in_order(chunk, modules, compilation);
+    if self.align_with_js
+      && let Some(conflicts) = conflicts
+    {
+      let chunk_name = chunk_display_name(chunk_ukey, compilation);
+      order_conflicts.extend(conflicts.into_iter().map(|conflict| OrderConflict {
+        chunk: *chunk_ukey,
+        chunk_name: chunk_name.clone(),
+        failed_module: conflict.failed_module,
+        selected_module: conflict.selected_module,
+      }));
+    }
     let mut module_identifiers: Vec<ModuleIdentifier> = Vec::with_capacity(ordered_modules.len());
     for (i, module) in ordered_modules.iter().enumerate() {
       let module_identifier = module.identifier();
@@ -180,7 +237,7 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
   // assuming that when a module is a dependency of another module
   // it will always appear before it in every chunk.
   let mut all_dependents: IdentifierMap<HashSet<ModuleIdentifier>> = IdentifierMap::default();
-  if !self.strict {
+  if !strict {
     let start = logger.time("guess the dependents of modules from the order");
     for b in &remaining_modules {
       let mut dependents = HashSet::new();
@@ -309,8 +366,8 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
             match all_chunk_states.get(chunk_ukey) {
               None => {
                 // New chunk group, can add it, but should we?
-                // We only add that if below min size
-                if current_size >= self.min_size {
+                // We only add that if below min size, and never when aligning with the js chunks
+                if self.align_with_js || current_size >= self.min_size {
                   continue 'outer;
                 }
               }
@@ -320,6 +377,15 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
           }
         }
 
+        // The css chunk must be loaded by the same chunks as the ones owning it
+        if self.align_with_js
+          && all_chunk_states
+            .keys()
+            .any(|cs| !next_chunk_states.contains_key(cs))
+        {
+          continue;
+        }
+
         // Global CSS must not leak into unrelated chunks
         #[allow(clippy::unwrap_used)]
         let is_global = is_global_css(&module_infos[&next_module_identifier].1);
@@ -408,6 +474,37 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
   }
   logger.time_end(start);
 
+  // The conflicts between the modules grouped into the same css chunk are reported by the css plugin
+  // when the css chunk is rendered, only report the ones split into different css chunks here
+  let module_graph = compilation.get_module_graph();
+  let diagnostics = order_conflicts
+    .into_iter()
+    .filter(|conflict| {
+      new_chunks_by_module.get(&conflict.failed_module)
+        != new_chunks_by_module.get(&conflict.selected_module)
+    })
+    .map(|conflict| {
+      let failed_module = module_graph
+        .module_by_identifier(&conflict.failed_module)
+        .expect("should have module");
+      let selected_module = module_graph
+        .module_by_identifier(&conflict.selected_module)
+        .expect("should have module");
+      let mut diagnostic = Diagnostic::warn(
+        "Conflicting order".into(),
+        format!(
+          "chunk {} [rspack.CssChunkingPlugin]\nConflicting order between {} and {}",
+          conflict.chunk_name,
+          failed_module.readable_identifier(&compilation.options.context),
+          selected_module.readable_identifier(&compilation.options.context)
+        ),
+      );
+      diagnostic.chunk = Some(conflict.chunk.as_u32());
+      diagnostic
+    })
+    .collect::<Vec<_>>();
+  compilation.extend_diagnostics(diagnostics);
+
   Ok(None)
 }
 
@@ -0,0 +1,113 @@
+use std::sync::Mutex;
+
+use rspack_core::{
+  Compilation, CompilationParams, CompilerAfterEmit, CompilerThisCompilation, Logger, Plugin,
+};
+use rspack_error::Result;
+use rspack_hook::{HookTimingReport, HookTimingSession, plugin, plugin_hook};
+
+const PLUGIN_NAME: &str = "rspack.HookTimingPlugin";
+
+#[derive(Debug, Default)]
+pub struct HookTimingPluginOptions {
+  /// The filename of the JSON report relative to the output path,
+  /// the report is only written to the stats logging if it is not set.
+  pub filename: Option<String>,
+}
+
+/// Records the wall time of each tap of the hooks during a compilation,
+/// including the taps of JavaScript plugins, and reports them sorted by cost.
+///
+/// Each compilation is recorded in its own session, the taps are not attributed to the compilers,
+/// so the compilations running at the same time, e.g. in a multi compiler, are reported together.
+#[plugin]
+#[derive(Debug)]
+pub struct HookTimingPlugin {
+  options: HookTimingPluginOptions,
+  session: Mutex<Option<HookTimingSession>>,
+}
+
+impl HookTimingPlugin {
+  pub fn new(options: HookTimingPluginOptions) -> Self {
+    Self::new_inner(options, Default::default())
+  }
+}
+
+fn format_ms(duration: std::time::Duration) -> String {
+  format!("{:.2} ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn log_report(compilation: &Compilation, report: &HookTimingReport) {
+  let logger = compilation.get_logger(PLUGIN_NAME);
+  logger.group(format!("hook taps: {}", format_ms(report.total())));
+  for tap in &report.taps {
+    logger.log(format!(
+      "{}: {} {} (stage {}, {} calls, max {})",
+      format_ms(tap.total),
+      tap.hook,
+      tap.tap,
+      tap.stage,
+      tap.calls,
+      format_ms(tap.max)
+    ));
+  }
+  logger.group_end();
+}
+
+#[plugin_hook(CompilerThisCompilation for HookTimingPlugin, stage = i32::MIN)]
+async fn this_compilation(
+  &self,
+  _compilation: &mut Compilation,
+  _params: &mut CompilationParams,
+) -> Result<()> {
+  // the timings recorded before the compilation are dropped with the previous session
+  *self
+    .session
+    .lock()
+    .expect("should lock hook timing session") = Some(HookTimingSession::start());
+  Ok(())
+}
+
+#[plugin_hook(CompilerAfterEmit for HookTimingPlugin, stage = i32::MAX)]
+async fn after_emit(&self, compilation: &mut Compilation) -> Result<()> {
+  // the session ends after the compilation, so the taps are not timed between the compilations
+  let Some(session) = self
+    .session
+    .lock()
+    .expect("should lock hook timing session")
+    .take()
+  else {
+    return Ok(());
+  };
+  let report = session.take();
+  drop(session);
+  log_report(compilation, &report);
+
+  if let Some(filename) = &self.options.filename {
+    let file_path = compilation.options.output.path.join(filename);
+    if let Some(parent) = file_path.parent() {
+      compilation.output_filesystem.create_dir_all(parent).await?;
+    }
+    let content = serde_json::to_vec_pretty(&report.to_json()).expect("should serialize report");
+    compilation
+      .output_filesystem
+      .write(&file_path, &content)
+      .await?;
+  }
+  Ok(())
+}
+
+impl Plugin for HookTimingPlugin {
+  fn name(&self) -> &'static str {
+    PLUGIN_NAME
+  }
+
+  fn apply(&self, ctx: &mut rspack_core::ApplyContext<'_>) -> Result<()> {
+    ctx
+      .compiler_hooks
+      .this_compilation
+      .tap(this_compilation::new(self));
+    ctx.compiler_hooks.after_emit.tap(after_emit::new(self));
+    Ok(())
+  }
+}
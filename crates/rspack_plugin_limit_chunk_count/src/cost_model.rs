@@ -0,0 +1,254 @@
+use rspack_collections::{IdentifierMap, UkeyMap};
+use rspack_core::{ChunkByUkey, ChunkGraph, ChunkUkey, Compilation, ModuleGraph, ModuleIdentifier};
+
+#[derive(Debug, Clone)]
+pub struct LoadTimeCostOptions {
+  /// Estimated ratio of the gzip size to the raw size of the modules,
+  /// the sources are not generated yet when the chunks are optimized.
+  pub compression_ratio: f64,
+  /// Latency of a request, in milliseconds
+  pub request_latency: f64,
+  /// Bytes downloaded per second
+  pub bandwidth: f64,
+}
+
+impl Default for LoadTimeCostOptions {
+  fn default() -> Self {
+    Self {
+      compression_ratio: 0.3,
+      request_latency: 50.0,
+      bandwidth: 1_000_000.0,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default)]
+pub enum ChunkCostModel {
+  /// Merge the chunks with the largest raw size benefit first,
+  /// based on `chunk_overhead` and `entry_chunk_multiplicator`.
+  #[default]
+  Size,
+  /// Merge the chunks with the largest expected load time benefit first,
+  /// based on the estimated gzip size and the latency of a request.
+  LoadTime(LoadTimeCostOptions),
+}
+
+pub(crate) struct CombinationCost {
+  pub a_size: f64,
+  pub b_size: f64,
+  pub integrated_size: f64,
+  pub size_diff: f64,
+}
+
+/// Caches the raw sizes of the modules and the chunks, so updating the combinations of a merged
+/// chunk doesn't walk the modules of every affected pair again.
+#[derive(Default)]
+pub(crate) struct ModuleSizeCache {
+  modules: IdentifierMap<f64>,
+  chunks: UkeyMap<ChunkUkey, f64>,
+}
+
+impl ModuleSizeCache {
+  fn module_size(
+    &mut self,
+    id: &ModuleIdentifier,
+    module_graph: &ModuleGraph,
+    compilation: &Compilation,
+  ) -> f64 {
+    *self.modules.entry(*id).or_insert_with(|| {
+      module_graph
+        .module_by_identifier(id)
+        .map(|module| {
+          module
+            .source_types(module_graph)
+            .iter()
+            .map(|t| module.size(Some(t), Some(compilation)))
+            .sum::<f64>()
+        })
+        .unwrap_or_default()
+    })
+  }
+
+  fn chunk_size(
+    &mut self,
+    chunk: &ChunkUkey,
+    chunk_graph: &ChunkGraph,
+    module_graph: &ModuleGraph,
+    compilation: &Compilation,
+  ) -> f64 {
+    if let Some(size) = self.chunks.get(chunk) {
+      return *size;
+    }
+    let size = chunk_graph
+      .get_chunk_modules_identifier(chunk)
+      .iter()
+      .map(|id| self.module_size(id, module_graph, compilation))
+      .sum();
+    self.chunks.insert(*chunk, size);
+    size
+  }
+
+  /// The size of the modules in both chunks, only the modules of the smaller chunk are walked.
+  fn shared_size(
+    &mut self,
+    a: &ChunkUkey,
+    b: &ChunkUkey,
+    chunk_graph: &ChunkGraph,
+    module_graph: &ModuleGraph,
+    compilation: &Compilation,
+  ) -> f64 {
+    let a_modules = chunk_graph.get_chunk_modules_identifier(a);
+    let b_modules = chunk_graph.get_chunk_modules_identifier(b);
+    let (smaller, larger) = if a_modules.len() <= b_modules.len() {
+      (a_modules, b_modules)
+    } else {
+      (b_modules, a_modules)
+    };
+    smaller
+      .iter()
+      .filter(|id| larger.contains(*id))
+      .map(|id| self.module_size(id, module_graph, compilation))
+      .sum()
+  }
+
+  /// Drops the cached size of a chunk whose modules are changed
+  pub(crate) fn invalidate_chunk(&mut self, chunk: &ChunkUkey) {
+    self.chunks.remove(chunk);
+  }
+}
+
+impl LoadTimeCostOptions {
+  fn transfer_time(&self, size: f64) -> f64 {
+    size * 1000.0 / self.bandwidth
+  }
+
+  /// The sizes are the estimated gzip sizes, and the `size_diff` is the load time saved by merging
+  /// the two chunks, summed over the chunk groups loading any of them: a chunk group loading both
+  /// saves a request and the duplicated modules, while a chunk group loading only one of them has
+  /// to download the modules of the other one.
+  #[allow(clippy::too_many_arguments)]
+  pub(crate) fn combination_cost(
+    &self,
+    a: &ChunkUkey,
+    b: &ChunkUkey,
+    chunk_graph: &ChunkGraph,
+    chunk_by_ukey: &ChunkByUkey,
+    module_graph: &ModuleGraph,
+    compilation: &Compilation,
+    sizes: &mut ModuleSizeCache,
+  ) -> CombinationCost {
+    let a_raw_size = sizes.chunk_size(a, chunk_graph, module_graph, compilation);
+    let b_raw_size = sizes.chunk_size(b, chunk_graph, module_graph, compilation);
+    let shared_raw_size = sizes.shared_size(a, b, chunk_graph, module_graph, compilation);
+
+    let a_groups = chunk_by_ukey.expect_get(a).groups();
+    let b_groups = chunk_by_ukey.expect_get(b).groups();
+    let shared_groups = a_groups.intersection(b_groups).count();
+
+    self.cost(
+      a_raw_size,
+      b_raw_size,
+      shared_raw_size,
+      GroupCounts {
+        shared: shared_groups,
+        a_only: a_groups.len() - shared_groups,
+        b_only: b_groups.len() - shared_groups,
+      },
+    )
+  }
+
+  fn cost(
+    &self,
+    a_raw_size: f64,
+    b_raw_size: f64,
+    shared_raw_size: f64,
+    groups: GroupCounts,
+  ) -> CombinationCost {
+    let a_size = a_raw_size * self.compression_ratio;
+    let b_size = b_raw_size * self.compression_ratio;
+    let integrated_size = (a_raw_size + b_raw_size - shared_raw_size) * self.compression_ratio;
+
+    let size_diff = groups.shared as f64
+      * (self.request_latency + self.transfer_time(a_size + b_size - integrated_size))
+      - groups.a_only as f64 * self.transfer_time(integrated_size - a_size)
+      - groups.b_only as f64 * self.transfer_time(integrated_size - b_size);
+
+    CombinationCost {
+      a_size,
+      b_size,
+      integrated_size,
+      size_diff,
+    }
+  }
+}
+
+/// The number of chunk groups loading both chunks, and loading only one of them
+struct GroupCounts {
+  shared: usize,
+  a_only: usize,
+  b_only: usize,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{GroupCounts, LoadTimeCostOptions};
+
+  fn options() -> LoadTimeCostOptions {
+    LoadTimeCostOptions {
+      compression_ratio: 0.5,
+      request_latency: 50.0,
+      bandwidth: 1000.0,
+    }
+  }
+
+  #[test]
+  fn should_save_a_request_for_the_shared_chunk_groups() {
+    let cost = options().cost(
+      1000.0,
+      2000.0,
+      0.0,
+      GroupCounts {
+        shared: 2,
+        a_only: 0,
+        b_only: 0,
+      },
+    );
+    assert_eq!(cost.a_size, 500.0);
+    assert_eq!(cost.b_size, 1000.0);
+    assert_eq!(cost.integrated_size, 1500.0);
+    assert_eq!(cost.size_diff, 100.0);
+  }
+
+  #[test]
+  fn should_save_the_duplicated_modules() {
+    let cost = options().cost(
+      1000.0,
+      1000.0,
+      400.0,
+      GroupCounts {
+        shared: 1,
+        a_only: 0,
+        b_only: 0,
+      },
+    );
+    assert_eq!(cost.integrated_size, 800.0);
+    // a request and the 200 duplicated bytes
+    assert_eq!(cost.size_diff, 250.0);
+  }
+
+  #[test]
+  fn should_charge_the_chunk_groups_loading_only_one_chunk() {
+    let cost = options().cost(
+      1000.0,
+      2000.0,
+      0.0,
+      GroupCounts {
+        shared: 1,
+        a_only: 1,
+        b_only: 2,
+      },
+    );
+    // 50ms saved by the shared group, 1000ms and 2 * 500ms more downloaded by the others
+    assert_eq!(cost.size_diff, 50.0 - 1000.0 - 2.0 * 500.0);
+  }
+}
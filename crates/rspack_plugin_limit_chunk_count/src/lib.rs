@@ -1,12 +1,15 @@
 mod chunk_combination;
+mod cost_model;
 
 use std::collections::HashSet;
 
 use chunk_combination::{ChunkCombination, ChunkCombinationBucket, ChunkCombinationUkey};
+pub use cost_model::{ChunkCostModel, LoadTimeCostOptions};
+use cost_model::{CombinationCost, ModuleSizeCache};
 use rspack_collections::{UkeyMap, UkeySet};
 use rspack_core::{
-  ChunkSizeOptions, ChunkUkey, Compilation, CompilationOptimizeChunks, Plugin,
-  compare_chunks_with_graph, incremental::Mutation,
+  ChunkByUkey, ChunkGroupByUkey, ChunkSizeOptions, ChunkUkey, Compilation,
+  CompilationOptimizeChunks, Plugin, compare_chunks_with_graph, incremental::Mutation,
 };
 use rspack_error::Result;
 use rspack_hook::{plugin, plugin_hook};
@@ -40,6 +43,10 @@ pub struct LimitChunkCountPluginOptions {
   pub entry_chunk_multiplicator: Option<f64>,
   // Limit the maximum number of chunks using a value greater greater than or equal to 1.
   pub max_chunks: usize,
+  // Limit the maximum number of chunks loaded by a chunk group.
+  pub max_chunks_per_route: Option<usize>,
+  // How to choose the chunks to merge first.
+  pub cost_model: ChunkCostModel,
 }
 
 #[plugin]
@@ -52,6 +59,35 @@ impl LimitChunkCountPlugin {
   pub fn new(options: LimitChunkCountPluginOptions) -> Self {
     Self::new_inner(options)
   }
+
+  fn exceeds_max_chunks_per_route(&self, chunk_group_by_ukey: &ChunkGroupByUkey) -> bool {
+    self.options.max_chunks_per_route.is_some_and(|max| {
+      chunk_group_by_ukey
+        .values()
+        .any(|group| group.chunks.len() > max)
+    })
+  }
+
+  /// Whether merging the two chunks reduces the chunks of a chunk group exceeding `max_chunks_per_route`
+  fn reduces_chunks_per_route(
+    &self,
+    a: &ChunkUkey,
+    b: &ChunkUkey,
+    chunk_by_ukey: &ChunkByUkey,
+    chunk_group_by_ukey: &ChunkGroupByUkey,
+  ) -> bool {
+    let Some(max) = self.options.max_chunks_per_route else {
+      return false;
+    };
+    let (Some(a), Some(b)) = (chunk_by_ukey.get(a), chunk_by_ukey.get(b)) else {
+      return false;
+    };
+    a.groups().intersection(b.groups()).any(|group| {
+      chunk_group_by_ukey
+        .get(group)
+        .is_some_and(|group| group.chunks.len() > max)
+    })
+  }
 }
 
 #[plugin_hook(CompilationOptimizeChunks for LimitChunkCountPlugin, stage = Compilation::OPTIMIZE_CHUNKS_STAGE_ADVANCED)]
@@ -66,7 +102,9 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
     .keys()
     .copied()
     .collect::<Vec<_>>();
-  if chunks_ukeys.len() <= max_chunks {
+  if chunks_ukeys.len() <= max_chunks
+    && !self.exceeds_max_chunks_per_route(&compilation.chunk_group_by_ukey)
+  {
     return Ok(None);
   }
 
@@ -79,7 +117,8 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
 
   //    let chunk_graph = &compilation.chunk_graph.clone();
   let module_graph = compilation.get_module_graph();
-  let mut remaining_chunks_to_merge = (chunks_ukeys.len() - max_chunks) as i64;
+  // it's not positive when only `max_chunks_per_route` is exceeded
+  let mut remaining_chunks_to_merge = chunks_ukeys.len() as i64 - max_chunks as i64;
 
   // order chunks in a deterministic way
   chunks_ukeys.sort_by(|a, b| compare_chunks_with_graph(chunk_graph, module_graph, a, b));
@@ -100,6 +139,7 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
     chunk_overhead: self.options.chunk_overhead,
     entry_chunk_multiplicator: self.options.entry_chunk_multiplicator,
   };
+  let mut module_sizes = ModuleSizeCache::default();
 
   for (b_idx, b) in chunks_ukeys.iter().enumerate() {
     for (a_idx, a) in chunks_ukeys.iter().enumerate().take(b_idx) {
@@ -107,36 +147,60 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
         continue;
       }
 
-      let integrated_size = chunk_graph.get_integrated_chunks_size(
-        a,
-        b,
-        &chunk_size_option,
-        chunk_by_ukey,
-        chunk_group_by_ukey,
-        module_graph,
-        compilation,
-      );
-      let a_size = chunk_graph.get_chunk_size(
-        a,
-        &chunk_size_option,
-        chunk_by_ukey,
-        chunk_group_by_ukey,
-        module_graph,
-        compilation,
-      );
-      let b_size = chunk_graph.get_chunk_size(
-        b,
-        &chunk_size_option,
-        chunk_by_ukey,
-        chunk_group_by_ukey,
-        module_graph,
-        compilation,
-      );
+      let CombinationCost {
+        a_size,
+        b_size,
+        integrated_size,
+        size_diff,
+      } = match &self.options.cost_model {
+        ChunkCostModel::Size => {
+          let integrated_size = chunk_graph.get_integrated_chunks_size(
+            a,
+            b,
+            &chunk_size_option,
+            chunk_by_ukey,
+            chunk_group_by_ukey,
+            module_graph,
+            compilation,
+          );
+          let a_size = chunk_graph.get_chunk_size(
+            a,
+            &chunk_size_option,
+            chunk_by_ukey,
+            chunk_group_by_ukey,
+            module_graph,
+            compilation,
+          );
+          let b_size = chunk_graph.get_chunk_size(
+            b,
+            &chunk_size_option,
+            chunk_by_ukey,
+            chunk_group_by_ukey,
+            module_graph,
+            compilation,
+          );
+          CombinationCost {
+            a_size,
+            b_size,
+            integrated_size,
+            size_diff: a_size + b_size - integrated_size,
+          }
+        }
+        ChunkCostModel::LoadTime(options) => options.combination_cost(
+          a,
+          b,
+          chunk_graph,
+          chunk_by_ukey,
+          module_graph,
+          compilation,
+          &mut module_sizes,
+        ),
+      };
 
       let c = ChunkCombination {
         ukey: ChunkCombinationUkey::new(),
         deleted: false,
-        size_diff: a_size + b_size - integrated_size,
+        size_diff,
         integrated_size,
         a: *a,
         b: *b,
@@ -166,6 +230,13 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
     let b = combination.b;
     let integrated_size = combination.integrated_size;
 
+    // only the chunk groups exceeding `max_chunks_per_route` are left to be fixed
+    if remaining_chunks_to_merge <= 0
+      && !self.reduces_chunks_per_route(&a, &b, &new_chunk_by_ukey, &new_chunk_group_by_ukey)
+    {
+      continue;
+    }
+
     // skip over pair when
     // one of the already merged chunks is a parent of one of the chunks
     if !modified_chunks.is_empty() {
@@ -212,9 +283,13 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
 
       // flag chunk a as modified as further optimization are possible for all children here
       modified_chunks.insert(a);
+      module_sizes.invalidate_chunk(&a);
+      module_sizes.invalidate_chunk(&b);
 
       remaining_chunks_to_merge -= 1;
-      if remaining_chunks_to_merge <= 0 {
+      if remaining_chunks_to_merge <= 0
+        && !self.exceeds_max_chunks_per_route(&new_chunk_group_by_ukey)
+      {
         break;
       }
 
@@ -248,6 +323,24 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
               continue;
             }
             // Update size
+            if let ChunkCostModel::LoadTime(options) = &self.options.cost_model {
+              let cost = options.combination_cost(
+                &a,
+                &combination.b,
+                &new_chunk_graph,
+                &new_chunk_by_ukey,
+                module_graph,
+                compilation,
+                &mut module_sizes,
+              );
+              combination.a = a;
+              combination.integrated_size = cost.integrated_size;
+              combination.a_size = cost.a_size;
+              combination.b_size = cost.b_size;
+              combination.size_diff = cost.size_diff;
+              combinations.update();
+              continue;
+            }
             let new_integrated_size = chunk_graph.get_integrated_chunks_size(
               &a,
               &combination.b,
@@ -269,6 +362,24 @@ async fn optimize_chunks(&self, compilation: &mut Compilation) -> Result<Option<
               continue;
             }
             // Update size
+            if let ChunkCostModel::LoadTime(options) = &self.options.cost_model {
+              let cost = options.combination_cost(
+                &combination.a,
+                &a,
+                &new_chunk_graph,
+                &new_chunk_by_ukey,
+                module_graph,
+                compilation,
+                &mut module_sizes,
+              );
+              combination.b = a;
+              combination.integrated_size = cost.integrated_size;
+              combination.a_size = cost.a_size;
+              combination.b_size = cost.b_size;
+              combination.size_diff = cost.size_diff;
+              combinations.update();
+              continue;
+            }
             let new_integrated_size = chunk_graph.get_integrated_chunks_size(
               &combination.a,
               &a,
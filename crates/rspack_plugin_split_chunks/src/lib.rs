@@ -13,5 +13,6 @@ pub use options::{
   cache_group::CacheGroup,
   cache_group_test::{CacheGroupTest, CacheGroupTestFnCtx},
   chunk_name::{ChunkNameGetter, ChunkNameGetterFnCtx},
+  co_loading::{CoLoadingOptions, LoadSet},
 };
 pub use plugin::{PluginOptions, SplitChunksPlugin};
@@ -0,0 +1,30 @@
+use crate::common::SplitChunkSizes;
+
+/// A set of chunks and modules loaded together by a navigation, collected from real-user data.
+#[derive(Debug, Default, Clone)]
+pub struct LoadSet {
+  /// Names of the chunk groups (or chunks) loaded by the navigation
+  pub chunks: Vec<String>,
+  /// Identifiers of the modules loaded by the navigation,
+  /// matched against both the identifier and the readable identifier of a module
+  pub modules: Vec<String>,
+  /// How often the navigation happens, only the ratio between load sets matters
+  pub frequency: f64,
+}
+
+/// For `splitChunks.coLoading` config
+///
+/// With a co-loading profile, async chunks are merged or split after the cache groups are applied,
+/// to minimize the expected bytes and requests per navigation.
+#[derive(Debug, Default, Clone)]
+pub struct CoLoadingOptions {
+  pub load_sets: Vec<LoadSet>,
+  /// The cost of one more request in a navigation, in bytes
+  pub request_cost: f64,
+  /// Minimum size of a chunk split from the shared modules of two chunks
+  pub min_size: SplitChunkSizes,
+  /// Maximum size of a chunk merged from two chunks
+  pub max_async_size: SplitChunkSizes,
+  pub max_async_requests: f64,
+  pub max_initial_requests: f64,
+}
@@ -1,3 +1,4 @@
 pub(crate) mod cache_group;
 pub(crate) mod cache_group_test;
 pub(crate) mod chunk_name;
+pub(crate) mod co_loading;
@@ -0,0 +1,646 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use rspack_collections::{DatabaseItem, UkeyMap, UkeySet};
+use rspack_core::{
+  ChunkGroupUkey, ChunkUkey, Compilation, ModuleIdentifier, incremental::Mutation,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use super::MaxSizeSetting;
+use crate::{CoLoadingOptions, SplitChunkSizes, SplitChunksPlugin, common::ModuleSizes};
+
+/// An async chunk loaded by at least one load set of the co-loading profile
+#[derive(Debug)]
+struct ProfiledChunk {
+  /// Indexes of the load sets loading the chunk
+  load_sets: FxHashSet<usize>,
+  sizes: SplitChunkSizes,
+  /// Bumped when the chunk is changed by a move, which outdates the scored moves of the chunk
+  version: usize,
+}
+
+impl ProfiledChunk {
+  fn size(&self) -> f64 {
+    self.sizes.values().sum()
+  }
+}
+
+#[derive(Debug)]
+enum CoLoadingMove {
+  /// Integrate the second chunk into the first one, which saves a request for the navigations
+  /// loading both of them.
+  Merge(ChunkUkey, ChunkUkey),
+  /// Move the modules shared by the two chunks into a new chunk, which saves the duplicated bytes
+  /// for the navigations loading both of them.
+  Split(ChunkUkey, ChunkUkey, Vec<ModuleIdentifier>),
+}
+
+impl CoLoadingMove {
+  fn chunks(&self) -> (ChunkUkey, ChunkUkey) {
+    match self {
+      CoLoadingMove::Merge(a, b) | CoLoadingMove::Split(a, b, _) => (*a, *b),
+    }
+  }
+
+  /// The merge of a pair is preferred over the split of it when they have the same delta
+  fn kind(&self) -> u8 {
+    match self {
+      CoLoadingMove::Merge(..) => 0,
+      CoLoadingMove::Split(..) => 1,
+    }
+  }
+}
+
+/// A move with the change of the expected cost, which is outdated once one of its chunks is changed
+#[derive(Debug)]
+struct ScoredMove {
+  delta: f64,
+  versions: (usize, usize),
+  candidate: CoLoadingMove,
+}
+
+impl ScoredMove {
+  fn is_outdated(&self, chunks: &UkeyMap<ChunkUkey, ProfiledChunk>) -> bool {
+    let (a, b) = self.candidate.chunks();
+    match (chunks.get(&a), chunks.get(&b)) {
+      (Some(a), Some(b)) => (a.version, b.version) != self.versions,
+      _ => true,
+    }
+  }
+
+  fn key(&self) -> (ChunkUkey, ChunkUkey, u8) {
+    let (a, b) = self.candidate.chunks();
+    (a, b, self.candidate.kind())
+  }
+}
+
+impl PartialEq for ScoredMove {
+  fn eq(&self, other: &Self) -> bool {
+    self.cmp(other) == Ordering::Equal
+  }
+}
+
+impl Eq for ScoredMove {}
+
+impl PartialOrd for ScoredMove {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for ScoredMove {
+  /// The best move is the greatest one: the one with the lowest delta, then the one of the first pair
+  fn cmp(&self, other: &Self) -> Ordering {
+    other
+      .delta
+      .total_cmp(&self.delta)
+      .then_with(|| other.key().cmp(&self.key()))
+  }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct CoLoadingStats {
+  pub merged: usize,
+  pub split: usize,
+}
+
+/// The frequencies of the navigations loading both chunks, only the first one and only the second one
+fn co_loading_frequencies(
+  a: &ProfiledChunk,
+  b: &ProfiledChunk,
+  frequencies: &[f64],
+) -> (f64, f64, f64) {
+  let sum = |load_sets: &FxHashSet<usize>| load_sets.iter().map(|i| frequencies[*i]).sum::<f64>();
+  let both = a
+    .load_sets
+    .intersection(&b.load_sets)
+    .map(|i| frequencies[*i])
+    .sum::<f64>();
+  (both, sum(&a.load_sets) - both, sum(&b.load_sets) - both)
+}
+
+fn modules_sizes<'a>(
+  modules: impl Iterator<Item = &'a ModuleIdentifier>,
+  module_sizes: &ModuleSizes,
+) -> SplitChunkSizes {
+  let mut sizes = SplitChunkSizes::empty();
+  for module in modules {
+    if let Some(module_sizes) = module_sizes.get(module) {
+      for (ty, size) in module_sizes {
+        *sizes.entry(*ty).or_default() += size;
+      }
+    }
+  }
+  sizes
+}
+
+impl SplitChunksPlugin {
+  /// Affected by `splitChunks.coLoading`
+  ///
+  /// The expected cost of a navigation is `frequency * (bytes + request_cost * requests)` summed over
+  /// the load sets of the profile. The profiled async chunks are greedily merged or split, as long as the
+  /// best move reduces the expected cost and respects the size and request limits.
+  pub(crate) fn ensure_co_loading_fit(
+    &self,
+    compilation: &mut Compilation,
+    options: &CoLoadingOptions,
+    module_sizes: &ModuleSizes,
+    max_size_setting_map: &mut UkeyMap<ChunkUkey, MaxSizeSetting>,
+  ) -> CoLoadingStats {
+    let frequencies = options
+      .load_sets
+      .iter()
+      .map(|load_set| load_set.frequency.max(0.0))
+      .collect::<Vec<_>>();
+    let mut chunks = Self::collect_profiled_chunks(compilation, options, module_sizes);
+    let mut stats = CoLoadingStats::default();
+
+    // the pairs are scored once and only the pairs of the changed chunks are scored again,
+    // the moves of the changed chunks left in the queue are outdated and skipped
+    let mut queue: BinaryHeap<ScoredMove> = BinaryHeap::new();
+    let mut sorted_chunks = chunks.keys().copied().collect::<Vec<_>>();
+    sorted_chunks.sort_unstable();
+    for (i, a) in sorted_chunks.iter().enumerate() {
+      for b in &sorted_chunks[i + 1..] {
+        queue.extend(Self::score_co_loading_pair(
+          compilation,
+          options,
+          &chunks,
+          &frequencies,
+          module_sizes,
+          *a,
+          *b,
+        ));
+      }
+    }
+    // the moves exceeding the limits, which may fit after a merge reduces the chunks of the groups
+    let mut blocked: Vec<ScoredMove> = vec![];
+
+    while let Some(scored) = queue.pop() {
+      if scored.is_outdated(&chunks) {
+        continue;
+      }
+      if !Self::can_apply_co_loading_move(
+        compilation,
+        options,
+        &chunks,
+        module_sizes,
+        &scored.candidate,
+      ) {
+        blocked.push(scored);
+        continue;
+      }
+      tracing::trace!("apply co-loading move {:?}", scored.candidate);
+      let changed_chunks = match scored.candidate {
+        CoLoadingMove::Merge(a, b) => {
+          Self::merge_profiled_chunks(compilation, a, b, max_size_setting_map);
+          let chunk_b = chunks.remove(&b).expect("should have profiled chunk");
+          let chunk_a = chunks.get_mut(&a).expect("should have profiled chunk");
+          chunk_a.load_sets.extend(chunk_b.load_sets);
+          chunk_a.sizes = Self::get_chunk_sizes(compilation, &a, module_sizes);
+          chunk_a.version += 1;
+          queue.extend(blocked.drain(..));
+          stats.merged += 1;
+          vec![a]
+        }
+        CoLoadingMove::Split(a, b, modules) => {
+          let new_chunk = Self::split_profiled_chunks(compilation, a, b, &modules);
+          let load_sets = chunks[&a]
+            .load_sets
+            .union(&chunks[&b].load_sets)
+            .copied()
+            .collect();
+          for chunk in [a, b] {
+            let profiled = chunks.get_mut(&chunk).expect("should have profiled chunk");
+            profiled.sizes = Self::get_chunk_sizes(compilation, &chunk, module_sizes);
+            profiled.version += 1;
+          }
+          chunks.insert(
+            new_chunk,
+            ProfiledChunk {
+              load_sets,
+              sizes: modules_sizes(modules.iter(), module_sizes),
+              version: 0,
+            },
+          );
+          stats.split += 1;
+          vec![a, b, new_chunk]
+        }
+      };
+
+      let mut pairs = changed_chunks
+        .iter()
+        .flat_map(|changed| {
+          chunks
+            .keys()
+            .filter(move |other| *other != changed)
+            .map(move |other| (*changed.min(other), *changed.max(other)))
+        })
+        .collect::<Vec<_>>();
+      pairs.sort_unstable();
+      pairs.dedup();
+      for (a, b) in pairs {
+        queue.extend(Self::score_co_loading_pair(
+          compilation,
+          options,
+          &chunks,
+          &frequencies,
+          module_sizes,
+          a,
+          b,
+        ));
+      }
+    }
+
+    stats
+  }
+
+  fn get_chunk_sizes(
+    compilation: &Compilation,
+    chunk: &ChunkUkey,
+    module_sizes: &ModuleSizes,
+  ) -> SplitChunkSizes {
+    modules_sizes(
+      compilation
+        .chunk_graph
+        .get_chunk_modules_identifier(chunk)
+        .iter(),
+      module_sizes,
+    )
+  }
+
+  fn is_movable_chunk(compilation: &Compilation, chunk: &ChunkUkey) -> bool {
+    let chunk_by_ukey = &compilation.chunk_by_ukey;
+    let chunk = chunk_by_ukey.expect_get(chunk);
+    !chunk.can_be_initial(&compilation.chunk_group_by_ukey)
+      && !chunk.prevent_integration()
+      && compilation
+        .chunk_graph
+        .get_number_of_entry_modules(&chunk.ukey())
+        == 0
+  }
+
+  fn collect_profiled_chunks(
+    compilation: &Compilation,
+    options: &CoLoadingOptions,
+    module_sizes: &ModuleSizes,
+  ) -> UkeyMap<ChunkUkey, ProfiledChunk> {
+    let module_by_name = if options
+      .load_sets
+      .iter()
+      .any(|load_set| !load_set.modules.is_empty())
+    {
+      let context = &compilation.options.context;
+      compilation
+        .get_module_graph()
+        .modules()
+        .iter()
+        .flat_map(|(identifier, module)| {
+          [
+            (identifier.to_string(), *identifier),
+            (module.readable_identifier(context).to_string(), *identifier),
+          ]
+        })
+        .collect::<FxHashMap<_, _>>()
+    } else {
+      Default::default()
+    };
+
+    let mut chunks: UkeyMap<ChunkUkey, ProfiledChunk> = Default::default();
+    for (index, load_set) in options.load_sets.iter().enumerate() {
+      let mut groups: UkeySet<ChunkGroupUkey> = Default::default();
+      for name in &load_set.chunks {
+        if let Some(group) = compilation
+          .named_chunk_groups
+          .get(name)
+          .or_else(|| compilation.entrypoints.get(name))
+        {
+          groups.insert(*group);
+        } else if let Some(chunk) = compilation.named_chunks.get(name) {
+          groups.extend(compilation.chunk_by_ukey.expect_get(chunk).groups());
+        }
+      }
+      for module in load_set
+        .modules
+        .iter()
+        .filter_map(|name| module_by_name.get(name))
+      {
+        for chunk in compilation.chunk_graph.get_module_chunks(*module) {
+          groups.extend(compilation.chunk_by_ukey.expect_get(chunk).groups());
+        }
+      }
+
+      for group in groups {
+        for chunk in &compilation.chunk_group_by_ukey.expect_get(&group).chunks {
+          if let Some(profiled) = chunks.get_mut(chunk) {
+            profiled.load_sets.insert(index);
+          } else if Self::is_movable_chunk(compilation, chunk) {
+            chunks.insert(
+              *chunk,
+              ProfiledChunk {
+                load_sets: FxHashSet::from_iter([index]),
+                sizes: Self::get_chunk_sizes(compilation, chunk, module_sizes),
+                version: 0,
+              },
+            );
+          }
+        }
+      }
+    }
+    chunks
+  }
+
+  /// The moves of a pair of chunks which reduce the expected cost, without checking the limits
+  fn score_co_loading_pair(
+    compilation: &Compilation,
+    options: &CoLoadingOptions,
+    chunks: &UkeyMap<ChunkUkey, ProfiledChunk>,
+    frequencies: &[f64],
+    module_sizes: &ModuleSizes,
+    a: ChunkUkey,
+    b: ChunkUkey,
+  ) -> Vec<ScoredMove> {
+    let chunk_a = &chunks[&a];
+    let chunk_b = &chunks[&b];
+    let (both, only_a, only_b) = co_loading_frequencies(chunk_a, chunk_b, frequencies);
+    // neither move makes things better when no navigation loads both chunks
+    if both <= 0.0 {
+      return vec![];
+    }
+    let versions = (chunk_a.version, chunk_b.version);
+    let mut moves = vec![];
+
+    // the navigations loading both save a request,
+    // the navigations loading only one of them load the other one too
+    let delta = -options.request_cost * both + chunk_b.size() * only_a + chunk_a.size() * only_b;
+    // ignore the moves which do not make things better, which also guarantees the termination
+    if delta < -f64::EPSILON {
+      moves.push(ScoredMove {
+        delta,
+        versions,
+        candidate: CoLoadingMove::Merge(a, b),
+      });
+    }
+
+    let chunk_graph = &compilation.chunk_graph;
+    let a_modules = chunk_graph.get_chunk_modules_identifier(&a);
+    let b_modules = chunk_graph.get_chunk_modules_identifier(&b);
+    let (smaller, larger) = if a_modules.len() <= b_modules.len() {
+      (a_modules, b_modules)
+    } else {
+      (b_modules, a_modules)
+    };
+    let mut modules = smaller
+      .iter()
+      .filter(|module| larger.contains(*module))
+      .filter(|module| {
+        compilation
+          .module_by_identifier(module)
+          .is_none_or(|module| {
+            module
+              .chunk_condition(&a, compilation)
+              .is_none_or(|condition| condition)
+          })
+      })
+      .copied()
+      .collect::<Vec<_>>();
+    if !modules.is_empty() {
+      modules.sort_unstable();
+      let size = modules_sizes(modules.iter(), module_sizes)
+        .values()
+        .sum::<f64>();
+      // the navigations loading both save the duplicated bytes, every navigation needs one more request
+      let delta = -size * both + options.request_cost * (both + only_a + only_b);
+      if delta < -f64::EPSILON {
+        moves.push(ScoredMove {
+          delta,
+          versions,
+          candidate: CoLoadingMove::Split(a, b, modules),
+        });
+      }
+    }
+    moves
+  }
+
+  fn can_apply_co_loading_move(
+    compilation: &Compilation,
+    options: &CoLoadingOptions,
+    chunks: &UkeyMap<ChunkUkey, ProfiledChunk>,
+    module_sizes: &ModuleSizes,
+    candidate: &CoLoadingMove,
+  ) -> bool {
+    match candidate {
+      CoLoadingMove::Merge(a, b) => {
+        Self::can_merge_profiled_chunks(compilation, options, chunks, a, b)
+      }
+      CoLoadingMove::Split(a, b, modules) => Self::can_split_profiled_chunks(
+        compilation,
+        options,
+        a,
+        b,
+        modules,
+        &modules_sizes(modules.iter(), module_sizes),
+      ),
+    }
+  }
+
+  fn can_merge_profiled_chunks(
+    compilation: &Compilation,
+    options: &CoLoadingOptions,
+    chunks: &UkeyMap<ChunkUkey, ProfiledChunk>,
+    a: &ChunkUkey,
+    b: &ChunkUkey,
+  ) -> bool {
+    let mut sizes = chunks[a].sizes.clone();
+    sizes.add_by(&chunks[b].sizes);
+    !sizes.bigger_than(&options.max_async_size)
+      && compilation.chunk_graph.can_chunks_be_integrated(
+        a,
+        b,
+        &compilation.chunk_by_ukey,
+        &compilation.chunk_group_by_ukey,
+      )
+  }
+
+  fn can_split_profiled_chunks(
+    compilation: &Compilation,
+    options: &CoLoadingOptions,
+    a: &ChunkUkey,
+    b: &ChunkUkey,
+    modules: &[ModuleIdentifier],
+    sizes: &SplitChunkSizes,
+  ) -> bool {
+    if modules.is_empty() || sizes.smaller_than(&options.min_size) {
+      return false;
+    }
+    let chunk_graph = &compilation.chunk_graph;
+    // keep both chunks, otherwise the move is a merge
+    if [a, b]
+      .iter()
+      .any(|chunk| chunk_graph.get_number_of_chunk_modules(chunk) <= modules.len())
+    {
+      return false;
+    }
+
+    // every chunk group loading the chunks needs one more request
+    let chunk_by_ukey = &compilation.chunk_by_ukey;
+    let chunk_group_by_ukey = &compilation.chunk_group_by_ukey;
+    chunk_by_ukey
+      .expect_get(a)
+      .groups()
+      .iter()
+      .chain(chunk_by_ukey.expect_get(b).groups())
+      .map(|group| chunk_group_by_ukey.expect_get(group))
+      .all(|group| {
+        let max_requests = if group.is_initial() {
+          options.max_initial_requests
+        } else {
+          options.max_async_requests
+        };
+        (group.chunks.len() + 1) as f64 <= max_requests
+      })
+  }
+
+  fn merge_profiled_chunks(
+    compilation: &mut Compilation,
+    a: ChunkUkey,
+    b: ChunkUkey,
+    max_size_setting_map: &mut UkeyMap<ChunkUkey, MaxSizeSetting>,
+  ) {
+    let mut chunk_graph = std::mem::take(&mut compilation.chunk_graph);
+    let mut chunk_by_ukey = std::mem::take(&mut compilation.chunk_by_ukey);
+    let mut chunk_group_by_ukey = std::mem::take(&mut compilation.chunk_group_by_ukey);
+    chunk_graph.integrate_chunks(
+      &a,
+      &b,
+      &mut chunk_by_ukey,
+      &mut chunk_group_by_ukey,
+      compilation.get_module_graph(),
+    );
+    chunk_by_ukey.remove(&b);
+    compilation.chunk_graph = chunk_graph;
+    compilation.chunk_by_ukey = chunk_by_ukey;
+    compilation.chunk_group_by_ukey = chunk_group_by_ukey;
+
+    compilation.named_chunks.retain(|_, chunk| *chunk != b);
+    if let Some(name) = compilation.chunk_by_ukey.expect_get(&a).name() {
+      compilation.named_chunks.insert(name.to_string(), a);
+    }
+    if let Some(setting) = max_size_setting_map.remove(&b) {
+      max_size_setting_map.entry(a).or_insert(setting);
+    }
+
+    if let Some(mut mutations) = compilation.incremental.mutations_write() {
+      mutations.add(Mutation::ChunksIntegrate { to: a });
+      mutations.add(Mutation::ChunkRemove { chunk: b });
+    }
+  }
+
+  fn split_profiled_chunks(
+    compilation: &mut Compilation,
+    a: ChunkUkey,
+    b: ChunkUkey,
+    modules: &[ModuleIdentifier],
+  ) -> ChunkUkey {
+    let new_chunk = Compilation::add_chunk(&mut compilation.chunk_by_ukey);
+    if let Some(mut mutations) = compilation.incremental.mutations_write() {
+      mutations.add(Mutation::ChunkAdd { chunk: new_chunk });
+    }
+    *compilation
+      .chunk_by_ukey
+      .expect_get_mut(&new_chunk)
+      .chunk_reason_mut() = Some("split chunk (co-loading)".to_string());
+    compilation.chunk_graph.add_chunk(new_chunk);
+
+    compilation
+      .chunk_graph
+      .disconnect_chunks_and_modules(&[a, b], modules);
+    compilation
+      .chunk_graph
+      .connect_chunk_and_modules(new_chunk, modules);
+
+    for original_chunk_ukey in [a, b] {
+      let [Some(new_chunk_mut), Some(original_chunk)] = compilation
+        .chunk_by_ukey
+        .get_many_mut([&new_chunk, &original_chunk_ukey])
+      else {
+        panic!("split_profiled_chunks failed")
+      };
+      original_chunk.split(new_chunk_mut, &mut compilation.chunk_group_by_ukey);
+      if let Some(mut mutations) = compilation.incremental.mutations_write() {
+        mutations.add(Mutation::ChunkSplit {
+          from: original_chunk_ukey,
+          to: new_chunk,
+        });
+      }
+    }
+    new_chunk
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::BinaryHeap;
+
+  use rspack_core::ChunkUkey;
+  use rustc_hash::FxHashSet;
+
+  use super::{CoLoadingMove, ProfiledChunk, ScoredMove, co_loading_frequencies};
+  use crate::SplitChunkSizes;
+
+  fn profiled_chunk(load_sets: &[usize]) -> ProfiledChunk {
+    ProfiledChunk {
+      load_sets: load_sets.iter().copied().collect::<FxHashSet<_>>(),
+      sizes: SplitChunkSizes::empty(),
+      version: 0,
+    }
+  }
+
+  #[test]
+  fn should_compute_co_loading_frequencies() {
+    let frequencies = [5.0, 3.0, 2.0, 7.0];
+    let a = profiled_chunk(&[0, 1]);
+    let b = profiled_chunk(&[1, 2]);
+    assert_eq!(
+      co_loading_frequencies(&a, &b, &frequencies),
+      (3.0, 5.0, 2.0)
+    );
+
+    let c = profiled_chunk(&[3]);
+    assert_eq!(
+      co_loading_frequencies(&a, &c, &frequencies),
+      (0.0, 8.0, 7.0)
+    );
+  }
+
+  fn scored_move(delta: f64, candidate: CoLoadingMove) -> ScoredMove {
+    ScoredMove {
+      delta,
+      versions: (0, 0),
+      candidate,
+    }
+  }
+
+  #[test]
+  fn should_pop_the_best_move_first() {
+    let [a, b, c] = [1, 2, 3].map(ChunkUkey::from);
+    let mut queue = BinaryHeap::from([
+      scored_move(-10.0, CoLoadingMove::Split(a, b, vec![])),
+      scored_move(-20.0, CoLoadingMove::Merge(b, c)),
+      scored_move(-10.0, CoLoadingMove::Merge(a, c)),
+      scored_move(-10.0, CoLoadingMove::Merge(a, b)),
+    ]);
+    let order = std::iter::from_fn(|| queue.pop())
+      .map(|scored| (scored.delta, scored.key()))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      order,
+      vec![
+        (-20.0, (b, c, 0)),
+        (-10.0, (a, b, 0)),
+        (-10.0, (a, b, 1)),
+        (-10.0, (a, c, 0)),
+      ]
+    );
+  }
+}
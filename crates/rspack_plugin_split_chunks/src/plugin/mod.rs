@@ -1,4 +1,5 @@
 mod chunk;
+mod co_loading;
 mod max_request;
 mod max_size;
 mod min_size;
@@ -15,7 +16,7 @@ use rspack_util::{fx_hash::FxIndexMap, tracing_preset::TRACING_BENCH_TARGET};
 use tracing::instrument;
 
 use crate::{
-  CacheGroup, SplitChunkSizes,
+  CacheGroup, CoLoadingOptions, SplitChunkSizes,
   common::FallbackCacheGroup,
   module_group::{IndexedCacheGroup, ModuleGroup},
 };
@@ -27,6 +28,7 @@ pub struct PluginOptions {
   pub cache_groups: Vec<CacheGroup>,
   pub fallback_cache_group: FallbackCacheGroup,
   pub hide_path_info: Option<bool>,
+  pub co_loading: Option<CoLoadingOptions>,
 }
 
 #[plugin]
@@ -34,6 +36,7 @@ pub struct SplitChunksPlugin {
   cache_groups: Box<[CacheGroup]>,
   fallback_cache_group: FallbackCacheGroup,
   hide_path_info: bool,
+  co_loading: Option<CoLoadingOptions>,
 }
 
 impl SplitChunksPlugin {
@@ -43,6 +46,7 @@ impl SplitChunksPlugin {
       options.cache_groups.into(),
       options.fallback_cache_group,
       options.hide_path_info.unwrap_or(false),
+      options.co_loading,
     )
   }
   #[instrument(name = "Compilation:SplitChunks",target=TRACING_BENCH_TARGET, skip_all)]
@@ -266,6 +270,21 @@ impl SplitChunksPlugin {
     }
     logger.time_end(start);
 
+    if let Some(co_loading) = &self.co_loading {
+      let start = logger.time("ensure co-loading fit");
+      let stats = self.ensure_co_loading_fit(
+        compilation,
+        co_loading,
+        &module_sizes,
+        &mut max_size_setting_map,
+      );
+      logger.time_end(start);
+      logger.debug(format!(
+        "{} chunks merged and {} chunks split by the co-loading profile",
+        stats.merged, stats.split
+      ));
+    }
+
     let start = logger.time("ensure max size fit");
     self
       .ensure_max_size_fit(compilation, &max_size_setting_map)
@@ -1,9 +1,9 @@
-use std::sync::Arc;
+use std::{io::ErrorKind, sync::Arc};
 
 mod error;
 use error::FsResultToStorageFsResult;
 pub use error::{BatchFSError, BatchFSResult, FSError, FSOperation, FSResult};
-use rspack_fs::{FileMetadata, IntermediateFileSystem, ReadStream, WriteStream};
+use rspack_fs::{FileMetadata, IntermediateFileSystem, MemoryReadStream, ReadStream, WriteStream};
 use rspack_paths::{Utf8Path, Utf8PathBuf};
 use rustc_hash::FxHashSet as HashSet;
 
@@ -13,6 +13,8 @@ pub trait FileSystem: std::fmt::Debug + Sync + Send {
   async fn remove_dir(&self, path: &Utf8Path) -> FSResult<()>;
   async fn ensure_dir(&self, path: &Utf8Path) -> FSResult<()>;
   async fn write_file(&self, path: &Utf8Path) -> FSResult<Writer>;
+  /// Create a file with the contents only if it does not exist, returns false if it exists
+  async fn create_file(&self, path: &Utf8Path, data: &[u8]) -> FSResult<bool>;
   async fn read_file(&self, path: &Utf8Path) -> FSResult<Reader>;
   async fn read_dir(&self, path: &Utf8Path) -> FSResult<HashSet<String>>;
   async fn metadata(&self, path: &Utf8Path) -> FSResult<FileMetadata>;
@@ -71,6 +73,13 @@ pub struct Reader {
 }
 
 impl Reader {
+  /// Create a reader of the bytes which have been read from `path`
+  pub fn from_bytes(path: &Utf8Path, bytes: Vec<u8>) -> Self {
+    Self {
+      path: path.to_path_buf(),
+      stream: Box::new(MemoryReadStream::new(bytes)),
+    }
+  }
   pub async fn read_line(&mut self) -> FSResult<String> {
     self
       .stream
@@ -173,6 +182,22 @@ impl FileSystem for BridgeFileSystem {
     })
   }
 
+  async fn create_file(&self, path: &Utf8Path, data: &[u8]) -> FSResult<bool> {
+    self
+      .ensure_dir(path.parent().expect("should have parent"))
+      .await?;
+    match self
+      .0
+      .create_new(path, data)
+      .await
+      .to_storage_fs_result(path, FSOperation::Write)
+    {
+      Ok(()) => Ok(true),
+      Err(e) if matches!(e.kind(), ErrorKind::AlreadyExists) => Ok(false),
+      Err(e) => Err(e),
+    }
+  }
+
   async fn read_file(&self, path: &Utf8Path) -> FSResult<Reader> {
     let stream = self
       .0
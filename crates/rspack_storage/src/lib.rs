@@ -1,12 +1,19 @@
 mod error;
 mod fs;
 mod pack;
+mod remote;
 
 use std::sync::Arc;
 
 pub use error::Result;
 pub use fs::{BridgeFileSystem, FSError, FSOperation, FSResult, FileSystem, Reader, Writer};
-pub use pack::{PackStorage, PackStorageOptions};
+pub use pack::{
+  PackCompression, PackInspection, PackStatus, PackStorage, PackStorageInspector, PackStorageMode,
+  PackStorageOptions, PruneResult, ScopeInspection, VersionInspection,
+};
+pub use remote::{
+  BlobStore, HttpBlobStore, LocalBlobStore, RemotePackStorage, RemotePackStorageOptions,
+};
 use tokio::sync::oneshot::Receiver;
 
 type ItemKey = Vec<u8>;
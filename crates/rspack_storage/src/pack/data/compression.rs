@@ -0,0 +1,57 @@
+use std::fmt::Display;
+
+/// Codec of pack files
+///
+/// The codec of each pack is recorded in the scope meta, so packs written
+/// with different codecs can be loaded by the same storage.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PackCompression {
+  #[default]
+  None,
+  /// zstd with a level from 1 (fastest) to 22 (smallest), 0 means the default level
+  Zstd { level: i32 },
+  /// lz4 block format, it is faster than zstd with a lower ratio and has no levels
+  Lz4,
+}
+
+impl PackCompression {
+  pub fn is_none(&self) -> bool {
+    matches!(self, Self::None)
+  }
+
+  pub fn compress(&self, data: Vec<u8>) -> std::io::Result<Vec<u8>> {
+    match self {
+      Self::None => Ok(data),
+      Self::Zstd { level } => zstd::bulk::compress(&data, *level),
+      Self::Lz4 => Ok(lz4_flex::compress_prepend_size(&data)),
+    }
+  }
+
+  pub fn decompress(&self, data: Vec<u8>) -> std::io::Result<Vec<u8>> {
+    match self {
+      Self::None => Ok(data),
+      Self::Zstd { .. } => zstd::stream::decode_all(data.as_slice()),
+      Self::Lz4 => lz4_flex::decompress_size_prepended(&data).map_err(std::io::Error::other),
+    }
+  }
+
+  /// Parse the codec recorded in the scope meta, such as `zstd:3` or `lz4`
+  pub fn parse(s: &str) -> Option<Self> {
+    match s.split_once(':') {
+      Some(("zstd", level)) => level.parse().ok().map(|level| Self::Zstd { level }),
+      None if s == "lz4" => Some(Self::Lz4),
+      None if s == "none" => Some(Self::None),
+      _ => None,
+    }
+  }
+}
+
+impl Display for PackCompression {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::None => write!(f, "none"),
+      Self::Zstd { level } => write!(f, "zstd:{level}"),
+      Self::Lz4 => write!(f, "lz4"),
+    }
+  }
+}
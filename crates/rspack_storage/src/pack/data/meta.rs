@@ -2,7 +2,7 @@ use rspack_paths::{Utf8Path, Utf8PathBuf};
 use rspack_util::current_time;
 use rustc_hash::FxHashSet as HashSet;
 
-use super::options::PackOptions;
+use super::{compression::PackCompression, options::PackOptions};
 
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub struct PackFileMeta {
@@ -11,6 +11,7 @@ pub struct PackFileMeta {
   pub size: usize,
   pub wrote: bool,
   pub generation: usize,
+  pub compression: PackCompression,
 }
 
 #[derive(Debug, Default, Clone)]
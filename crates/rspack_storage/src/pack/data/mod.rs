@@ -1,10 +1,12 @@
+mod compression;
 mod meta;
 mod options;
 mod pack;
 mod scope;
 
+pub use compression::PackCompression;
 pub use meta::{PackFileMeta, RootMeta, RootMetaFrom, ScopeMeta};
-pub use options::{PackOptions, RootOptions};
+pub use options::{PackOptions, PackStorageMode, RootOptions};
 pub use pack::{Pack, PackContents, PackGenerations, PackKeys};
 pub use rspack_util::current_time;
 pub use scope::{PackScope, RootMetaState};
@@ -6,9 +6,33 @@ pub struct PackOptions {
   pub pack_size: usize,
 }
 
+/// Access mode of pack storage
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PackStorageMode {
+  /// Load the existing contents and save updates
+  #[default]
+  ReadWrite,
+  /// Load the existing contents, but never write, clean or expire anything.
+  ///
+  /// Parallel processes can share one pre-warmed storage with this mode.
+  ReadOnly,
+  /// Ignore the existing contents and save a fresh storage, which is used to seed a shared storage.
+  WriteOnly,
+}
+
+impl PackStorageMode {
+  pub fn readable(&self) -> bool {
+    !matches!(self, Self::WriteOnly)
+  }
+  pub fn writable(&self) -> bool {
+    !matches!(self, Self::ReadOnly)
+  }
+}
+
 #[derive(Debug)]
 pub struct RootOptions {
   pub root: Utf8PathBuf,
   pub expire: u64,
   pub clean: bool,
+  pub mode: PackStorageMode,
 }
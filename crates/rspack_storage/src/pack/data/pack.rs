@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use rspack_paths::Utf8PathBuf;
 
+use super::PackCompression;
 use crate::{ItemKey, ItemValue};
 
 pub type PackKeys = Vec<Arc<ItemKey>>;
@@ -91,6 +92,8 @@ pub struct Pack {
   pub keys: PackKeysState,
   pub contents: PackContentsState,
   pub generations: PackGenerations,
+  /// Codec of the pack file
+  pub compression: PackCompression,
 }
 
 impl Pack {
@@ -100,6 +103,7 @@ impl Pack {
       keys: Default::default(),
       contents: Default::default(),
       generations: Default::default(),
+      compression: Default::default(),
     }
   }
 
@@ -0,0 +1,339 @@
+use std::sync::Arc;
+
+use futures::future::join_all;
+use itertools::Itertools;
+use rspack_paths::{Utf8Path, Utf8PathBuf};
+
+use super::{
+  data::{PackCompression, PackFileMeta, ScopeMeta, current_time},
+  strategy::{PackReadStrategy, RootStrategy, SplitPackStrategy, read_scope_meta},
+};
+use crate::{FileSystem, error::Result};
+
+const LOCK_FILES: [&str; 2] = ["move.lock", "remove.lock"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackStatus {
+  /// The pack is not validated
+  Unchecked,
+  Valid,
+  /// The pack is listed in the scope meta but the file does not exist
+  Missing,
+  /// The pack can not be read or its hash does not match the scope meta
+  Modified,
+}
+
+impl PackStatus {
+  pub fn is_corrupted(&self) -> bool {
+    matches!(self, Self::Missing | Self::Modified)
+  }
+}
+
+impl std::fmt::Display for PackStatus {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Unchecked => write!(f, "unchecked"),
+      Self::Valid => write!(f, "valid"),
+      Self::Missing => write!(f, "missing"),
+      Self::Modified => write!(f, "modified"),
+    }
+  }
+}
+
+#[derive(Debug)]
+pub struct PackInspection {
+  pub path: Utf8PathBuf,
+  pub bucket_id: usize,
+  pub hash: String,
+  pub size: usize,
+  pub generation: usize,
+  pub compression: PackCompression,
+  pub status: PackStatus,
+}
+
+#[derive(Debug)]
+pub struct ScopeInspection {
+  pub name: String,
+  pub path: Utf8PathBuf,
+  pub bucket_size: usize,
+  pub pack_size: usize,
+  pub generation: usize,
+  pub packs: Vec<PackInspection>,
+  /// The reason why the scope meta can not be read
+  pub error: Option<String>,
+}
+
+impl ScopeInspection {
+  fn broken(name: &str, path: Utf8PathBuf, error: String) -> Self {
+    Self {
+      name: name.to_string(),
+      path,
+      bucket_size: 0,
+      pack_size: 0,
+      generation: 0,
+      packs: vec![],
+      error: Some(error),
+    }
+  }
+
+  /// Total size of packs recorded in the scope meta
+  pub fn size(&self) -> usize {
+    self.packs.iter().map(|pack| pack.size).sum()
+  }
+
+  pub fn is_corrupted(&self) -> bool {
+    self.error.is_some() || self.packs.iter().any(|pack| pack.status.is_corrupted())
+  }
+}
+
+#[derive(Debug)]
+pub struct VersionInspection {
+  pub version: String,
+  pub path: Utf8PathBuf,
+  pub expire_time: Option<u64>,
+  pub expired: bool,
+  /// Lock files of an unfinished save exist, they will be recovered by the next compilation
+  pub locked: bool,
+  pub scopes: Vec<ScopeInspection>,
+  /// The reason why the root meta can not be read
+  pub error: Option<String>,
+}
+
+impl VersionInspection {
+  pub fn size(&self) -> usize {
+    self.scopes.iter().map(|scope| scope.size()).sum()
+  }
+}
+
+#[derive(Debug, Default)]
+pub struct PruneResult {
+  pub removed_versions: Vec<String>,
+  /// Pairs of version and scope name
+  pub removed_scopes: Vec<(String, String)>,
+  pub removed_packs: Vec<Utf8PathBuf>,
+}
+
+/// Inspector of a `PackStorage` root which works without running a compilation
+///
+/// The root is the `root` of `PackStorageOptions`, each directory in it holds
+/// the packs of one version. It should not be used while a compilation is
+/// saving to the same root.
+#[derive(Debug)]
+pub struct PackStorageInspector {
+  root: Utf8PathBuf,
+  fs: Arc<dyn FileSystem>,
+}
+
+impl PackStorageInspector {
+  pub fn new(root: Utf8PathBuf, fs: Arc<dyn FileSystem>) -> Self {
+    Self { root, fs }
+  }
+
+  fn get_strategy(&self, version: &str) -> SplitPackStrategy {
+    // nothing is written to the temp root during inspection
+    SplitPackStrategy::new(
+      self.root.join(version),
+      self.root.join(".temp").join(version),
+      self.fs.clone(),
+      None,
+      None,
+      PackCompression::None,
+    )
+  }
+
+  /// List versions and scopes in the root, pack hashes are checked against
+  /// the scope meta when `validate` is true
+  pub async fn inspect(&self, validate: bool) -> Result<Vec<VersionInspection>> {
+    if !self.fs.exists(&self.root).await? {
+      return Ok(vec![]);
+    }
+
+    let mut res = vec![];
+    for version in self.fs.read_dir(&self.root).await?.into_iter().sorted() {
+      // skip hidden dirs such as the temp root
+      if version.starts_with('.') || version.contains(".lock") {
+        continue;
+      }
+      if !self
+        .fs
+        .metadata(&self.root.join(&version))
+        .await?
+        .is_directory
+      {
+        continue;
+      }
+      res.push(self.inspect_version(version, validate).await?);
+    }
+    Ok(res)
+  }
+
+  async fn inspect_version(&self, version: String, validate: bool) -> Result<VersionInspection> {
+    let strategy = self.get_strategy(&version);
+    let mut locked = false;
+    for lock_file in LOCK_FILES {
+      locked |= self.fs.exists(&strategy.root.join(lock_file)).await?;
+    }
+
+    let mut res = VersionInspection {
+      version,
+      path: (*strategy.root).clone(),
+      expire_time: None,
+      expired: false,
+      locked,
+      scopes: vec![],
+      error: None,
+    };
+
+    let root_meta = match strategy.read_root_meta().await {
+      Ok(Some(root_meta)) => root_meta,
+      Ok(None) => {
+        res.error = Some("`storage_meta` does not exist".to_string());
+        return Ok(res);
+      }
+      Err(e) => {
+        res.error = Some(e.to_string());
+        return Ok(res);
+      }
+    };
+    res.expire_time = Some(root_meta.expire_time);
+    res.expired = current_time() > root_meta.expire_time;
+
+    for name in root_meta
+      .scopes
+      .iter()
+      .filter(|name| !name.is_empty())
+      .sorted()
+    {
+      res
+        .scopes
+        .push(inspect_scope(&strategy, name, self.fs.clone(), validate).await);
+    }
+    Ok(res)
+  }
+
+  /// Remove expired versions and scopes with corrupted packs
+  ///
+  /// A scope is only consistent as a whole, so a corrupted scope is removed with
+  /// all of its packs, just like a compilation invalidates it. Versions with
+  /// lock files are skipped since the next compilation will recover them.
+  pub async fn prune(&self) -> Result<PruneResult> {
+    let mut res = PruneResult::default();
+    for version in self.inspect(true).await? {
+      if version.locked {
+        continue;
+      }
+
+      if version.expired || version.error.is_some() {
+        self.fs.remove_dir(&version.path).await?;
+        res.removed_versions.push(version.version);
+        continue;
+      }
+
+      let corrupted_scopes = version
+        .scopes
+        .into_iter()
+        .filter(|scope| scope.is_corrupted())
+        .collect_vec();
+      if corrupted_scopes.is_empty() {
+        continue;
+      }
+
+      // update the root meta first, the remaining scope dirs are unused and
+      // will be cleaned by the next compilation even if removing them failed
+      let strategy = self.get_strategy(&version.version);
+      let Some(mut root_meta) = strategy.read_root_meta().await? else {
+        continue;
+      };
+      for scope in &corrupted_scopes {
+        root_meta.scopes.remove(&scope.name);
+      }
+      strategy.write_root_meta(&root_meta).await?;
+
+      for scope in corrupted_scopes {
+        res.removed_packs.extend(
+          scope
+            .packs
+            .into_iter()
+            .filter(|pack| pack.status == PackStatus::Modified)
+            .map(|pack| pack.path),
+        );
+        self.fs.remove_dir(&scope.path).await?;
+        res
+          .removed_scopes
+          .push((version.version.clone(), scope.name));
+      }
+    }
+    Ok(res)
+  }
+}
+
+async fn inspect_scope(
+  strategy: &SplitPackStrategy,
+  name: &str,
+  fs: Arc<dyn FileSystem>,
+  validate: bool,
+) -> ScopeInspection {
+  let path = strategy.root.join(name);
+  let meta = match read_scope_meta(None, &ScopeMeta::get_path(&path), fs).await {
+    Ok(Some(meta)) => meta,
+    Ok(None) => {
+      return ScopeInspection::broken(name, path, "`scope_meta` does not exist".to_string());
+    }
+    Err(e) => return ScopeInspection::broken(name, path, e.to_string()),
+  };
+
+  let scope_path = &path;
+  let packs = join_all(
+    meta
+      .packs
+      .iter()
+      .enumerate()
+      .flat_map(|(bucket_id, bucket)| bucket.iter().map(move |pack_meta| (bucket_id, pack_meta)))
+      .map(|(bucket_id, pack_meta)| async move {
+        let path = scope_path.join(bucket_id.to_string()).join(&pack_meta.name);
+        let status = if validate {
+          validate_pack(strategy, &path, pack_meta).await
+        } else {
+          PackStatus::Unchecked
+        };
+        PackInspection {
+          path,
+          bucket_id,
+          hash: pack_meta.hash.clone(),
+          size: pack_meta.size,
+          generation: pack_meta.generation,
+          compression: pack_meta.compression,
+          status,
+        }
+      }),
+  )
+  .await;
+
+  ScopeInspection {
+    name: name.to_string(),
+    path,
+    bucket_size: meta.bucket_size,
+    pack_size: meta.pack_size,
+    generation: meta.generation,
+    packs,
+    error: None,
+  }
+}
+
+async fn validate_pack(
+  strategy: &SplitPackStrategy,
+  path: &Utf8Path,
+  pack_meta: &PackFileMeta,
+) -> PackStatus {
+  match strategy.read_pack_keys(path, pack_meta.compression).await {
+    Ok(Some(keys)) => match strategy
+      .get_pack_hash(path, &keys, &Default::default())
+      .await
+    {
+      Ok(res) if res == pack_meta.hash => PackStatus::Valid,
+      _ => PackStatus::Modified,
+    },
+    Ok(None) => PackStatus::Missing,
+    Err(_) => PackStatus::Modified,
+  }
+}
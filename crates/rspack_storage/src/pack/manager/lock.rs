@@ -0,0 +1,345 @@
+use std::{
+  sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+  },
+  time::Duration,
+};
+
+use rspack_paths::{Utf8Path, Utf8PathBuf};
+use tokio::{sync::Mutex, task::JoinHandle};
+
+use crate::{FileSystem, Result, pack::data::current_time};
+
+static LOCK_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Cross-process lock of the storage root
+///
+/// The lock file is created exclusively and records the owner and the time it was refreshed.
+/// The owner refreshes the time periodically while holding the lock, so a lock which is not
+/// refreshed for `stale_timeout` is considered to be left by a crashed process and can be taken over.
+#[derive(Debug)]
+pub struct ProcessLock {
+  path: Utf8PathBuf,
+  fs: Arc<dyn FileSystem>,
+  owner: String,
+  stale_timeout: u64,
+  wait_timeout: u64,
+  /// The number of holders in current process
+  holders: Mutex<usize>,
+  heartbeat: std::sync::Mutex<Option<JoinHandle<()>>>,
+}
+
+impl ProcessLock {
+  pub fn new(
+    root: &Utf8Path,
+    fs: Arc<dyn FileSystem>,
+    stale_timeout: u64,
+    wait_timeout: u64,
+  ) -> Self {
+    Self {
+      path: Self::get_path(root),
+      fs,
+      owner: format!(
+        "{}-{}-{}",
+        std::process::id(),
+        current_time(),
+        LOCK_ID.fetch_add(1, Ordering::Relaxed)
+      ),
+      stale_timeout,
+      wait_timeout,
+      holders: Mutex::new(0),
+      heartbeat: Default::default(),
+    }
+  }
+
+  pub fn get_path(root: &Utf8Path) -> Utf8PathBuf {
+    root.join("process.lock")
+  }
+
+  fn content(owner: &str) -> String {
+    format!("{owner}\n{}\n", current_time())
+  }
+
+  async fn read_owner(fs: &dyn FileSystem, path: &Utf8Path) -> Result<Option<(String, u64)>> {
+    if !fs.exists(path).await? {
+      return Ok(None);
+    }
+    let mut reader = fs.read_file(path).await?;
+    let owner = reader.read_line().await.unwrap_or_default();
+    let time = match reader.read_line().await.map(|line| line.parse::<u64>()) {
+      Ok(Ok(time)) => time,
+      // the lock file is created before its content is written,
+      // fall back to the modified time so a lock left empty by a crashed process still goes stale
+      _ => fs.metadata(path).await?.mtime_ms,
+    };
+    Ok(Some((owner, time)))
+  }
+
+  /// Rewrite the refresh time of the lock through a rename, so the lock file never disappears
+  async fn refresh(fs: &dyn FileSystem, path: &Utf8Path, owner: &str) -> Result<bool> {
+    if !matches!(Self::read_owner(fs, path).await?, Some((current, _)) if current == owner) {
+      return Ok(false);
+    }
+    let temp_path = path.with_extension(format!("lock.{owner}"));
+    let mut writer = fs.write_file(&temp_path).await?;
+    writer.write_all(Self::content(owner).as_bytes()).await?;
+    writer.flush().await?;
+    fs.move_file(&temp_path, path).await?;
+    Ok(true)
+  }
+
+  fn start_heartbeat(&self) {
+    let fs = self.fs.clone();
+    let path = self.path.clone();
+    let owner = self.owner.clone();
+    let interval = Duration::from_millis((self.stale_timeout / 4).max(10));
+    let handle = tokio::spawn(async move {
+      loop {
+        tokio::time::sleep(interval).await;
+        match Self::refresh(fs.as_ref(), &path, &owner).await {
+          Ok(true) => {}
+          // the lock is taken over or removed, nothing to refresh anymore
+          Ok(false) => break,
+          Err(e) => tracing::warn!("refresh storage lock failed: {e}"),
+        }
+      }
+    });
+    if let Some(prev) = self
+      .heartbeat
+      .lock()
+      .expect("should get lock")
+      .replace(handle)
+    {
+      prev.abort();
+    }
+  }
+
+  fn stop_heartbeat(&self) {
+    if let Some(handle) = self.heartbeat.lock().expect("should get lock").take() {
+      handle.abort();
+    }
+  }
+
+  fn is_stale(&self, time: u64) -> bool {
+    current_time().saturating_sub(time) >= self.stale_timeout
+  }
+
+  async fn held_by_others(&self) -> bool {
+    match Self::read_owner(self.fs.as_ref(), &self.path).await {
+      Ok(Some((owner, time))) => owner != self.owner && !self.is_stale(time),
+      Ok(None) => false,
+      // the lock file may be written by others at the same time, treat it as held
+      Err(_) => true,
+    }
+  }
+
+  /// Remove the lock file if it is left by a crashed process
+  ///
+  /// The lock is moved aside to a unique path before removing, so a lock taken by another
+  /// process right after the check is never removed by its path.
+  async fn remove_stale(&self) -> Result<()> {
+    let Some((owner, time)) = Self::read_owner(self.fs.as_ref(), &self.path).await? else {
+      return Ok(());
+    };
+    if !self.is_stale(time) {
+      return Ok(());
+    }
+    let stale_path = self
+      .path
+      .with_extension(format!("lock.stale.{}", self.owner));
+    if self.fs.move_file(&self.path, &stale_path).await.is_err() {
+      // another process has moved or removed it
+      return Ok(());
+    }
+    if let Some((moved_owner, moved_time)) = Self::read_owner(self.fs.as_ref(), &stale_path).await?
+      && (moved_owner != owner || !self.is_stale(moved_time))
+    {
+      // the lock was taken over before moving, put it back unless another lock is created
+      self
+        .fs
+        .create_file(
+          &self.path,
+          format!("{moved_owner}\n{moved_time}\n").as_bytes(),
+        )
+        .await?;
+    }
+    self.fs.remove_file(&stale_path).await?;
+    Ok(())
+  }
+
+  /// Wait `wait_timeout` for other processes to release the lock without taking it,
+  /// return false if the lock is still held by others.
+  pub async fn wait(&self) -> bool {
+    let start = current_time();
+    while self.held_by_others().await {
+      if current_time().saturating_sub(start) >= self.wait_timeout {
+        return false;
+      }
+      tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    true
+  }
+
+  /// Try to take the lock.
+  ///
+  /// It will wait `wait_timeout` for other processes to release the lock,
+  /// and return false if the lock is still held by others.
+  pub async fn acquire(&self) -> Result<bool> {
+    let mut holders = self.holders.lock().await;
+    if *holders > 0 {
+      *holders += 1;
+      return Ok(true);
+    }
+
+    let start = current_time();
+    loop {
+      // the exclusive creation fails if any other process holds the lock
+      if self
+        .fs
+        .create_file(&self.path, Self::content(&self.owner).as_bytes())
+        .await?
+      {
+        *holders = 1;
+        self.start_heartbeat();
+        return Ok(true);
+      }
+      self.remove_stale().await?;
+      if current_time().saturating_sub(start) >= self.wait_timeout {
+        return Ok(false);
+      }
+      tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+  }
+
+  /// Release the lock, the lock file is removed when all holders in current process release it.
+  pub async fn release(&self) -> Result<()> {
+    let mut holders = self.holders.lock().await;
+    *holders = holders.saturating_sub(1);
+    if *holders > 0 {
+      return Ok(());
+    }
+    self.stop_heartbeat();
+    if matches!(
+      Self::read_owner(self.fs.as_ref(), &self.path).await,
+      Ok(Some((owner, _))) if owner == self.owner
+    ) {
+      self.fs.remove_file(&self.path).await?;
+    }
+    Ok(())
+  }
+}
+
+impl Drop for ProcessLock {
+  fn drop(&mut self) {
+    self.stop_heartbeat();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use rspack_fs::MemoryFileSystem;
+  use rspack_paths::Utf8Path;
+
+  use super::ProcessLock;
+  use crate::{BridgeFileSystem, FileSystem, Result};
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn should_lock_between_processes() -> Result<()> {
+    let fs: Arc<dyn FileSystem> = Arc::new(BridgeFileSystem(Arc::new(MemoryFileSystem::default())));
+    let root = Utf8Path::new("/cache");
+    let lock_a = ProcessLock::new(root, fs.clone(), 60_000, 100);
+    let lock_b = ProcessLock::new(root, fs.clone(), 60_000, 100);
+
+    assert!(lock_a.acquire().await?);
+    // reentrant for the same owner
+    assert!(lock_a.acquire().await?);
+    assert!(!lock_b.acquire().await?);
+
+    lock_a.release().await?;
+    assert!(fs.exists(&ProcessLock::get_path(root)).await?);
+    assert!(!lock_b.acquire().await?);
+
+    lock_a.release().await?;
+    assert!(!fs.exists(&ProcessLock::get_path(root)).await?);
+    assert!(lock_b.acquire().await?);
+    lock_b.release().await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn should_take_over_stale_lock() -> Result<()> {
+    let fs: Arc<dyn FileSystem> = Arc::new(BridgeFileSystem(Arc::new(MemoryFileSystem::default())));
+    let root = Utf8Path::new("/cache");
+    let crashed = ProcessLock::new(root, fs.clone(), 0, 100);
+    let lock = ProcessLock::new(root, fs.clone(), 0, 100);
+
+    // the crashed process never releases the lock
+    assert!(crashed.acquire().await?);
+    drop(crashed);
+    assert!(lock.acquire().await?);
+    lock.release().await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn should_only_remove_stale_lock() -> Result<()> {
+    let fs: Arc<dyn FileSystem> = Arc::new(BridgeFileSystem(Arc::new(MemoryFileSystem::default())));
+    let root = Utf8Path::new("/cache");
+    let crashed = ProcessLock::new(root, fs.clone(), 60_000, 100);
+    let lock_a = ProcessLock::new(root, fs.clone(), 500, 100);
+    let lock_b = ProcessLock::new(root, fs.clone(), 60_000, 100);
+
+    assert!(crashed.acquire().await?);
+    drop(crashed);
+    tokio::time::sleep(std::time::Duration::from_millis(600)).await;
+    // the stale lock is moved aside and removed by one process, then taken by the other one
+    let (a, b) = tokio::join!(lock_a.remove_stale(), lock_b.acquire());
+    a?;
+    assert!(b?);
+    lock_a.remove_stale().await?;
+    assert!(!lock_a.wait().await);
+    assert_eq!(fs.read_dir(root).await?.len(), 1);
+
+    lock_b.release().await?;
+    assert!(!fs.exists(&ProcessLock::get_path(root)).await?);
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn should_acquire_exclusively() -> Result<()> {
+    let fs: Arc<dyn FileSystem> = Arc::new(BridgeFileSystem(Arc::new(MemoryFileSystem::default())));
+    let root = Utf8Path::new("/cache");
+    let lock_a = ProcessLock::new(root, fs.clone(), 60_000, 100);
+    let lock_b = ProcessLock::new(root, fs.clone(), 60_000, 100);
+
+    let (a, b) = tokio::join!(lock_a.acquire(), lock_b.acquire());
+    assert!(a? ^ b?);
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn should_keep_lock_fresh_while_held() -> Result<()> {
+    let fs: Arc<dyn FileSystem> = Arc::new(BridgeFileSystem(Arc::new(MemoryFileSystem::default())));
+    let root = Utf8Path::new("/cache");
+    let lock_a = ProcessLock::new(root, fs.clone(), 100, 50);
+    let lock_b = ProcessLock::new(root, fs.clone(), 100, 50);
+
+    assert!(lock_a.acquire().await?);
+    // longer than the stale timeout, the heartbeat keeps the lock alive
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+    assert!(!lock_b.acquire().await?);
+
+    lock_a.release().await?;
+    assert!(lock_b.acquire().await?);
+    lock_b.release().await?;
+    Ok(())
+  }
+}
@@ -1,9 +1,14 @@
+mod lock;
 mod queue;
 
-use std::sync::Arc;
+use std::sync::{
+  Arc,
+  atomic::{AtomicBool, Ordering},
+};
 
 use futures::future::join_all;
-use queue::TaskQueue;
+pub use lock::ProcessLock;
+pub(crate) use queue::TaskQueue;
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use tokio::sync::{Mutex, oneshot, oneshot::Receiver};
 
@@ -13,12 +18,17 @@ use super::{
   strategy::{ScopeStrategy, WriteScopeResult},
 };
 use crate::{
-  ItemPairs, Result,
+  FileSystem, ItemPairs, Result,
   error::{Error, ErrorType, ValidateResult},
 };
 
 type ScopeMap = HashMap<String, PackScope>;
 
+/// A lock not refreshed for 2 minutes is considered to be left by a crashed process
+const LOCK_STALE_TIMEOUT: u64 = 2 * 60 * 1000;
+/// Wait 10 seconds for other processes before falling back to read-only
+const LOCK_WAIT_TIMEOUT: u64 = 10 * 1000;
+
 #[derive(Debug)]
 pub struct ScopeManager {
   pub root_options: Arc<RootOptions>,
@@ -27,6 +37,12 @@ pub struct ScopeManager {
   pub scopes: Arc<Mutex<ScopeMap>>,
   pub root_meta: Arc<Mutex<RootMetaState>>,
   pub queue: TaskQueue,
+  /// The lock shared with other processes using the same root
+  pub lock: Arc<ProcessLock>,
+  /// Whether the storage falls back to read-only since the lock is held by another process
+  pub fallback_read_only: Arc<AtomicBool>,
+  /// The updates skipped while falling back to read-only, saved with the next updates
+  pub skipped_updates: Arc<Mutex<ScopeUpdates>>,
 }
 
 impl ScopeManager {
@@ -34,8 +50,17 @@ impl ScopeManager {
     root_options: Arc<RootOptions>,
     pack_options: Arc<PackOptions>,
     strategy: Arc<dyn ScopeStrategy>,
+    fs: Arc<dyn FileSystem>,
   ) -> Self {
     ScopeManager {
+      lock: Arc::new(ProcessLock::new(
+        &root_options.root,
+        fs,
+        LOCK_STALE_TIMEOUT,
+        LOCK_WAIT_TIMEOUT,
+      )),
+      fallback_read_only: Default::default(),
+      skipped_updates: Default::default(),
       root_options,
       pack_options,
       strategy,
@@ -45,7 +70,19 @@ impl ScopeManager {
     }
   }
 
+  pub fn writable(&self) -> bool {
+    self.root_options.mode.writable() && !self.fallback_read_only.load(Ordering::Relaxed)
+  }
+
   pub fn save(&self, updates: ScopeUpdates) -> Result<Receiver<Result<()>>> {
+    if !self.root_options.mode.writable() {
+      let (tx, rx) = oneshot::channel();
+      let _ = tx.send(Ok(()));
+      return Ok(rx);
+    }
+    let lock = self.lock.clone();
+    let fallback_read_only = self.fallback_read_only.clone();
+    let skipped_updates = self.skipped_updates.clone();
     let strategy = self.strategy.clone();
     let scopes = self.scopes.clone();
     let root_meta = self.root_meta.clone();
@@ -53,46 +90,40 @@ impl ScopeManager {
     let root_options = self.root_options.clone();
     let (tx, rx) = oneshot::channel();
     self.queue.add_task(Box::pin(async move {
-      let mut scopes_lock = scopes.lock().await;
-      match update_scopes(
-        &mut scopes_lock,
-        updates,
-        pack_options.clone(),
-        strategy.as_ref(),
-      )
-      .await
-      {
-        Ok(_) => {
-          *root_meta.lock().await = RootMetaState::Value(Some(RootMeta::new(
-            scopes_lock
-              .iter()
-              .filter(|(_, scope)| scope.loaded())
-              .map(|(name, _)| name.clone())
-              .collect::<HashSet<_>>(),
-            root_options.expire,
-          )));
+      // the later updates override the skipped ones
+      let mut skipped = skipped_updates.lock().await;
+      for (scope, scope_update) in updates {
+        skipped.entry(scope).or_default().extend(scope_update);
+      }
+      // another process is writing the same root, skip saving instead of corrupting it
+      match lock.acquire().await {
+        Ok(true) => {
+          fallback_read_only.store(false, Ordering::Relaxed);
+        }
+        Ok(false) => {
+          // keep the updates until the lock is released by the other process
+          fallback_read_only.store(true, Ordering::Relaxed);
+          let _ = tx.send(Ok(()));
+          return;
         }
         Err(e) => {
           let _ = tx.send(Err(e));
           return;
         }
-      };
-
-      let root_meta = root_meta
-        .lock()
-        .await
-        .expect_value()
-        .clone()
-        .expect("should have root meta");
-      let old_scopes = std::mem::take(&mut *scopes_lock);
-      let res = save_scopes(old_scopes, &root_meta, strategy.as_ref(), &root_options).await;
-      let _ = match res {
-        Ok(new_scopes) => {
-          let _ = std::mem::replace(&mut *scopes_lock, new_scopes);
-          tx.send(Ok(()))
-        }
-        Err(e) => tx.send(Err(e)),
-      };
+      }
+      let updates = std::mem::take(&mut *skipped);
+      drop(skipped);
+      let res = save_with_lock(
+        &scopes,
+        &root_meta,
+        updates,
+        &pack_options,
+        &root_options,
+        strategy.as_ref(),
+      )
+      .await;
+      let res = res.and(lock.release().await);
+      let _ = tx.send(res);
     }));
 
     Ok(rx)
@@ -122,9 +153,37 @@ impl ScopeManager {
         )
       });
 
+    // ignore the existing contents and save a fresh scope
+    if !self.root_options.mode.readable() {
+      let mut root_meta = self.root_meta.lock().await;
+      if matches!(*root_meta, RootMetaState::Pending) {
+        *root_meta = RootMetaState::Value(None);
+      }
+      drop(root_meta);
+      self.clear_scope(name).await;
+      return Ok(vec![]);
+    }
+
+    // wait for other processes which are writing the same root
+    let acquired = if self.root_options.mode.writable() {
+      let acquired = self.lock.acquire().await?;
+      self.fallback_read_only.store(!acquired, Ordering::Relaxed);
+      acquired
+    } else {
+      self.lock.wait().await;
+      false
+    };
+    let res = self.load_scope(name).await;
+    if acquired {
+      self.lock.release().await?;
+    }
+    res
+  }
+
+  async fn load_scope(&self, name: &'static str) -> Result<ItemPairs> {
     // only check lock file and root meta for the first time
     if matches!(*self.root_meta.lock().await, RootMetaState::Pending) {
-      match self.strategy.before_load().await {
+      match self.before_load().await {
         Ok(()) => {
           let loaded = self.strategy.read_root_meta().await?;
           *self.root_meta.lock().await = RootMetaState::Value(loaded);
@@ -203,9 +262,20 @@ impl ScopeManager {
     }
   }
 
+  /// Recover the storage, read-only storage can only check it.
+  async fn before_load(&self) -> Result<()> {
+    if self.writable() {
+      self.strategy.before_load().await
+    } else {
+      self.strategy.check_lock().await
+    }
+  }
+
   pub async fn reset(&self) {
     // remove directory
-    self.strategy.reset().await;
+    if self.writable() {
+      self.strategy.reset().await;
+    }
     // reset fields
     self.scopes.lock().await.clear();
     *self.root_meta.lock().await = RootMetaState::Pending;
@@ -213,9 +283,12 @@ impl ScopeManager {
 
   /// Get list of all available scopes in the storage
   pub async fn scopes(&self) -> Result<Vec<String>> {
+    if !self.root_options.mode.readable() {
+      return Ok(vec![]);
+    }
     // only check lock file and root meta for the first time
     if matches!(*self.root_meta.lock().await, RootMetaState::Pending) {
-      match self.strategy.before_load().await {
+      match self.before_load().await {
         Ok(()) => {
           let loaded = self.strategy.read_root_meta().await?;
           *self.root_meta.lock().await = RootMetaState::Value(loaded);
@@ -236,6 +309,33 @@ impl ScopeManager {
   }
 }
 
+/// Update and save scopes, the process lock should be held
+async fn save_with_lock(
+  scopes: &Mutex<ScopeMap>,
+  root_meta: &Mutex<RootMetaState>,
+  updates: ScopeUpdates,
+  pack_options: &Arc<PackOptions>,
+  root_options: &RootOptions,
+  strategy: &dyn ScopeStrategy,
+) -> Result<()> {
+  let mut scopes_lock = scopes.lock().await;
+  update_scopes(&mut scopes_lock, updates, pack_options.clone(), strategy).await?;
+  let new_root_meta = RootMeta::new(
+    scopes_lock
+      .iter()
+      .filter(|(_, scope)| scope.loaded())
+      .map(|(name, _)| name.clone())
+      .collect::<HashSet<_>>(),
+    root_options.expire,
+  );
+  *root_meta.lock().await = RootMetaState::Value(Some(new_root_meta.clone()));
+
+  let old_scopes = std::mem::take(&mut *scopes_lock);
+  let new_scopes = save_scopes(old_scopes, &new_root_meta, strategy, root_options).await?;
+  let _ = std::mem::replace(&mut *scopes_lock, new_scopes);
+  Ok(())
+}
+
 #[tracing::instrument("Cache::Storage::update_scopes", skip_all)]
 async fn update_scopes(
   scopes: &mut ScopeMap,
@@ -334,8 +434,8 @@ mod tests {
     BridgeFileSystem, FileSystem, ItemKey, ItemValue,
     error::Result,
     pack::{
-      data::{PackOptions, RootOptions},
-      manager::ScopeManager,
+      data::{PackCompression, PackOptions, PackStorageMode, RootOptions},
+      manager::{ProcessLock, ScopeManager},
       strategy::SplitPackStrategy,
     },
   };
@@ -371,6 +471,7 @@ mod tests {
       expire: 60000,
       root: root.parent().expect("should get parent").to_path_buf(),
       clean: true,
+      mode: PackStorageMode::ReadWrite,
     });
     let pack_options = Arc::new(PackOptions {
       bucket_size: 10,
@@ -383,8 +484,9 @@ mod tests {
       fs.clone(),
       Some(1),
       Some(2),
+      PackCompression::None,
     ));
-    let manager = ScopeManager::new(root_options, pack_options, strategy);
+    let manager = ScopeManager::new(root_options, pack_options, strategy, fs.clone());
 
     // start with empty
     assert!(manager.load("scope1").await?.is_empty());
@@ -425,6 +527,7 @@ mod tests {
       expire: 60000,
       root: root.parent().expect("should get parent").to_path_buf(),
       clean: true,
+      mode: PackStorageMode::ReadWrite,
     });
     let pack_options = Arc::new(PackOptions {
       bucket_size: 10,
@@ -437,8 +540,9 @@ mod tests {
       fs.clone(),
       Some(1),
       Some(2),
+      PackCompression::None,
     ));
-    let manager = ScopeManager::new(root_options, pack_options, strategy);
+    let manager = ScopeManager::new(root_options, pack_options, strategy, fs.clone());
 
     // read from files
     assert_eq!(manager.load("scope1").await?.len(), 100);
@@ -498,6 +602,7 @@ mod tests {
       expire: 60000,
       root: root.parent().expect("should get parent").to_path_buf(),
       clean: true,
+      mode: PackStorageMode::ReadWrite,
     });
     let pack_options = Arc::new(PackOptions {
       // different bucket size
@@ -511,8 +616,14 @@ mod tests {
       fs.clone(),
       Some(1),
       Some(2),
+      PackCompression::None,
     ));
-    let manager = ScopeManager::new(root_options.clone(), pack_options.clone(), strategy.clone());
+    let manager = ScopeManager::new(
+      root_options.clone(),
+      pack_options.clone(),
+      strategy.clone(),
+      fs.clone(),
+    );
     // should report error when invalid failed
     assert_eq!(
       manager.load("scope1").await.unwrap_err().to_string(),
@@ -534,7 +645,7 @@ mod tests {
     // assert_eq!(manager.load("scope1").await?.len(), 100);
 
     // // will override cache files to new one
-    // let manager2 = ScopeManager::new(root_options, pack_options, strategy);
+    // let manager2 = ScopeManager::new(root_options, pack_options, strategy, fs.clone());
     // assert_eq!(manager2.load("scope1").await?.len(), 100);
 
     Ok(())
@@ -545,6 +656,7 @@ mod tests {
       expire: 60000,
       root: root.parent().expect("should get parent").to_path_buf(),
       clean: true,
+      mode: PackStorageMode::ReadWrite,
     });
     let pack_options = Arc::new(PackOptions {
       bucket_size: 100,
@@ -557,8 +669,9 @@ mod tests {
       fs.clone(),
       Some(1),
       Some(2),
+      PackCompression::None,
     ));
-    let manager = ScopeManager::new(root_options, pack_options, strategy);
+    let manager = ScopeManager::new(root_options, pack_options, strategy, fs.clone());
 
     // read from files
     assert_eq!(manager.load("scope1").await?.len(), 100);
@@ -586,4 +699,76 @@ mod tests {
     test_manager().await?;
     Ok(())
   }
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn should_save_after_lock_released() -> Result<()> {
+    let fs: Arc<dyn FileSystem> = Arc::new(BridgeFileSystem(Arc::new(MemoryFileSystem::default())));
+    let root = Utf8PathBuf::from("/cache/test_lock_released");
+    let temp = Utf8PathBuf::from("/temp/test_lock_released");
+    let root_options = Arc::new(RootOptions {
+      expire: 60000,
+      root: root.parent().expect("should get parent").to_path_buf(),
+      clean: true,
+      mode: PackStorageMode::ReadWrite,
+    });
+    let pack_options = Arc::new(PackOptions {
+      bucket_size: 10,
+      pack_size: 500,
+    });
+    let strategy = Arc::new(SplitPackStrategy::new(
+      root.clone(),
+      temp,
+      fs.clone(),
+      Some(1),
+      Some(2),
+      PackCompression::None,
+    ));
+    let manager = ScopeManager {
+      // wait shortly for the lock held by the other process
+      lock: Arc::new(ProcessLock::new(
+        &root_options.root,
+        fs.clone(),
+        60_000,
+        100,
+      )),
+      ..ScopeManager::new(root_options.clone(), pack_options, strategy, fs.clone())
+    };
+    let other = ProcessLock::new(&root_options.root, fs.clone(), 60_000, 100);
+
+    assert!(manager.load("scope1").await?.is_empty());
+    assert!(other.acquire().await?);
+    let mut scope_updates = HashMap::default();
+    scope_updates.insert(
+      "scope1",
+      (0..50)
+        .map(|i| (mock_key(i), mock_insert_value(i)))
+        .collect::<HashMap<_, _>>(),
+    );
+    manager
+      .save(scope_updates)?
+      .await
+      .unwrap_or_else(|e| panic!("save failed: {e:?}"))?;
+    // nothing is saved while the other process holds the lock
+    assert!(!manager.writable());
+    assert!(!(fs.exists(root.join("scope1/scope_meta").as_path()).await?));
+
+    other.release().await?;
+    let mut scope_updates = HashMap::default();
+    scope_updates.insert(
+      "scope1",
+      (50..100)
+        .map(|i| (mock_key(i), mock_insert_value(i)))
+        .collect::<HashMap<_, _>>(),
+    );
+    manager
+      .save(scope_updates)?
+      .await
+      .unwrap_or_else(|e| panic!("save failed: {e:?}"))?;
+    // the skipped updates are saved together once the lock is taken again
+    assert!(manager.writable());
+    assert!(fs.exists(root.join("scope1/scope_meta").as_path()).await?);
+    assert_eq!(manager.load("scope1").await?.len(), 100);
+    Ok(())
+  }
 }
@@ -1,4 +1,5 @@
 mod data;
+mod inspect;
 mod manager;
 mod strategy;
 
@@ -7,8 +8,13 @@ use std::{
   sync::{Arc, Mutex},
 };
 
+pub use data::{PackCompression, PackStorageMode};
 use data::{PackOptions, RootOptions};
+pub use inspect::{
+  PackInspection, PackStatus, PackStorageInspector, PruneResult, ScopeInspection, VersionInspection,
+};
 use manager::ScopeManager;
+pub(crate) use manager::TaskQueue;
 use rspack_paths::AssertUtf8;
 use rustc_hash::FxHashMap as HashMap;
 use strategy::{ScopeUpdate, SplitPackStrategy};
@@ -34,6 +40,9 @@ pub struct PackStorageOptions {
   pub clean: bool,
   pub fresh_generation: Option<usize>,
   pub release_generation: Option<usize>,
+  /// Codec of new packs, packs written with other codecs can still be loaded
+  pub compression: PackCompression,
+  pub mode: PackStorageMode,
 }
 
 impl PackStorage {
@@ -44,6 +53,7 @@ impl PackStorage {
           root: options.root.clone().assert_utf8(),
           expire: options.expire,
           clean: options.clean,
+          mode: options.mode,
         }),
         Arc::new(PackOptions {
           bucket_size: options.bucket_size,
@@ -52,10 +62,12 @@ impl PackStorage {
         Arc::new(SplitPackStrategy::new(
           options.root.join(&options.version).assert_utf8(),
           options.temp_root.join(&options.version).assert_utf8(),
-          options.fs,
+          options.fs.clone(),
           options.fresh_generation,
           options.release_generation,
+          options.compression,
         )),
+        options.fs,
       ),
       updates: Default::default(),
     }
@@ -68,11 +80,18 @@ impl Storage for PackStorage {
     self.manager.load(name).await
   }
   fn set(&self, scope: &'static str, key: ItemKey, value: ItemValue) {
+    // the updates of read-only storage will never be saved
+    if !self.manager.root_options.mode.writable() {
+      return;
+    }
     let mut updates = self.updates.lock().expect("should get lock");
     let scope_update = updates.entry(scope).or_default();
     scope_update.insert(key, Some(value));
   }
   fn remove(&self, scope: &'static str, key: &[u8]) {
+    if !self.manager.root_options.mode.writable() {
+      return;
+    }
     let mut updates = self.updates.lock().expect("should get lock");
     let scope_update = updates.entry(scope).or_default();
     scope_update.insert(key.to_vec(), None);
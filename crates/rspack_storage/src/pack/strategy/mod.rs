@@ -3,11 +3,11 @@ mod split;
 use async_trait::async_trait;
 use rspack_paths::{Utf8Path, Utf8PathBuf};
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
-pub use split::SplitPackStrategy;
+pub use split::{SplitPackStrategy, read_scope_meta};
 
 use super::data::{
-  Pack, PackContents, PackFileMeta, PackGenerations, PackKeys, PackOptions, PackScope, RootMeta,
-  RootOptions,
+  Pack, PackCompression, PackContents, PackFileMeta, PackGenerations, PackKeys, PackOptions,
+  PackScope, RootMeta, RootOptions,
 };
 use crate::{
   ItemKey, ItemValue,
@@ -35,6 +35,8 @@ pub trait ScopeStrategy:
 #[async_trait]
 pub trait RootStrategy {
   async fn before_load(&self) -> Result<()>;
+  /// Check the storage is complete without recovering it, used by read-only storage.
+  async fn check_lock(&self) -> Result<()>;
   async fn read_root_meta(&self) -> Result<Option<RootMeta>>;
   async fn write_root_meta(&self, root_meta: &RootMeta) -> Result<()>;
   async fn validate_root(&self, root_meta: &RootMeta) -> Result<ValidateResult>;
@@ -55,8 +57,21 @@ pub struct PackMainContents {
 
 #[async_trait]
 pub trait PackReadStrategy {
-  async fn read_pack_keys(&self, path: &Utf8Path) -> Result<Option<PackKeys>>;
-  async fn read_pack_contents(&self, path: &Utf8Path) -> Result<Option<PackMainContents>>;
+  async fn read_pack_keys(
+    &self,
+    path: &Utf8Path,
+    compression: PackCompression,
+  ) -> Result<Option<PackKeys>>;
+  async fn read_pack_contents(
+    &self,
+    path: &Utf8Path,
+    compression: PackCompression,
+  ) -> Result<Option<PackMainContents>>;
+  async fn read_pack(
+    &self,
+    path: &Utf8Path,
+    compression: PackCompression,
+  ) -> Result<Option<(PackKeys, PackMainContents)>>;
 }
 
 #[async_trait]
@@ -16,6 +16,7 @@ use handle_file::{
   remove_unused_scopes,
 };
 use itertools::Itertools;
+pub use read_scope::read_scope_meta;
 use rspack_paths::{Utf8Path, Utf8PathBuf};
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet, FxHasher};
 use util::get_name;
@@ -26,7 +27,8 @@ use crate::{
   error::{Result, ValidateResult},
   fs::{FSError, FSOperation},
   pack::data::{
-    PackContents, PackKeys, PackScope, RootMeta, RootMetaFrom, RootOptions, current_time,
+    PackCompression, PackContents, PackKeys, PackScope, RootMeta, RootMetaFrom, RootOptions,
+    current_time,
   },
 };
 
@@ -37,6 +39,8 @@ pub struct SplitPackStrategy {
   pub temp_root: Arc<Utf8PathBuf>,
   pub fresh_generation: Option<usize>,
   pub release_generation: Option<usize>,
+  /// Codec of the packs to be written, existing packs keep their own codec until they are rewritten
+  pub compression: PackCompression,
 }
 
 impl SplitPackStrategy {
@@ -46,6 +50,7 @@ impl SplitPackStrategy {
     fs: Arc<dyn FileSystem>,
     fresh_generation: Option<usize>,
     release_generation: Option<usize>,
+    compression: PackCompression,
   ) -> Self {
     Self {
       fs,
@@ -53,6 +58,7 @@ impl SplitPackStrategy {
       temp_root: Arc::new(temp_root),
       fresh_generation,
       release_generation,
+      compression,
     }
   }
 
@@ -78,8 +84,27 @@ impl SplitPackStrategy {
 #[async_trait::async_trait]
 impl RootStrategy for SplitPackStrategy {
   async fn before_load(&self) -> Result<()> {
-    recovery_remove_lock(&self.root, &self.temp_root, self.fs.clone()).await?;
+    // the old files are removed after the new files are moved, recover in the same order
     recovery_move_lock(&self.root, &self.temp_root, self.fs.clone()).await?;
+    recovery_remove_lock(&self.root, &self.temp_root, self.fs.clone()).await?;
+    Ok(())
+  }
+  async fn check_lock(&self) -> Result<()> {
+    for lock in ["remove.lock", "move.lock"] {
+      let lock_file = self.root.join(lock);
+      if self.fs.exists(&lock_file).await? {
+        return Err(
+          FSError::from_message(
+            &lock_file,
+            FSOperation::Read,
+            format!(
+              "incomplete storage due to `{lock}`, it can not be recovered in read-only mode"
+            ),
+          )
+          .into(),
+        );
+      }
+    }
     Ok(())
   }
   async fn read_root_meta(&self) -> Result<Option<RootMeta>> {
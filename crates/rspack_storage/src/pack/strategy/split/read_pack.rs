@@ -5,116 +5,131 @@ use rspack_paths::Utf8Path;
 
 use super::SplitPackStrategy;
 use crate::{
-  FSError, FSOperation,
+  FSError, FSOperation, Reader,
   error::Result,
   pack::{
-    data::PackKeys,
+    data::{PackCompression, PackKeys},
     strategy::{PackMainContents, PackReadStrategy},
   },
 };
 
-#[async_trait]
-impl PackReadStrategy for SplitPackStrategy {
-  async fn read_pack_keys(&self, path: &Utf8Path) -> Result<Option<PackKeys>> {
-    if !self.fs.exists(path).await? {
-      return Ok(None);
-    }
+/// The lines before the keys and the contents of a pack file
+struct PackHeader {
+  key_lengths: Vec<usize>,
+  content_lengths: Vec<usize>,
+  generations: Vec<usize>,
+}
 
-    let mut reader = self.fs.read_file(path).await?;
-    // read key lengths
-    let key_lengths = reader
-      .read_line()
-      .await?
+fn parse_line(path: &Utf8Path, line: String, name: &str) -> Result<Vec<usize>> {
+  Ok(
+    line
       .split(" ")
       .map(|item| {
         item.parse::<usize>().map_err(|e| {
           FSError::from_message(
             path,
             FSOperation::Read,
-            format!("parse pack key lengths failed: {e}"),
+            format!("parse pack {name} failed: {e}"),
           )
         })
       })
-      .collect::<std::result::Result<Vec<_>, FSError>>()?;
+      .collect::<std::result::Result<Vec<_>, FSError>>()?,
+  )
+}
 
-    // skip content lengths
-    reader.read_line().await?;
-    // skip generations
-    reader.read_line().await?;
+async fn read_header(path: &Utf8Path, reader: &mut Reader) -> Result<PackHeader> {
+  Ok(PackHeader {
+    key_lengths: parse_line(path, reader.read_line().await?, "key lengths")?,
+    content_lengths: parse_line(path, reader.read_line().await?, "content lengths")?,
+    generations: parse_line(path, reader.read_line().await?, "generations")?,
+  })
+}
+
+async fn read_items(reader: &mut Reader, lengths: &[usize]) -> Result<Vec<Arc<Vec<u8>>>> {
+  let mut items = Vec::with_capacity(lengths.len());
+  for len in lengths {
+    items.push(Arc::new(reader.read(*len).await?));
+  }
+  Ok(items)
+}
 
-    // read keys
-    let mut keys = vec![];
-    for key_len in key_lengths {
-      keys.push(Arc::new(reader.read(key_len).await?));
+impl SplitPackStrategy {
+  /// Open the pack file, compressed packs are decompressed into memory at once
+  async fn open_pack(&self, path: &Utf8Path, compression: PackCompression) -> Result<Reader> {
+    let mut reader = self.fs.read_file(path).await?;
+    if compression.is_none() {
+      return Ok(reader);
     }
-    Ok(Some(keys))
+    let bytes = compression
+      .decompress(reader.read_to_end().await?)
+      .map_err(|e| {
+        FSError::from_message(
+          path,
+          FSOperation::Read,
+          format!("decompress pack with `{compression}` failed: {e}"),
+        )
+      })?;
+    Ok(Reader::from_bytes(path, bytes))
   }
+}
 
-  async fn read_pack_contents(&self, path: &Utf8Path) -> Result<Option<PackMainContents>> {
+#[async_trait]
+impl PackReadStrategy for SplitPackStrategy {
+  async fn read_pack_keys(
+    &self,
+    path: &Utf8Path,
+    compression: PackCompression,
+  ) -> Result<Option<PackKeys>> {
     if !self.fs.exists(path).await? {
       return Ok(None);
     }
 
-    let mut reader = self.fs.read_file(path).await?;
-    let total_key_length = reader
-      .read_line()
-      .await?
-      .split(" ")
-      .map(|item| {
-        item.parse::<usize>().map_err(|e| {
-          FSError::from_message(
-            path,
-            FSOperation::Read,
-            format!("parse pack key lengths failed: {e}"),
-          )
-        })
-      })
-      .collect::<std::result::Result<Vec<_>, FSError>>()?
-      .iter()
-      .sum::<usize>();
-
-    let content_lengths: Vec<usize> = reader
-      .read_line()
-      .await?
-      .split(" ")
-      .map(|item| {
-        item.parse::<usize>().map_err(|e| {
-          FSError::from_message(
-            path,
-            FSOperation::Read,
-            format!("parse pack content lengths failed: {e}"),
-          )
-        })
-      })
-      .collect::<std::result::Result<Vec<_>, FSError>>()?;
-
-    let generations: Vec<usize> = reader
-      .read_line()
-      .await?
-      .split(" ")
-      .map(|item| {
-        item.parse::<usize>().map_err(|e| {
-          FSError::from_message(
-            path,
-            FSOperation::Read,
-            format!("parse pack generations failed: {e}"),
-          )
-        })
-      })
-      .collect::<std::result::Result<Vec<_>, FSError>>()?;
-
-    reader.skip(total_key_length).await?;
+    let mut reader = self.open_pack(path, compression).await?;
+    let header = read_header(path, &mut reader).await?;
+    Ok(Some(read_items(&mut reader, &header.key_lengths).await?))
+  }
 
-    let mut contents = vec![];
-    for len in content_lengths {
-      contents.push(Arc::new(reader.read(len).await?));
+  async fn read_pack_contents(
+    &self,
+    path: &Utf8Path,
+    compression: PackCompression,
+  ) -> Result<Option<PackMainContents>> {
+    if !self.fs.exists(path).await? {
+      return Ok(None);
     }
 
+    let mut reader = self.open_pack(path, compression).await?;
+    let header = read_header(path, &mut reader).await?;
+    reader
+      .skip(header.key_lengths.iter().sum::<usize>())
+      .await?;
     Ok(Some(PackMainContents {
-      contents,
-      generations,
+      contents: read_items(&mut reader, &header.content_lengths).await?,
+      generations: header.generations,
     }))
   }
+
+  async fn read_pack(
+    &self,
+    path: &Utf8Path,
+    compression: PackCompression,
+  ) -> Result<Option<(PackKeys, PackMainContents)>> {
+    if !self.fs.exists(path).await? {
+      return Ok(None);
+    }
+
+    let mut reader = self.open_pack(path, compression).await?;
+    let header = read_header(path, &mut reader).await?;
+    let keys = read_items(&mut reader, &header.key_lengths).await?;
+    let contents = read_items(&mut reader, &header.content_lengths).await?;
+    Ok(Some((
+      keys,
+      PackMainContents {
+        contents,
+        generations: header.generations,
+      },
+    )))
+  }
 }
 
 #[cfg(test)]
@@ -125,15 +140,21 @@ mod tests {
 
   use crate::{
     error::Result,
-    pack::strategy::{
-      PackReadStrategy, ScopeReadStrategy, SplitPackStrategy,
-      split::util::test_pack_utils::{clean_strategy, create_strategies, mock_pack_file},
+    pack::{
+      data::PackCompression,
+      strategy::{
+        PackReadStrategy, ScopeReadStrategy, SplitPackStrategy,
+        split::util::test_pack_utils::{clean_strategy, create_strategies, mock_pack_file},
+      },
     },
   };
 
   async fn test_read_keys_non_exists(strategy: &SplitPackStrategy) -> Result<()> {
     let non_exists_keys = strategy
-      .read_pack_keys(&strategy.get_path("/non_exists_path"))
+      .read_pack_keys(
+        &strategy.get_path("/non_exists_path"),
+        PackCompression::None,
+      )
       .await?;
     assert!(non_exists_keys.is_none());
     Ok(())
@@ -141,7 +162,10 @@ mod tests {
 
   async fn test_read_contents_non_exists(strategy: &SplitPackStrategy) -> Result<()> {
     let non_exists_contents = strategy
-      .read_pack_contents(&strategy.get_path("/non_exists_path"))
+      .read_pack_contents(
+        &strategy.get_path("/non_exists_path"),
+        PackCompression::None,
+      )
       .await?;
     assert!(non_exists_contents.is_none());
     Ok(())
@@ -149,7 +173,7 @@ mod tests {
 
   async fn test_read_keys(path: &Utf8PathBuf, strategy: &SplitPackStrategy) -> Result<()> {
     let keys = strategy
-      .read_pack_keys(path)
+      .read_pack_keys(path, PackCompression::None)
       .await?
       .unwrap_or_default()
       .into_iter()
@@ -162,7 +186,7 @@ mod tests {
 
   async fn test_read_contents(path: &Utf8PathBuf, strategy: &SplitPackStrategy) -> Result<()> {
     let contents = strategy
-      .read_pack_contents(path)
+      .read_pack_contents(path, PackCompression::None)
       .await?
       .unwrap_or_default()
       .contents
@@ -174,6 +198,22 @@ mod tests {
     Ok(())
   }
 
+  async fn test_read_pack(path: &Utf8PathBuf, strategy: &SplitPackStrategy) -> Result<()> {
+    let (keys, contents) = strategy
+      .read_pack(path, PackCompression::None)
+      .await?
+      .expect("should read pack");
+    assert_eq!(keys.len(), 20);
+    assert_eq!(contents.contents.len(), 20);
+    assert_eq!(contents.generations.len(), 20);
+    let pos = keys
+      .iter()
+      .position(|key| key.as_slice() == "key_mock_19".as_bytes())
+      .expect("should have key");
+    assert_eq!(contents.contents[pos].as_slice(), "val_mock_19".as_bytes());
+    Ok(())
+  }
+
   #[tokio::test]
   #[cfg_attr(miri, ignore)]
   async fn should_read_pack() -> Result<()> {
@@ -185,6 +225,7 @@ mod tests {
         .expect("should mock pack file");
       test_read_keys(&dir.join("./mock_pack"), &strategy).await?;
       test_read_contents(&dir.join("./mock_pack"), &strategy).await?;
+      test_read_pack(&dir.join("./mock_pack"), &strategy).await?;
       test_read_keys_non_exists(&strategy).await?;
       test_read_contents_non_exists(&strategy).await?;
     }
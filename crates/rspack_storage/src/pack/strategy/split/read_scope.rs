@@ -10,7 +10,7 @@ use crate::{
   FileSystem,
   error::{Error, ErrorType, Result},
   pack::{
-    data::{Pack, PackFileMeta, PackKeys, PackScope, ScopeMeta},
+    data::{Pack, PackCompression, PackFileMeta, PackKeys, PackScope, ScopeMeta},
     strategy::{PackMainContents, PackReadStrategy, ScopeReadStrategy},
   },
 };
@@ -20,7 +20,7 @@ impl ScopeReadStrategy for SplitPackStrategy {
   async fn ensure_meta(&self, scope: &mut PackScope) -> Result<()> {
     if !scope.meta.loaded() {
       let meta_path = ScopeMeta::get_path(&scope.path);
-      let meta = read_scope_meta(scope.name, &meta_path, self.fs.clone())
+      let meta = read_scope_meta(Some(scope.name), &meta_path, self.fs.clone())
         .await?
         .unwrap_or_else(|| ScopeMeta::new(&scope.path, &scope.options));
       scope.meta.set_value(meta);
@@ -43,7 +43,11 @@ impl ScopeReadStrategy for SplitPackStrategy {
             let bucket_path = scope.path.join(bucket_id.to_string());
             bucket_pack_metas
               .iter()
-              .map(|pack_meta| Pack::new(bucket_path.join(&pack_meta.name)))
+              .map(|pack_meta| {
+                let mut pack = Pack::new(bucket_path.join(&pack_meta.name));
+                pack.compression = pack_meta.compression;
+                pack
+              })
               .collect_vec()
           })
           .collect_vec(),
@@ -63,6 +67,10 @@ impl ScopeReadStrategy for SplitPackStrategy {
         .and_then(|packs| packs.get_mut(result.pack_pos))
       {
         pack.keys.set_value(result.keys);
+        if let Some(contents) = result.contents {
+          pack.contents.set_value(contents.contents);
+          pack.generations = contents.generations;
+        }
       }
     }
     Ok(())
@@ -90,8 +98,8 @@ impl ScopeReadStrategy for SplitPackStrategy {
   }
 }
 
-async fn read_scope_meta(
-  scope: &'static str,
+pub async fn read_scope_meta(
+  scope: Option<&'static str>,
   path: &Utf8Path,
   fs: Arc<dyn FileSystem>,
 ) -> Result<Option<ScopeMeta>> {
@@ -109,7 +117,7 @@ async fn read_scope_meta(
       item.parse::<usize>().map_err(|e| {
         Error::from_reason(
           Some(ErrorType::Load),
-          Some(scope),
+          scope,
           format!("parse option meta failed: {e}"),
         )
       })
@@ -119,7 +127,7 @@ async fn read_scope_meta(
   if option_items.len() < 3 {
     return Err(Error::from_reason(
       Some(ErrorType::Load),
-      Some(scope),
+      scope,
       "option meta not match".to_string(),
     ));
   }
@@ -138,10 +146,10 @@ async fn read_scope_meta(
         .filter(|i| !i.is_empty())
         .map(|i| i.split(",").collect::<Vec<_>>())
         .map(|i| {
-          if i.len() < 3 {
+          if i.len() < 4 {
             Err(Error::from_reason(
               Some(ErrorType::Load),
-              Some(scope),
+              scope,
               "file meta not match".to_string(),
             ))
           } else {
@@ -151,17 +159,28 @@ async fn read_scope_meta(
               size: i[2].parse::<usize>().map_err(|e| {
                 Error::from_reason(
                   Some(ErrorType::Load),
-                  Some(scope),
+                  scope,
                   format!("parse file meta failed: {e}"),
                 )
               })?,
               generation: i[3].parse::<usize>().map_err(|e| {
                 Error::from_reason(
                   Some(ErrorType::Load),
-                  Some(scope),
+                  scope,
                   format!("parse file meta failed: {e}"),
                 )
               })?,
+              // the codec is omitted for uncompressed packs
+              compression: match i.get(4) {
+                Some(codec) => PackCompression::parse(codec).ok_or_else(|| {
+                  Error::from_reason(
+                    Some(ErrorType::Load),
+                    scope,
+                    format!("unknown pack compression `{codec}`"),
+                  )
+                })?,
+                None => PackCompression::None,
+              },
               wrote: true,
             })
           }
@@ -173,7 +192,7 @@ async fn read_scope_meta(
   if packs.len() < bucket_size {
     return Err(Error::from_reason(
       Some(ErrorType::Load),
-      Some(scope),
+      scope,
       "bucket size not match".to_string(),
     ));
   }
@@ -192,6 +211,7 @@ struct ReadKeysResult {
   pub bucket_id: usize,
   pub pack_pos: usize,
   pub keys: PackKeys,
+  pub contents: Option<PackMainContents>,
 }
 
 fn read_keys_filter(pack: &Pack, _: &PackFileMeta) -> bool {
@@ -206,8 +226,27 @@ async fn read_keys(scope: &PackScope, strategy: &SplitPackStrategy) -> Result<Ve
     .map(|i| {
       let strategy = strategy.clone();
       let path = i.1.path.clone();
-      tokio::spawn(async move { strategy.read_pack_keys(&path).await })
-        .map_err(|e| Error::from_error(Some(ErrorType::Load), Some(scope.name), Box::new(e)))
+      let compression = i.0.compression;
+      tokio::spawn(async move {
+        // the whole compressed pack is decompressed to reach the keys,
+        // so keep its contents instead of decompressing it again later
+        if compression.is_none() {
+          Ok::<_, Error>(
+            strategy
+              .read_pack_keys(&path, compression)
+              .await?
+              .map(|keys| (keys, None)),
+          )
+        } else {
+          Ok(
+            strategy
+              .read_pack(&path, compression)
+              .await?
+              .map(|(keys, contents)| (keys, Some(contents))),
+          )
+        }
+      })
+      .map_err(|e| Error::from_error(Some(ErrorType::Load), Some(scope.name), Box::new(e)))
     })
     .collect_vec();
 
@@ -221,10 +260,11 @@ async fn read_keys(scope: &PackScope, strategy: &SplitPackStrategy) -> Result<Ve
     pack_keys
       .into_iter()
       .zip(pack_indexes.into_iter())
-      .map(|(keys, (bucket_id, pack_pos))| ReadKeysResult {
+      .map(|((keys, contents), (bucket_id, pack_pos))| ReadKeysResult {
         bucket_id,
         pack_pos,
         keys,
+        contents,
       })
       .collect_vec(),
   )
@@ -251,7 +291,8 @@ async fn read_contents(
     .map(|i| {
       let strategy = strategy.to_owned();
       let path = i.1.path.clone();
-      tokio::spawn(async move { strategy.read_pack_contents(&path).await })
+      let compression = i.0.compression;
+      tokio::spawn(async move { strategy.read_pack_contents(&path, compression).await })
         .map_err(|e| Error::from_error(Some(ErrorType::Load), Some(scope.name), Box::new(e)))
     })
     .collect_vec();
@@ -83,7 +83,7 @@ pub mod test_pack_utils {
     BridgeFileSystem, FileSystem,
     error::Result,
     pack::{
-      data::{PackOptions, PackScope, current_time},
+      data::{PackCompression, PackOptions, PackScope, current_time},
       strategy::{
         ScopeUpdate, ScopeWriteStrategy, SplitPackStrategy, WriteScopeResult,
         split::handle_file::prepare_scope,
@@ -297,6 +297,7 @@ pub mod test_pack_utils {
           fs,
           Some(1_usize),
           Some(2_usize),
+          PackCompression::None,
         )
       })
       .collect_vec()
@@ -12,7 +12,7 @@ use crate::{
   error::Result,
   pack::{
     ScopeUpdate,
-    data::{Pack, PackFileMeta, PackOptions},
+    data::{Pack, PackCompression, PackFileMeta, PackOptions},
     strategy::{PackReadStrategy, PackWriteStrategy, UpdatePacksResult, split::util::get_name},
   },
 };
@@ -53,7 +53,7 @@ impl PackWriteStrategy for SplitPackStrategy {
         acc
       });
     Ok(UpdatePacksResult {
-      new_packs: create(&dir, options, items),
+      new_packs: create(&dir, options, self.compression, items),
       remain_packs: vec![],
       removed_files,
     })
@@ -163,7 +163,7 @@ impl PackWriteStrategy for SplitPackStrategy {
 
     // generate packs
     let remain_packs = indexed_packs.into_values().collect_vec();
-    let new_packs: Vec<(PackFileMeta, Pack)> = create(&dir, options, items);
+    let new_packs: Vec<(PackFileMeta, Pack)> = create(&dir, options, self.compression, items);
 
     Ok(UpdatePacksResult {
       new_packs,
@@ -181,45 +181,58 @@ impl PackWriteStrategy for SplitPackStrategy {
       panic!("pack keys and contents length not match");
     }
 
-    let mut writer = self.fs.write_file(&path).await?;
+    let mut bytes = vec![];
 
     // key meta line
-    writer
-      .write_line(
-        keys
-          .iter()
-          .map(|key| key.len().to_string())
-          .join(" ")
-          .as_str(),
-      )
-      .await?;
+    bytes.extend(
+      keys
+        .iter()
+        .map(|key| key.len().to_string())
+        .join(" ")
+        .as_bytes(),
+    );
+    bytes.push(b'\n');
 
     // content meta line
-    writer
-      .write_line(
-        contents
-          .iter()
-          .map(|content| content.len().to_string())
-          .join(" ")
-          .as_str(),
-      )
-      .await?;
+    bytes.extend(
+      contents
+        .iter()
+        .map(|content| content.len().to_string())
+        .join(" ")
+        .as_bytes(),
+    );
+    bytes.push(b'\n');
 
     // content generation line
-    writer
-      .write_line(generations.iter().map(|g| g.to_string()).join(" ").as_str())
-      .await?;
+    bytes.extend(
+      generations
+        .iter()
+        .map(|g| g.to_string())
+        .join(" ")
+        .as_bytes(),
+    );
+    bytes.push(b'\n');
 
     // keys blob
     for key in keys {
-      writer.write(key).await?;
+      bytes.extend(key.as_slice());
     }
 
     // contents blob
     for content in contents {
-      writer.write(content).await?;
+      bytes.extend(content.as_slice());
     }
 
+    let bytes = pack.compression.compress(bytes).map_err(|e| {
+      FSError::from_message(
+        &path,
+        FSOperation::Write,
+        format!("compress pack with `{}` failed: {e}", pack.compression),
+      )
+    })?;
+
+    let mut writer = self.fs.write_file(&path).await?;
+    writer.write_all(&bytes).await?;
     writer.flush().await?;
 
     Ok(())
@@ -237,7 +250,10 @@ async fn reload_released_packs(
   let mut res = join_all(released_packs.into_iter().map(|mut pack| {
     let strategy = strategy.to_owned();
     async move {
-      match strategy.read_pack_contents(&pack.path).await {
+      match strategy
+        .read_pack_contents(&pack.path, pack.compression)
+        .await
+      {
         Ok(contents) => {
           if let Some(contents) = contents {
             pack.contents.set_value(contents.contents);
@@ -296,6 +312,7 @@ fn pour_pack(items: &mut HashSet<PackItemCandidate>, pack: &mut Pack) {
 fn create(
   dir: &Utf8Path,
   options: &PackOptions,
+  compression: PackCompression,
   candidates: HashSet<PackItemCandidate>,
 ) -> Vec<(PackFileMeta, Pack)> {
   let candidate_sizes = candidates
@@ -315,7 +332,7 @@ fn create(
   let mut new_packs = vec![];
 
   for item in big_items {
-    new_packs.push(create_pack(dir, vec![item.0]));
+    new_packs.push(create_pack(dir, compression, vec![item.0]));
   }
 
   items.sort_unstable_by(|(a, _), (b, _)| b.generation.cmp(&a.generation));
@@ -339,7 +356,7 @@ fn create(
     }
 
     if !batch_items.is_empty() {
-      new_packs.push(create_pack(dir, batch_items));
+      new_packs.push(create_pack(dir, compression, batch_items));
     }
 
     if items.is_empty() {
@@ -350,7 +367,11 @@ fn create(
   new_packs
 }
 
-fn create_pack(dir: &Utf8Path, candidates: Vec<PackItemCandidate>) -> (PackFileMeta, Pack) {
+fn create_pack(
+  dir: &Utf8Path,
+  compression: PackCompression,
+  candidates: Vec<PackItemCandidate>,
+) -> (PackFileMeta, Pack) {
   let mut keys = vec![];
   let mut contents = vec![];
   let mut generations = vec![];
@@ -369,6 +390,7 @@ fn create_pack(dir: &Utf8Path, candidates: Vec<PackItemCandidate>) -> (PackFileM
   new_pack.keys.set_value(keys);
   new_pack.contents.set_value(contents);
   new_pack.generations = generations;
+  new_pack.compression = compression;
   (
     PackFileMeta {
       name: file_name,
@@ -376,6 +398,7 @@ fn create_pack(dir: &Utf8Path, candidates: Vec<PackItemCandidate>) -> (PackFileM
       size: new_pack.size(),
       wrote: false,
       generation: latest_generation,
+      compression,
     },
     new_pack,
   )
@@ -16,7 +16,7 @@ use crate::{
   error::Result,
   fs::BatchFSError,
   pack::{
-    data::{Pack, PackScope},
+    data::{Pack, PackScope, ScopeMeta},
     strategy::{PackWriteStrategy, ScopeUpdate, ScopeWriteStrategy, WriteScopeResult},
   },
 };
@@ -28,19 +28,11 @@ impl ScopeWriteStrategy for SplitPackStrategy {
     Ok(())
   }
 
+  /// Commit the written files to the scope directories.
+  ///
+  /// A scope is switched by renaming its meta instead of swapping the whole directory,
+  /// since the unchanged packs stay in place and a non-empty directory can't be replaced by a rename.
   async fn merge_changed(&self, changed: WriteScopeResult) -> Result<()> {
-    // remove files with `.lock`
-    write_lock(
-      "remove.lock",
-      &changed.wrote_files,
-      &self.root,
-      &self.temp_root,
-      self.fs.clone(),
-    )
-    .await?;
-    remove_files(changed.removed_files, self.fs.clone()).await?;
-    remove_lock("remove.lock", &self.root, self.fs.clone()).await?;
-
     // move files with `.lock`
     write_lock(
       "move.lock",
@@ -50,14 +42,35 @@ impl ScopeWriteStrategy for SplitPackStrategy {
       self.fs.clone(),
     )
     .await?;
-    move_files(
-      changed.wrote_files,
+    // the scope meta is the commit point of a scope, it is moved by a rename after
+    // all of the packs are in place, so the scope is switched to the new one at once.
+    let (metas, packs): (HashSet<_>, HashSet<_>) =
+      changed.wrote_files.iter().cloned().partition(|path| {
+        path
+          .parent()
+          .is_some_and(|dir| ScopeMeta::get_path(dir) == *path)
+      });
+    move_files(packs, &self.root, &self.temp_root, self.fs.clone()).await?;
+    move_files(metas, &self.root, &self.temp_root, self.fs.clone()).await?;
+    remove_lock("move.lock", &self.root, self.fs.clone()).await?;
+
+    // the old packs are only removed after the new metas are committed,
+    // so a crash never leaves a meta pointing to removed packs
+    let removed_files = changed
+      .removed_files
+      .into_iter()
+      .filter(|path| !changed.wrote_files.contains(path))
+      .collect::<HashSet<_>>();
+    write_lock(
+      "remove.lock",
+      &removed_files,
       &self.root,
       &self.temp_root,
       self.fs.clone(),
     )
     .await?;
-    remove_lock("move.lock", &self.root, self.fs.clone()).await?;
+    remove_files(removed_files, self.fs.clone()).await?;
+    remove_lock("remove.lock", &self.root, self.fs.clone()).await?;
 
     self.fs.remove_dir(&self.temp_root).await?;
     Ok(())
@@ -314,10 +327,16 @@ impl ScopeWriteStrategy for SplitPackStrategy {
           packs
             .iter()
             .map(|meta| {
-              format!(
+              let line = format!(
                 "{},{},{},{}",
                 meta.name, meta.hash, meta.size, meta.generation
-              )
+              );
+              // keep the meta of uncompressed packs readable by older versions
+              if meta.compression.is_none() {
+                line
+              } else {
+                format!("{line},{}", meta.compression)
+              }
             })
             .join(" ")
         })
@@ -0,0 +1,220 @@
+use std::{hash::Hasher, sync::Arc};
+
+use itertools::Itertools;
+use rspack_hash::{HashDigest, HashFunction, RspackHash};
+use rspack_util::current_time;
+use rustc_hash::FxHashMap as HashMap;
+
+use crate::{
+  ItemKey, ItemPairs, ItemValue,
+  error::{Error, ErrorType, Result, ValidateResult},
+};
+
+/// Scope entry of the remote meta, packs are referenced by content hash
+#[derive(Debug, Default, Clone)]
+pub struct RemoteScopeMeta {
+  pub pack_size: usize,
+  pub packs: Vec<String>,
+}
+
+/// Meta of a storage version in the remote store
+#[derive(Debug, Default, Clone)]
+pub struct RemoteMeta {
+  pub version: String,
+  pub expire_time: u64,
+  pub scopes: HashMap<String, RemoteScopeMeta>,
+}
+
+impl RemoteMeta {
+  pub fn new(version: &str, expire: u64) -> Self {
+    Self {
+      version: version.to_string(),
+      expire_time: current_time() + expire,
+      scopes: Default::default(),
+    }
+  }
+
+  pub fn get_key(version: &str) -> String {
+    format!("{version}/storage_meta")
+  }
+
+  pub fn validate(&self, version: &str) -> ValidateResult {
+    if self.version != version {
+      return ValidateResult::invalid("`version` changed");
+    }
+    if current_time() > self.expire_time {
+      return ValidateResult::invalid("expiration");
+    }
+    ValidateResult::Valid
+  }
+
+  pub fn encode(&self) -> Vec<u8> {
+    let mut lines = vec![self.version.clone(), self.expire_time.to_string()];
+    for (name, scope) in self.scopes.iter().sorted_by(|a, b| a.0.cmp(b.0)) {
+      lines.push(format!(
+        "{} {} {}",
+        name,
+        scope.pack_size,
+        scope.packs.join(",")
+      ));
+    }
+    // the content hash on the first line detects a truncated or modified meta
+    let content = lines.join("\n");
+    format!("{}\n{content}", get_content_hash(content.as_bytes())).into_bytes()
+  }
+
+  pub fn decode(bytes: &[u8]) -> Result<Self> {
+    let parse_error = |reason: String| {
+      Error::from_reason(
+        Some(ErrorType::Load),
+        None,
+        format!("parse remote meta failed: {reason}"),
+      )
+    };
+
+    let (hash, content) = std::str::from_utf8(bytes)
+      .map_err(|e| parse_error(e.to_string()))?
+      .split_once('\n')
+      .ok_or_else(|| parse_error("missing content hash".to_string()))?;
+    if get_content_hash(content.as_bytes()) != hash {
+      return Err(parse_error("content hash mismatch".to_string()));
+    }
+    let mut lines = content.split('\n');
+    let version = lines
+      .next()
+      .ok_or_else(|| parse_error("missing version".to_string()))?
+      .to_string();
+    let expire_time = lines
+      .next()
+      .ok_or_else(|| parse_error("missing expire time".to_string()))?
+      .parse::<u64>()
+      .map_err(|e| parse_error(e.to_string()))?;
+
+    let mut scopes = HashMap::default();
+    for line in lines.filter(|line| !line.is_empty()) {
+      let mut items = line.split(' ');
+      let (Some(name), Some(pack_size), Some(packs)) = (items.next(), items.next(), items.next())
+      else {
+        return Err(parse_error(format!("illegal scope line `{line}`")));
+      };
+      scopes.insert(
+        name.to_string(),
+        RemoteScopeMeta {
+          pack_size: pack_size
+            .parse::<usize>()
+            .map_err(|e| parse_error(e.to_string()))?,
+          packs: packs
+            .split(',')
+            .filter(|hash| !hash.is_empty())
+            .map(|hash| hash.to_string())
+            .collect_vec(),
+        },
+      );
+    }
+
+    Ok(Self {
+      version,
+      expire_time,
+      scopes,
+    })
+  }
+}
+
+/// The prefix of the packs shared by all versions
+pub const PACKS_PREFIX: &str = "packs";
+
+pub fn get_pack_key(hash: &str) -> String {
+  format!("{PACKS_PREFIX}/{hash}")
+}
+
+pub fn get_content_hash(bytes: &[u8]) -> String {
+  let mut hasher = RspackHash::new(&HashFunction::Xxhash3_128);
+  hasher.write(bytes);
+  hasher.digest(&HashDigest::Hex).encoded().to_string()
+}
+
+/// Encode items with the same layout as the pack files of `SplitPackStrategy`,
+/// except that the generations line is omitted.
+pub fn encode_pack(items: &[(Arc<ItemKey>, Arc<ItemValue>)]) -> Vec<u8> {
+  let mut res = vec![];
+  res.extend(
+    items
+      .iter()
+      .map(|(key, _)| key.len())
+      .join(" ")
+      .into_bytes(),
+  );
+  res.push(b'\n');
+  res.extend(
+    items
+      .iter()
+      .map(|(_, value)| value.len())
+      .join(" ")
+      .into_bytes(),
+  );
+  res.push(b'\n');
+  for (key, _) in items {
+    res.extend_from_slice(key);
+  }
+  for (_, value) in items {
+    res.extend_from_slice(value);
+  }
+  res
+}
+
+pub fn decode_pack(bytes: &[u8]) -> Option<ItemPairs> {
+  fn read_lengths<'a>(bytes: &'a [u8]) -> Option<(Vec<usize>, &'a [u8])> {
+    let end = bytes.iter().position(|b| *b == b'\n')?;
+    let lengths = std::str::from_utf8(&bytes[..end])
+      .ok()?
+      .split(' ')
+      .map(|item| item.parse::<usize>().ok())
+      .collect::<Option<Vec<_>>>()?;
+    Some((lengths, &bytes[end + 1..]))
+  }
+
+  let (key_lengths, rest) = read_lengths(bytes)?;
+  let (value_lengths, mut rest) = read_lengths(rest)?;
+  if key_lengths.len() != value_lengths.len() {
+    return None;
+  }
+
+  let mut keys = vec![];
+  for len in key_lengths {
+    let (key, next) = rest.split_at_checked(len)?;
+    keys.push(Arc::new(key.to_vec()));
+    rest = next;
+  }
+  let mut res = vec![];
+  for (key, len) in keys.into_iter().zip(value_lengths) {
+    let (value, next) = rest.split_at_checked(len)?;
+    res.push((key, Arc::new(value.to_vec())));
+    rest = next;
+  }
+  rest.is_empty().then_some(res)
+}
+
+/// Split items into packs of about `pack_size` bytes.
+///
+/// Items are sorted by key so that the same contents always produce the same
+/// packs, which lets unchanged packs be shared by content hash.
+pub fn split_packs(mut items: ItemPairs, pack_size: usize) -> Vec<Vec<u8>> {
+  items.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+  let mut packs = vec![];
+  let mut current = vec![];
+  let mut current_size = 0;
+  for item in items {
+    current_size += item.0.len() + item.1.len();
+    current.push(item);
+    if current_size >= pack_size {
+      packs.push(encode_pack(&current));
+      current.clear();
+      current_size = 0;
+    }
+  }
+  if !current.is_empty() {
+    packs.push(encode_pack(&current));
+  }
+  packs
+}
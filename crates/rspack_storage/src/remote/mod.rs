@@ -0,0 +1,397 @@
+mod data;
+mod store;
+
+use std::sync::{Arc, Mutex};
+
+use data::{
+  PACKS_PREFIX, RemoteMeta, RemoteScopeMeta, decode_pack, get_content_hash, get_pack_key,
+  split_packs,
+};
+use futures::future::join_all;
+use itertools::Itertools;
+use rspack_util::current_time;
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+pub use store::{BlobStore, HttpBlobStore, LocalBlobStore};
+use tokio::sync::oneshot::{self, Receiver};
+
+use crate::{
+  ItemKey, ItemPairs, ItemValue, Storage,
+  error::{Error, ErrorType, InvalidDetail, Result, ValidateResult},
+  pack::{ScopeUpdates, TaskQueue},
+};
+
+pub struct RemotePackStorageOptions {
+  pub store: Arc<dyn BlobStore>,
+  pub version: String,
+  pub pack_size: usize,
+  pub expire: u64,
+}
+
+#[derive(Debug)]
+struct RemoteOptions {
+  version: String,
+  pack_size: usize,
+  expire: u64,
+}
+
+#[derive(Debug, Default)]
+enum RemoteMetaState {
+  #[default]
+  Pending,
+  Value(Option<RemoteMeta>),
+  Unreachable,
+}
+
+#[derive(Debug, Default)]
+struct RemoteState {
+  meta: RemoteMetaState,
+  scopes: HashMap<&'static str, HashMap<Arc<ItemKey>, Arc<ItemValue>>>,
+  /// The stale blobs are cleaned at the first save
+  cleaned: bool,
+}
+
+/// Storage that keeps packs in a content-addressed blob store
+///
+/// Packs are stored as `packs/<hash>` and shared by all versions, the
+/// `<version>/storage_meta` blob lists the packs of each scope. When the
+/// store is unreachable, the storage behaves as an empty one and skips
+/// saving, so the compilation falls back to a cold build.
+#[derive(Debug)]
+pub struct RemotePackStorage {
+  options: Arc<RemoteOptions>,
+  store: Arc<dyn BlobStore>,
+  state: Arc<tokio::sync::Mutex<RemoteState>>,
+  updates: Mutex<ScopeUpdates>,
+  queue: TaskQueue,
+}
+
+impl RemotePackStorage {
+  pub fn new(options: RemotePackStorageOptions) -> Self {
+    Self {
+      options: Arc::new(RemoteOptions {
+        version: options.version,
+        pack_size: options.pack_size,
+        expire: options.expire,
+      }),
+      store: options.store,
+      state: Default::default(),
+      updates: Default::default(),
+      queue: TaskQueue::new(),
+    }
+  }
+}
+
+async fn ensure_meta(
+  state: &mut RemoteState,
+  options: &RemoteOptions,
+  store: &dyn BlobStore,
+) -> Result<()> {
+  if !matches!(state.meta, RemoteMetaState::Pending) {
+    return Ok(());
+  }
+  match store.get(&RemoteMeta::get_key(&options.version)).await {
+    Ok(Some(bytes)) => match RemoteMeta::decode(&bytes) {
+      Ok(meta) => state.meta = RemoteMetaState::Value(Some(meta)),
+      Err(e) => {
+        state.meta = RemoteMetaState::Value(None);
+        return Err(e);
+      }
+    },
+    Ok(None) => state.meta = RemoteMetaState::Value(None),
+    Err(e) => {
+      state.meta = RemoteMetaState::Unreachable;
+      return Err(e);
+    }
+  }
+  Ok(())
+}
+
+async fn load_scope(
+  name: &'static str,
+  state: &mut RemoteState,
+  options: &RemoteOptions,
+  store: &dyn BlobStore,
+) -> Result<ItemPairs> {
+  if let Some(scope) = state.scopes.get(name) {
+    return Ok(
+      scope
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect_vec(),
+    );
+  }
+  // use an empty scope unless the remote one is loaded successfully
+  state.scopes.insert(name, Default::default());
+
+  if let Err(e) = ensure_meta(state, options, store).await {
+    return Err(if matches!(state.meta, RemoteMetaState::Unreachable) {
+      Error::from_reason(
+        Some(ErrorType::Load),
+        Some(name),
+        format!("remote storage is unreachable, fall back to a cold build: {e}"),
+      )
+    } else {
+      Error::from_error(Some(ErrorType::Load), Some(name), Box::new(e))
+    });
+  }
+  let RemoteMetaState::Value(Some(meta)) = &state.meta else {
+    return Ok(vec![]);
+  };
+  if let ValidateResult::Invalid(detail) = meta.validate(&options.version) {
+    return Err(Error::from_detail(
+      Some(ErrorType::Validate),
+      Some(name),
+      detail,
+    ));
+  }
+  let Some(scope_meta) = meta.scopes.get(name) else {
+    return Ok(vec![]);
+  };
+  if scope_meta.pack_size != options.pack_size {
+    return Err(Error::from_detail(
+      Some(ErrorType::Validate),
+      Some(name),
+      InvalidDetail {
+        reason: "`options.packSize` changed".to_string(),
+        packs: vec![],
+      },
+    ));
+  }
+
+  let results = join_all(
+    scope_meta
+      .packs
+      .iter()
+      .map(|hash| async move { (hash, store.get(&get_pack_key(hash)).await) }),
+  )
+  .await;
+
+  let mut items = vec![];
+  let mut missing_packs = vec![];
+  let mut invalid_packs = vec![];
+  for (hash, res) in results {
+    let res = res.map_err(|e| Error::from_error(Some(ErrorType::Load), Some(name), Box::new(e)))?;
+    let Some(bytes) = res else {
+      missing_packs.push(get_pack_key(hash));
+      continue;
+    };
+    match decode_pack(&bytes) {
+      Some(pack_items) if get_content_hash(&bytes) == *hash => items.extend(pack_items),
+      _ => invalid_packs.push(get_pack_key(hash)),
+    }
+  }
+  if !missing_packs.is_empty() {
+    return Err(Error::from_detail(
+      Some(ErrorType::Validate),
+      Some(name),
+      InvalidDetail {
+        reason: "some packs are missing".to_string(),
+        packs: missing_packs,
+      },
+    ));
+  }
+  if !invalid_packs.is_empty() {
+    return Err(Error::from_detail(
+      Some(ErrorType::Validate),
+      Some(name),
+      InvalidDetail {
+        reason: "some packs are modified".to_string(),
+        packs: invalid_packs,
+      },
+    ));
+  }
+
+  state.scopes.insert(
+    name,
+    items
+      .iter()
+      .map(|(key, value)| (key.clone(), value.clone()))
+      .collect(),
+  );
+  Ok(items)
+}
+
+#[tracing::instrument("Cache::Storage::save_remote_scopes", skip_all)]
+async fn save_scopes(
+  updates: ScopeUpdates,
+  state: &mut RemoteState,
+  options: &RemoteOptions,
+  store: &dyn BlobStore,
+) -> Result<()> {
+  if matches!(state.meta, RemoteMetaState::Unreachable) {
+    return Ok(());
+  }
+  let to_save_error = |e: Error| Error::from_error(Some(ErrorType::Save), None, Box::new(e));
+  // an illegal meta will be overwritten, only bail out when the store is unreachable
+  if let Err(e) = ensure_meta(state, options, store).await
+    && matches!(state.meta, RemoteMetaState::Unreachable)
+  {
+    return Err(to_save_error(e));
+  }
+
+  let mut meta = match &state.meta {
+    RemoteMetaState::Value(Some(meta)) if meta.validate(&options.version).is_valid() => {
+      meta.clone()
+    }
+    _ => RemoteMeta::new(&options.version, options.expire),
+  };
+  meta.expire_time = current_time() + options.expire;
+
+  for (name, scope_update) in updates {
+    if scope_update.is_empty() {
+      continue;
+    }
+    // a failed load leaves an empty scope, which will be overwritten
+    let _ = load_scope(name, state, options, store).await;
+    let scope = state.scopes.entry(name).or_default();
+    for (key, value) in scope_update {
+      match value {
+        Some(value) => scope.insert(Arc::new(key), Arc::new(value)),
+        None => scope.remove(&key),
+      };
+    }
+
+    let packs = split_packs(
+      scope
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect_vec(),
+      options.pack_size,
+    );
+    let hashes = join_all(packs.into_iter().map(|pack| async move {
+      let hash = get_content_hash(&pack);
+      let key = get_pack_key(&hash);
+      // packs are content-addressed, existing ones need no upload
+      if !store.exists(&key).await? {
+        store.put(&key, pack).await?;
+      }
+      Ok(hash)
+    }))
+    .await
+    .into_iter()
+    .collect::<Result<Vec<_>>>()
+    .map_err(to_save_error)?;
+
+    meta.scopes.insert(
+      name.to_string(),
+      RemoteScopeMeta {
+        pack_size: options.pack_size,
+        packs: hashes,
+      },
+    );
+  }
+
+  store
+    .put(&RemoteMeta::get_key(&options.version), meta.encode())
+    .await
+    .map_err(to_save_error)?;
+
+  if !state.cleaned {
+    state.cleaned = true;
+    if let Err(e) = clean_stale_blobs(&meta, options, store).await {
+      tracing::warn!("clean remote storage failed: {e}");
+    }
+  }
+  state.meta = RemoteMetaState::Value(Some(meta));
+  Ok(())
+}
+
+/// Remove the metas of the expired versions and the packs not referenced by any unexpired version.
+///
+/// The packs uploaded by a concurrent save before its meta may be removed as well,
+/// which are reported as missing packs on loading and fall back to a cold build.
+#[tracing::instrument("Cache::Storage::clean_remote_blobs", skip_all)]
+async fn clean_stale_blobs(
+  meta: &RemoteMeta,
+  options: &RemoteOptions,
+  store: &dyn BlobStore,
+) -> Result<()> {
+  let Some(versions) = store.list("").await? else {
+    return Ok(());
+  };
+  let mut used_packs = meta
+    .scopes
+    .values()
+    .flat_map(|scope| scope.packs.iter().cloned())
+    .collect::<HashSet<_>>();
+  for version in versions {
+    if version == PACKS_PREFIX || version == options.version {
+      continue;
+    }
+    let key = RemoteMeta::get_key(&version);
+    let Some(bytes) = store.get(&key).await? else {
+      continue;
+    };
+    match RemoteMeta::decode(&bytes) {
+      Ok(other) if current_time() <= other.expire_time => {
+        used_packs.extend(other.scopes.into_values().flat_map(|scope| scope.packs));
+      }
+      _ => store.delete(&key).await?,
+    }
+  }
+
+  let Some(packs) = store.list(PACKS_PREFIX).await? else {
+    return Ok(());
+  };
+  join_all(
+    packs
+      .iter()
+      .filter(|hash| !used_packs.contains(*hash))
+      .map(|hash| async move { store.delete(&get_pack_key(hash)).await }),
+  )
+  .await
+  .into_iter()
+  .collect::<Result<Vec<_>>>()?;
+  Ok(())
+}
+
+#[async_trait::async_trait]
+impl Storage for RemotePackStorage {
+  async fn load(&self, name: &'static str) -> Result<ItemPairs> {
+    let mut state = self.state.lock().await;
+    load_scope(name, &mut state, &self.options, self.store.as_ref()).await
+  }
+  fn set(&self, scope: &'static str, key: ItemKey, value: ItemValue) {
+    let mut updates = self.updates.lock().expect("should get lock");
+    let scope_update = updates.entry(scope).or_default();
+    scope_update.insert(key, Some(value));
+  }
+  fn remove(&self, scope: &'static str, key: &[u8]) {
+    let mut updates = self.updates.lock().expect("should get lock");
+    let scope_update = updates.entry(scope).or_default();
+    scope_update.insert(key.to_vec(), None);
+  }
+  fn trigger_save(&self) -> Result<Receiver<Result<()>>> {
+    let updates = std::mem::take(&mut *self.updates.lock().expect("should get lock"));
+    let state = self.state.clone();
+    let options = self.options.clone();
+    let store = self.store.clone();
+    let (tx, rx) = oneshot::channel();
+    self.queue.add_task(Box::pin(async move {
+      let mut state = state.lock().await;
+      let _ = tx.send(save_scopes(updates, &mut state, &options, store.as_ref()).await);
+    }));
+    Ok(rx)
+  }
+  async fn reset(&self) {
+    // the shared store is never removed, the next save overwrites the meta of this version
+    let mut state = self.state.lock().await;
+    state.scopes.clear();
+    state.meta = RemoteMetaState::Value(None);
+  }
+  async fn scopes(&self) -> Result<Vec<String>> {
+    let mut state = self.state.lock().await;
+    if ensure_meta(&mut state, &self.options, self.store.as_ref())
+      .await
+      .is_err()
+    {
+      return Ok(vec![]);
+    }
+    match &state.meta {
+      RemoteMetaState::Value(Some(meta)) if meta.validate(&self.options.version).is_valid() => {
+        Ok(meta.scopes.keys().cloned().collect())
+      }
+      _ => Ok(vec![]),
+    }
+  }
+}
@@ -0,0 +1,218 @@
+use std::{sync::Arc, time::Duration};
+
+use rspack_http::{HttpClient, HttpEndpoint, HttpResponse};
+use rspack_paths::Utf8PathBuf;
+
+use crate::{
+  FileSystem,
+  error::{Error, Result},
+};
+
+/// Content-addressed blob store used by `RemotePackStorage`
+///
+/// Keys are relative paths such as `packs/<hash>` or `<version>/storage_meta`.
+#[async_trait::async_trait]
+pub trait BlobStore: std::fmt::Debug + Sync + Send {
+  /// Return `None` if the blob does not exist
+  async fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+  async fn put(&self, key: &str, value: Vec<u8>) -> Result<()>;
+  async fn exists(&self, key: &str) -> Result<bool>;
+  /// List the names of the blobs and directories under the prefix, e.g. `packs`.
+  ///
+  /// Return `None` if the store does not support listing.
+  async fn list(&self, prefix: &str) -> Result<Option<Vec<String>>>;
+  /// Remove the blob, it is a no-op if the blob does not exist
+  async fn delete(&self, key: &str) -> Result<()>;
+}
+
+/// Blob store backed by a directory, such as a network mount shared by CI runners
+#[derive(Debug)]
+pub struct LocalBlobStore {
+  root: Utf8PathBuf,
+  fs: Arc<dyn FileSystem>,
+}
+
+impl LocalBlobStore {
+  pub fn new(root: Utf8PathBuf, fs: Arc<dyn FileSystem>) -> Self {
+    Self { root, fs }
+  }
+}
+
+#[async_trait::async_trait]
+impl BlobStore for LocalBlobStore {
+  async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+    let path = self.root.join(key);
+    if !self.fs.exists(&path).await? {
+      return Ok(None);
+    }
+    let mut reader = self.fs.read_file(&path).await?;
+    Ok(Some(reader.read_to_end().await?))
+  }
+
+  async fn put(&self, key: &str, value: Vec<u8>) -> Result<()> {
+    // write to a temp file first so that readers never see a partial blob
+    let path = self.root.join(key);
+    let temp_path = self.root.join(format!("{key}.{}.tmp", std::process::id()));
+    let mut writer = self.fs.write_file(&temp_path).await?;
+    writer.write_all(&value).await?;
+    writer.flush().await?;
+    self.fs.move_file(&temp_path, &path).await?;
+    Ok(())
+  }
+
+  async fn exists(&self, key: &str) -> Result<bool> {
+    Ok(self.fs.exists(&self.root.join(key)).await?)
+  }
+
+  async fn list(&self, prefix: &str) -> Result<Option<Vec<String>>> {
+    let path = self.root.join(prefix);
+    if !self.fs.exists(&path).await? {
+      return Ok(Some(vec![]));
+    }
+    Ok(Some(
+      self
+        .fs
+        .read_dir(&path)
+        .await?
+        .into_iter()
+        // the blobs being written by other processes
+        .filter(|name| !name.ends_with(".tmp"))
+        .collect(),
+    ))
+  }
+
+  async fn delete(&self, key: &str) -> Result<()> {
+    let path = self.root.join(key);
+    if self.fs.exists(&path).await? {
+      self.fs.remove_file(&path).await?;
+    }
+    Ok(())
+  }
+}
+
+/// Blob store served by a plain http cache server
+///
+/// Blobs are read with `GET`, written with `PUT`, checked with `HEAD` and removed
+/// with `DELETE` on `<endpoint>/<key>`. The blobs under a prefix are listed by
+/// `GET <endpoint>/<prefix>/`, which responds one name per line, and the servers
+/// without listing skip the cleaning of stale packs. Only `http://` endpoints are supported.
+#[derive(Debug)]
+pub struct HttpBlobStore {
+  client: Arc<HttpClient>,
+}
+
+impl HttpBlobStore {
+  pub fn new(endpoint: &str, timeout: Duration) -> Result<Self> {
+    let endpoint = HttpEndpoint::parse(endpoint).map_err(|reason| {
+      Error::from_reason(
+        None,
+        None,
+        format!("invalid remote storage endpoint `{endpoint}`: {reason}"),
+      )
+    })?;
+    Ok(Self {
+      client: Arc::new(HttpClient::new(endpoint, timeout)),
+    })
+  }
+
+  async fn request(
+    &self,
+    method: &'static str,
+    path: String,
+    body: Vec<u8>,
+  ) -> Result<HttpResponse> {
+    let client = self.client.clone();
+    let task_path = path.clone();
+    tokio::task::spawn_blocking(move || client.request(method, &task_path, None, &body))
+      .await
+      .map_err(|e| self.error(method, &path, Box::new(e)))?
+      .map_err(|e| self.error(method, &path, Box::new(e)))
+  }
+
+  fn error(
+    &self,
+    method: &str,
+    path: &str,
+    error: Box<dyn std::error::Error + Send + Sync>,
+  ) -> Error {
+    Error::from_reason(
+      None,
+      None,
+      format!(
+        "{method} `{}` failed due to `{error}`",
+        self.client.url(path)
+      ),
+    )
+  }
+}
+
+#[async_trait::async_trait]
+impl BlobStore for HttpBlobStore {
+  async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+    let path = format!("/{key}");
+    match self.request("GET", path.clone(), vec![]).await? {
+      HttpResponse { status: 200, body } => Ok(Some(body)),
+      HttpResponse { status: 404, .. } => Ok(None),
+      HttpResponse { status, .. } => {
+        Err(self.error("GET", &path, format!("status {status}").into()))
+      }
+    }
+  }
+
+  async fn put(&self, key: &str, value: Vec<u8>) -> Result<()> {
+    let path = format!("/{key}");
+    match self.request("PUT", path.clone(), value).await? {
+      response if response.is_success() => Ok(()),
+      HttpResponse { status, .. } => {
+        Err(self.error("PUT", &path, format!("status {status}").into()))
+      }
+    }
+  }
+
+  async fn exists(&self, key: &str) -> Result<bool> {
+    let path = format!("/{key}");
+    match self.request("HEAD", path.clone(), vec![]).await?.status {
+      200 => Ok(true),
+      404 => Ok(false),
+      status => Err(self.error("HEAD", &path, format!("status {status}").into())),
+    }
+  }
+
+  async fn list(&self, prefix: &str) -> Result<Option<Vec<String>>> {
+    let path = if prefix.is_empty() {
+      "/".to_string()
+    } else {
+      format!("/{prefix}/")
+    };
+    match self.request("GET", path.clone(), vec![]).await? {
+      HttpResponse { status: 200, body } => {
+        let body = String::from_utf8(body).map_err(|e| self.error("GET", &path, Box::new(e)))?;
+        Ok(Some(
+          body
+            .lines()
+            .map(|line| line.trim().trim_end_matches('/'))
+            .filter(|name| !name.is_empty())
+            .map(|name| name.to_string())
+            .collect(),
+        ))
+      }
+      HttpResponse {
+        status: 404 | 405 | 501,
+        ..
+      } => Ok(None),
+      HttpResponse { status, .. } => {
+        Err(self.error("GET", &path, format!("status {status}").into()))
+      }
+    }
+  }
+
+  async fn delete(&self, key: &str) -> Result<()> {
+    let path = format!("/{key}");
+    match self.request("DELETE", path.clone(), vec![]).await? {
+      response if response.is_success() || response.status == 404 => Ok(()),
+      HttpResponse { status, .. } => {
+        Err(self.error("DELETE", &path, format!("status {status}").into()))
+      }
+    }
+  }
+}
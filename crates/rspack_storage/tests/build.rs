@@ -5,7 +5,8 @@ mod test_storage_build {
   use rspack_fs::{MemoryFileSystem, NativeFileSystem};
   use rspack_paths::{AssertUtf8, Utf8PathBuf};
   use rspack_storage::{
-    BridgeFileSystem, FileSystem, PackStorage, PackStorageOptions, Result, Storage,
+    BridgeFileSystem, FileSystem, PackCompression, PackStorage, PackStorageMode,
+    PackStorageOptions, Result, Storage,
   };
 
   pub fn get_native_path(p: &str) -> (PathBuf, PathBuf) {
@@ -37,6 +38,8 @@ mod test_storage_build {
       clean: true,
       fresh_generation: Some(1),
       release_generation: Some(2),
+      compression: PackCompression::None,
+      mode: PackStorageMode::ReadWrite,
     }
   }
 
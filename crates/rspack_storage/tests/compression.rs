@@ -0,0 +1,186 @@
+#[cfg(test)]
+mod test_storage_compression {
+  use std::{collections::HashMap, path::PathBuf, sync::Arc};
+
+  use rspack_fs::{MemoryFileSystem, NativeFileSystem};
+  use rspack_paths::{AssertUtf8, Utf8PathBuf};
+  use rspack_storage::{
+    BridgeFileSystem, FileSystem, PackCompression, PackStorage, PackStorageInspector,
+    PackStorageMode, PackStorageOptions, Result, Storage,
+  };
+
+  pub fn get_native_path(p: &str) -> (PathBuf, PathBuf) {
+    let base = std::env::temp_dir()
+      .join("rspack_test/storage/test_storage_compression")
+      .join(p);
+    (base.join("cache"), base.join("temp"))
+  }
+
+  pub fn get_memory_path(p: &str) -> (PathBuf, PathBuf) {
+    let base = PathBuf::from("/rspack_test/storage/test_storage_compression/").join(p);
+    (base.join("cache"), base.join("temp"))
+  }
+
+  fn create_pack_options(
+    root: &Utf8PathBuf,
+    temp_root: &Utf8PathBuf,
+    fs: Arc<dyn FileSystem>,
+    compression: PackCompression,
+  ) -> PackStorageOptions {
+    PackStorageOptions {
+      version: "xxx".to_string(),
+      root: root.into(),
+      temp_root: temp_root.into(),
+      fs,
+      bucket_size: 10,
+      pack_size: 2000,
+      expire: 7 * 24 * 60 * 60 * 1000,
+      clean: true,
+      fresh_generation: Some(1),
+      release_generation: Some(2),
+      compression,
+      mode: PackStorageMode::ReadWrite,
+    }
+  }
+
+  async fn load_data(storage: &PackStorage) -> Result<HashMap<String, String>> {
+    Ok(
+      storage
+        .load("test_scope")
+        .await?
+        .into_iter()
+        .map(|(k, v)| {
+          (
+            String::from_utf8(k.to_vec()).expect("should be utf8"),
+            String::from_utf8(v.to_vec()).expect("should be utf8"),
+          )
+        })
+        .collect::<HashMap<_, _>>(),
+    )
+  }
+
+  async fn get_compressions(
+    root: &Utf8PathBuf,
+    fs: Arc<dyn FileSystem>,
+  ) -> Result<Vec<PackCompression>> {
+    let versions = PackStorageInspector::new(root.clone(), fs)
+      .inspect(true)
+      .await?;
+    let scope = &versions[0].scopes[0];
+    assert!(!scope.is_corrupted());
+    Ok(scope.packs.iter().map(|pack| pack.compression).collect())
+  }
+
+  async fn test_uncompressed_build(options: PackStorageOptions) -> Result<()> {
+    let storage = PackStorage::new(options);
+    for i in 0..1000 {
+      storage.set(
+        "test_scope",
+        format!("key_{i:0>4}").as_bytes().to_vec(),
+        format!("val_{i:0>4}").repeat(10).as_bytes().to_vec(),
+      );
+    }
+    storage.trigger_save()?.await.expect("should save")?;
+    Ok(())
+  }
+
+  async fn test_compressed_update(
+    options: PackStorageOptions,
+    key: usize,
+    value: &str,
+  ) -> Result<()> {
+    let storage = PackStorage::new(options);
+    // packs written without compression can be loaded
+    assert_eq!(load_data(&storage).await?.len(), 1000);
+    storage.set(
+      "test_scope",
+      format!("key_{key:0>4}").as_bytes().to_vec(),
+      value.as_bytes().to_vec(),
+    );
+    storage.trigger_save()?.await.expect("should save")?;
+    Ok(())
+  }
+
+  async fn test_compressed_recovery(options: PackStorageOptions) -> Result<()> {
+    let storage = PackStorage::new(options);
+    let data = load_data(&storage).await?;
+    assert_eq!(data.len(), 1000);
+    assert_eq!(
+      data.get("key_0000").expect("should have value"),
+      "zstd_value"
+    );
+    assert_eq!(
+      data.get("key_0999").expect("should have value"),
+      "lz4_value"
+    );
+    assert_eq!(
+      data.get("key_0500").expect("should have value"),
+      &"val_0500".repeat(10)
+    );
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn test_compression() -> Result<()> {
+    let cases = [
+      (
+        get_native_path("test_compression_native"),
+        Arc::new(BridgeFileSystem(Arc::new(NativeFileSystem::new(false)))),
+      ),
+      (
+        get_memory_path("test_compression_memory"),
+        Arc::new(BridgeFileSystem(Arc::new(MemoryFileSystem::default()))),
+      ),
+    ];
+
+    for ((root, temp_root), fs) in cases {
+      let root = root.assert_utf8();
+      let temp_root = temp_root.assert_utf8();
+      fs.remove_dir(&root).await.expect("should remove root");
+      fs.remove_dir(&temp_root)
+        .await
+        .expect("should remove temp root");
+
+      test_uncompressed_build(create_pack_options(
+        &root,
+        &temp_root,
+        fs.clone(),
+        PackCompression::None,
+      ))
+      .await?;
+      let compressions = get_compressions(&root, fs.clone()).await?;
+      assert!(compressions.iter().all(|c| c.is_none()));
+
+      let zstd = PackCompression::Zstd { level: 19 };
+      test_compressed_update(
+        create_pack_options(&root, &temp_root, fs.clone(), zstd),
+        0,
+        "zstd_value",
+      )
+      .await?;
+      let compressions = get_compressions(&root, fs.clone()).await?;
+      assert!(compressions.contains(&zstd));
+      assert!(compressions.contains(&PackCompression::None));
+
+      test_compressed_update(
+        create_pack_options(&root, &temp_root, fs.clone(), PackCompression::Lz4),
+        999,
+        "lz4_value",
+      )
+      .await?;
+      let compressions = get_compressions(&root, fs.clone()).await?;
+      assert!(compressions.contains(&PackCompression::Lz4));
+
+      // packs with different codecs are loaded by a storage without compression
+      test_compressed_recovery(create_pack_options(
+        &root,
+        &temp_root,
+        fs.clone(),
+        PackCompression::None,
+      ))
+      .await?;
+    }
+    Ok(())
+  }
+}
@@ -5,7 +5,8 @@ mod test_storage_expire {
   use rspack_fs::{MemoryFileSystem, NativeFileSystem};
   use rspack_paths::{AssertUtf8, Utf8PathBuf};
   use rspack_storage::{
-    BridgeFileSystem, FileSystem, PackStorage, PackStorageOptions, Result, Storage,
+    BridgeFileSystem, FileSystem, PackCompression, PackStorage, PackStorageMode,
+    PackStorageOptions, Result, Storage,
   };
 
   pub fn get_native_path(p: &str) -> (PathBuf, PathBuf) {
@@ -37,6 +38,8 @@ mod test_storage_expire {
       clean: true,
       fresh_generation: Some(1),
       release_generation: Some(2),
+      compression: PackCompression::None,
+      mode: PackStorageMode::ReadWrite,
     });
     let data = storage.load("test_scope").await?;
     assert!(data.is_empty());
@@ -74,6 +77,8 @@ mod test_storage_expire {
       clean: true,
       fresh_generation: Some(1),
       release_generation: Some(2),
+      compression: PackCompression::None,
+      mode: PackStorageMode::ReadWrite,
     });
     assert!(storage.load("test_scope").await.is_err_and(|e| {
       e.to_string()
@@ -101,6 +106,8 @@ mod test_storage_expire {
       clean: true,
       fresh_generation: Some(1),
       release_generation: Some(2),
+      compression: PackCompression::None,
+      mode: PackStorageMode::ReadWrite,
     });
     let data = storage.load("test_scope").await?;
     assert!(data.is_empty());
@@ -0,0 +1,184 @@
+#[cfg(test)]
+mod test_storage_inspect {
+  use std::{path::PathBuf, sync::Arc, time::Duration};
+
+  use rspack_fs::{MemoryFileSystem, NativeFileSystem};
+  use rspack_paths::{AssertUtf8, Utf8PathBuf};
+  use rspack_storage::{
+    BridgeFileSystem, FileSystem, PackCompression, PackStatus, PackStorage, PackStorageInspector,
+    PackStorageMode, PackStorageOptions, Result, Storage,
+  };
+
+  pub fn get_native_path(p: &str) -> (PathBuf, PathBuf) {
+    let base = std::env::temp_dir()
+      .join("rspack_test/storage/test_storage_inspect")
+      .join(p);
+    (base.join("cache"), base.join("temp"))
+  }
+
+  pub fn get_memory_path(p: &str) -> (PathBuf, PathBuf) {
+    let base = PathBuf::from("/rspack_test/storage/test_storage_inspect/").join(p);
+    (base.join("cache"), base.join("temp"))
+  }
+
+  fn create_storage(
+    version: &str,
+    expire: u64,
+    root: &Utf8PathBuf,
+    temp_root: &Utf8PathBuf,
+    fs: Arc<dyn FileSystem>,
+  ) -> PackStorage {
+    PackStorage::new(PackStorageOptions {
+      version: version.to_string(),
+      root: root.into(),
+      temp_root: temp_root.into(),
+      fs,
+      bucket_size: 2,
+      pack_size: 200,
+      expire,
+      clean: false,
+      fresh_generation: Some(1),
+      release_generation: Some(2),
+      compression: PackCompression::None,
+      mode: PackStorageMode::ReadWrite,
+    })
+  }
+
+  async fn test_initial_build(
+    root: &Utf8PathBuf,
+    temp_root: &Utf8PathBuf,
+    fs: Arc<dyn FileSystem>,
+  ) -> Result<()> {
+    let expired = create_storage("xxx", 0, root, temp_root, fs.clone());
+    expired.set("test_scope", b"key".to_vec(), b"val".to_vec());
+    expired.trigger_save()?.await.expect("should save")?;
+
+    let storage = create_storage("yyy", 7 * 24 * 60 * 60 * 1000, root, temp_root, fs);
+    for scope in ["scope_a", "scope_b"] {
+      for i in 0..100 {
+        storage.set(
+          scope,
+          format!("key_{i:0>3}").as_bytes().to_vec(),
+          format!("val_{i:0>3}").as_bytes().to_vec(),
+        );
+      }
+    }
+    storage.trigger_save()?.await.expect("should save")?;
+    // make sure version `xxx` is expired
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    Ok(())
+  }
+
+  async fn test_inspect(root: &Utf8PathBuf, fs: Arc<dyn FileSystem>) -> Result<()> {
+    let inspector = PackStorageInspector::new(root.clone(), fs);
+    let versions = inspector.inspect(true).await?;
+    assert_eq!(versions.len(), 2);
+    assert_eq!(versions[0].version, "xxx");
+    assert!(versions[0].expired);
+    assert_eq!(versions[1].version, "yyy");
+    assert!(!versions[1].expired);
+    assert!(!versions[1].locked);
+
+    let scopes = &versions[1].scopes;
+    assert_eq!(
+      scopes
+        .iter()
+        .map(|scope| scope.name.as_str())
+        .collect::<Vec<_>>(),
+      vec!["scope_a", "scope_b"]
+    );
+    for scope in scopes {
+      assert_eq!(scope.bucket_size, 2);
+      assert_eq!(scope.pack_size, 200);
+      assert!(!scope.packs.is_empty());
+      assert!(scope.size() > 0);
+      assert!(!scope.is_corrupted());
+      assert!(
+        scope
+          .packs
+          .iter()
+          .all(|pack| pack.status == PackStatus::Valid)
+      );
+    }
+
+    let versions = inspector.inspect(false).await?;
+    assert!(
+      versions[1]
+        .scopes
+        .iter()
+        .flat_map(|scope| scope.packs.iter())
+        .all(|pack| pack.status == PackStatus::Unchecked)
+    );
+    Ok(())
+  }
+
+  async fn test_corrupted_pack(root: &Utf8PathBuf, fs: Arc<dyn FileSystem>) -> Result<()> {
+    let inspector = PackStorageInspector::new(root.clone(), fs.clone());
+    let versions = inspector.inspect(false).await?;
+    let pack_path = versions[1].scopes[1].packs[0].path.clone();
+    let mut writer = fs.write_file(&pack_path).await?;
+    writer.write_all("1 1\nab".as_bytes()).await?;
+    writer.flush().await?;
+
+    let versions = inspector.inspect(true).await?;
+    let scopes = &versions[1].scopes;
+    assert!(!scopes[0].is_corrupted());
+    assert!(scopes[1].is_corrupted());
+    assert_eq!(scopes[1].packs[0].status, PackStatus::Modified);
+    Ok(())
+  }
+
+  async fn test_prune(
+    root: &Utf8PathBuf,
+    temp_root: &Utf8PathBuf,
+    fs: Arc<dyn FileSystem>,
+  ) -> Result<()> {
+    let inspector = PackStorageInspector::new(root.clone(), fs.clone());
+    let res = inspector.prune().await?;
+    assert_eq!(res.removed_versions, vec!["xxx".to_string()]);
+    assert_eq!(
+      res.removed_scopes,
+      vec![("yyy".to_string(), "scope_b".to_string())]
+    );
+    assert_eq!(res.removed_packs.len(), 1);
+    assert!(!fs.exists(&root.join("xxx")).await?);
+    assert!(!fs.exists(&root.join("yyy/scope_b")).await?);
+
+    // the remaining scopes are still loadable
+    let storage = create_storage("yyy", 7 * 24 * 60 * 60 * 1000, root, temp_root, fs);
+    assert_eq!(storage.scopes().await?, vec!["scope_a".to_string()]);
+    assert_eq!(storage.load("scope_a").await?.len(), 100);
+    assert!(storage.load("scope_b").await?.is_empty());
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn test_inspect_and_prune() -> Result<()> {
+    let cases = [
+      (
+        get_native_path("test_inspect_native"),
+        Arc::new(BridgeFileSystem(Arc::new(NativeFileSystem::new(false)))),
+      ),
+      (
+        get_memory_path("test_inspect_memory"),
+        Arc::new(BridgeFileSystem(Arc::new(MemoryFileSystem::default()))),
+      ),
+    ];
+
+    for ((root, temp_root), fs) in cases {
+      let root = root.assert_utf8();
+      let temp_root = temp_root.assert_utf8();
+      fs.remove_dir(&root).await.expect("should remove root");
+      fs.remove_dir(&temp_root)
+        .await
+        .expect("should remove temp root");
+
+      test_initial_build(&root, &temp_root, fs.clone()).await?;
+      test_inspect(&root, fs.clone()).await?;
+      test_corrupted_pack(&root, fs.clone()).await?;
+      test_prune(&root, &temp_root, fs.clone()).await?;
+    }
+    Ok(())
+  }
+}
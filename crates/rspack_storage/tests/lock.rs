@@ -8,8 +8,8 @@ mod test_storage_lock {
   use rspack_fs::{FileMetadata, MemoryFileSystem, NativeFileSystem};
   use rspack_paths::{AssertUtf8, Utf8Path, Utf8PathBuf};
   use rspack_storage::{
-    BridgeFileSystem, FSError, FSOperation, FSResult, FileSystem, PackStorage, PackStorageOptions,
-    Reader, Result, Storage, Writer,
+    BridgeFileSystem, FSError, FSOperation, FSResult, FileSystem, PackCompression, PackStorage,
+    PackStorageMode, PackStorageOptions, Reader, Result, Storage, Writer,
   };
   use rustc_hash::FxHashSet as HashSet;
 
@@ -38,6 +38,10 @@ mod test_storage_lock {
       self.fs.write_file(path).await
     }
 
+    async fn create_file(&self, path: &Utf8Path, data: &[u8]) -> FSResult<bool> {
+      self.fs.create_file(path, data).await
+    }
+
     async fn read_file(&self, path: &Utf8Path) -> FSResult<Reader> {
       self.fs.read_file(path).await
     }
@@ -100,6 +104,8 @@ mod test_storage_lock {
       clean: true,
       fresh_generation: Some(1),
       release_generation: Some(2),
+      compression: PackCompression::None,
+      mode: PackStorageMode::ReadWrite,
     });
     let data = storage.load("test_scope").await?;
     assert!(data.is_empty());
@@ -137,6 +143,8 @@ mod test_storage_lock {
       clean: true,
       fresh_generation: Some(1),
       release_generation: Some(2),
+      compression: PackCompression::None,
+      mode: PackStorageMode::ReadWrite,
     });
     assert_eq!(storage.load("test_scope").await?.len(), 100);
     Ok(())
@@ -159,6 +167,8 @@ mod test_storage_lock {
       clean: true,
       fresh_generation: Some(1),
       release_generation: Some(2),
+      compression: PackCompression::None,
+      mode: PackStorageMode::ReadWrite,
     });
     assert!(storage.load("test_scope").await.is_err_and(|e| {
       e.to_string()
@@ -0,0 +1,170 @@
+#[cfg(test)]
+mod test_storage_mode {
+  use std::{path::PathBuf, sync::Arc};
+
+  use rspack_fs::{MemoryFileSystem, NativeFileSystem};
+  use rspack_paths::{AssertUtf8, Utf8PathBuf};
+  use rspack_storage::{
+    BridgeFileSystem, FileSystem, PackCompression, PackStorage, PackStorageMode,
+    PackStorageOptions, Result, Storage,
+  };
+
+  pub fn get_native_path(p: &str) -> (PathBuf, PathBuf) {
+    let base = std::env::temp_dir()
+      .join("rspack_test/storage/test_storage_mode")
+      .join(p);
+    (base.join("cache"), base.join("temp"))
+  }
+
+  pub fn get_memory_path(p: &str) -> (PathBuf, PathBuf) {
+    let base = PathBuf::from("/rspack_test/storage/test_storage_mode/").join(p);
+    (base.join("cache"), base.join("temp"))
+  }
+
+  fn create_pack_options(
+    root: &Utf8PathBuf,
+    temp_root: &Utf8PathBuf,
+    fs: Arc<dyn FileSystem>,
+    mode: PackStorageMode,
+  ) -> PackStorageOptions {
+    PackStorageOptions {
+      version: "xxx".to_string(),
+      root: root.into(),
+      temp_root: temp_root.into(),
+      fs,
+      bucket_size: 10,
+      pack_size: 200,
+      expire: 7 * 24 * 60 * 60 * 1000,
+      clean: true,
+      fresh_generation: Some(1),
+      release_generation: Some(2),
+      compression: PackCompression::None,
+      mode,
+    }
+  }
+
+  async fn test_seed(options: PackStorageOptions, prefix: &str, count: usize) -> Result<()> {
+    let storage = PackStorage::new(options);
+    // existing contents are ignored
+    assert!(storage.load("test_scope").await?.is_empty());
+    assert!(storage.scopes().await?.is_empty());
+    for i in 0..count {
+      storage.set(
+        "test_scope",
+        format!("{prefix}_key_{i:0>3}").as_bytes().to_vec(),
+        format!("{prefix}_val_{i:0>3}").as_bytes().to_vec(),
+      );
+    }
+    storage.trigger_save()?.await.expect("should save")?;
+    Ok(())
+  }
+
+  async fn test_read_only(options: PackStorageOptions) -> Result<()> {
+    let storage = PackStorage::new(options);
+    let data = storage.load("test_scope").await?;
+    assert_eq!(data.len(), 50);
+    assert!(data.iter().all(|(key, _)| key.starts_with(b"new_")));
+    storage.set(
+      "test_scope",
+      "new_key_000".as_bytes().to_vec(),
+      "modified".as_bytes().to_vec(),
+    );
+    storage.remove("test_scope", "new_key_001".as_bytes());
+    storage.trigger_save()?.await.expect("should save")?;
+    storage.reset().await;
+    Ok(())
+  }
+
+  async fn test_read_write(options: PackStorageOptions) -> Result<()> {
+    let storage = PackStorage::new(options);
+    let data = storage.load("test_scope").await?;
+    // nothing is changed by the read-only storage
+    assert_eq!(data.len(), 50);
+    let (_, value) = data
+      .iter()
+      .find(|(key, _)| key.as_slice() == b"new_key_000")
+      .expect("should have key");
+    assert_eq!(value.as_slice(), b"new_val_000");
+    Ok(())
+  }
+
+  async fn test_read_only_with_lock(
+    options: PackStorageOptions,
+    root: &Utf8PathBuf,
+    fs: Arc<dyn FileSystem>,
+  ) -> Result<()> {
+    let lock_file = root.join("xxx/move.lock");
+    let mut writer = fs.write_file(&lock_file).await?;
+    writer.write_all("".as_bytes()).await?;
+    writer.flush().await?;
+
+    let storage = PackStorage::new(options);
+    let error = storage
+      .load("test_scope")
+      .await
+      .expect_err("should fail to load");
+    assert!(error.to_string().contains("read-only mode"));
+    // the lock file is kept for the writable storage to recover
+    assert!(fs.exists(&lock_file).await?);
+    fs.remove_file(&lock_file).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn test_mode() -> Result<()> {
+    let cases = [
+      (
+        get_native_path("test_mode_native"),
+        Arc::new(BridgeFileSystem(Arc::new(NativeFileSystem::new(false)))),
+      ),
+      (
+        get_memory_path("test_mode_memory"),
+        Arc::new(BridgeFileSystem(Arc::new(MemoryFileSystem::default()))),
+      ),
+    ];
+
+    for ((root, temp_root), fs) in cases {
+      let root = root.assert_utf8();
+      let temp_root = temp_root.assert_utf8();
+      fs.remove_dir(&root).await.expect("should remove root");
+      fs.remove_dir(&temp_root)
+        .await
+        .expect("should remove temp root");
+
+      test_seed(
+        create_pack_options(&root, &temp_root, fs.clone(), PackStorageMode::WriteOnly),
+        "old",
+        100,
+      )
+      .await?;
+      test_seed(
+        create_pack_options(&root, &temp_root, fs.clone(), PackStorageMode::WriteOnly),
+        "new",
+        50,
+      )
+      .await?;
+      test_read_only(create_pack_options(
+        &root,
+        &temp_root,
+        fs.clone(),
+        PackStorageMode::ReadOnly,
+      ))
+      .await?;
+      test_read_write(create_pack_options(
+        &root,
+        &temp_root,
+        fs.clone(),
+        PackStorageMode::ReadWrite,
+      ))
+      .await?;
+      test_read_only_with_lock(
+        create_pack_options(&root, &temp_root, fs.clone(), PackStorageMode::ReadOnly),
+        &root,
+        fs.clone(),
+      )
+      .await?;
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,206 @@
+#[cfg(test)]
+mod test_storage_remote {
+  use std::{collections::HashMap, sync::Arc, time::Duration};
+
+  use rspack_fs::MemoryFileSystem;
+  use rspack_paths::Utf8PathBuf;
+  use rspack_storage::{
+    BlobStore, BridgeFileSystem, FileSystem, HttpBlobStore, LocalBlobStore, RemotePackStorage,
+    RemotePackStorageOptions, Result, Storage,
+  };
+
+  fn create_options(store: Arc<dyn BlobStore>, version: &str) -> RemotePackStorageOptions {
+    RemotePackStorageOptions {
+      store,
+      version: version.to_string(),
+      pack_size: 200,
+      expire: 7 * 24 * 60 * 60 * 1000,
+    }
+  }
+
+  async fn save_items(storage: &RemotePackStorage, prefix: &str) -> Result<()> {
+    for i in 0..100 {
+      storage.set(
+        "test_scope",
+        format!("key_{i:0>3}").as_bytes().to_vec(),
+        format!("{prefix}_{i:0>3}").as_bytes().to_vec(),
+      );
+    }
+    let rx = storage.trigger_save()?;
+    rx.await.expect("should save")
+  }
+
+  async fn test_initial_build(store: Arc<dyn BlobStore>) -> Result<()> {
+    let storage = RemotePackStorage::new(create_options(store.clone(), "xxx"));
+    assert!(storage.load("test_scope").await?.is_empty());
+    for i in 0..1000 {
+      storage.set(
+        "test_scope",
+        format!("key_{i:0>3}").as_bytes().to_vec(),
+        format!("val_{i:0>3}").as_bytes().to_vec(),
+      );
+    }
+    let rx = storage.trigger_save()?;
+    rx.await.expect("should save")?;
+    assert!(store.exists("xxx/storage_meta").await?);
+    Ok(())
+  }
+
+  async fn test_shared_build(store: Arc<dyn BlobStore>) -> Result<()> {
+    // another machine with the same store
+    let storage = RemotePackStorage::new(create_options(store.clone(), "xxx"));
+    assert_eq!(storage.scopes().await?, vec!["test_scope".to_string()]);
+    assert_eq!(storage.load("test_scope").await?.len(), 1000);
+    storage.set(
+      "test_scope",
+      format!("key_{:0>3}", 222).as_bytes().to_vec(),
+      format!("new_{:0>3}", 222).as_bytes().to_vec(),
+    );
+    storage.remove("test_scope", format!("key_{:0>3}", 333).as_bytes().as_ref());
+    let rx = storage.trigger_save()?;
+    rx.await.expect("should save")?;
+
+    let storage = RemotePackStorage::new(create_options(store.clone(), "xxx"));
+    let data = storage
+      .load("test_scope")
+      .await?
+      .into_iter()
+      .map(|(k, v)| {
+        (
+          String::from_utf8(k.to_vec()).expect("should be utf8"),
+          String::from_utf8(v.to_vec()).expect("should be utf8"),
+        )
+      })
+      .collect::<HashMap<_, _>>();
+    assert_eq!(data.len(), 999);
+    assert_eq!(
+      *data
+        .get(&format!("key_{:0>3}", 222))
+        .expect("should get modified value"),
+      format!("new_{:0>3}", 222)
+    );
+    Ok(())
+  }
+
+  async fn test_other_version(store: Arc<dyn BlobStore>) -> Result<()> {
+    let storage = RemotePackStorage::new(create_options(store, "yyy"));
+    assert!(storage.scopes().await?.is_empty());
+    assert!(storage.load("test_scope").await?.is_empty());
+    Ok(())
+  }
+
+  async fn test_modified_pack(fs: Arc<dyn FileSystem>, root: &Utf8PathBuf) -> Result<()> {
+    let packs = fs.read_dir(&root.join("packs")).await?;
+    let pack = packs.iter().next().expect("should have pack");
+    let mut writer = fs.write_file(&root.join("packs").join(pack)).await?;
+    writer.write_all("1 1\nab".as_bytes()).await?;
+    writer.flush().await?;
+
+    let store = Arc::new(LocalBlobStore::new(root.clone(), fs));
+    let storage = RemotePackStorage::new(create_options(store, "xxx"));
+    let error = storage
+      .load("test_scope")
+      .await
+      .expect_err("should fail to load");
+    assert!(
+      error
+        .to_string()
+        .starts_with("validate scope `test_scope` failed due to some packs are modified")
+    );
+    // fall back to an empty scope
+    assert!(storage.load("test_scope").await?.is_empty());
+    Ok(())
+  }
+
+  async fn test_truncated_meta(fs: Arc<dyn FileSystem>, root: &Utf8PathBuf) -> Result<()> {
+    let meta_path = root.join("xxx/storage_meta");
+    let mut meta = fs.read_file(&meta_path).await?.read_to_end().await?;
+    meta.truncate(meta.len() - 10);
+    let mut writer = fs.write_file(&meta_path).await?;
+    writer.write_all(&meta).await?;
+    writer.flush().await?;
+
+    let store = Arc::new(LocalBlobStore::new(root.clone(), fs));
+    let storage = RemotePackStorage::new(create_options(store, "xxx"));
+    let error = storage
+      .load("test_scope")
+      .await
+      .expect_err("should fail to load");
+    assert!(error.to_string().contains("content hash mismatch"));
+    // fall back to an empty scope
+    assert!(storage.load("test_scope").await?.is_empty());
+    Ok(())
+  }
+
+  async fn test_clean_stale_blobs(store: Arc<dyn BlobStore>) -> Result<()> {
+    // the meta of this version expires at once
+    let storage = RemotePackStorage::new(RemotePackStorageOptions {
+      expire: 0,
+      ..create_options(store.clone(), "expired")
+    });
+    save_items(&storage, "expired").await?;
+    let packs = store.list("packs").await?.expect("should list packs");
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    let storage = RemotePackStorage::new(create_options(store.clone(), "fresh"));
+    save_items(&storage, "fresh").await?;
+    assert!(!store.exists("expired/storage_meta").await?);
+    assert!(store.exists("fresh/storage_meta").await?);
+    let current_packs = store.list("packs").await?.expect("should list packs");
+    // the packs only used by the expired version are removed
+    assert!(packs.iter().all(|pack| !current_packs.contains(pack)));
+    let storage = RemotePackStorage::new(create_options(store, "fresh"));
+    assert_eq!(storage.load("test_scope").await?.len(), 100);
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn test_local_store() -> Result<()> {
+    let fs: Arc<dyn FileSystem> = Arc::new(BridgeFileSystem(Arc::new(MemoryFileSystem::default())));
+    let root = Utf8PathBuf::from("/rspack_test/storage/test_storage_remote/store");
+    let store: Arc<dyn BlobStore> = Arc::new(LocalBlobStore::new(root.clone(), fs.clone()));
+
+    test_initial_build(store.clone()).await?;
+    test_shared_build(store.clone()).await?;
+    test_other_version(store).await?;
+    test_modified_pack(fs.clone(), &root).await?;
+    test_truncated_meta(fs, &root).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn test_clean_store() -> Result<()> {
+    let fs: Arc<dyn FileSystem> = Arc::new(BridgeFileSystem(Arc::new(MemoryFileSystem::default())));
+    let root = Utf8PathBuf::from("/rspack_test/storage/test_storage_remote/clean");
+    let store: Arc<dyn BlobStore> = Arc::new(LocalBlobStore::new(root, fs));
+
+    test_clean_stale_blobs(store).await
+  }
+
+  #[tokio::test]
+  #[cfg_attr(miri, ignore)]
+  async fn test_unreachable_store() -> Result<()> {
+    let store = Arc::new(HttpBlobStore::new(
+      "http://127.0.0.1:1/cache",
+      Duration::from_secs(1),
+    )?);
+    let storage = RemotePackStorage::new(create_options(store, "xxx"));
+    let error = storage
+      .load("test_scope")
+      .await
+      .expect_err("should fail to load");
+    assert!(
+      error
+        .to_string()
+        .starts_with("load scope `test_scope` failed due to remote storage is unreachable")
+    );
+    // cold build without saving
+    assert!(storage.load("test_scope").await?.is_empty());
+    storage.set("test_scope", b"key".to_vec(), b"val".to_vec());
+    let rx = storage.trigger_save()?;
+    rx.await.expect("should save")?;
+    Ok(())
+  }
+}
@@ -3,7 +3,9 @@ mod snapshot;
 
 use std::{collections::VecDeque, sync::Arc};
 
-use rspack_core::cache::persistent::storage::{Storage, StorageOptions, create_storage};
+use rspack_core::cache::persistent::storage::{
+  DEFAULT_EXPIRE, DEFAULT_PACK_SIZE, Storage, StorageOptions, create_storage,
+};
 use rspack_error::{Result, error};
 use rspack_fs::{NativeFileSystem, ReadableFileSystem};
 use rspack_paths::Utf8PathBuf;
@@ -59,13 +61,19 @@ pub fn load_storages_from_path(path: &Utf8PathBuf) -> HashMap<String, Arc<dyn St
     }
 
     // Create storage for this version
-    let storage = create_storage(
+    let Ok(storage) = create_storage(
       StorageOptions::FileSystem {
         directory: path.clone().into(),
+        pack_size: DEFAULT_PACK_SIZE,
+        expire: DEFAULT_EXPIRE,
+        compression: Default::default(),
+        mode: Default::default(),
       },
       v.clone(),
       fs.clone(),
-    );
+    ) else {
+      continue;
+    };
 
     storages.insert(v, storage);
   }
@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use rspack_error::{Result, error};
+use rspack_fs::NativeFileSystem;
+use rspack_paths::Utf8PathBuf;
+use rspack_storage::{BridgeFileSystem, PackStorageInspector, VersionInspection};
+use rspack_util::current_time;
+
+use crate::compare::find_relative_cache_path;
+
+fn create_inspector(path: Utf8PathBuf) -> PackStorageInspector {
+  PackStorageInspector::new(
+    path,
+    Arc::new(BridgeFileSystem(Arc::new(NativeFileSystem::new(false)))),
+  )
+}
+
+fn print_version(version: &VersionInspection) {
+  let state = if let Some(error) = &version.error {
+    format!("broken: {error}")
+  } else if version.expired {
+    "expired".to_string()
+  } else {
+    let expire_time = version.expire_time.unwrap_or_default();
+    let hours = expire_time.saturating_sub(current_time()) / (60 * 60 * 1000);
+    format!("expires in {hours}h")
+  };
+  let locked = if version.locked {
+    ", unfinished save"
+  } else {
+    ""
+  };
+  println!(
+    "  Version {} ({state}{locked}, {} bytes)",
+    version.version,
+    version.size()
+  );
+
+  for scope in &version.scopes {
+    if let Some(error) = &scope.error {
+      println!("    Scope {}: broken: {error}", scope.name);
+      continue;
+    }
+    println!(
+      "    Scope {}: {} packs, {} bytes, generation {}, bucket size {}, pack size {}",
+      scope.name,
+      scope.packs.len(),
+      scope.size(),
+      scope.generation,
+      scope.bucket_size,
+      scope.pack_size
+    );
+    for pack in scope.packs.iter().filter(|pack| pack.status.is_corrupted()) {
+      println!("      {} pack: {}", pack.status, pack.path);
+    }
+  }
+}
+
+/// Print versions, scopes and packs of all caches in the directory
+///
+/// Returns an error if any pack is corrupted when `validate` is true.
+pub async fn inspect_cache_dir(path: Utf8PathBuf, validate: bool) -> Result<()> {
+  let mut corrupted_scopes = 0;
+  for cache_relative_path in find_relative_cache_path(&path) {
+    let cache_path = path.join(cache_relative_path);
+    println!("Cache: {cache_path}");
+
+    let versions = create_inspector(cache_path).inspect(validate).await?;
+    for version in &versions {
+      print_version(version);
+      corrupted_scopes += version
+        .scopes
+        .iter()
+        .filter(|scope| scope.is_corrupted())
+        .count();
+    }
+    println!();
+  }
+
+  if corrupted_scopes > 0 {
+    return Err(error!("Found {corrupted_scopes} corrupted scopes"));
+  }
+  Ok(())
+}
+
+/// Remove expired versions and corrupted scopes of all caches in the directory
+pub async fn prune_cache_dir(path: Utf8PathBuf) -> Result<()> {
+  for cache_relative_path in find_relative_cache_path(&path) {
+    let cache_path = path.join(cache_relative_path);
+    println!("Cache: {cache_path}");
+
+    let res = create_inspector(cache_path).prune().await?;
+    for version in &res.removed_versions {
+      println!("  Removed version {version}");
+    }
+    for (version, scope) in &res.removed_scopes {
+      println!("  Removed scope {scope} of version {version}");
+    }
+    for pack in &res.removed_packs {
+      println!("  Removed corrupted pack {pack}");
+    }
+    println!();
+  }
+  Ok(())
+}
@@ -1,5 +1,7 @@
 mod compare;
 mod debug_info;
+mod inspect;
 mod utils;
 
 pub use compare::compare_cache_dir;
+pub use inspect::{inspect_cache_dir, prune_cache_dir};
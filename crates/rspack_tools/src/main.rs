@@ -1,7 +1,7 @@
 use clap::{Parser, Subcommand};
 use rspack_error::Diagnostic;
 use rspack_paths::Utf8PathBuf;
-use rspack_tools::compare_cache_dir;
+use rspack_tools::{compare_cache_dir, inspect_cache_dir, prune_cache_dir};
 
 /// Toolkit for debugging and testing rspack internals
 #[derive(Parser, Debug)]
@@ -25,6 +25,32 @@ enum Commands {
     #[arg(value_name = "CACHE2")]
     cache2: String,
   },
+  /// List versions, scopes and packs in a rspack cache directory
+  Inspect {
+    /// Path to the cache directory
+    #[arg(value_name = "CACHE")]
+    cache: String,
+
+    /// Validate the hash of every pack against the scope meta
+    #[arg(long)]
+    validate: bool,
+  },
+  /// Remove expired versions and corrupted scopes in a rspack cache directory
+  Prune {
+    /// Path to the cache directory
+    #[arg(value_name = "CACHE")]
+    cache: String,
+  },
+}
+
+fn exit_with_error(err: rspack_error::Error) -> ! {
+  eprintln!(
+    "{}",
+    Diagnostic::from(err)
+      .render_report(true)
+      .expect("render error failed")
+  );
+  std::process::exit(1);
 }
 
 #[tokio::main]
@@ -42,16 +68,24 @@ async fn main() {
       let path2 = Utf8PathBuf::from(&cache2);
 
       if let Err(err) = compare_cache_dir(path1, path2).await {
-        eprintln!(
-          "{}",
-          Diagnostic::from(err)
-            .render_report(true)
-            .expect("render error failed")
-        );
-        std::process::exit(1);
+        exit_with_error(err);
       }
 
       println!("✓ Cache directories are identical");
     }
+    Commands::Inspect { cache, validate } => {
+      if let Err(err) = inspect_cache_dir(Utf8PathBuf::from(&cache), validate).await {
+        exit_with_error(err);
+      }
+
+      if validate {
+        println!("✓ All packs are valid");
+      }
+    }
+    Commands::Prune { cache } => {
+      if let Err(err) = prune_cache_dir(Utf8PathBuf::from(&cache)).await {
+        exit_with_error(err);
+      }
+    }
   }
 }
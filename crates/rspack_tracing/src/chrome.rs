@@ -0,0 +1,622 @@
+use std::{
+  collections::HashMap,
+  io::{BufWriter, Write},
+  sync::{Arc, Mutex},
+  thread::ThreadId,
+  time::Instant,
+};
+
+use serde_json::{Map, Value, json};
+use tracing::{
+  Event, Subscriber,
+  field::{Field, Visit},
+  span::{Attributes, Id, Record},
+};
+use tracing_subscriber::{Layer, layer::Context, registry::LookupSpan};
+
+use crate::{
+  TraceEvent,
+  tracer::{Layered, Tracer},
+};
+
+// keep the same process and track names as the `PerfettoLayer`
+static DEFAULT_PROCESS_NAME: &str = "Rspack Build Overall";
+static DEFAULT_THREAD_NAME: &str = "Main Phase";
+static JAVASCRIPT_ANALYSIS_TRACK: &str = "JavaScript Analysis";
+/// The numeric fields of events with this prefix are emitted as counters, e.g.
+/// `tracing::info!(counter.modules = 42)` draws the `modules` counter.
+const COUNTER_PREFIX: &str = "counter.";
+
+/// A track in the trace viewer, `pid` and `tid` in the Chrome trace event format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Track {
+  pid: u64,
+  tid: u64,
+}
+
+/// Writes the events to a `{"traceEvents": [...]}` JSON object incrementally,
+/// and allocates the pid and tid of the named processes and tracks.
+struct ChromeWriter {
+  writer: BufWriter<std::fs::File>,
+  has_events: bool,
+  /// The events after the JSON is closed are dropped
+  finished: bool,
+  processes: HashMap<String, u64>,
+  tracks: HashMap<(u64, String), u64>,
+  threads: HashMap<ThreadId, u64>,
+  next_id: u64,
+}
+
+impl ChromeWriter {
+  fn new(output: &str) -> Self {
+    let file = std::fs::File::create(output)
+      .unwrap_or_else(|e| panic!("Failed to create trace file: {output} due to {e}"));
+    let mut writer = BufWriter::new(file);
+    let _ = writer.write_all(b"{\"traceEvents\":[\n");
+    Self {
+      writer,
+      has_events: false,
+      finished: false,
+      processes: HashMap::new(),
+      tracks: HashMap::new(),
+      threads: HashMap::new(),
+      next_id: 1,
+    }
+  }
+
+  fn write_event(&mut self, event: &Value) {
+    if self.finished {
+      return;
+    }
+    if self.has_events {
+      let _ = self.writer.write_all(b",\n");
+    }
+    self.has_events = true;
+    let _ = serde_json::to_writer(&mut self.writer, event);
+  }
+
+  fn write_metadata(&mut self, name: &str, track: Track, value: &str) {
+    self.write_event(&json!({
+      "name": name,
+      "ph": "M",
+      "pid": track.pid,
+      "tid": track.tid,
+      "args": { "name": value },
+    }));
+  }
+
+  fn alloc_id(&mut self) -> u64 {
+    let id = self.next_id;
+    self.next_id += 1;
+    id
+  }
+
+  fn process(&mut self, name: Option<&str>) -> u64 {
+    let name = name.unwrap_or(DEFAULT_PROCESS_NAME);
+    if let Some(pid) = self.processes.get(name) {
+      return *pid;
+    }
+    let pid = self.alloc_id();
+    self.processes.insert(name.to_string(), pid);
+    self.write_metadata("process_name", Track { pid, tid: 0 }, name);
+    pid
+  }
+
+  /// Returns the track of the process with the name, it is created at the first time.
+  fn named_track(&mut self, process_name: Option<&str>, track_name: &str) -> Track {
+    let pid = self.process(process_name);
+    if let Some(tid) = self.tracks.get(&(pid, track_name.to_string())) {
+      return Track { pid, tid: *tid };
+    }
+    let tid = self.alloc_id();
+    self.tracks.insert((pid, track_name.to_string()), tid);
+    let track = Track { pid, tid };
+    self.write_metadata("thread_name", track, track_name);
+    track
+  }
+
+  /// Returns the default track of the current thread, so that the spans of
+  /// different threads are not drawn on top of each other.
+  fn thread_track(&mut self, process_name: Option<&str>) -> Track {
+    let pid = self.process(process_name);
+    let thread = std::thread::current();
+    if let Some(tid) = self.threads.get(&thread.id()) {
+      return Track { pid, tid: *tid };
+    }
+    let tid = self.alloc_id();
+    self.threads.insert(thread.id(), tid);
+    let track = Track { pid, tid };
+    let name = match thread.name() {
+      Some(name) => format!("{DEFAULT_THREAD_NAME} ({name})"),
+      None => format!("{DEFAULT_THREAD_NAME} ({tid})"),
+    };
+    self.write_metadata("thread_name", track, &name);
+    track
+  }
+
+  /// Resolve the track of a span or an event, the user set track name takes precedence
+  /// over the track inherited from the parent span.
+  fn resolve_track(&mut self, visitor: &ArgsVisitor, inherited_track: Option<Track>) -> Track {
+    let process_name = visitor.process_name.as_deref();
+    match (&visitor.track_name, inherited_track) {
+      (Some(track_name), _) => self.named_track(process_name, track_name),
+      (None, Some(track)) => track,
+      (None, None) => self.thread_track(process_name),
+    }
+  }
+
+  fn finish(&mut self) {
+    if self.finished {
+      return;
+    }
+    self.finished = true;
+    let _ = self.writer.write_all(b"\n],\"displayTimeUnit\":\"ms\"}\n");
+    let _ = self.writer.flush();
+  }
+}
+
+/// Records the span fields as Chrome trace event args, and the
+/// `perfetto.track_name` and `perfetto.process_name` fields as the track of the span.
+#[derive(Default)]
+struct ArgsVisitor {
+  args: Map<String, Value>,
+  counters: Map<String, Value>,
+  track_name: Option<String>,
+  process_name: Option<String>,
+}
+
+impl ArgsVisitor {
+  fn insert(&mut self, field: &Field, value: Value) {
+    let name = field.name();
+    if let Some(counter) = name.strip_prefix(COUNTER_PREFIX)
+      && value.is_number()
+    {
+      self.counters.insert(counter.to_string(), value);
+    } else {
+      self.args.insert(name.to_string(), value);
+    }
+  }
+}
+
+impl Visit for ArgsVisitor {
+  fn record_f64(&mut self, field: &Field, value: f64) {
+    self.insert(field, json!(value));
+  }
+
+  fn record_i64(&mut self, field: &Field, value: i64) {
+    self.insert(field, json!(value));
+  }
+
+  fn record_u64(&mut self, field: &Field, value: u64) {
+    self.insert(field, json!(value));
+  }
+
+  fn record_bool(&mut self, field: &Field, value: bool) {
+    self.insert(field, json!(value));
+  }
+
+  fn record_str(&mut self, field: &Field, value: &str) {
+    match field.name() {
+      "perfetto.track_name" => self.track_name = Some(value.to_string()),
+      "perfetto.process_name" => self.process_name = Some(value.to_string()),
+      _ => self.insert(field, json!(value)),
+    }
+  }
+
+  fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+    self.insert(field, json!(format!("{value:?}")));
+  }
+}
+
+/// The data of an open span, stored in the span extensions.
+struct SpanData {
+  track: Track,
+  start: u64,
+  args: Map<String, Value>,
+}
+
+struct ChromeLayer {
+  writer: Arc<Mutex<ChromeWriter>>,
+  start: Instant,
+}
+
+impl ChromeLayer {
+  fn get_ts(&self) -> u64 {
+    self.start.elapsed().as_nanos() as u64
+  }
+}
+
+/// Convert the nanosecond timestamp to the microsecond timestamp of the Chrome trace event format
+fn to_micros(nanos: u64) -> f64 {
+  nanos as f64 / 1000.0
+}
+
+impl<S> Layer<S> for ChromeLayer
+where
+  S: Subscriber + for<'a> LookupSpan<'a>,
+{
+  fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+    let Some(span) = ctx.span(id) else {
+      return;
+    };
+    let mut visitor = ArgsVisitor::default();
+    attrs.record(&mut visitor);
+
+    // the span inherits the track of the parent span if it doesn't set a track name
+    let inherited_track = span
+      .parent()
+      .and_then(|parent| parent.extensions().get::<SpanData>().map(|data| data.track));
+    let track = self
+      .writer
+      .lock()
+      .expect("Failed to lock writer")
+      .resolve_track(&visitor, inherited_track);
+    span.extensions_mut().insert(SpanData {
+      track,
+      start: self.get_ts(),
+      args: visitor.args,
+    });
+  }
+
+  fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+    let Some(span) = ctx.span(id) else {
+      return;
+    };
+    if let Some(data) = span.extensions_mut().get_mut::<SpanData>() {
+      let mut visitor = ArgsVisitor::default();
+      values.record(&mut visitor);
+      data.args.append(&mut visitor.args);
+    }
+  }
+
+  fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+    let mut visitor = ArgsVisitor::default();
+    event.record(&mut visitor);
+    let inherited_track = event
+      .parent()
+      .cloned()
+      .or_else(|| ctx.current_span().id().cloned())
+      .and_then(|id| ctx.span(&id))
+      .and_then(|span| span.extensions().get::<SpanData>().map(|data| data.track));
+    let ts = to_micros(self.get_ts());
+    let metadata = event.metadata();
+
+    let mut writer = self.writer.lock().expect("Failed to lock writer");
+    let track = writer.resolve_track(&visitor, inherited_track);
+    if !visitor.counters.is_empty() {
+      for (name, value) in visitor.counters {
+        let mut args = Map::new();
+        args.insert(name.clone(), value);
+        writer.write_event(&json!({
+          "name": name,
+          "cat": metadata.target(),
+          "ph": "C",
+          "ts": ts,
+          "pid": track.pid,
+          "tid": track.tid,
+          "args": args,
+        }));
+      }
+      // the events only carrying counters are not written as instant events
+      if visitor.args.keys().all(|key| key == "message") {
+        return;
+      }
+    }
+    writer.write_event(&json!({
+      "name": metadata.name(),
+      "cat": metadata.target(),
+      "ph": "i",
+      "s": "t",
+      "ts": ts,
+      "pid": track.pid,
+      "tid": track.tid,
+      "args": visitor.args,
+    }));
+  }
+
+  fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+    let Some(span) = ctx.span(&id) else {
+      return;
+    };
+    let Some(data) = span.extensions_mut().remove::<SpanData>() else {
+      return;
+    };
+    let end = self.get_ts();
+    let metadata = span.metadata();
+    // the spans are written as complete events, because the async spans
+    // may be entered and exited on different threads
+    self
+      .writer
+      .lock()
+      .expect("Failed to lock writer")
+      .write_event(&json!({
+        "name": metadata.name(),
+        "cat": metadata.target(),
+        "ph": "X",
+        "ts": to_micros(data.start),
+        "dur": to_micros(end.saturating_sub(data.start)),
+        "pid": data.track.pid,
+        "tid": data.track.tid,
+        "args": data.args,
+      }));
+  }
+}
+
+/// Parse the args of the JavaScript events, the values are always JSON strings.
+fn to_args(args: Option<HashMap<String, String>>) -> Map<String, Value> {
+  args
+    .unwrap_or_default()
+    .into_iter()
+    .map(|(key, value)| {
+      let value = serde_json::from_str(&value).unwrap_or(Value::String(value));
+      (key, value)
+    })
+    .collect()
+}
+
+/// `ChromeTracer` writes the trace in the [Chrome Trace Event Format](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU/preview),
+/// which can be opened by `chrome://tracing`, Perfetto UI and speedscope.
+///
+/// The spans are written as complete (`X`) events with the same names and fields as the
+/// [`crate::PerfettoTracer`], the tracks set by `perfetto.process_name` and `perfetto.track_name`
+/// are mapped to named processes and threads, and the numeric `counter.*` fields of events are written as counters.
+#[derive(Default)]
+pub struct ChromeTracer {
+  writer: Option<Arc<Mutex<ChromeWriter>>>,
+}
+
+impl Tracer for ChromeTracer {
+  fn setup(&mut self, output: &str) -> Option<Layered> {
+    let writer = Arc::new(Mutex::new(ChromeWriter::new(output)));
+    self.writer = Some(writer.clone());
+    Some(Box::new(ChromeLayer {
+      writer,
+      start: Instant::now(),
+    }))
+  }
+
+  fn sync_trace(&mut self, events: Vec<TraceEvent>) {
+    let Some(writer) = &self.writer else {
+      return;
+    };
+    let mut writer = writer.lock().expect("Failed to lock writer");
+    // Track begin events by uuid to match with end events
+    let mut pending_events: HashMap<u32, TraceEvent> = HashMap::new();
+    for event in events {
+      match event.ph.as_str() {
+        "b" => {
+          pending_events.insert(event.uuid, event);
+        }
+        "e" => {
+          let Some(begin_event) = pending_events.remove(&event.uuid) else {
+            continue;
+          };
+          let process_name = begin_event
+            .process_name
+            .as_deref()
+            .unwrap_or(JAVASCRIPT_ANALYSIS_TRACK);
+          let track_name = begin_event
+            .track_name
+            .as_deref()
+            .unwrap_or(JAVASCRIPT_ANALYSIS_TRACK);
+          let track = writer.named_track(Some(process_name), track_name);
+          writer.write_event(&json!({
+            "name": begin_event.name,
+            "cat": begin_event.categories.unwrap_or_default().join(","),
+            "ph": "X",
+            "ts": to_micros(begin_event.ts),
+            "dur": to_micros(event.ts.saturating_sub(begin_event.ts)),
+            "pid": track.pid,
+            "tid": track.tid,
+            "args": to_args(begin_event.args),
+          }));
+        }
+        "P" | "X" => {
+          let track_name = event
+            .track_name
+            .as_deref()
+            .unwrap_or(JAVASCRIPT_ANALYSIS_TRACK);
+          let track = writer.named_track(event.process_name.as_deref(), track_name);
+          // keep the `Profile` and `ProfileChunk` sample events of the CPU profile,
+          // the other events are written as instant events like the `PerfettoTracer`
+          let ph = if event.ph == "P" { "P" } else { "i" };
+          writer.write_event(&json!({
+            "name": event.name,
+            "cat": event.categories.unwrap_or_default().join(","),
+            "ph": ph,
+            "s": "t",
+            "ts": to_micros(event.ts),
+            "pid": track.pid,
+            "tid": track.tid,
+            "id": format!("0x{:x}", event.uuid),
+            "args": to_args(event.args),
+          }));
+        }
+        _ => {}
+      }
+    }
+  }
+
+  fn teardown(&mut self) {
+    if let Some(writer) = self.writer.take() {
+      writer.lock().expect("Failed to lock writer").finish();
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+
+  use serde_json::Value;
+  use tracing::Dispatch;
+  use tracing_subscriber::{Registry, layer::SubscriberExt};
+
+  use super::ChromeTracer;
+  use crate::{TraceEvent, tracer::Tracer};
+
+  fn trace(name: &str, f: impl FnOnce(&Dispatch), events: Vec<TraceEvent>) -> Vec<Value> {
+    let output = std::env::temp_dir().join(format!("rspack_test_chrome_{name}.json"));
+    let output = output.to_str().expect("should be utf8");
+    let mut tracer = ChromeTracer::default();
+    let layer = tracer.setup(output).expect("should setup layer");
+    let dispatch = Dispatch::new(Registry::default().with(layer));
+    tracing::dispatcher::with_default(&dispatch, || f(&dispatch));
+    tracer.sync_trace(events);
+    tracer.teardown();
+
+    let content = std::fs::read_to_string(output).expect("should read trace file");
+    let _ = std::fs::remove_file(output);
+    let trace: Value = serde_json::from_str(&content).expect("should be valid json");
+    trace["traceEvents"]
+      .as_array()
+      .expect("should have trace events")
+      .clone()
+  }
+
+  fn find<'a>(events: &'a [Value], ph: &str, name: &str) -> &'a Value {
+    events
+      .iter()
+      .find(|event| event["ph"] == ph && event["name"] == name)
+      .unwrap_or_else(|| panic!("should have `{ph}` event `{name}`"))
+  }
+
+  fn track_name(events: &[Value], event: &Value) -> String {
+    let metadata = events
+      .iter()
+      .find(|item| {
+        item["ph"] == "M"
+          && item["name"] == "thread_name"
+          && item["pid"] == event["pid"]
+          && item["tid"] == event["tid"]
+      })
+      .expect("should have thread name");
+    metadata["args"]["name"]
+      .as_str()
+      .expect("should be string")
+      .to_string()
+  }
+
+  #[test]
+  fn should_write_complete_events_of_spans() {
+    let events = trace(
+      "spans",
+      |dispatch| {
+        let outer = tracing::info_span!("outer", module = "./a.js");
+        let _outer = outer.enter();
+        {
+          let inner = tracing::info_span!("inner", count = 1);
+          let _inner = inner.enter();
+        }
+        // the spans of other threads are drawn on another track
+        let dispatch = dispatch.clone();
+        std::thread::Builder::new()
+          .name("worker".to_string())
+          .spawn(move || {
+            tracing::dispatcher::with_default(&dispatch, || {
+              let _worker = tracing::info_span!("worker").entered();
+            })
+          })
+          .expect("should spawn thread")
+          .join()
+          .expect("should join thread");
+      },
+      vec![],
+    );
+
+    let outer = find(&events, "X", "outer");
+    let inner = find(&events, "X", "inner");
+    let worker = find(&events, "X", "worker");
+    assert!(outer["dur"].as_f64().expect("should have dur") >= 0.0);
+    assert!(inner["dur"].as_f64().expect("should have dur") >= 0.0);
+    assert!(outer["dur"].as_f64() >= inner["dur"].as_f64());
+    assert_eq!(outer["args"]["module"], "./a.js");
+    assert_eq!(inner["args"]["count"], 1);
+
+    // the nested span is on the track of the parent span
+    assert_eq!(inner["pid"], outer["pid"]);
+    assert_eq!(inner["tid"], outer["tid"]);
+    assert_eq!(worker["pid"], outer["pid"]);
+    assert_ne!(worker["tid"], outer["tid"]);
+    assert_eq!(track_name(&events, &worker), "Main Phase (worker)");
+
+    let process = find(&events, "M", "process_name");
+    assert_eq!(process["pid"], outer["pid"]);
+    assert_eq!(process["args"]["name"], "Rspack Build Overall");
+  }
+
+  #[test]
+  fn should_write_named_tracks_and_counters() {
+    let events = trace(
+      "tracks",
+      |_| {
+        let plugin = tracing::info_span!(
+          "plugin",
+          perfetto.process_name = "Plugin Analysis",
+          perfetto.track_name = "CssChunkingPlugin"
+        );
+        let _plugin = plugin.enter();
+        let _nested = tracing::info_span!("nested").entered();
+        tracing::info!(counter.modules = 42u64);
+        tracing::info!(counter.chunks = 3u64, "chunks created");
+        tracing::info!(counter.chunks = 4u64, plugin = "css", "chunks merged");
+      },
+      vec![],
+    );
+
+    let plugin = find(&events, "X", "plugin");
+    let nested = find(&events, "X", "nested");
+    assert_eq!(track_name(&events, plugin), "CssChunkingPlugin");
+    assert_eq!(nested["tid"], plugin["tid"]);
+    assert!(events.iter().any(|event| event["ph"] == "M"
+      && event["name"] == "process_name"
+      && event["pid"] == plugin["pid"]
+      && event["args"]["name"] == "Plugin Analysis"));
+    // the perfetto fields are not written as args
+    assert!(plugin["args"].get("perfetto.track_name").is_none());
+
+    let modules = find(&events, "C", "modules");
+    assert_eq!(modules["args"]["modules"], 42);
+    assert_eq!(modules["tid"], plugin["tid"]);
+    assert!(modules["ts"].is_number());
+    let chunks = events
+      .iter()
+      .filter(|event| event["ph"] == "C" && event["name"] == "chunks")
+      .map(|event| event["args"]["chunks"].clone())
+      .collect::<Vec<_>>();
+    assert_eq!(chunks, vec![3, 4]);
+    // only the event with other fields than the counters and the message is written as an instant event
+    let instants = events
+      .iter()
+      .filter(|event| event["ph"] == "i")
+      .collect::<Vec<_>>();
+    assert_eq!(instants.len(), 1);
+    assert_eq!(instants[0]["args"]["message"], "chunks merged");
+    assert_eq!(instants[0]["args"]["plugin"], "css");
+  }
+
+  #[test]
+  fn should_write_javascript_events() {
+    let event = |ph: &str, ts: u64| TraceEvent {
+      name: "loader".to_string(),
+      track_name: None,
+      process_name: None,
+      args: Some(HashMap::from([("count".to_string(), "2".to_string())])),
+      uuid: 1,
+      ts,
+      ph: ph.to_string(),
+      categories: Some(vec!["rspack".to_string()]),
+    };
+    let events = trace(
+      "javascript",
+      |_| {},
+      vec![event("b", 1000), event("e", 3000)],
+    );
+
+    let loader = find(&events, "X", "loader");
+    assert_eq!(loader["ts"], 1.0);
+    assert_eq!(loader["dur"], 2.0);
+    assert_eq!(loader["args"]["count"], 2);
+    assert_eq!(track_name(&events, loader), "JavaScript Analysis");
+  }
+}
@@ -1,7 +1,11 @@
+mod chrome;
+mod otlp;
 mod perfetto;
 mod stdout;
 mod tracer;
 
+pub use chrome::ChromeTracer;
+pub use otlp::OtlpTracer;
 pub use perfetto::PerfettoTracer;
 pub use stdout::StdoutTracer;
 pub use tracer::{TraceEvent, Tracer};
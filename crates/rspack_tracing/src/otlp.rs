@@ -0,0 +1,577 @@
+use std::{
+  collections::HashMap,
+  hash::{BuildHasher, RandomState},
+  io::Write,
+  sync::{
+    Arc, Mutex,
+    atomic::{AtomicU64, Ordering},
+    mpsc::{Sender, channel},
+  },
+  thread::JoinHandle,
+  time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use rspack_http::{HttpClient, HttpEndpoint};
+use serde_json::{Value, json};
+use tracing::{
+  Subscriber,
+  field::{Field, Visit},
+  span::{Attributes, Id, Record},
+};
+use tracing_subscriber::{Layer, layer::Context, registry::LookupSpan};
+
+use crate::{
+  TraceEvent,
+  tracer::{Layered, Tracer},
+};
+
+/// The spans are exported in batches of this size, the rest are exported on teardown.
+const EXPORT_BATCH_SIZE: usize = 2048;
+/// The default path of the OTLP/HTTP traces endpoint
+const DEFAULT_TRACES_PATH: &str = "/v1/traces";
+/// `SPAN_KIND_INTERNAL` in the OTLP protocol
+const SPAN_KIND_INTERNAL: u8 = 1;
+/// The process name of the plugin spans generated by `#[plugin_hook]`
+const PLUGIN_PROCESS_NAME: &str = "Plugin Analysis";
+/// The connect, read and write timeout of a request to the collector
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn random_id() -> u64 {
+  static COUNTER: AtomicU64 = AtomicU64::new(0);
+  // zero is an invalid id in the OTLP protocol
+  RandomState::new()
+    .hash_one(COUNTER.fetch_add(1, Ordering::Relaxed))
+    .max(1)
+}
+
+fn now_nanos() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .expect("System time before UNIX epoch")
+    .as_nanos() as u64
+}
+
+/// Parse the trace id and the parent span id from the W3C `traceparent` header,
+/// e.g. `00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01`.
+fn parse_traceparent(traceparent: &str) -> Option<(String, String)> {
+  let mut parts = traceparent.trim().split('-');
+  let _version = parts.next()?;
+  let trace_id = parts.next()?;
+  let span_id = parts.next()?;
+  // the ids are lowercase hex strings in the W3C spec
+  let is_hex = |id: &str, len: usize| {
+    id.len() == len && id.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+  };
+  (is_hex(trace_id, 32) && is_hex(span_id, 16)).then(|| (trace_id.to_string(), span_id.to_string()))
+}
+
+fn string_value(value: impl Into<String>) -> Value {
+  json!({ "stringValue": value.into() })
+}
+
+fn to_attributes(attributes: Vec<(String, Value)>) -> Vec<Value> {
+  attributes
+    .into_iter()
+    .map(|(key, value)| json!({ "key": key, "value": value }))
+    .collect()
+}
+
+enum OtlpOutput {
+  File(std::fs::File),
+  Http(HttpClient),
+}
+
+impl OtlpOutput {
+  fn new(output: &str) -> Result<Self, String> {
+    if output.starts_with("http://") {
+      let mut endpoint = HttpEndpoint::parse(output)
+        .map_err(|e| format!("Invalid OTLP endpoint: {output} due to {e}"))?;
+      if endpoint.path.is_empty() {
+        endpoint.path = DEFAULT_TRACES_PATH.to_string();
+      }
+      return Ok(Self::Http(HttpClient::new(endpoint, EXPORT_TIMEOUT)));
+    }
+    if output.starts_with("https://") {
+      return Err(format!(
+        "OTLP exporter only supports http:// endpoints, got: {output}"
+      ));
+    }
+    std::fs::File::create(output)
+      .map(Self::File)
+      .map_err(|e| format!("Failed to create trace file: {output} due to {e}"))
+  }
+
+  fn export(&mut self, body: &[u8]) -> std::io::Result<()> {
+    match self {
+      // The file is written in the format of the OTLP JSON file exporter, one request per line
+      Self::File(file) => {
+        file.write_all(body)?;
+        file.write_all(b"\n")?;
+        file.flush()
+      }
+      Self::Http(client) => {
+        let response = client.request("POST", "", Some("application/json"), body)?;
+        if response.is_success() {
+          Ok(())
+        } else {
+          Err(std::io::Error::other(format!(
+            "unexpected response status {} from {}",
+            response.status,
+            client.url("")
+          )))
+        }
+      }
+    }
+  }
+}
+
+/// Collects the finished spans and exports them as OTLP `ExportTraceServiceRequest`s.
+///
+/// The requests are exported on a background thread, so that a slow collector never blocks
+/// the thread closing the spans.
+struct OtlpExporter {
+  sender: Option<Sender<Vec<u8>>>,
+  worker: Option<JoinHandle<()>>,
+  trace_id: String,
+  /// The parent span from the `TRACEPARENT` environment variable, the root spans are attached to it.
+  parent_span_id: Option<String>,
+  resource: Value,
+  spans: Vec<Value>,
+}
+
+impl OtlpExporter {
+  fn new(output: &str) -> Result<Self, String> {
+    let mut output = OtlpOutput::new(output)?;
+    let (sender, receiver) = channel::<Vec<u8>>();
+    let worker = std::thread::Builder::new()
+      .name("rspack-otlp-exporter".to_string())
+      .spawn(move || {
+        for body in receiver {
+          if let Err(e) = output.export(&body) {
+            tracing::warn!("Failed to export OTLP traces: {e}");
+          }
+        }
+      })
+      .map_err(|e| format!("Failed to spawn the OTLP exporter thread due to {e}"))?;
+    let (trace_id, parent_span_id) = match std::env::var("TRACEPARENT")
+      .ok()
+      .and_then(|traceparent| parse_traceparent(&traceparent))
+    {
+      Some((trace_id, span_id)) => (trace_id, Some(span_id)),
+      None => (format!("{:016x}{:016x}", random_id(), random_id()), None),
+    };
+    let service_name = std::env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| "rspack".to_string());
+    let resource = json!({
+      "attributes": to_attributes(vec![
+        ("service.name".to_string(), string_value(service_name)),
+        (
+          "process.pid".to_string(),
+          json!({ "intValue": std::process::id().to_string() }),
+        ),
+      ]),
+    });
+    Ok(Self {
+      sender: Some(sender),
+      worker: Some(worker),
+      trace_id,
+      parent_span_id,
+      resource,
+      spans: Vec::new(),
+    })
+  }
+
+  fn push(
+    &mut self,
+    name: &str,
+    span_id: u64,
+    parent_span_id: Option<u64>,
+    (start, end): (u64, u64),
+    attributes: Vec<(String, Value)>,
+  ) {
+    let parent_span_id = parent_span_id
+      .map(|id| format!("{id:016x}"))
+      .or_else(|| self.parent_span_id.clone())
+      .unwrap_or_default();
+    self.spans.push(json!({
+      "traceId": self.trace_id,
+      "spanId": format!("{span_id:016x}"),
+      "parentSpanId": parent_span_id,
+      "name": name,
+      "kind": SPAN_KIND_INTERNAL,
+      "startTimeUnixNano": start.to_string(),
+      "endTimeUnixNano": end.to_string(),
+      "attributes": to_attributes(attributes),
+    }));
+    if self.spans.len() >= EXPORT_BATCH_SIZE {
+      self.flush();
+    }
+  }
+
+  fn flush(&mut self) {
+    if self.spans.is_empty() {
+      return;
+    }
+    let request = json!({
+      "resourceSpans": [{
+        "resource": self.resource,
+        "scopeSpans": [{
+          "scope": { "name": "rspack_tracing" },
+          "spans": std::mem::take(&mut self.spans),
+        }],
+      }],
+    });
+    let body = serde_json::to_vec(&request).expect("Failed to serialize OTLP request");
+    if let Some(sender) = &self.sender {
+      // the worker only stops after the sender is dropped in `finish`
+      let _ = sender.send(body);
+    }
+  }
+
+  /// Export the remaining spans and wait for the pending requests to be exported.
+  fn finish(&mut self) {
+    self.flush();
+    self.sender.take();
+    if let Some(worker) = self.worker.take() {
+      let _ = worker.join();
+    }
+  }
+}
+
+/// The data of an open span, stored in the span extensions.
+struct SpanData {
+  span_id: u64,
+  start: u64,
+  attributes: Vec<(String, Value)>,
+}
+
+/// Records the span fields as OTLP attributes.
+struct AttributeVisitor<'a>(&'a mut Vec<(String, Value)>);
+
+impl AttributeVisitor<'_> {
+  fn insert(&mut self, field: &Field, value: Value) {
+    let key = field.name().to_string();
+    match self.0.iter_mut().find(|(k, _)| *k == key) {
+      Some((_, v)) => *v = value,
+      None => self.0.push((key, value)),
+    }
+  }
+}
+
+impl Visit for AttributeVisitor<'_> {
+  fn record_f64(&mut self, field: &Field, value: f64) {
+    self.insert(field, json!({ "doubleValue": value }));
+  }
+
+  fn record_i64(&mut self, field: &Field, value: i64) {
+    self.insert(field, json!({ "intValue": value.to_string() }));
+  }
+
+  fn record_u64(&mut self, field: &Field, value: u64) {
+    self.insert(field, json!({ "intValue": value.to_string() }));
+  }
+
+  fn record_bool(&mut self, field: &Field, value: bool) {
+    self.insert(field, json!({ "boolValue": value }));
+  }
+
+  fn record_str(&mut self, field: &Field, value: &str) {
+    self.insert(field, string_value(value));
+  }
+
+  fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+    self.insert(field, string_value(format!("{value:?}")));
+  }
+}
+
+/// Map the span fields to the semantic attributes of rspack, and classify the span
+/// to compilation phases, plugin hooks and loader calls.
+fn to_span_attributes(name: &str, fields: Vec<(String, Value)>) -> Vec<(String, Value)> {
+  let mut attributes = Vec::with_capacity(fields.len() + 2);
+  let mut track_name = None;
+  let mut process_name = None;
+  for (key, value) in fields {
+    let key = match key.as_str() {
+      "perfetto.track_name" => {
+        track_name = Some(value);
+        continue;
+      }
+      "perfetto.process_name" => {
+        process_name = Some(value);
+        continue;
+      }
+      "identifier" | "module.identifier" => "rspack.module.identifier".to_string(),
+      "resource" | "module.resource" => "rspack.module.resource".to_string(),
+      "module.loaders" => "rspack.module.loaders".to_string(),
+      _ => key,
+    };
+    attributes.push((key, value));
+  }
+
+  let is_plugin = process_name
+    .as_ref()
+    .and_then(|value| value.get("stringValue"))
+    .and_then(Value::as_str)
+    == Some(PLUGIN_PROCESS_NAME);
+  let category = if let Some(hook) = name.strip_prefix("hook:") {
+    attributes.push(("rspack.hook.name".to_string(), string_value(hook)));
+    "hook"
+  } else if name.starts_with("run_loader:") || name.starts_with("LoaderRunner:") {
+    "loader"
+  } else if is_plugin && let Some(plugin) = track_name {
+    attributes.push(("rspack.plugin.name".to_string(), plugin));
+    "plugin"
+  } else {
+    "phase"
+  };
+  attributes.push(("rspack.span.category".to_string(), string_value(category)));
+  attributes
+}
+
+struct OtlpLayer {
+  exporter: Arc<Mutex<OtlpExporter>>,
+}
+
+impl<S> Layer<S> for OtlpLayer
+where
+  S: Subscriber + for<'a> LookupSpan<'a>,
+{
+  fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+    let Some(span) = ctx.span(id) else {
+      return;
+    };
+    let mut attributes = Vec::new();
+    attrs.record(&mut AttributeVisitor(&mut attributes));
+    span.extensions_mut().insert(SpanData {
+      span_id: random_id(),
+      start: now_nanos(),
+      attributes,
+    });
+  }
+
+  fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+    let Some(span) = ctx.span(id) else {
+      return;
+    };
+    if let Some(data) = span.extensions_mut().get_mut::<SpanData>() {
+      values.record(&mut AttributeVisitor(&mut data.attributes));
+    }
+  }
+
+  fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+    let Some(span) = ctx.span(&id) else {
+      return;
+    };
+    let Some(data) = span.extensions_mut().remove::<SpanData>() else {
+      return;
+    };
+    let parent_span_id = span.parent().and_then(|parent| {
+      parent
+        .extensions()
+        .get::<SpanData>()
+        .map(|data| data.span_id)
+    });
+    let name = span.name();
+    self.exporter.lock().expect("Failed to lock exporter").push(
+      name,
+      data.span_id,
+      parent_span_id,
+      (data.start, now_nanos()),
+      to_span_attributes(name, data.attributes),
+    );
+  }
+}
+
+/// `OtlpTracer` exports the spans in the OTLP/JSON format.
+///
+/// The output is either a file, where each line is an `ExportTraceServiceRequest` that can be read by
+/// the `otlpjsonfile` receiver of the OpenTelemetry Collector, or an `http://` endpoint of a collector.
+/// The trace is attached to the `TRACEPARENT` environment variable if it is set,
+/// so that the build spans show up in the trace of the CI pipeline.
+pub struct OtlpTracer {
+  begin_ts: u64,
+  exporter: Arc<Mutex<OtlpExporter>>,
+}
+
+impl OtlpTracer {
+  /// Create the exporter of the output, `https://` endpoints are not supported.
+  pub fn new(output: &str) -> Result<Self, String> {
+    Ok(Self {
+      begin_ts: 0,
+      exporter: Arc::new(Mutex::new(OtlpExporter::new(output)?)),
+    })
+  }
+}
+
+impl Tracer for OtlpTracer {
+  fn setup(&mut self, _output: &str) -> Option<Layered> {
+    // Record the start time in nanoseconds since UNIX epoch
+    self.begin_ts = now_nanos();
+    Some(Box::new(OtlpLayer {
+      exporter: self.exporter.clone(),
+    }))
+  }
+
+  fn sync_trace(&mut self, events: Vec<TraceEvent>) {
+    let mut exporter = self.exporter.lock().expect("Failed to lock exporter");
+    // Track begin events by uuid to match with end events
+    let mut pending_events: HashMap<u32, TraceEvent> = HashMap::new();
+    for event in events {
+      match event.ph.as_str() {
+        "b" => {
+          pending_events.insert(event.uuid, event);
+        }
+        "e" => {
+          let Some(begin_event) = pending_events.remove(&event.uuid) else {
+            continue;
+          };
+          // Convert relative nanosecond timestamps to absolute nanoseconds
+          let start = self.begin_ts + begin_event.ts;
+          let end = self.begin_ts + event.ts;
+          let mut attributes = begin_event
+            .args
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(key, value)| (key, string_value(value)))
+            .collect::<Vec<_>>();
+          if let Some(track_name) = begin_event.track_name {
+            attributes.push(("rspack.track.name".to_string(), string_value(track_name)));
+          }
+          attributes.push((
+            "rspack.span.category".to_string(),
+            string_value("javascript"),
+          ));
+          exporter.push(
+            &begin_event.name,
+            random_id(),
+            None,
+            (start, end.max(start)),
+            attributes,
+          );
+        }
+        _ => {}
+      }
+    }
+  }
+
+  fn teardown(&mut self) {
+    self
+      .exporter
+      .lock()
+      .expect("Failed to lock exporter")
+      .finish();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::json;
+
+  use super::{OtlpOutput, parse_traceparent, string_value, to_span_attributes};
+
+  fn find<'a>(attributes: &'a [(String, serde_json::Value)], key: &str) -> Option<&'a str> {
+    attributes
+      .iter()
+      .find(|(k, _)| k == key)
+      .and_then(|(_, value)| value.get("stringValue"))
+      .and_then(serde_json::Value::as_str)
+  }
+
+  #[test]
+  fn should_parse_traceparent() {
+    assert_eq!(
+      parse_traceparent("00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01\n"),
+      Some((
+        "0af7651916cd43dd8448eb211c80319c".to_string(),
+        "b7ad6b7169203331".to_string()
+      ))
+    );
+    // uppercase hex, wrong lengths and missing parts are rejected
+    assert_eq!(
+      parse_traceparent("00-0AF7651916CD43DD8448EB211C80319C-b7ad6b7169203331-01"),
+      None
+    );
+    assert_eq!(
+      parse_traceparent("00-0af7651916cd43dd-b7ad6b7169203331-01"),
+      None
+    );
+    assert_eq!(
+      parse_traceparent("00-0af7651916cd43dd8448eb211c80319c-b7ad6b71"),
+      None
+    );
+    assert_eq!(parse_traceparent("00"), None);
+  }
+
+  #[test]
+  fn should_parse_http_output() {
+    let Ok(OtlpOutput::Http(client)) = OtlpOutput::new("http://localhost:4318") else {
+      panic!("should create http output");
+    };
+    assert_eq!(client.url(""), "http://localhost:4318/v1/traces");
+    let Ok(OtlpOutput::Http(client)) = OtlpOutput::new("http://collector/otlp/traces") else {
+      panic!("should create http output");
+    };
+    assert_eq!(client.url(""), "http://collector:80/otlp/traces");
+    assert!(OtlpOutput::new("https://localhost:4318").is_err());
+  }
+
+  #[test]
+  fn should_map_span_attributes() {
+    let attributes = to_span_attributes(
+      "build_module",
+      vec![
+        ("module.identifier".to_string(), string_value("./a.js")),
+        ("resource".to_string(), string_value("/src/a.js")),
+        (
+          "module.loaders".to_string(),
+          string_value("builtin:swc-loader"),
+        ),
+        ("custom".to_string(), json!({ "intValue": "1" })),
+      ],
+    );
+    assert_eq!(
+      find(&attributes, "rspack.module.identifier"),
+      Some("./a.js")
+    );
+    assert_eq!(
+      find(&attributes, "rspack.module.resource"),
+      Some("/src/a.js")
+    );
+    assert_eq!(
+      find(&attributes, "rspack.module.loaders"),
+      Some("builtin:swc-loader")
+    );
+    assert!(attributes.iter().any(|(k, _)| k == "custom"));
+    assert_eq!(find(&attributes, "rspack.span.category"), Some("phase"));
+
+    let attributes = to_span_attributes("hook:CompilerThisCompilation", vec![]);
+    assert_eq!(
+      find(&attributes, "rspack.hook.name"),
+      Some("CompilerThisCompilation")
+    );
+    assert_eq!(find(&attributes, "rspack.span.category"), Some("hook"));
+
+    let attributes = to_span_attributes("run_loader:builtin:swc-loader", vec![]);
+    assert_eq!(find(&attributes, "rspack.span.category"), Some("loader"));
+
+    let attributes = to_span_attributes(
+      "CssChunkingPlugin",
+      vec![
+        (
+          "perfetto.process_name".to_string(),
+          string_value("Plugin Analysis"),
+        ),
+        (
+          "perfetto.track_name".to_string(),
+          string_value("CssChunkingPlugin"),
+        ),
+      ],
+    );
+    assert_eq!(
+      find(&attributes, "rspack.plugin.name"),
+      Some("CssChunkingPlugin")
+    );
+    assert_eq!(find(&attributes, "rspack.span.category"), Some("plugin"));
+    // the perfetto fields are not exported as attributes
+    assert!(!attributes.iter().any(|(k, _)| k.starts_with("perfetto.")));
+  }
+}
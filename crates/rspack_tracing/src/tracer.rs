@@ -13,7 +13,7 @@ pub struct TraceEvent {
   pub args: Option<HashMap<String, String>>,
   // track_uuid
   pub uuid: u32,
-  // timestamp in microseconds
+  // timestamp in nanoseconds
   pub ts: u64,
   // ph
   pub ph: String,
@@ -0,0 +1,372 @@
+use std::{
+  collections::{BTreeMap, BTreeSet},
+  path::{Path, PathBuf},
+  sync::atomic::{AtomicU32, Ordering},
+  time::UNIX_EPOCH,
+};
+
+use rspack_error::{Result, error};
+use rspack_paths::ArcPath;
+use rspack_util::{current_time, fx_hash::FxHashSet as HashSet};
+
+const HEADER: &[u8] = b"rspack-watcher-clock 2\n";
+
+static CLOCK_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+/// The digest of a path on disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathDigest {
+  Missing,
+  File {
+    mtime: u64,
+    size: u64,
+  },
+  /// The children are recorded to list the created and removed entries of a directory,
+  /// the mtime of a directory only tells that something in it has changed.
+  Dir {
+    mtime: u64,
+    children: BTreeSet<String>,
+  },
+}
+
+impl PathDigest {
+  const MISSING: u8 = 0;
+  const FILE: u8 = 1;
+  const DIR: u8 = 2;
+
+  fn read(path: &Path) -> Self {
+    let Ok(metadata) = std::fs::metadata(path) else {
+      return Self::Missing;
+    };
+    let mtime = metadata
+      .modified()
+      .or_else(|_| metadata.created())
+      .ok()
+      .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+      .map_or(0, |duration| duration.as_nanos() as u64);
+    if metadata.is_dir() {
+      let children = std::fs::read_dir(path)
+        .map(|entries| {
+          entries
+            .filter_map(|entry| Some(entry.ok()?.file_name().to_string_lossy().to_string()))
+            .collect()
+        })
+        .unwrap_or_default();
+      Self::Dir { mtime, children }
+    } else {
+      Self::File {
+        mtime,
+        size: metadata.len(),
+      }
+    }
+  }
+
+  fn write(&self, writer: &mut Writer) {
+    match self {
+      Self::Missing => writer.u8(Self::MISSING),
+      Self::File { mtime, size } => {
+        writer.u8(Self::FILE);
+        writer.u64(*mtime);
+        writer.u64(*size);
+      }
+      Self::Dir { mtime, children } => {
+        writer.u8(Self::DIR);
+        writer.u64(*mtime);
+        writer.u64(children.len() as u64);
+        for child in children {
+          writer.str(child);
+        }
+      }
+    }
+  }
+
+  fn read_from(reader: &mut Reader) -> Option<Self> {
+    Some(match reader.u8()? {
+      Self::MISSING => Self::Missing,
+      Self::FILE => Self::File {
+        mtime: reader.u64()?,
+        size: reader.u64()?,
+      },
+      Self::DIR => {
+        let mtime = reader.u64()?;
+        let len = reader.u64()?;
+        let children = (0..len)
+          .map(|_| reader.str())
+          .collect::<Option<BTreeSet<_>>>()?;
+        Self::Dir { mtime, children }
+      }
+      _ => return None,
+    })
+  }
+}
+
+/// A writer of the length-prefixed clock file, so any path can be saved without escaping.
+#[derive(Default)]
+struct Writer(Vec<u8>);
+
+impl Writer {
+  fn u8(&mut self, value: u8) {
+    self.0.push(value);
+  }
+
+  fn u64(&mut self, value: u64) {
+    self.0.extend_from_slice(&value.to_le_bytes());
+  }
+
+  fn str(&mut self, value: &str) {
+    self.u64(value.len() as u64);
+    self.0.extend_from_slice(value.as_bytes());
+  }
+}
+
+/// A reader of the content written by [`Writer`], it returns `None` for truncated or invalid content.
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+  fn bytes(&mut self, len: usize) -> Option<&[u8]> {
+    if self.0.len() < len {
+      return None;
+    }
+    let (bytes, rest) = self.0.split_at(len);
+    self.0 = rest;
+    Some(bytes)
+  }
+
+  fn u8(&mut self) -> Option<u8> {
+    self.bytes(1).map(|bytes| bytes[0])
+  }
+
+  fn u64(&mut self) -> Option<u64> {
+    self
+      .bytes(8)
+      .map(|bytes| u64::from_le_bytes(bytes.try_into().expect("should be 8 bytes")))
+  }
+
+  fn str(&mut self) -> Option<String> {
+    let len = usize::try_from(self.u64()?).ok()?;
+    String::from_utf8(self.bytes(len)?.to_vec()).ok()
+  }
+}
+
+/// The paths changed since a clock
+#[derive(Debug, Default, Clone)]
+pub struct ClockChanges {
+  /// The paths which are created or modified, it can be passed to compiler as modified files.
+  pub changed_files: HashSet<String>,
+  /// The paths which are deleted, it can be passed to compiler as removed files.
+  pub deleted_files: HashSet<String>,
+}
+
+impl ClockChanges {
+  pub fn is_empty(&self) -> bool {
+    self.changed_files.is_empty() && self.deleted_files.is_empty()
+  }
+}
+
+/// A saved state of the watched paths, like the clock of watchman.
+///
+/// The clock records the mtime and size of each path when it is captured.
+/// It can be saved to disk before the watcher exits and loaded on the next startup
+/// to answer which paths are changed since then, without waiting for any events.
+#[derive(Debug, Clone)]
+pub struct FsWatcherClock {
+  id: String,
+  entries: BTreeMap<PathBuf, PathDigest>,
+}
+
+impl FsWatcherClock {
+  /// Capture the current state of the paths on disk.
+  pub fn capture(paths: impl Iterator<Item = ArcPath>) -> Self {
+    Self {
+      id: format!(
+        "c:{}:{}:{}",
+        current_time(),
+        std::process::id(),
+        CLOCK_COUNTER.fetch_add(1, Ordering::Relaxed)
+      ),
+      entries: paths
+        .map(|path| {
+          let digest = PathDigest::read(&path);
+          (path.to_path_buf(), digest)
+        })
+        .collect(),
+    }
+  }
+
+  /// The unique id of the clock
+  pub fn id(&self) -> &str {
+    &self.id
+  }
+
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  /// The paths recorded by the clock, the changes of other paths are unknown.
+  pub fn paths(&self) -> impl Iterator<Item = &Path> {
+    self.entries.keys().map(PathBuf::as_path)
+  }
+
+  /// Calculate the paths changed since this clock was captured.
+  ///
+  /// The entries created or removed in a recorded directory are listed as well.
+  pub fn changes(&self) -> ClockChanges {
+    let mut changes = ClockChanges::default();
+    for (path, digest) in &self.entries {
+      let current = PathDigest::read(path);
+      if current == *digest {
+        continue;
+      }
+      if let (PathDigest::Dir { children: old, .. }, PathDigest::Dir { children: new, .. }) =
+        (digest, &current)
+      {
+        let child = |name: &String| path.join(name).to_string_lossy().to_string();
+        changes.changed_files.extend(new.difference(old).map(child));
+        changes.deleted_files.extend(old.difference(new).map(child));
+      }
+      let path = path.to_string_lossy().to_string();
+      if current == PathDigest::Missing {
+        changes.deleted_files.insert(path);
+      } else {
+        changes.changed_files.insert(path);
+      }
+    }
+    changes
+  }
+
+  /// Save the clock to the file, the parent directory will be created if it does not exist.
+  pub fn save(&self, file: &Path) -> Result<()> {
+    let mut writer = Writer::default();
+    writer.0.extend_from_slice(HEADER);
+    writer.str(&self.id);
+    writer.u64(self.entries.len() as u64);
+    for (path, digest) in &self.entries {
+      writer.str(&path.to_string_lossy());
+      digest.write(&mut writer);
+    }
+    if let Some(parent) = file.parent() {
+      std::fs::create_dir_all(parent)
+        .map_err(|e| error!("failed to create clock dir {}: {e}", parent.display()))?;
+    }
+    std::fs::write(file, writer.0)
+      .map_err(|e| error!("failed to save clock {}: {e}", file.display()))
+  }
+
+  /// Load the clock saved by [`FsWatcherClock::save`].
+  pub fn load(file: &Path) -> Result<Self> {
+    let content =
+      std::fs::read(file).map_err(|e| error!("failed to read clock {}: {e}", file.display()))?;
+    let parse = || {
+      let mut reader = Reader(content.strip_prefix(HEADER)?);
+      let id = reader.str()?;
+      let len = reader.u64()?;
+      let entries = (0..len)
+        .map(|_| {
+          Some((
+            PathBuf::from(reader.str()?),
+            PathDigest::read_from(&mut reader)?,
+          ))
+        })
+        .collect::<Option<BTreeMap<_, _>>>()?;
+      reader.0.is_empty().then_some(Self { id, entries })
+    };
+    parse().ok_or_else(|| error!("invalid clock file {}", file.display()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rspack_paths::ArcPath;
+
+  use super::FsWatcherClock;
+
+  #[test]
+  fn should_report_changes_since_clock() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::write(root.join("a.js"), "a").unwrap();
+    std::fs::write(root.join("b.js"), "b").unwrap();
+    std::fs::write(root.join("c.js"), "c").unwrap();
+
+    let clock = FsWatcherClock::capture(
+      ["a.js", "b.js", "c.js", "d.js"]
+        .into_iter()
+        .map(|name| ArcPath::from(root.join(name))),
+    );
+    let clock_file = root.join("state/clock");
+    clock.save(&clock_file).unwrap();
+    assert!(clock.changes().is_empty());
+
+    std::fs::write(root.join("a.js"), "modified").unwrap();
+    std::fs::remove_file(root.join("b.js")).unwrap();
+    std::fs::write(root.join("d.js"), "d").unwrap();
+
+    let clock = FsWatcherClock::load(&clock_file).unwrap();
+    assert_eq!(clock.len(), 4);
+    let changes = clock.changes();
+    let path = |name: &str| root.join(name).to_string_lossy().to_string();
+    assert_eq!(changes.changed_files.len(), 2);
+    assert!(changes.changed_files.contains(&path("a.js")));
+    assert!(changes.changed_files.contains(&path("d.js")));
+    assert_eq!(changes.deleted_files.len(), 1);
+    assert!(changes.deleted_files.contains(&path("b.js")));
+  }
+
+  #[test]
+  fn should_list_entries_of_changed_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().join("src");
+    std::fs::create_dir(&root).unwrap();
+    std::fs::write(root.join("old.js"), "old").unwrap();
+
+    let clock_file = dir.path().join("clock");
+    FsWatcherClock::capture(std::iter::once(ArcPath::from(root.as_path())))
+      .save(&clock_file)
+      .unwrap();
+
+    std::fs::remove_file(root.join("old.js")).unwrap();
+    std::fs::write(root.join("new.js"), "new").unwrap();
+
+    let changes = FsWatcherClock::load(&clock_file).unwrap().changes();
+    let path = |name: &str| root.join(name).to_string_lossy().to_string();
+    assert!(changes.changed_files.contains(&path("new.js")));
+    assert!(changes.deleted_files.contains(&path("old.js")));
+  }
+
+  // tabs and newlines are not allowed in file names on windows
+  #[cfg(unix)]
+  #[test]
+  fn should_save_paths_with_special_characters() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("a\tb\nc.js");
+    std::fs::write(&file, "a").unwrap();
+
+    let clock_file = dir.path().join("clock");
+    let clock = FsWatcherClock::capture(std::iter::once(ArcPath::from(file.as_path())));
+    clock.save(&clock_file).unwrap();
+
+    let loaded = FsWatcherClock::load(&clock_file).unwrap();
+    assert_eq!(loaded.id(), clock.id());
+    assert_eq!(loaded.paths().collect::<Vec<_>>(), vec![file.as_path()]);
+    assert!(loaded.changes().is_empty());
+  }
+
+  #[test]
+  fn should_reject_invalid_clock_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let clock_file = dir.path().join("clock");
+    std::fs::write(&clock_file, "invalid").unwrap();
+    assert!(FsWatcherClock::load(&clock_file).is_err());
+    assert!(FsWatcherClock::load(&dir.path().join("not_exist")).is_err());
+
+    let clock = FsWatcherClock::capture(std::iter::once(ArcPath::from(dir.path())));
+    clock.save(&clock_file).unwrap();
+    let content = std::fs::read(&clock_file).unwrap();
+    std::fs::write(&clock_file, &content[..content.len() - 1]).unwrap();
+    assert!(FsWatcherClock::load(&clock_file).is_err());
+  }
+}
@@ -0,0 +1,79 @@
+use std::path::Path;
+
+use rspack_paths::ArcPath;
+use rspack_util::fx_hash::FxDashMap;
+use xxhash_rust::xxh3::xxh3_128;
+
+/// The 128-bit xxh3 hash of the file content.
+///
+/// A wider hash than the fx hash is used, since a collision silently drops a real change.
+pub(crate) type ContentHash = u128;
+
+/// `ContentHashes` keeps the content hashes of the watched files.
+///
+/// It is used to suppress the events which do not change the content of files,
+/// e.g. `git checkout` or editors touching files, and to pair the removed and created files as renames.
+#[derive(Debug, Default)]
+pub struct ContentHashes {
+  hashes: FxDashMap<ArcPath, ContentHash>,
+}
+
+impl ContentHashes {
+  /// Returns the content hash of the file, `None` if it is not a readable file.
+  pub fn hash_file(path: &Path) -> Option<ContentHash> {
+    if !path.is_file() {
+      return None;
+    }
+    let content = std::fs::read(path).ok()?;
+    Some(xxh3_128(&content))
+  }
+
+  /// Record the current content hash of the file if it is not recorded.
+  pub fn record(&self, path: &ArcPath) {
+    if self.hashes.contains_key(path) {
+      return;
+    }
+    if let Some(hash) = Self::hash_file(path) {
+      self.hashes.insert(path.clone(), hash);
+    }
+  }
+
+  /// Update the content hash of the file.
+  ///
+  /// Returns `None` if the content is not changed, otherwise returns the new hash,
+  /// which is also `None` if the path is not a readable file.
+  pub fn update(&self, path: &ArcPath) -> Option<Option<ContentHash>> {
+    let Some(hash) = Self::hash_file(path) else {
+      self.hashes.remove(path);
+      return Some(None);
+    };
+    match self.hashes.insert(path.clone(), hash) {
+      Some(old) if old == hash => None,
+      _ => Some(Some(hash)),
+    }
+  }
+
+  /// Remove the file and returns the last known content hash of it.
+  pub fn remove(&self, path: &ArcPath) -> Option<ContentHash> {
+    self.hashes.remove(path).map(|(_, hash)| hash)
+  }
+
+  /// Evict the hashes of the unwatched files and the files under the unwatched directories.
+  pub fn evict<'a>(
+    &self,
+    files: impl Iterator<Item = &'a ArcPath>,
+    directories: impl Iterator<Item = &'a ArcPath>,
+  ) {
+    for file in files {
+      self.hashes.remove(file);
+    }
+    let directories = directories.collect::<Vec<_>>();
+    if !directories.is_empty() {
+      self.hashes.retain(|path, _| {
+        !directories
+          .iter()
+          .any(|directory| path.starts_with(directory.as_ref()))
+      });
+    }
+  }
+}
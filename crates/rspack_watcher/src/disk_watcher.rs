@@ -1,25 +1,80 @@
 use std::{sync::Arc, time::Duration};
 
-use notify::{Event, EventKind, RecommendedWatcher, Watcher, event::ModifyKind};
+use notify::{
+  Event, EventKind, PollWatcher, RecommendedWatcher, Watcher,
+  event::{ModifyKind, RenameMode},
+};
 use rspack_paths::ArcPath;
 use rspack_util::fx_hash::FxHashSet as HashSet;
 
-use crate::{FsEventKind, WatchPattern, trigger};
+use crate::{FsEventKind, FsWatcherRules, WatchPattern, trigger};
+
+/// The default interval in milliseconds to poll the paths matching [`crate::FsWatcherRules::poll`].
+const DEFAULT_FALLBACK_POLL_INTERVAL: u32 = 1000;
 
 /// `DiskWatcher` is responsible for managing the underlying file system watcher
 /// and keeping track of the currently watched paths.
 pub struct DiskWatcher {
   /// The actual file system watcher from the `notify` crate.
   inner: Option<RecommendedWatcher>,
+  /// The polling watcher for the paths where native events are not reliable, e.g. network mounts.
+  fallback: Option<PollWatcher>,
+  rules: Arc<FsWatcherRules>,
   /// A set of pattern that are currently being watched.
   watch_patterns: HashSet<WatchPattern>,
 }
 
+fn create_event_handler(
+  trigger: Arc<trigger::Trigger>,
+) -> impl Fn(notify::Result<Event>) + Send + 'static {
+  move |result: notify::Result<Event>| match result {
+    Ok(event) => {
+      let paths = &event.paths;
+
+      if paths.is_empty() {
+        return; // Ignore events with no paths
+      }
+
+      let kind = match event.kind {
+        EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+          FsEventKind::Create
+        }
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if paths.len() == 2 => {
+          // /path/to/index.js -> /path/to/index.js.map
+          // path/to/index.js is removed, and path/to/index.js.map is created,
+          // the executor pairs them as a rename by the content hash.
+          trigger.on_event(&ArcPath::from(paths[0].as_path()), FsEventKind::Remove);
+          trigger.on_event(&ArcPath::from(paths[1].as_path()), FsEventKind::Create);
+          return;
+        }
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) | EventKind::Remove(_) => {
+          FsEventKind::Remove
+        }
+        EventKind::Modify(
+          ModifyKind::Data(_) | ModifyKind::Any | ModifyKind::Name(_) | ModifyKind::Metadata(_),
+        ) => FsEventKind::Change,
+        _ => return, // Ignore other kinds of events
+      };
+      let paths = event.paths.into_iter().map(ArcPath::from);
+      for path in paths {
+        trigger.on_event(&path, kind);
+      }
+    }
+
+    Err(e) => {
+      // Handle error, e.g., log it or notify the user
+      eprintln!("Error in file watcher: {e:?}",);
+    }
+  }
+}
+
 impl DiskWatcher {
   /// Creates a new `DiskWatcher` with the given configuration and trigger.
   pub fn new(
     follow_symlinks: bool,
     poll_interval: Option<u32>,
+    fallback_poll_interval: Option<u32>,
+    rules: Arc<FsWatcherRules>,
     trigger: Arc<trigger::Trigger>,
   ) -> Self {
     let config = match poll_interval {
@@ -29,47 +84,36 @@ impl DiskWatcher {
       None => notify::Config::default().with_follow_symlinks(follow_symlinks),
     };
 
-    let inner = RecommendedWatcher::new(
-      move |result: notify::Result<Event>| match result {
-        Ok(event) => {
-          let paths = &event.paths;
-
-          if paths.is_empty() {
-            return; // Ignore events with no paths
-          }
-
-          let kind = match event.kind {
-            EventKind::Create(_) => FsEventKind::Create,
-            EventKind::Modify(
-              ModifyKind::Data(_) | ModifyKind::Any | ModifyKind::Name(_) | ModifyKind::Metadata(_),
-            ) => FsEventKind::Change,
-            EventKind::Remove(_) => FsEventKind::Remove,
-            // TODO: handle this case /path/to/index.js -> /path/to/index.js.map
-            // path/to/index.js should be removed, and path/to/index.js.map should be changed
-            // Now /path/to/index.js and /path/to/index.js.map will both be changed
-            _ => return, // Ignore other kinds of events
-          };
-          let paths = event.paths.into_iter().map(ArcPath::from);
-          for path in paths {
-            trigger.on_event(&path, kind);
-          }
-        }
+    let inner = RecommendedWatcher::new(create_event_handler(trigger.clone()), config)
+      .expect("Failed to create disk watcher");
 
-        Err(e) => {
-          // Handle error, e.g., log it or notify the user
-          eprintln!("Error in file watcher: {e:?}",);
-        }
-      },
-      config,
-    )
-    .expect("Failed to create disk watcher");
+    let fallback = (!rules.poll.is_empty()).then(|| {
+      let interval = fallback_poll_interval.unwrap_or(DEFAULT_FALLBACK_POLL_INTERVAL);
+      PollWatcher::new(
+        create_event_handler(trigger),
+        notify::Config::default()
+          .with_follow_symlinks(follow_symlinks)
+          .with_poll_interval(Duration::from_millis(u64::from(interval))),
+      )
+      .expect("Failed to create polling watcher")
+    });
 
     DiskWatcher {
       inner: Some(inner),
+      fallback,
+      rules,
       watch_patterns: HashSet::default(),
     }
   }
 
+  /// Returns the watcher for the path, the polling watcher is used if the path matches the poll rules.
+  fn watcher_for(&mut self, path: &ArcPath) -> Option<&mut dyn Watcher> {
+    if self.fallback.is_some() && self.rules.should_poll(path) {
+      return self.fallback.as_mut().map(|w| w as &mut dyn Watcher);
+    }
+    self.inner.as_mut().map(|w| w as &mut dyn Watcher)
+  }
+
   /// Watches the given path with the specified recursive mode.
   ///
   /// # Returns
@@ -89,14 +133,18 @@ impl DiskWatcher {
     let current_should_watch_paths = patterns.iter().map(|p| &p.path).collect::<HashSet<_>>();
 
     // notify::Watcher only unwatchs the path, so we need to check which paths instead of patterns.
-    for pattern in already_watched_paths.difference(&current_should_watch_paths) {
+    let unwatched_paths = already_watched_paths
+      .difference(&current_should_watch_paths)
+      .map(|path| ArcPath::clone(path))
+      .collect::<Vec<_>>();
+    for pattern in unwatched_paths {
       // If the path is no longer in the patterns to watch, unwatch it
-      if let Some(watcher) = &mut self.inner {
+      if let Some(watcher) = self.watcher_for(&pattern) {
         // Currently, we unwatch the path even if it might still be in other patterns, as we lack a way to track paths precisely.
         // The `notify` crate automatically removes the watch path when it is removed internally.
         // If we attempt to unwatch the path again, it may return an error.
         // Consider enhancing the tracking of paths to avoid unnecessary `unwatch` calls and handle errors more robustly.
-        if let Err(e) = watcher.unwatch(pattern)
+        if let Err(e) = watcher.unwatch(&pattern)
           && !matches!(e.kind, notify::ErrorKind::WatchNotFound)
         {
           return Err(rspack_error::error!(e.to_string()));
@@ -109,7 +157,7 @@ impl DiskWatcher {
         continue;
       }
 
-      if let Some(watcher) = &mut self.inner {
+      if let Some(watcher) = self.watcher_for(&pattern.path) {
         watcher
           .watch(&pattern.path, pattern.mode)
           .map_err(|e| rspack_error::error!(e.to_string()))?;
@@ -124,5 +172,6 @@ impl DiskWatcher {
   pub fn close(&mut self) {
     // the trigger.tx is dropped in the FsWatcher
     std::mem::drop(self.inner.take());
+    std::mem::drop(self.fallback.take());
   }
 }
@@ -3,36 +3,124 @@ use std::sync::{
   atomic::{AtomicBool, Ordering},
 };
 
-use rspack_util::fx_hash::FxHashSet as HashSet;
+use rspack_util::fx_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use tokio::sync::{
   Mutex,
   mpsc::{self, UnboundedReceiver, UnboundedSender},
 };
 
 use super::{EventAggregateHandler, EventHandler, FsEventKind};
-use crate::EventBatch;
+use crate::{ContentHash, EventBatch, FsChange, FsChangeReason, FsEvent, FsWatcherRules};
 
 type ThreadSafetyReceiver<T> = ThreadSafety<UnboundedReceiver<T>>;
 type ThreadSafety<T> = Arc<Mutex<T>>;
 
+/// The merged events of a path during the aggregate timeout
+#[derive(Debug)]
+struct PendingChange {
+  /// The kind of the last event
+  kind: FsEventKind,
+  /// Whether the path is created during the aggregate timeout
+  created: bool,
+  /// The latest content hash, or the last known one if the file is removed
+  hash: Option<ContentHash>,
+}
+
 #[derive(Debug, Default)]
 struct FilesData {
-  changed: HashSet<String>,
-  deleted: HashSet<String>,
+  pending: HashMap<String, PendingChange>,
+  /// Whether there are pending source files
+  has_source: bool,
+  /// Whether there are pending generated files
+  has_generated: bool,
 }
 
 impl FilesData {
   fn is_empty(&self) -> bool {
-    self.changed.is_empty() && self.deleted.is_empty()
+    self.pending.is_empty()
+  }
+
+  fn add_event(&mut self, path: String, event: &FsEvent) {
+    let change = self.pending.entry(path).or_insert(PendingChange {
+      kind: event.kind,
+      created: false,
+      hash: None,
+    });
+    change.kind = event.kind;
+    match event.kind {
+      FsEventKind::Create => {
+        change.created = true;
+        change.hash = event.hash;
+      }
+      FsEventKind::Change | FsEventKind::Remove => {
+        change.hash = event.hash.or(change.hash);
+      }
+    }
+  }
+
+  /// Convert the pending events to the changes sorted by path.
+  ///
+  /// A removed file and a created file with the same content hash are paired as a rename.
+  fn into_changes(self) -> Vec<FsChange> {
+    let mut pending = self.pending.into_iter().collect::<Vec<_>>();
+    pending.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut removed = HashMap::default();
+    for (path, change) in &pending {
+      if change.kind == FsEventKind::Remove
+        && let Some(hash) = change.hash
+      {
+        removed.entry(hash).or_insert_with(|| path.clone());
+      }
+    }
+
+    let mut renamed = HashSet::default();
+    let mut changes = pending
+      .into_iter()
+      .map(|(path, change)| {
+        let reason = match change.kind {
+          FsEventKind::Remove => FsChangeReason::Removed,
+          _ if change.created => match change.hash.and_then(|hash| removed.remove(&hash)) {
+            Some(from) => {
+              renamed.insert(from.clone());
+              FsChangeReason::Renamed { from }
+            }
+            None => FsChangeReason::Created,
+          },
+          _ => FsChangeReason::Modified,
+        };
+        FsChange { path, reason }
+      })
+      .collect::<Vec<_>>();
+    changes
+      .retain(|change| change.reason != FsChangeReason::Removed || !renamed.contains(&change.path));
+    changes
+  }
+
+  /// The aggregate timeout for the pending files, the longest one is used
+  /// if both source files and generated files are pending.
+  fn aggregate_timeout(&self, timeouts: AggregateTimeouts) -> u64 {
+    match (self.has_source, self.has_generated) {
+      (true, true) => timeouts.source.max(timeouts.generated),
+      (false, true) => timeouts.generated,
+      _ => timeouts.source,
+    }
   }
 }
 
+#[derive(Debug, Clone, Copy)]
+struct AggregateTimeouts {
+  source: u64,
+  generated: u64,
+}
+
 /// `WatcherExecutor` is responsible for managing the execution of file system event handlers,
 /// aggregating file change and delete events, and invoking the provided event handler after
 /// a configurable aggregate timeout. It receives events from a channel, tracks changed and
 /// deleted files, and coordinates the event handling logic.
 pub struct Executor {
-  aggregate_timeout: u32,
+  aggregate_timeouts: AggregateTimeouts,
+  rules: Arc<FsWatcherRules>,
   rx: ThreadSafetyReceiver<EventBatch>,
   files_data: ThreadSafety<FilesData>,
   exec_aggregate_tx: UnboundedSender<ExecAggregateEvent>,
@@ -65,8 +153,17 @@ enum ExecEvent {
 }
 
 impl Executor {
-  /// Create a new `WatcherExecutor` with the given receiver and optional aggregate timeout.
-  pub fn new(rx: UnboundedReceiver<EventBatch>, aggregate_timeout: Option<u32>) -> Self {
+  /// Create a new `WatcherExecutor` with the given receiver and optional aggregate timeouts.
+  ///
+  /// The `generated_aggregate_timeout` is used for the generated files matching the watch rules,
+  /// it falls back to `aggregate_timeout`.
+  pub fn new(
+    rx: UnboundedReceiver<EventBatch>,
+    aggregate_timeout: Option<u32>,
+    generated_aggregate_timeout: Option<u32>,
+    rules: Arc<FsWatcherRules>,
+  ) -> Self {
+    let aggregate_timeout = aggregate_timeout.unwrap_or(DEFAULT_AGGREGATE_TIMEOUT);
     let (exec_aggregate_tx, exec_aggregate_rx) = mpsc::unbounded_channel::<ExecAggregateEvent>();
     let (exec_tx, exec_rx) = mpsc::unbounded_channel::<ExecEvent>();
 
@@ -82,7 +179,11 @@ impl Executor {
       exec_tx,
       execute_aggregate_handle: None,
       execute_handle: None,
-      aggregate_timeout: aggregate_timeout.unwrap_or(DEFAULT_AGGREGATE_TIMEOUT),
+      aggregate_timeouts: AggregateTimeouts {
+        source: u64::from(aggregate_timeout),
+        generated: u64::from(generated_aggregate_timeout.unwrap_or(aggregate_timeout)),
+      },
+      rules,
     }
   }
 
@@ -133,22 +234,20 @@ impl Executor {
       let exec_tx = self.exec_tx.clone();
       let paused = Arc::clone(&self.paused);
       let aggregate_running = Arc::clone(&self.aggregate_running);
+      let rules = Arc::clone(&self.rules);
 
       let future = async move {
         while let Some(events) = rx.lock().await.recv().await {
           for event in &events {
+            let is_generated = rules.is_generated(&event.path);
             let path = event.path.to_string_lossy().to_string();
-            match event.kind {
-              FsEventKind::Change => {
-                files_data.lock().await.changed.insert(path);
-              }
-              FsEventKind::Remove => {
-                files_data.lock().await.deleted.insert(path);
-              }
-              FsEventKind::Create => {
-                files_data.lock().await.changed.insert(path);
-              }
+            let mut data = files_data.lock().await;
+            if is_generated {
+              data.has_generated = true;
+            } else {
+              data.has_source = true;
             }
+            data.add_event(path, event);
           }
 
           if !paused.load(Ordering::Relaxed) && !aggregate_running.load(Ordering::Relaxed) {
@@ -182,7 +281,7 @@ impl Executor {
       event_aggregate_handler,
       Arc::clone(&self.exec_aggregate_rx),
       Arc::clone(&self.files_data),
-      self.aggregate_timeout as u64,
+      self.aggregate_timeouts,
       Arc::clone(&self.aggregate_running),
     ));
 
@@ -231,7 +330,7 @@ fn create_execute_aggregate_task(
   event_handler: Box<dyn EventAggregateHandler + Send>,
   exec_aggregate_rx: ThreadSafetyReceiver<ExecAggregateEvent>,
   files: ThreadSafety<FilesData>,
-  aggregate_timeout: u64,
+  aggregate_timeouts: AggregateTimeouts,
   running: Arc<AtomicBool>,
 ) -> tokio::task::JoinHandle<()> {
   let future = async move {
@@ -248,8 +347,17 @@ fn create_execute_aggregate_task(
 
       if let ExecAggregateEvent::Execute = aggregate_rx {
         running.store(true, Ordering::Relaxed);
-        // Wait for the aggregate timeout before executing the handler
-        tokio::time::sleep(tokio::time::Duration::from_millis(aggregate_timeout)).await;
+        // Wait for the aggregate timeout before executing the handler,
+        // the timeout may be extended by the generated files arrived during waiting.
+        let mut waited = 0;
+        loop {
+          let timeout = files.lock().await.aggregate_timeout(aggregate_timeouts);
+          if waited >= timeout {
+            break;
+          }
+          tokio::time::sleep(tokio::time::Duration::from_millis(timeout - waited)).await;
+          waited = timeout;
+        }
 
         // Get the files to process
         let files = {
@@ -261,8 +369,8 @@ fn create_execute_aggregate_task(
           std::mem::take(&mut *files)
         };
 
-        // Call the event handler with the changed and deleted files
-        event_handler.on_event_handle(files.changed, files.deleted);
+        // Call the event handler with the changes of files
+        event_handler.on_changes_handle(files.into_changes());
         running.store(false, Ordering::Relaxed);
       }
     }
@@ -270,3 +378,57 @@ fn create_execute_aggregate_task(
 
   tokio::spawn(future)
 }
+
+#[cfg(test)]
+mod tests {
+  use rspack_paths::ArcPath;
+
+  use super::*;
+
+  fn event(path: &str, kind: FsEventKind, hash: Option<ContentHash>) -> (String, FsEvent) {
+    (
+      path.to_string(),
+      FsEvent {
+        path: ArcPath::from(path),
+        kind,
+        hash,
+      },
+    )
+  }
+
+  #[test]
+  fn test_into_changes() {
+    let mut files = FilesData::default();
+    for (path, event) in [
+      event("/src/a.js", FsEventKind::Remove, Some(1)),
+      event("/src/b.js", FsEventKind::Create, Some(1)),
+      event("/src/c.js", FsEventKind::Create, Some(2)),
+      event("/src/c.js", FsEventKind::Change, Some(3)),
+      event("/src/d.js", FsEventKind::Remove, Some(4)),
+      event("/src", FsEventKind::Change, None),
+      event("/src/e.js", FsEventKind::Change, Some(5)),
+    ] {
+      files.add_event(path, &event);
+    }
+
+    let change = |path: &str, reason| FsChange {
+      path: path.to_string(),
+      reason,
+    };
+    assert_eq!(
+      files.into_changes(),
+      vec![
+        change("/src", FsChangeReason::Modified),
+        change(
+          "/src/b.js",
+          FsChangeReason::Renamed {
+            from: "/src/a.js".to_string()
+          }
+        ),
+        change("/src/c.js", FsChangeReason::Created),
+        change("/src/d.js", FsChangeReason::Removed),
+        change("/src/e.js", FsChangeReason::Modified),
+      ]
+    );
+  }
+}
@@ -26,7 +26,7 @@ impl Debug for FsWatcherIgnored {
 
 /// Normalize the path by replacing backslashes with forward slashes.
 /// Smooth out the differences in the system, specifically for Windows
-fn normalize_path<'a>(path: &'a str) -> Cow<'a, str> {
+pub(crate) fn normalize_path<'a>(path: &'a str) -> Cow<'a, str> {
   path.cow_replace("\\", "/")
 }
 
@@ -1,14 +1,19 @@
 mod analyzer;
+mod clock;
+mod content_hash;
 mod disk_watcher;
 mod executor;
 mod ignored;
 mod paths;
+mod rules;
 mod scanner;
 mod trigger;
 
-use std::{sync::Arc, time::SystemTime};
+use std::{path::PathBuf, sync::Arc, time::SystemTime};
 
 use analyzer::{Analyzer, RecommendedAnalyzer};
+pub use clock::{ClockChanges, FsWatcherClock};
+use content_hash::{ContentHash, ContentHashes};
 use disk_watcher::DiskWatcher;
 use executor::Executor;
 pub use ignored::FsWatcherIgnored;
@@ -16,6 +21,7 @@ use paths::PathManager;
 use rspack_error::Result;
 use rspack_paths::ArcPath;
 use rspack_util::fx_hash::FxHashSet as HashSet;
+pub use rules::{FsWatcherMatcher, FsWatcherRules};
 use scanner::Scanner;
 use tokio::sync::mpsc;
 use trigger::Trigger;
@@ -37,10 +43,57 @@ pub enum FsEventKind {
 pub(crate) struct FsEvent {
   pub path: ArcPath,
   pub kind: FsEventKind,
+  /// The content hash of the file, it is the last known hash for removed files.
+  pub hash: Option<ContentHash>,
 }
 
 pub(crate) type EventBatch = Vec<FsEvent>;
 
+/// The reason why a path is reported by the watcher
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FsChangeReason {
+  Created,
+  Modified,
+  Removed,
+  /// The path is renamed from another path, which is detected by the same content hash
+  /// of the removed path and the created path.
+  Renamed {
+    from: String,
+  },
+}
+
+/// A changed path with the reason of the change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FsChange {
+  pub path: String,
+  pub reason: FsChangeReason,
+}
+
+impl FsChange {
+  /// Split the changes into changed files and deleted files.
+  ///
+  /// The source path of a rename is treated as deleted and the target path as changed.
+  pub fn into_files(changes: Vec<FsChange>) -> (HashSet<String>, HashSet<String>) {
+    let mut changed_files = HashSet::default();
+    let mut deleted_files = HashSet::default();
+    for change in changes {
+      match change.reason {
+        FsChangeReason::Created | FsChangeReason::Modified => {
+          changed_files.insert(change.path);
+        }
+        FsChangeReason::Removed => {
+          deleted_files.insert(change.path);
+        }
+        FsChangeReason::Renamed { from } => {
+          deleted_files.insert(from);
+          changed_files.insert(change.path);
+        }
+      }
+    }
+    (changed_files, deleted_files)
+  }
+}
+
 /// `EventAggregateHandler` is a trait for handling aggregated file system events.
 /// It provides methods to handle changes and deletions of files, as well as errors.
 /// Implementors of this trait can define custom behavior for these events.
@@ -51,6 +104,14 @@ pub trait EventAggregateHandler {
   /// Handle a batch of file system events.
   fn on_event_handle(&self, _changed_files: HashSet<String>, _deleted_files: HashSet<String>);
 
+  /// Handle a batch of file system events with the reason of each change.
+  ///
+  /// The default implementation splits the changes and calls `on_event_handle`.
+  fn on_changes_handle(&self, changes: Vec<FsChange>) {
+    let (changed_files, deleted_files) = FsChange::into_files(changes);
+    self.on_event_handle(changed_files, deleted_files);
+  }
+
   /// Handle an error that occurs during file system watching.
   fn on_error(&self, _error: rspack_error::Error) {
     // Default implementation does nothing.
@@ -82,6 +143,20 @@ pub struct FsWatcherOptions {
 
   /// The timeout in milliseconds to aggregate events.
   pub aggregate_timeout: Option<u32>,
+
+  /// The timeout in milliseconds to aggregate events of generated files,
+  /// defaults to `aggregate_timeout`.
+  pub generated_aggregate_timeout: Option<u32>,
+
+  /// The interval in milliseconds to poll the paths matching the poll rules.
+  pub fallback_poll_interval: Option<u32>,
+
+  /// The include, exclude, poll and generated rules of watched paths.
+  pub rules: FsWatcherRules,
+
+  /// The file to save the clock of watched paths to each time watching starts,
+  /// it can be loaded on the next startup to get the files changed since then.
+  pub clock_file: Option<PathBuf>,
 }
 
 pub struct FsWatcher {
@@ -91,6 +166,7 @@ pub struct FsWatcher {
   scanner: Scanner,
   analyzer: RecommendedAnalyzer,
   trigger: Option<Arc<Trigger>>,
+  clock_file: Option<PathBuf>,
 }
 
 impl FsWatcher {
@@ -98,15 +174,28 @@ impl FsWatcher {
   pub fn new(options: FsWatcherOptions, ignored: FsWatcherIgnored) -> Self {
     let (tx, rx) = mpsc::unbounded_channel();
 
-    let path_manager = Arc::new(PathManager::new(ignored));
-    let trigger = Arc::new(Trigger::new(Arc::clone(&path_manager), tx.clone()));
+    let rules = Arc::new(options.rules);
+    let path_manager = Arc::new(PathManager::new(ignored, Arc::clone(&rules)));
+    let content_hashes = Arc::new(ContentHashes::default());
+    let trigger = Arc::new(Trigger::new(
+      Arc::clone(&path_manager),
+      Arc::clone(&content_hashes),
+      tx.clone(),
+    ));
     let disk_watcher = DiskWatcher::new(
       options.follow_symlinks,
       options.poll_interval,
+      options.fallback_poll_interval,
+      Arc::clone(&rules),
       trigger.clone(),
     );
-    let executor = Executor::new(rx, options.aggregate_timeout);
-    let scanner = Scanner::new(tx, Arc::clone(&path_manager));
+    let executor = Executor::new(
+      rx,
+      options.aggregate_timeout,
+      options.generated_aggregate_timeout,
+      rules,
+    );
+    let scanner = Scanner::new(tx, Arc::clone(&path_manager), content_hashes);
 
     Self {
       disk_watcher,
@@ -115,6 +204,7 @@ impl FsWatcher {
       scanner,
       analyzer: RecommendedAnalyzer::default(),
       trigger: Some(trigger),
+      clock_file: options.clock_file,
     }
   }
 
@@ -141,6 +231,7 @@ impl FsWatcher {
       event_aggregate_handler.on_error(e);
       return;
     };
+    self.save_clock();
 
     self
       .executor
@@ -164,6 +255,28 @@ impl FsWatcher {
     }
   }
 
+  /// Captures a clock of the watched paths.
+  ///
+  /// Save it before the watcher exits, and use [`FsWatcherClock::changes`] on the next startup
+  /// to get the files changed since then.
+  pub fn clock(&self) -> FsWatcherClock {
+    FsWatcherClock::capture(self.path_manager.access().all())
+  }
+
+  /// Saves the clock of the watched paths to the clock file in background.
+  ///
+  /// A missing or stale clock only makes the next startup validate every path,
+  /// so the failure is ignored rather than interrupting watching.
+  fn save_clock(&self) {
+    let Some(clock_file) = self.clock_file.clone() else {
+      return;
+    };
+    let paths = self.path_manager.access().all().collect::<Vec<_>>();
+    tokio::task::spawn_blocking(move || {
+      let _ = FsWatcherClock::capture(paths.into_iter()).save(&clock_file);
+    });
+  }
+
   /// Pauses the file system watcher, stopping the execution of the event loop.
   pub fn pause(&self) -> Result<()> {
     self.executor.pause();
@@ -1,10 +1,10 @@
-use std::{fmt::Debug, ops::Deref, path::PathBuf};
+use std::{fmt::Debug, ops::Deref, path::PathBuf, sync::Arc};
 
 use dashmap::setref::multiple::RefMulti;
 use rspack_error::Result;
 use rspack_paths::{ArcPath, ArcPathDashSet};
 
-use super::FsWatcherIgnored;
+use super::{FsWatcherIgnored, FsWatcherRules};
 
 /// An iterator that chains together references to all files, directories, and missing paths
 /// stored in the [`PathTracker`]. This allows iteration over all registered paths as a single sequence.
@@ -111,12 +111,16 @@ where
 
 impl PathUpdater {
   /// Update the paths in the given set.
-  fn update(self, watch_tracker: &PathTracker, ignored: &FsWatcherIgnored) -> Result<()> {
+  fn update(
+    self,
+    watch_tracker: &PathTracker,
+    should_be_ignored: impl Fn(&ArcPath) -> bool,
+  ) -> Result<()> {
     let added_paths = self.added;
     let removed_paths = self.removed;
 
     for added in added_paths {
-      if ignored.should_be_ignored(added.to_str().expect("Path should be valid UTF-8")) {
+      if should_be_ignored(&added) {
         continue; // Skip ignored paths
       }
 
@@ -186,19 +190,27 @@ pub(crate) struct PathManager {
   directories: PathTracker,
   missing: PathTracker,
   pub ignored: FsWatcherIgnored,
+  pub rules: Arc<FsWatcherRules>,
 }
 
 impl PathManager {
-  /// Create a new `PathManager` with an optional ignored paths filter.
-  pub fn new(ignored: FsWatcherIgnored) -> Self {
+  /// Create a new `PathManager` with an optional ignored paths filter and watch rules.
+  pub fn new(ignored: FsWatcherIgnored, rules: Arc<FsWatcherRules>) -> Self {
     Self {
       files: PathTracker::default(),
       directories: PathTracker::default(),
       missing: PathTracker::default(),
       ignored,
+      rules,
     }
   }
 
+  fn is_ignored(&self, path: &ArcPath) -> bool {
+    self
+      .ignored
+      .should_be_ignored(path.to_str().expect("Path should be valid UTF-8"))
+  }
+
   /// Reset the state of the `PathManager`, clearing all tracked paths.
   pub fn reset(&self) {
     self.files.reset();
@@ -213,9 +225,12 @@ impl PathManager {
     directories: (impl Iterator<Item = ArcPath>, impl Iterator<Item = ArcPath>),
     missing: (impl Iterator<Item = ArcPath>, impl Iterator<Item = ArcPath>),
   ) -> Result<()> {
-    PathUpdater::from(files).update(&self.files, &self.ignored)?;
-    PathUpdater::from(directories).update(&self.directories, &self.ignored)?;
-    PathUpdater::from(missing).update(&self.missing, &self.ignored)?;
+    // include rules only apply to files and missing paths, the directories are always watched
+    let ignore_file = |path: &ArcPath| self.is_ignored(path) || !self.rules.is_included(path);
+    let ignore_directory = |path: &ArcPath| self.is_ignored(path) || self.rules.is_excluded(path);
+    PathUpdater::from(files).update(&self.files, ignore_file)?;
+    PathUpdater::from(directories).update(&self.directories, ignore_directory)?;
+    PathUpdater::from(missing).update(&self.missing, ignore_file)?;
 
     Ok(())
   }
@@ -250,7 +265,11 @@ mod tests {
 
     let path_tracker = PathTracker::default();
 
-    updater.update(&path_tracker, &ignored).unwrap();
+    updater
+      .update(&path_tracker, |path| {
+        ignored.should_be_ignored(path.to_str().unwrap())
+      })
+      .unwrap();
 
     let all = path_tracker.all;
 
@@ -305,7 +324,7 @@ mod tests {
       "**/node_modules/**".to_string(),
       "**/.git/**".to_string(),
     ]);
-    let path_manager = PathManager::new(ignored);
+    let path_manager = PathManager::new(ignored, Default::default());
     let files = (
       vec![ArcPath::from(Utf8Path::new("src/index.js"))].into_iter(),
       vec![].into_iter(),
@@ -341,4 +360,46 @@ mod tests {
       assert!(all_paths.iter().any(|p| p.ends_with(path)));
     }
   }
+
+  #[test]
+  fn test_manager_with_rules() {
+    let rules = FsWatcherRules {
+      include: vec![crate::FsWatcherMatcher::Glob("**/*.js".to_string())],
+      exclude: vec![crate::FsWatcherMatcher::Glob("**/dist/**".to_string())],
+      ..Default::default()
+    };
+    let path_manager = PathManager::new(FsWatcherIgnored::None, Arc::new(rules));
+    let files = (
+      vec![
+        ArcPath::from(Utf8Path::new("src/index.js")),
+        ArcPath::from(Utf8Path::new("src/index.css")),
+        ArcPath::from(Utf8Path::new("dist/main.js")),
+      ]
+      .into_iter(),
+      vec![].into_iter(),
+    );
+    let directories = (
+      vec![
+        ArcPath::from(Utf8Path::new("src")),
+        ArcPath::from(Utf8Path::new("dist/chunks")),
+      ]
+      .into_iter(),
+      vec![].into_iter(),
+    );
+
+    path_manager
+      .update(files, directories, (std::iter::empty(), std::iter::empty()))
+      .unwrap();
+
+    let accessor = path_manager.access();
+    let mut all_paths = accessor
+      .all()
+      .map(|p| p.to_string_lossy().to_string())
+      .collect::<Vec<_>>();
+    all_paths.sort();
+
+    assert_eq!(all_paths.len(), 2);
+    assert!(all_paths[0].ends_with("src"));
+    assert!(all_paths[1].ends_with("src/index.js"));
+  }
 }
@@ -0,0 +1,112 @@
+use std::{fmt::Debug, path::Path};
+
+use fast_glob::glob_match;
+use rspack_regex::RspackRegex;
+
+use crate::ignored::normalize_path;
+
+/// A glob or regex to match the watched paths
+pub enum FsWatcherMatcher {
+  Glob(String),
+  Regex(RspackRegex),
+}
+
+impl Debug for FsWatcherMatcher {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      FsWatcherMatcher::Glob(s) => write!(f, "FsWatcherMatcher::Glob({s})"),
+      FsWatcherMatcher::Regex(reg) => write!(f, "FsWatcherMatcher::Regex({reg:?})"),
+    }
+  }
+}
+
+impl FsWatcherMatcher {
+  pub fn matches(&self, p: &str) -> bool {
+    match self {
+      FsWatcherMatcher::Glob(glob) => glob_match(glob, normalize_path(p).as_bytes()),
+      FsWatcherMatcher::Regex(reg) => reg.test(&normalize_path(p)),
+    }
+  }
+}
+
+fn any_matches(matchers: &[FsWatcherMatcher], path: &Path) -> bool {
+  if matchers.is_empty() {
+    return false;
+  }
+  let path = path.to_string_lossy();
+  matchers.iter().any(|matcher| matcher.matches(&path))
+}
+
+/// `FsWatcherRules` contains structured rules for the watched paths.
+///
+/// They are applied on top of [`crate::FsWatcherIgnored`].
+#[derive(Debug, Default)]
+pub struct FsWatcherRules {
+  /// Only the files and missing paths matching one of them are watched.
+  /// All paths are watched if it is empty.
+  pub include: Vec<FsWatcherMatcher>,
+
+  /// The paths matching one of them are not watched,
+  /// and their events will not change the watched parent directories.
+  pub exclude: Vec<FsWatcherMatcher>,
+
+  /// The paths matching one of them are watched by polling instead of native events,
+  /// e.g. the paths on network mounts.
+  pub poll: Vec<FsWatcherMatcher>,
+
+  /// The paths matching one of them are generated files such as codegen output,
+  /// they use the aggregate timeout of generated files.
+  pub generated: Vec<FsWatcherMatcher>,
+}
+
+impl FsWatcherRules {
+  /// Whether the file or missing path should be watched.
+  pub fn is_included(&self, path: &Path) -> bool {
+    (self.include.is_empty() || any_matches(&self.include, path)) && !self.is_excluded(path)
+  }
+
+  pub fn is_excluded(&self, path: &Path) -> bool {
+    any_matches(&self.exclude, path)
+  }
+
+  pub fn should_poll(&self, path: &Path) -> bool {
+    any_matches(&self.poll, path)
+  }
+
+  pub fn is_generated(&self, path: &Path) -> bool {
+    any_matches(&self.generated, path)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::path::Path;
+
+  use rspack_regex::RspackRegex;
+
+  use super::{FsWatcherMatcher, FsWatcherRules};
+
+  #[test]
+  fn test_rules() {
+    let rules = FsWatcherRules {
+      include: vec![FsWatcherMatcher::Glob("**/src/**".to_string())],
+      exclude: vec![FsWatcherMatcher::Regex(
+        RspackRegex::new(r"\.swp$").expect("should be valid regex"),
+      )],
+      poll: vec![FsWatcherMatcher::Glob("/mnt/**".to_string())],
+      generated: vec![FsWatcherMatcher::Glob("**/__generated__/**".to_string())],
+    };
+
+    assert!(rules.is_included(Path::new("/project/src/index.js")));
+    assert!(!rules.is_included(Path::new("/project/lib/index.js")));
+    assert!(!rules.is_included(Path::new("/project/src/.index.js.swp")));
+    assert!(rules.is_excluded(Path::new("/project/lib/.index.js.swp")));
+    assert!(rules.should_poll(Path::new("/mnt/share/index.js")));
+    assert!(!rules.should_poll(Path::new("/project/src/index.js")));
+    assert!(rules.is_generated(Path::new("/project/src/__generated__/schema.js")));
+
+    let rules = FsWatcherRules::default();
+    assert!(rules.is_included(Path::new("/project/lib/index.js")));
+    assert!(!rules.is_excluded(Path::new("/project/lib/index.js")));
+  }
+}
@@ -4,19 +4,25 @@ use rspack_paths::{ArcPath, ArcPathDashSet};
 use tokio::sync::mpsc::UnboundedSender;
 
 use super::{FsEvent, FsEventKind, PathManager};
-use crate::EventBatch;
+use crate::{ContentHashes, EventBatch};
 
 // Scanner will scann the path whether it is exist or not in disk on initialization
 pub struct Scanner {
   path_manager: Arc<PathManager>,
+  content_hashes: Arc<ContentHashes>,
   tx: Option<UnboundedSender<EventBatch>>,
 }
 
 impl Scanner {
   /// Creates a new `Scanner` that will send events to the provided sender when paths are scanned.
-  pub fn new(tx: UnboundedSender<EventBatch>, path_manager: Arc<PathManager>) -> Self {
+  pub fn new(
+    tx: UnboundedSender<EventBatch>,
+    path_manager: Arc<PathManager>,
+    content_hashes: Arc<ContentHashes>,
+  ) -> Self {
     Self {
       path_manager,
+      content_hashes,
       tx: Some(tx),
     }
   }
@@ -26,6 +32,23 @@ impl Scanner {
   pub fn scan(&self, start_time: SystemTime) {
     if let Some(tx) = self.tx.clone() {
       let accessor = self.path_manager.access();
+      // forget the content hashes of the unwatched paths
+      let removed_files = accessor
+        .files()
+        .2
+        .iter()
+        .map(|file| file.deref().clone())
+        .collect::<Vec<_>>();
+      let removed_directories = accessor
+        .directories()
+        .2
+        .iter()
+        .map(|directory| directory.deref().clone())
+        .collect::<Vec<_>>();
+      self
+        .content_hashes
+        .evict(removed_files.iter(), removed_directories.iter());
+
       // only apply for added files
       let files = accessor
         .files()
@@ -35,9 +58,14 @@ impl Scanner {
         .collect::<Vec<_>>();
       let missing = accessor.missing().0.clone();
       let _tx = tx.clone();
+      let content_hashes = Arc::clone(&self.content_hashes);
       tokio::spawn(async move {
         _ = scan_path_missing(&files, &missing, &_tx);
         _ = scan_path_changed(&files, &start_time, &_tx);
+        // record the initial content hashes, so that the later events without content changes can be suppressed
+        for file in &files {
+          content_hashes.record(file);
+        }
       });
 
       let directories = accessor
@@ -73,6 +101,7 @@ fn scan_path_missing(
     .map(|path| FsEvent {
       path,
       kind: FsEventKind::Remove,
+      hash: None,
     })
     .collect::<Vec<_>>();
   if remove_event.is_empty() {
@@ -93,6 +122,7 @@ fn scan_path_changed(
     .map(|path| FsEvent {
       path,
       kind: FsEventKind::Change,
+      hash: None,
     })
     .collect::<Vec<_>>();
 
@@ -141,7 +171,11 @@ mod tests {
     path_manager.update(files, dirs, missing).unwrap();
 
     let (tx, mut _rx) = tokio::sync::mpsc::unbounded_channel();
-    let mut scanner = Scanner::new(tx, Arc::new(path_manager));
+    let mut scanner = Scanner::new(
+      tx,
+      Arc::new(path_manager),
+      Arc::new(ContentHashes::default()),
+    );
 
     let collector = tokio::spawn(async move {
       let mut collected_events = Vec::new();
@@ -161,11 +195,13 @@ mod tests {
 
     assert!(collected_events.contains(&vec![FsEvent {
       path: ArcPath::from(current_dir.join("___test_file.txt")),
-      kind: FsEventKind::Remove
+      kind: FsEventKind::Remove,
+      hash: None,
     }]));
     assert!(collected_events.contains(&vec![FsEvent {
       path: ArcPath::from(current_dir.join("___test_dir/a/b/c")),
       kind: FsEventKind::Remove,
+      hash: None,
     }]));
   }
 }
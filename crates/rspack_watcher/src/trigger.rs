@@ -4,7 +4,7 @@ use rspack_paths::{ArcPath, ArcPathDashSet};
 use tokio::sync::mpsc::UnboundedSender;
 
 use super::{FsEvent, FsEventKind};
-use crate::{EventBatch, paths::PathManager};
+use crate::{ContentHash, ContentHashes, EventBatch, paths::PathManager};
 /// `DependencyFinder` provides references to sets of files, directories, and missing paths,
 /// allowing efficient lookup and dependency resolution for a given path.
 ///
@@ -89,14 +89,24 @@ impl<'a> DependencyFinder<'a> {
 pub struct Trigger {
   /// Shared reference to the path register, which tracks watched files/directories/missing.
   path_manager: Arc<PathManager>,
+  /// Shared content hashes of the files, used to suppress the events without content changes.
+  content_hashes: Arc<ContentHashes>,
   /// Sender for communicating file system events to the watcher executor.
   tx: UnboundedSender<EventBatch>,
 }
 
 impl Trigger {
-  /// Create a new `Trigger` with the given path register and event sender.
-  pub fn new(path_manager: Arc<PathManager>, tx: UnboundedSender<EventBatch>) -> Self {
-    Self { path_manager, tx }
+  /// Create a new `Trigger` with the given path register, content hashes and event sender.
+  pub fn new(
+    path_manager: Arc<PathManager>,
+    content_hashes: Arc<ContentHashes>,
+    tx: UnboundedSender<EventBatch>,
+  ) -> Self {
+    Self {
+      path_manager,
+      content_hashes,
+      tx,
+    }
   }
 
   /// Called when a file system event occurs.
@@ -112,10 +122,26 @@ impl Trigger {
   /// If the file `/path/to/file.js` is changed, the trigger will send an event for the following paths:
   /// - `/path`
   /// - `/path/to`
+  ///
+  /// The event is dropped if the content hash of the file is not changed.
   pub fn on_event(&self, path: &ArcPath, kind: FsEventKind) {
+    if self.path_manager.rules.is_excluded(path) {
+      return;
+    }
     let finder = self.finder();
     let associated_event = finder.find_associated_event(path, kind);
-    self.trigger_events(associated_event);
+    let hash = match kind {
+      // Keep the last known hash of the removed file to detect renames.
+      FsEventKind::Remove => self.content_hashes.remove(path),
+      // Only the watched files are hashed, the others are never reported.
+      _ if associated_event.is_empty() => return,
+      FsEventKind::Change | FsEventKind::Create => match self.content_hashes.update(path) {
+        Some(hash) => hash,
+        // The content is not changed, e.g. the file is only touched.
+        None => return,
+      },
+    };
+    self.trigger_events(path, hash, associated_event);
   }
 
   /// Helper to construct a `DependencyFinder` for the current path register state.
@@ -133,15 +159,24 @@ impl Trigger {
     }
   }
 
-  /// Sends a group of file system events for the given path and event kind.
+  /// Sends a group of file system events for the given path and event kind,
+  /// the content hash is attached to the event of the origin path.
   /// If the event is successfully sent, it returns true; otherwise, it returns false.
-  fn trigger_events(&self, events: Vec<(ArcPath, FsEventKind)>) -> bool {
+  fn trigger_events(
+    &self,
+    origin: &ArcPath,
+    hash: Option<ContentHash>,
+    events: Vec<(ArcPath, FsEventKind)>,
+  ) -> bool {
     self
       .tx
       .send(
         events
           .into_iter()
-          .map(|(path, kind)| FsEvent { path, kind })
+          .map(|(path, kind)| {
+            let hash = if &path == origin { hash } else { None };
+            FsEvent { path, kind, hash }
+          })
           .collect(),
       )
       .is_ok()
@@ -207,4 +242,75 @@ mod tests {
     assert!(associated_events.contains(&(dir_0, FsEventKind::Change)));
     assert!(associated_events.contains(&(dir_1, FsEventKind::Change)));
   }
+
+  #[test]
+  fn test_suppress_events_without_content_changes() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = ArcPath::from(dir.path().join("index.js"));
+    std::fs::write(&file, "a").unwrap();
+
+    let path_manager = PathManager::default();
+    path_manager
+      .update(
+        (vec![file.clone()].into_iter(), vec![].into_iter()),
+        (vec![].into_iter(), vec![].into_iter()),
+        (vec![].into_iter(), vec![].into_iter()),
+      )
+      .unwrap();
+    let content_hashes = Arc::new(ContentHashes::default());
+    content_hashes.record(&file);
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let trigger = Trigger::new(Arc::new(path_manager), content_hashes, tx);
+
+    // touch without content changes
+    std::fs::write(&file, "a").unwrap();
+    trigger.on_event(&file, FsEventKind::Change);
+    assert!(rx.try_recv().is_err());
+
+    std::fs::write(&file, "b").unwrap();
+    trigger.on_event(&file, FsEventKind::Change);
+    let events = rx.try_recv().unwrap();
+    assert_eq!(events.len(), 1);
+    let hash = events[0].hash;
+    assert!(hash.is_some());
+
+    std::fs::remove_file(&file).unwrap();
+    trigger.on_event(&file, FsEventKind::Remove);
+    let events = rx.try_recv().unwrap();
+    assert_eq!(events[0].kind, FsEventKind::Remove);
+    assert_eq!(events[0].hash, hash);
+  }
+
+  #[test]
+  fn test_skip_hashing_unwatched_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let watched = ArcPath::from(dir.path().join("index.js"));
+    let unwatched = ArcPath::from(dir.path().join("other.js"));
+    std::fs::write(&watched, "a").unwrap();
+    std::fs::write(&unwatched, "a").unwrap();
+
+    let path_manager = PathManager::default();
+    path_manager
+      .update(
+        (vec![watched.clone()].into_iter(), vec![].into_iter()),
+        (vec![].into_iter(), vec![].into_iter()),
+        (vec![].into_iter(), vec![].into_iter()),
+      )
+      .unwrap();
+    let content_hashes = Arc::new(ContentHashes::default());
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let trigger = Trigger::new(Arc::new(path_manager), Arc::clone(&content_hashes), tx);
+
+    trigger.on_event(&unwatched, FsEventKind::Change);
+    assert!(rx.try_recv().is_err());
+    assert!(content_hashes.remove(&unwatched).is_none());
+
+    trigger.on_event(&watched, FsEventKind::Change);
+    assert!(rx.try_recv().is_ok());
+
+    // the hash is evicted once the file is unwatched
+    content_hashes.evict(std::iter::once(&watched), std::iter::empty());
+    assert!(content_hashes.remove(&watched).is_none());
+  }
 }
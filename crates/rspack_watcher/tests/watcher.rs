@@ -95,3 +95,135 @@ fn should_watch_a_single_file() {
     },
   );
 }
+
+#[test]
+fn should_aggregate_generated_files_with_longer_timeout() {
+  let mut helper = h!(FsWatcherOptions {
+    aggregate_timeout: Some(100),
+    generated_aggregate_timeout: Some(1500),
+    rules: rspack_watcher::FsWatcherRules {
+      generated: vec![rspack_watcher::FsWatcherMatcher::Glob("**/b".to_string())],
+      ..Default::default()
+    },
+    ..Default::default()
+  });
+
+  helper.file("a");
+  helper.file("b");
+  let rx = watch!(helper, "a", "b");
+
+  helper.tick(|| {
+    helper.file("b");
+    std::thread::sleep(std::time::Duration::from_millis(300));
+    helper.file("a");
+  });
+
+  helper.collect_events(
+    rx,
+    |_, _| {},
+    |changes, abort| {
+      // the source file changed during the timeout of generated file is aggregated together
+      changes.assert_changed(helper.join("a"));
+      changes.assert_changed(helper.join("b"));
+      *abort = true;
+    },
+  );
+}
+
+#[test]
+fn should_not_watch_excluded_files() {
+  let mut helper = h!(FsWatcherOptions {
+    aggregate_timeout: Some(100),
+    rules: rspack_watcher::FsWatcherRules {
+      exclude: vec![rspack_watcher::FsWatcherMatcher::Glob("**/b".to_string())],
+      ..Default::default()
+    },
+    ..Default::default()
+  });
+
+  helper.file("a");
+  helper.file("b");
+  let rx = watch!(helper, "a", "b");
+
+  helper.tick(|| {
+    helper.file("b");
+    std::thread::sleep(std::time::Duration::from_millis(300));
+    helper.file("a");
+  });
+
+  helper.collect_events(
+    rx,
+    |file, _| {
+      file.assert_path(helper.join("a"));
+    },
+    |changes, abort| {
+      changes.assert_changed(helper.join("a"));
+      assert_eq!(changes.changed_files.len(), 1);
+      *abort = true;
+    },
+  );
+}
+
+#[test]
+fn should_not_report_files_without_content_changes() {
+  let mut helper = h!(FsWatcherOptions {
+    aggregate_timeout: Some(100),
+    ..Default::default()
+  });
+
+  helper.file("a");
+  std::fs::write(helper.join("b"), "b").unwrap();
+  let rx = watch!(helper, "a", "b");
+
+  helper.tick(|| {
+    // only touch b with the same content
+    std::fs::write(helper.join("b"), "b").unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(300));
+    helper.file("a");
+  });
+
+  helper.collect_events(
+    rx,
+    |file, _| {
+      file.assert_path(helper.join("a"));
+    },
+    |changes, abort| {
+      changes.assert_changed(helper.join("a"));
+      assert_eq!(changes.changed_files.len(), 1);
+      *abort = true;
+    },
+  );
+}
+
+#[test]
+fn should_save_clock_when_watching_starts() {
+  let clock_dir = tempfile::tempdir().unwrap();
+  let clock_file = clock_dir.path().join("clock");
+  let mut helper = h!(FsWatcherOptions {
+    aggregate_timeout: Some(100),
+    clock_file: Some(clock_file.clone()),
+    ..Default::default()
+  });
+
+  helper.file("a");
+  let rx = watch!(helper, "a");
+
+  helper.tick(|| {
+    helper.file("a");
+  });
+
+  helper.collect_events(
+    rx,
+    |_, _| {},
+    |changes, abort| {
+      changes.assert_changed(helper.join("a"));
+      *abort = true;
+    },
+  );
+
+  let clock = rspack_watcher::FsWatcherClock::load(&clock_file).unwrap();
+  let path = helper.join("a");
+  assert!(clock.paths().any(|item| item == path.as_std_path()));
+  // the change happens after the clock is saved
+  assert!(clock.changes().changed_files.contains(path.as_str()));
+}
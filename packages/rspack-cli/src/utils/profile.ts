@@ -16,7 +16,12 @@ export async function applyProfile(
 ) {
   const { asyncExitHook } = await import('exit-hook');
 
-  if (traceLayer !== 'logger' && traceLayer !== 'perfetto') {
+  if (
+    traceLayer !== 'logger' &&
+    traceLayer !== 'perfetto' &&
+    traceLayer !== 'otlp' &&
+    traceLayer !== 'chrome'
+  ) {
     throw new Error(`unsupported trace layer: ${traceLayer}`);
   }
   const timestamp = Date.now();
@@ -28,20 +33,41 @@ export async function applyProfile(
       defaultOutputDir,
       'rspack.pftrace',
     );
+    const defaultRustTraceOtlpOutput = path.resolve(
+      defaultOutputDir,
+      'rspack.otlp.jsonl',
+    );
+    const defaultRustTraceChromeOutput = path.resolve(
+      defaultOutputDir,
+      'rspack.trace.json',
+    );
     const defaultRustTraceLoggerOutput = 'stdout';
 
     const defaultTraceOutput =
       traceLayer === 'perfetto'
         ? defaultRustTracePerfettoOutput
-        : defaultRustTraceLoggerOutput;
+        : traceLayer === 'otlp'
+          ? defaultRustTraceOtlpOutput
+          : traceLayer === 'chrome'
+            ? defaultRustTraceChromeOutput
+            : defaultRustTraceLoggerOutput;
 
     traceOutput = defaultTraceOutput;
+  } else if (isHttpEndpoint(traceOutput)) {
+    // the otlp layer exports to the endpoint of a collector directly
+    if (traceOutput.startsWith('https://')) {
+      throw new Error(
+        `The otlp layer only supports http:// endpoints, got: ${traceOutput}`,
+      );
+    }
   } else if (traceOutput !== 'stdout' && traceOutput !== 'stderr') {
     // if traceOutput is not stdout or stderr, we need to ensure the directory exists
     traceOutput = path.resolve(defaultOutputDir, traceOutput);
   }
 
-  await ensureFileDir(traceOutput);
+  if (!isHttpEndpoint(traceOutput)) {
+    await ensureFileDir(traceOutput);
+  }
   await rspack.experiments.globalTrace.register(
     filterValue,
     traceLayer,
@@ -52,6 +78,10 @@ export async function applyProfile(
   });
 }
 
+function isHttpEndpoint(output: string) {
+  return output.startsWith('http://') || output.startsWith('https://');
+}
+
 async function ensureFileDir(outputFilePath: string) {
   const dir = path.dirname(outputFilePath);
   await fs.promises.mkdir(dir, { recursive: true });
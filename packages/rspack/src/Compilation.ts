@@ -168,6 +168,7 @@ export interface KnownNormalizedStatsOptions {
   logging: false | 'none' | 'error' | 'warn' | 'info' | 'log' | 'verbose';
   loggingDebug: ((value: string) => boolean)[];
   loggingTrace: boolean;
+  cacheInvalidation: boolean;
   chunkModules: boolean;
   chunkRelations: boolean;
   reasons: boolean;
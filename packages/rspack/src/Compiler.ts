@@ -49,6 +49,7 @@ import type { rspack } from './index';
 import Cache from './lib/Cache';
 import CacheFacade from './lib/CacheFacade';
 import { Logger } from './logging/Logger';
+import NativeWatchFileSystem from './NativeWatchFileSystem';
 import { NormalModuleFactory } from './NormalModuleFactory';
 import { ResolverFactory } from './ResolverFactory';
 import { RuleSetCompiler } from './RuleSetCompiler';
@@ -844,6 +845,13 @@ class Compiler {
         return;
       }
       this.#initial = false;
+      if (
+        this.watchMode &&
+        this.watchFileSystem instanceof NativeWatchFileSystem &&
+        this.watchFileSystem.clockFile
+      ) {
+        instance!.loadWatcherClock(this.watchFileSystem.clockFile);
+      }
       instance!.build(callback);
     });
   }
@@ -1093,6 +1101,10 @@ class Compiler {
         jsTaps.push({
           function: createTap(queried),
           stage: liteTapable.safeStage(from + 1),
+          // the names of the plugins are reported in the hook timings
+          name: [...new Set(queried.tapsInRange.map((tap) => tap.name))].join(
+            ', ',
+          ),
         });
       }
       compiler.#decorateJsTaps(jsTaps);
@@ -69,6 +69,7 @@ class ThreadsafeInputNodeFS implements ThreadsafeNodeFS {
   stat!: (name: string) => Promise<NodeFsStats | void>;
   lstat!: (name: string) => Promise<NodeFsStats | void>;
   chmod?: (name: string, mode: number) => Promise<void>;
+  fsync?: (name: string) => Promise<void>;
   realpath!: (name: string) => Promise<string | void>;
   open!: (name: string, flags: string) => Promise<number | void>;
   rename!: (from: string, to: string) => Promise<void>;
@@ -168,6 +169,7 @@ class ThreadsafeOutputNodeFS implements ThreadsafeNodeFS {
   stat!: (name: string) => Promise<NodeFsStats | void>;
   lstat!: (name: string) => Promise<NodeFsStats | void>;
   chmod?: (name: string, mode: number) => Promise<void>;
+  fsync?: (name: string) => Promise<void>;
   realpath!: (name: string) => Promise<string | void>;
   open!: (name: string, flags: string) => Promise<number | void>;
   rename!: (from: string, to: string) => Promise<void>;
@@ -223,6 +225,42 @@ class ThreadsafeOutputNodeFS implements ThreadsafeNodeFS {
       };
     });
     this.chmod = memoizeFn(() => util.promisify(fs.chmod.bind(fs)));
+    const rename = fs.rename;
+    if (rename) {
+      this.rename = memoizeFn(() => util.promisify(rename.bind(fs)));
+    } else {
+      // fail loudly instead of leaving the staging directory in place
+      this.rename = async (from: string, to: string) => {
+        throw new Error(
+          `The output file system does not support \`rename\`, which is required to move ${from} to ${to}`,
+        );
+      };
+    }
+    const { open, fsync, close } = fs;
+    if (open && fsync && close) {
+      this.fsync = memoizeFn(() => {
+        const openFn = util.promisify(open.bind(fs));
+        const fsyncFn = util.promisify(fsync.bind(fs));
+        const closeFn = util.promisify(close.bind(fs));
+        return async (name: string) => {
+          let fd: number;
+          try {
+            fd = (await openFn(name, 'r')) as number;
+          } catch (err) {
+            // directories can not be opened on Windows, there is nothing to flush for them
+            if ((err as NodeJS.ErrnoException).code === 'EISDIR') {
+              return;
+            }
+            throw err;
+          }
+          try {
+            await fsyncFn(fd);
+          } finally {
+            await closeFn(fd);
+          }
+        };
+      });
+    }
   }
 
   static __to_binding(fs?: OutputFileSystem) {
@@ -1,5 +1,6 @@
 import binding from '@rspack/binding';
 import type Watchpack from 'watchpack';
+import type { WatchOptions } from './config';
 import type {
   FileSystemInfoEntry,
   InputFileSystem,
@@ -40,6 +41,11 @@ export default class NativeWatchFileSystem implements WatchFileSystem {
   #inner: binding.NativeWatcher | undefined;
   #isFirstWatch = true;
   #inputFileSystem: InputFileSystem;
+  /**
+   * The file to save the clock of watched paths to, the compiler loads it
+   * on the first build to get the files changed since the last session.
+   */
+  clockFile: string | undefined;
 
   constructor(inputFileSystem: InputFileSystem) {
     this.#inputFileSystem = inputFileSystem;
@@ -172,11 +178,18 @@ export default class NativeWatchFileSystem implements WatchFileSystem {
       return this.#inner;
     }
 
+    // the options only supported by native watcher are not in the watchpack types
+    const { generatedAggregateTimeout, fallbackPollInterval, rules } =
+      options as WatchOptions;
     const nativeWatcherOptions: binding.NativeWatcherOptions = {
       followSymlinks: options.followSymlinks,
       aggregateTimeout: options.aggregateTimeout,
       pollInterval: typeof options.poll === 'boolean' ? 0 : options.poll,
       ignored: toJsWatcherIgnored(options.ignored),
+      generatedAggregateTimeout,
+      fallbackPollInterval,
+      rules,
+      clockFile: this.clockFile,
     };
     const nativeWatcher = new binding.NativeWatcher(nativeWatcherOptions);
     this.#inner = nativeWatcher;
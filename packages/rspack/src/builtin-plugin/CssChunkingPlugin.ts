@@ -4,6 +4,11 @@ import { create } from './base';
 
 export interface CssChunkingPluginOptions {
   strict?: boolean;
+  /**
+   * Only group CSS modules loaded by the same JS chunks, so CSS chunks keep the load boundaries of their owning JS chunks.
+   * Implies `strict`, and emits a warning for CSS modules imported in conflicting order instead of reordering them silently.
+   */
+  alignWithJs?: boolean;
   minSize?: number;
   maxSize?: number;
   /**
@@ -22,6 +27,7 @@ export const CssChunkingPlugin = create(
     if (options.nextjs) {
       return {
         strict: options.strict,
+        alignWithJs: options.alignWithJs,
         minSize: options.minSize,
         maxSize: options.maxSize,
         exclude: /^pages\//,
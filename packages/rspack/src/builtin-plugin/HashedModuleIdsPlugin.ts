@@ -0,0 +1,43 @@
+import binding from '@rspack/binding';
+
+import type { HashDigest, HashFunction } from '../config';
+import { create } from './base';
+
+export interface HashedModuleIdsPluginOptions {
+  /**
+   * The context the module identifiers are made relative to.
+   * @default compiler.context
+   */
+  context?: string;
+  /**
+   * The hash function to use.
+   * @default 'md4'
+   */
+  hashFunction?: HashFunction;
+  /**
+   * The encoding of the hash.
+   * @default 'base64'
+   */
+  hashDigest?: HashDigest;
+  /**
+   * The length of the ids, it is increased for a module whose id conflicts with another one.
+   * @default 4
+   */
+  hashDigestLength?: number;
+}
+
+/**
+ * Uses the hash of the relative module path as the module id, same as the `HashedModuleIdsPlugin` of webpack.
+ */
+export const HashedModuleIdsPlugin = create(
+  binding.BuiltinPluginName.HashedModuleIdsPlugin,
+  (
+    options: HashedModuleIdsPluginOptions = {},
+  ): binding.RawHashedModuleIdsPluginOptions => ({
+    context: options.context,
+    hashFunction: options.hashFunction,
+    hashDigest: options.hashDigest,
+    hashDigestLength: options.hashDigestLength,
+  }),
+  'compilation',
+);
@@ -0,0 +1,24 @@
+import binding from '@rspack/binding';
+
+import { create } from './base';
+
+export interface HookTimingPluginOptions {
+  /**
+   * The filename of the JSON report, relative to `output.path`.
+   * If it is not set, the report is only written to the stats logging.
+   */
+  filename?: string;
+}
+
+/**
+ * Records the wall time of each plugin tap of each hook during the compilation,
+ * including the JavaScript plugins, and reports them sorted by cost.
+ */
+export const HookTimingPlugin = create(
+  binding.BuiltinPluginName.HookTimingPlugin,
+  (
+    options: HookTimingPluginOptions = {},
+  ): binding.RawHookTimingPluginOptions => ({
+    filename: options.filename,
+  }),
+);
@@ -5,15 +5,53 @@ import {
 
 import { create } from './base';
 
+export type LimitChunkCountCostModel =
+  | 'size'
+  | {
+      type: 'loadTime';
+      /**
+       * Estimated ratio of the gzip size to the raw size of the modules.
+       * @default 0.3
+       */
+      compressionRatio?: number;
+      /**
+       * Latency of a request, in milliseconds.
+       * @default 50
+       */
+      requestLatency?: number;
+      /**
+       * Bytes downloaded per second.
+       * @default 1000000
+       */
+      bandwidth?: number;
+    };
+
 export type LimitChunkCountOptions = {
   chunkOverhead?: number;
   entryChunkMultiplicator?: number;
   maxChunks: number;
+  /**
+   * Limit the maximum number of chunks loaded by a chunk group.
+   */
+  maxChunksPerRoute?: number;
+  /**
+   * How to choose the chunks to merge first.
+   * `'size'` merges the chunks with the largest raw size benefit first,
+   * `'loadTime'` merges the chunks with the largest expected load time benefit first,
+   * estimated from the gzip size and the latency of a request.
+   * @default 'size'
+   */
+  costModel?: LimitChunkCountCostModel;
 };
 
 export const LimitChunkCountPlugin = create(
   BuiltinPluginName.LimitChunkCountPlugin,
   (options: LimitChunkCountOptions): RawLimitChunkCountPluginOptions => {
-    return options;
+    const { costModel, ...rest } = options;
+    return {
+      ...rest,
+      costModel:
+        typeof costModel === 'string' ? { type: costModel } : costModel,
+    };
   },
 );
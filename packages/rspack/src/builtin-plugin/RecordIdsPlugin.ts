@@ -0,0 +1,22 @@
+import binding from '@rspack/binding';
+
+import { create } from './base';
+
+export interface RecordIdsPluginOptions {
+  /**
+   * Path of the JSON records file, relative paths are resolved against `context`.
+   * Commit the file to keep the ids stable across machines.
+   */
+  path: string;
+}
+
+/**
+ * Keeps the module ids and chunk ids stable across builds by persisting them to a records file.
+ * The modules and chunks without a recorded id are assigned deterministic ids.
+ */
+export const RecordIdsPlugin = create(
+  binding.BuiltinPluginName.RecordIdsPlugin,
+  (options: RecordIdsPluginOptions): binding.RawRecordIdsPluginOptions => ({
+    path: options.path,
+  }),
+);
@@ -35,6 +35,8 @@ export * from './FetchCompileAsyncWasmPlugin';
 export * from './FileUriPlugin';
 export * from './FlagDependencyExportsPlugin';
 export * from './FlagDependencyUsagePlugin';
+export * from './HashedModuleIdsPlugin';
+export * from './HookTimingPlugin';
 export * from './HotModuleReplacementPlugin';
 export * from './HttpExternalsRspackPlugin';
 export * from './HttpUriPlugin';
@@ -66,6 +68,7 @@ export * from './OccurrenceChunkIdsPlugin';
 export * from './ProgressPlugin';
 export * from './ProvidePlugin';
 export * from './RealContentHashPlugin';
+export * from './RecordIdsPlugin';
 export * from './RemoveDuplicateModulesPlugin';
 export * from './RemoveEmptyChunksPlugin';
 export * from './RsdoctorPlugin';
@@ -701,6 +701,7 @@ const applyOutputDefaults = (
   D(output, 'assetModuleFilename', '[hash][ext][query]');
   D(output, 'webassemblyModuleFilename', '[hash].module.wasm');
   D(output, 'compareBeforeEmit', true);
+  D(output, 'atomicEmit', false);
   F(output, 'path', () => path.join(process.cwd(), 'dist'));
   // IGNORE(output.pathinfo): Rspack disabled pathinfo by default
   F(output, 'pathinfo', () => false);
@@ -223,6 +223,7 @@ export const getNormalizedRspackOptions = (
         chunkLoadTimeout: output.chunkLoadTimeout,
         environment: cloneObject(output.environment),
         compareBeforeEmit: output.compareBeforeEmit,
+        atomicEmit: output.atomicEmit,
         bundlerInfo: output.bundlerInfo,
       };
     }),
@@ -292,14 +293,34 @@ export const getNormalizedRspackOptions = (
           managedPaths: optionalNestedArray(snapshot.managedPaths, (p) => [
             ...p,
           ]) || [/[\\/]node_modules[\\/][^.]/],
+          contentHashOnly: snapshot.contentHashOnly ?? false,
         },
-        storage: {
-          type: 'filesystem',
-          directory: path.resolve(
-            config.context || process.cwd(),
-            cache.storage?.directory || 'node_modules/.cache/rspack',
-          ),
-        },
+        storage:
+          cache.storage?.type === 'remote'
+            ? {
+                type: 'remote',
+                // the directories are resolved from the context, the urls are validated by the storage
+                endpoint: /^[a-z][a-z0-9+.-]*:\/\//i.test(cache.storage.endpoint)
+                  ? cache.storage.endpoint
+                  : path.resolve(
+                      config.context || process.cwd(),
+                      cache.storage.endpoint,
+                    ),
+                packSize: cache.storage.packSize,
+                expire: cache.storage.expire,
+              }
+            : {
+                type: 'filesystem',
+                directory: path.resolve(
+                  config.context || process.cwd(),
+                  cache.storage?.directory || 'node_modules/.cache/rspack',
+                ),
+                packSize: cache.storage?.packSize,
+                expire: cache.storage?.expire,
+                compression: cache.storage?.compression || 'none',
+                compressionLevel: cache.storage?.compressionLevel,
+                mode: cache.storage?.mode || 'read-write',
+              },
         portable: cache.portable,
       };
     }),
@@ -577,6 +598,7 @@ export interface OutputNormalized {
   environment?: Environment;
   chunkLoadTimeout?: number;
   compareBeforeEmit?: boolean;
+  atomicEmit?: boolean;
   bundlerInfo?: BundlerInfoOptions;
 }
 
@@ -592,6 +614,7 @@ export type CacheNormalized =
   | boolean
   | {
       type: 'memory';
+      maxSize?: number;
     }
   | {
       type: 'persistent';
@@ -601,11 +624,24 @@ export type CacheNormalized =
         immutablePaths: (string | RegExp)[];
         unmanagedPaths: (string | RegExp)[];
         managedPaths: (string | RegExp)[];
+        contentHashOnly: boolean;
       };
-      storage: {
-        type: 'filesystem';
-        directory: string;
-      };
+      storage:
+        | {
+            type: 'filesystem';
+            directory: string;
+            packSize?: number;
+            expire?: number;
+            compression: 'none' | 'zstd' | 'lz4';
+            compressionLevel?: number;
+            mode: 'read-write' | 'read-only' | 'write-only';
+          }
+        | {
+            type: 'remote';
+            endpoint: string;
+            packSize?: number;
+            expire?: number;
+          };
       portable?: boolean;
     };
 
@@ -350,7 +350,12 @@ export type HashDigest = string;
 export type HashDigestLength = number;
 
 /** The hashing algorithm to use. */
-export type HashFunction = 'md4' | 'xxhash64' | 'sha256';
+export type HashFunction =
+  | 'md4'
+  | 'xxhash64'
+  | 'xxhash3-128'
+  | 'sha256'
+  | 'blake3';
 
 /** An optional salt to update the hash. */
 export type HashSalt = string;
@@ -683,6 +688,13 @@ export type Output = {
    */
   compareBeforeEmit?: boolean;
 
+  /**
+   * Emit into a staging directory and rename it over `output.path` once all assets are written,
+   * so a watching consumer never sees a half-emitted build.
+   * @default false
+   */
+  atomicEmit?: boolean;
+
   /**
    * Information about the bundler.
    */
@@ -1786,22 +1798,78 @@ export type CacheSnapshotOptions = {
    * @default [/[\\/]node_modules[\\/][^.]/]
    */
   managedPaths?: (string | RegExp)[];
+  /**
+   * Only compare the content hash of files and ignore the modified time.
+   * The hashes are read from the git index when the files are in a git repository,
+   * so a fresh clone can validate the cache without hashing unchanged files.
+   * @default false
+   */
+  contentHashOnly?: boolean;
 };
 
 /**
  * Storage options for persistent cache.
  */
-export type CacheStorageOptions = {
-  /**
-   * Storage type, currently only supports 'filesystem'.
-   */
-  type: 'filesystem';
-  /**
-   * Cache directory path.
-   * @default 'node_modules/.cache/rspack'
-   */
-  directory?: string;
-};
+export type CacheStorageOptions =
+  | {
+      /**
+       * Storage type, the cache is stored in a local directory.
+       */
+      type: 'filesystem';
+      /**
+       * Cache directory path.
+       * @default 'node_modules/.cache/rspack'
+       */
+      directory?: string;
+      /**
+       * The max size of a pack file in bytes.
+       * @default 512000
+       */
+      packSize?: number;
+      /**
+       * The cache not used for this period is removed, in milliseconds.
+       * @default 604800000
+       */
+      expire?: number;
+      /**
+       * Compress the pack files, `zstd` has a higher ratio and `lz4` is faster.
+       * @default false
+       */
+      compression?: false | 'zstd' | 'lz4';
+      /**
+       * The level of the `zstd` compression, from 1 (fastest) to 22 (smallest).
+       * @default 3
+       */
+      compressionLevel?: number;
+      /**
+       * The access mode of the storage.
+       * - `read-write`: load the cache and save the updates.
+       * - `read-only`: load the cache but never write it, parallel processes can share a pre-warmed cache.
+       * - `write-only`: ignore the existing cache and save a fresh one, which is used to seed a shared cache.
+       * @default 'read-write'
+       */
+      mode?: 'read-write' | 'read-only' | 'write-only';
+    }
+  | {
+      /**
+       * Storage type, the cache is stored in a content-addressed store shared by multiple machines.
+       */
+      type: 'remote';
+      /**
+       * The endpoint of the store, either an `http://` cache server or a shared directory path.
+       */
+      endpoint: string;
+      /**
+       * The max size of a pack in bytes.
+       * @default 512000
+       */
+      packSize?: number;
+      /**
+       * The cache not used for this period is removed, in milliseconds.
+       * @default 604800000
+       */
+      expire?: number;
+    };
 
 /**
  * Persistent cache options.
@@ -1845,6 +1913,12 @@ export type MemoryCacheOptions = {
    * Cache type.
    */
   type: 'memory';
+  /**
+   * The maximum approximate bytes of the memory cache in total.
+   * The least recently used items are evicted after each compilation when it's exceeded.
+   * @default undefined
+   */
+  maxSize?: number;
 };
 
 /**
@@ -2060,6 +2134,12 @@ export type StatsOptions = {
    * @default true
    */
   loggingTrace?: boolean;
+  /**
+   * Enables or disables the display of the paths which invalidated the persistent cache,
+   * the strategies which decided them and the modules rebuilt because of them.
+   * @default false
+   */
+  cacheInvalidation?: boolean;
   /**
    * Enables or disables the display of runtime modules.
    * @default true
@@ -2498,6 +2578,25 @@ export type OptimizationSplitChunksOptions = {
    * The value is `false` in development mode.
    * */
   hidePathInfo?: boolean;
+
+  /**
+   * Merge or split async chunks by a co-loading profile collected from real-user navigations,
+   * to reduce the expected bytes and requests per navigation.
+   * `maxAsyncRequests`, `maxInitialRequests`, `minSize` and `maxAsyncSize` are still respected.
+   */
+  coLoading?: {
+    /** Chunks and modules loaded together by a navigation, with how often the navigation happens. */
+    loadSets: {
+      chunks?: string[];
+      modules?: string[];
+      frequency?: number;
+    }[];
+    /**
+     * The cost of one more request in a navigation, in bytes.
+     * @default 20000
+     */
+    requestCost?: number;
+  };
 } & SharedOptimizationSplitChunksCacheGroup;
 
 export type Optimization = {
@@ -2883,6 +2982,51 @@ export type WatchOptions = {
    * Stop watching when stdin stream has ended.
    */
   stdin?: boolean;
+
+  /**
+   * Add a delay before rebuilding once a generated file matching `rules.generated` changed.
+   * Only supported by `experiments.nativeWatcher`.
+   * @default the value of `aggregateTimeout`
+   */
+  generatedAggregateTimeout?: number;
+
+  /**
+   * The polling interval in milliseconds of the paths matching `rules.poll`.
+   * Only supported by `experiments.nativeWatcher`.
+   */
+  fallbackPollInterval?: number;
+
+  /**
+   * Structured rules of the watched paths, they are applied on top of `ignored`.
+   * Only supported by `experiments.nativeWatcher`.
+   */
+  rules?: WatchRules;
+};
+
+/**
+ * Glob or RegExp rules of the watched paths.
+ */
+export type WatchRules = {
+  /**
+   * Only the files and missing paths matching one of them are watched.
+   */
+  include?: (string | RegExp)[];
+
+  /**
+   * The paths matching one of them are not watched.
+   */
+  exclude?: (string | RegExp)[];
+
+  /**
+   * The paths matching one of them are watched by polling, e.g. the paths on network mounts.
+   */
+  poll?: (string | RegExp)[];
+
+  /**
+   * The paths matching one of them are generated files such as codegen output,
+   * they use `generatedAggregateTimeout`.
+   */
+  generated?: (string | RegExp)[];
 };
 //#endregion
 
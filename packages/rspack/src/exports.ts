@@ -214,9 +214,19 @@ interface Webworker {
 
 export const webworker: Webworker = { WebWorkerTemplatePlugin };
 
+import { HashedModuleIdsPlugin } from './builtin-plugin';
+
+interface Ids {
+  HashedModuleIdsPlugin: typeof HashedModuleIdsPlugin;
+}
+
+export const ids: Ids = { HashedModuleIdsPlugin };
+
 import {
   CssChunkingPlugin,
+  HookTimingPlugin,
   LimitChunkCountPlugin,
+  RecordIdsPlugin,
   RemoveDuplicateModulesPlugin,
   RsdoctorPlugin,
   RslibPlugin,
@@ -367,7 +377,7 @@ interface Experiments {
   globalTrace: {
     register: (
       filter: string,
-      layer: 'logger' | 'perfetto',
+      layer: 'logger' | 'perfetto' | 'otlp' | 'chrome',
       output: string,
     ) => Promise<void>;
     cleanup: () => Promise<void>;
@@ -389,6 +399,8 @@ interface Experiments {
     sync: typeof resolveSync;
   };
   CssChunkingPlugin: typeof CssChunkingPlugin;
+  HookTimingPlugin: typeof HookTimingPlugin;
+  RecordIdsPlugin: typeof RecordIdsPlugin;
   createNativePlugin: typeof createNativePlugin;
   VirtualModulesPlugin: typeof VirtualModulesPlugin;
   rsc: typeof rsc;
@@ -440,6 +452,8 @@ export const experiments: Experiments = {
     sync: resolveSync,
   },
   CssChunkingPlugin,
+  HookTimingPlugin,
+  RecordIdsPlugin,
   createNativePlugin,
   VirtualModulesPlugin,
   rsc,
@@ -8,6 +8,7 @@
  * https://github.com/webpack/webpack/blob/main/LICENSE
  */
 import fs from 'node:fs';
+import path from 'node:path';
 // @ts-expect-error we directly import from enhanced-resolve inner js file to improve performance
 import CachedInputFileSystem from 'enhanced-resolve/lib/CachedInputFileSystem';
 
@@ -53,7 +54,23 @@ export default class NodeEnvironmentPlugin {
     compiler.intermediateFileSystem = null;
 
     if (compiler.options.experiments.nativeWatcher) {
-      compiler.watchFileSystem = new NativeWatchFileSystem(inputFileSystem);
+      const watchFileSystem = new NativeWatchFileSystem(inputFileSystem);
+      compiler.watchFileSystem = watchFileSystem;
+      // the cache options are available after defaults are applied
+      compiler.hooks.afterEnvironment.tap('NodeEnvironmentPlugin', () => {
+        const { cache, name } = compiler.options;
+        if (
+          typeof cache === 'object' &&
+          cache.type === 'persistent' &&
+          cache.storage.type === 'filesystem'
+        ) {
+          // hidden files in the cache directory are skipped by the cache cleanup
+          watchFileSystem.clockFile = path.join(
+            cache.storage.directory,
+            `.watcher-clock${name ? `-${encodeURIComponent(name)}` : ''}`,
+          );
+        }
+      });
     } else {
       compiler.watchFileSystem = new NodeWatchFileSystem(inputFileSystem);
     }
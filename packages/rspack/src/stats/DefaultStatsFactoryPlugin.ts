@@ -864,6 +864,18 @@ const SIMPLE_EXTRACTORS: SimpleExtractors = {
         }
       }
     },
+    cacheInvalidation: (
+      object,
+      compilation,
+      context: KnownStatsFactoryContext,
+    ) => {
+      const cacheInvalidation = context
+        .getInner(compilation)
+        .getCacheInvalidation();
+      if (cacheInvalidation) {
+        object.cacheInvalidation = cacheInvalidation;
+      }
+    },
     hash: (object, compilation, context: KnownStatsFactoryContext) => {
       const statsCompilation = context.getStatsCompilation(compilation);
       object.hash = statsCompilation.hash;
@@ -46,6 +46,7 @@ const NAMED_PRESETS: Record<string, StatsOptions> = {
     errorStack: true,
     publicPath: true,
     logging: 'verbose',
+    cacheInvalidation: true,
     orphanModules: true,
     runtimeModules: true,
     // exclude: false,
@@ -241,6 +242,7 @@ const DEFAULTS: StatsDefault = {
     forToString && all !== false ? 'info' : false,
   loggingDebug: () => [],
   loggingTrace: OFF_FOR_TO_STRING,
+  cacheInvalidation: OFF_FOR_TO_STRING,
   excludeModules: () => [],
   excludeAssets: () => [],
   modulesSort: () => 'depth',
@@ -283,6 +283,7 @@ export type KnownStatsCompilation = {
   filteredModules?: number;
   children?: StatsCompilation[];
   logging?: Record<string, StatsLogging>;
+  cacheInvalidation?: StatsCacheInvalidation;
 
   // TODO: not aligned with webpack
   // env?: any;
@@ -294,6 +295,13 @@ export type StatsCompilation = KnownStatsCompilation & Record<string, any>;
 
 export type StatsLogging = KnownStatsLogging & Record<string, any>;
 
+export type StatsCacheInvalidation = {
+  buildDependencies: StatsInvalidatedPath[];
+  paths: StatsInvalidatedPath[];
+};
+
+export type StatsInvalidatedPath = binding.JsStatsInvalidatedPath;
+
 export type KnownStatsLogging = {
   entries: StatsLoggingEntry[];
   filteredEntries: number;
@@ -10,8 +10,11 @@
 
 import { createRequire } from 'node:module';
 import Hash from './hash';
+import { encodeBase62 } from './hash/base62';
+import createBlake3 from './hash/blake3';
 import createMd4 from './hash/md4';
 import createXXHash64 from './hash/xxhash64';
+import createXXHash3_128 from './hash/xxhash3-128';
 
 const require = createRequire(import.meta.url);
 
@@ -21,6 +24,15 @@ const BULK_SIZE = 2000;
 // so access to it can be optimized by v8
 const digestCaches: Record<string, Map<string, string>> = {};
 
+/**
+ * Calculates the digest with encoding, `base62` isn't supported by node so it's encoded from the raw digest
+ * @param hash the hash
+ * @param encoding encoding of the return value
+ * @returns {string} digest
+ */
+const digestWithEncoding = (hash: Hash, encoding: string): string =>
+  encoding === 'base62' ? encodeBase62(hash.digest()) : hash.digest(encoding);
+
 class BulkUpdateDecorator extends Hash {
   hash: Hash | undefined;
   hashFactory: (() => Hash) | undefined;
@@ -113,7 +125,9 @@ class BulkUpdateDecorator extends Hash {
     if (buffer.length > 0) {
       this.hash.update(Buffer.from(buffer));
     }
-    const result = encoding ? this.hash.digest(encoding) : this.hash.digest();
+    const result = encoding
+      ? digestWithEncoding(this.hash, encoding)
+      : this.hash.digest();
     if (digestCache !== undefined && typeof result === 'string') {
       digestCache.set(buffer, result);
     }
@@ -220,7 +234,9 @@ class WasmHashAdapter extends Hash {
    * @returns {string|Buffer} digest
    */
   digest(encoding?: string): string | Buffer {
-    return encoding ? this.wasmHash.digest(encoding) : this.wasmHash.digest();
+    return encoding
+      ? digestWithEncoding(this.wasmHash, encoding)
+      : this.wasmHash.digest();
   }
 }
 
@@ -233,6 +249,8 @@ export const createHash = (
   algorithm:
     | 'debug'
     | 'xxhash64'
+    | 'xxhash3-128'
+    | 'blake3'
     | 'md4'
     | 'native-md4'
     | (string & {})
@@ -249,6 +267,10 @@ export const createHash = (
       const hash = createXXHash64();
       return new WasmHashAdapter(hash);
     }
+    case 'xxhash3-128':
+      return new BulkUpdateDecorator(createXXHash3_128, 'xxhash3-128');
+    case 'blake3':
+      return new BulkUpdateDecorator(createBlake3, 'blake3');
     case 'md4': {
       const hash = createMd4();
       return new WasmHashAdapter(hash);
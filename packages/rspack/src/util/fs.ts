@@ -149,6 +149,24 @@ export interface OutputFileSystem {
     arg1: number,
     arg2: (arg0?: NodeJS.ErrnoException | null) => void,
   ) => void;
+  rename?: (
+    arg0: string,
+    arg1: string,
+    arg2: (arg0?: null | NodeJS.ErrnoException) => void,
+  ) => void;
+  open?: (
+    arg0: string,
+    arg1: string,
+    arg2: (arg0?: null | NodeJS.ErrnoException, arg1?: number) => void,
+  ) => void;
+  fsync?: (
+    arg0: number,
+    arg1: (arg0?: null | NodeJS.ErrnoException) => void,
+  ) => void;
+  close?: (
+    arg0: number,
+    arg1: (arg0?: null | NodeJS.ErrnoException) => void,
+  ) => void;
   join?: (arg0: string, arg1: string) => string;
   relative?: (arg0: string, arg1: string) => string;
   dirname?: (arg0: string) => string;
@@ -0,0 +1,24 @@
+const BASE62_ALPHABET =
+  '0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ';
+
+/**
+ * Encodes the digest as a big-endian number in base62, the same as the `base62` digest of the Rust side
+ * @param data digest
+ * @returns encoded digest, left-padded with `0` to `ceil(bits / log2(62))` characters
+ */
+export const encodeBase62 = (data: Uint8Array): string => {
+  const len = Math.ceil((data.length * 8 * 1000) / 5954);
+  const num = Uint8Array.from(data);
+  const chars = new Array<string>(len);
+  for (let i = len - 1; i >= 0; i--) {
+    // divide the number by 62 in place, the remainder is the next digit
+    let rem = 0;
+    for (let j = 0; j < num.length; j++) {
+      const acc = (rem << 8) | num[j];
+      num[j] = Math.floor(acc / 62);
+      rem = acc % 62;
+    }
+    chars[i] = BASE62_ALPHABET[rem];
+  }
+  return chars.join('');
+};
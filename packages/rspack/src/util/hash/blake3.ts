@@ -0,0 +1,191 @@
+/**
+ * BLAKE3 with the default 32-byte output, following the reference implementation
+ * https://github.com/BLAKE3-team/BLAKE3/blob/master/reference_impl/reference_impl.rs
+ */
+import OneShotHash from './one-shot';
+
+const IV = new Uint32Array([
+  0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c,
+  0x1f83d9ab, 0x5be0cd19,
+]);
+const MSG_PERMUTATION = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+const BLOCK_LEN = 64;
+const CHUNK_LEN = 1024;
+
+const CHUNK_START = 1;
+const CHUNK_END = 2;
+const PARENT = 4;
+const ROOT = 8;
+
+const rotr = (x: number, n: number) => (x >>> n) | (x << (32 - n));
+
+const g = (
+  state: Uint32Array,
+  a: number,
+  b: number,
+  c: number,
+  d: number,
+  mx: number,
+  my: number,
+) => {
+  state[a] = state[a] + state[b] + mx;
+  state[d] = rotr(state[d] ^ state[a], 16);
+  state[c] = state[c] + state[d];
+  state[b] = rotr(state[b] ^ state[c], 12);
+  state[a] = state[a] + state[b] + my;
+  state[d] = rotr(state[d] ^ state[a], 8);
+  state[c] = state[c] + state[d];
+  state[b] = rotr(state[b] ^ state[c], 7);
+};
+
+/**
+ * Compresses a block and returns the first 8 words of the output, the chaining value
+ */
+const compress = (
+  chainingValue: Uint32Array,
+  blockWords: Uint32Array,
+  counter: number,
+  blockLen: number,
+  flags: number,
+): Uint32Array => {
+  const state = new Uint32Array(16);
+  state.set(chainingValue, 0);
+  state.set(IV.subarray(0, 4), 8);
+  state[12] = counter;
+  state[13] = Math.floor(counter / 0x100000000);
+  state[14] = blockLen;
+  state[15] = flags;
+  let m = Uint32Array.from(blockWords);
+  for (let round = 0; round < 7; round++) {
+    g(state, 0, 4, 8, 12, m[0], m[1]);
+    g(state, 1, 5, 9, 13, m[2], m[3]);
+    g(state, 2, 6, 10, 14, m[4], m[5]);
+    g(state, 3, 7, 11, 15, m[6], m[7]);
+    g(state, 0, 5, 10, 15, m[8], m[9]);
+    g(state, 1, 6, 11, 12, m[10], m[11]);
+    g(state, 2, 7, 8, 13, m[12], m[13]);
+    g(state, 3, 4, 9, 14, m[14], m[15]);
+    const permuted = new Uint32Array(16);
+    for (let i = 0; i < 16; i++) {
+      permuted[i] = m[MSG_PERMUTATION[i]];
+    }
+    m = permuted;
+  }
+  const output = new Uint32Array(8);
+  for (let i = 0; i < 8; i++) {
+    output[i] = state[i] ^ state[i + 8];
+  }
+  return output;
+};
+
+const readBlockWords = (input: Buffer, offset: number, len: number) => {
+  const block = Buffer.alloc(BLOCK_LEN);
+  input.copy(block, 0, offset, offset + len);
+  const words = new Uint32Array(16);
+  for (let i = 0; i < 16; i++) {
+    words[i] = block.readUInt32LE(i * 4);
+  }
+  return words;
+};
+
+/**
+ * The input of the last compression, it's compressed with `ROOT` if it is the root node
+ */
+type Output = {
+  chainingValue: Uint32Array;
+  blockWords: Uint32Array;
+  counter: number;
+  blockLen: number;
+  flags: number;
+};
+
+const outputChainingValue = (output: Output) =>
+  compress(
+    output.chainingValue,
+    output.blockWords,
+    output.counter,
+    output.blockLen,
+    output.flags,
+  );
+
+const chunkOutput = (
+  input: Buffer,
+  offset: number,
+  counter: number,
+): Output => {
+  const end = Math.min(offset + CHUNK_LEN, input.length);
+  let chainingValue = IV;
+  let blockOffset = offset;
+  let flags = CHUNK_START;
+  // every block except the last one of the chunk is compressed right away
+  while (end - blockOffset > BLOCK_LEN) {
+    chainingValue = compress(
+      chainingValue,
+      readBlockWords(input, blockOffset, BLOCK_LEN),
+      counter,
+      BLOCK_LEN,
+      flags,
+    );
+    blockOffset += BLOCK_LEN;
+    flags = 0;
+  }
+  const blockLen = end - blockOffset;
+  return {
+    chainingValue,
+    blockWords: readBlockWords(input, blockOffset, blockLen),
+    counter,
+    blockLen,
+    flags: flags | CHUNK_END,
+  };
+};
+
+const parentOutput = (left: Uint32Array, right: Uint32Array): Output => {
+  const blockWords = new Uint32Array(16);
+  blockWords.set(left, 0);
+  blockWords.set(right, 8);
+  return {
+    chainingValue: IV,
+    blockWords,
+    counter: 0,
+    blockLen: BLOCK_LEN,
+    flags: PARENT,
+  };
+};
+
+const blake3 = (input: Buffer): Buffer => {
+  const chunkCount = Math.max(1, Math.ceil(input.length / CHUNK_LEN));
+  const stack: Uint32Array[] = [];
+  let output = chunkOutput(input, 0, 0);
+  for (let chunk = 1; chunk < chunkCount; chunk++) {
+    // merge the completed subtrees, one for each trailing zero bit of the chunk count
+    let chainingValue = outputChainingValue(output);
+    let totalChunks = chunk;
+    while ((totalChunks & 1) === 0) {
+      chainingValue = outputChainingValue(
+        parentOutput(stack.pop()!, chainingValue),
+      );
+      totalChunks >>= 1;
+    }
+    stack.push(chainingValue);
+    output = chunkOutput(input, chunk * CHUNK_LEN, chunk);
+  }
+  while (stack.length > 0) {
+    output = parentOutput(stack.pop()!, outputChainingValue(output));
+  }
+
+  const root = compress(
+    output.chainingValue,
+    output.blockWords,
+    0,
+    output.blockLen,
+    output.flags | ROOT,
+  );
+  const result = Buffer.alloc(32);
+  for (let i = 0; i < 8; i++) {
+    result.writeUInt32LE(root[i], i * 4);
+  }
+  return result;
+};
+
+export default () => new OneShotHash(blake3);
@@ -0,0 +1,56 @@
+import Hash from '../hash';
+
+/**
+ * Buffers the updates and hashes them at once when digesting
+ */
+export default class OneShotHash extends Hash {
+  private hash: (data: Buffer) => Buffer;
+  private chunks: Buffer[];
+
+  /**
+   * @param hash function hashing the whole input
+   */
+  constructor(hash: (data: Buffer) => Buffer) {
+    super();
+    this.hash = hash;
+    this.chunks = [];
+  }
+
+  /**
+   * Update hash {@link https://nodejs.org/api/crypto.html#crypto_hash_update_data_inputencoding}
+   * @param data data
+   * @param inputEncoding data encoding
+   * @returns updated hash
+   */
+  update(data: string, inputEncoding: string): this;
+  update(data: Buffer): this;
+  update(data: string | Buffer, inputEncoding?: string): this {
+    this.chunks.push(
+      Buffer.isBuffer(data)
+        ? data
+        : Buffer.from(data, inputEncoding as BufferEncoding),
+    );
+    return this;
+  }
+
+  /**
+   * Calculates the digest without encoding
+   * @returns {Buffer} digest
+   */
+  digest(): Buffer;
+  /**
+   * Calculates the digest with encoding
+   * @param encoding encoding of the return value
+   * @returns {string} digest
+   */
+  digest(encoding: string): string;
+  /**
+   * Calculates the digest {@link https://nodejs.org/api/crypto.html#crypto_hash_digest_encoding}
+   * @param {string=} encoding encoding of the return value
+   * @returns {string|Buffer} digest
+   */
+  digest(encoding?: string): string | Buffer {
+    const result = this.hash(Buffer.concat(this.chunks));
+    return encoding ? result.toString(encoding as BufferEncoding) : result;
+  }
+}
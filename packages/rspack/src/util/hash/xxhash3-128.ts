@@ -0,0 +1,292 @@
+/**
+ * XXH3 128-bit with the default secret and seed 0, ported from
+ * https://github.com/DoumanAsh/xxhash-rust/blob/master/src/const_xxh3.rs
+ *
+ * The digest is the big-endian bytes of the 128-bit hash, the same as `xxhash3-128` of the Rust side.
+ */
+import OneShotHash from './one-shot';
+
+const MASK_64 = (1n << 64n) - 1n;
+const MASK_32 = (1n << 32n) - 1n;
+
+const PRIME32_1 = 0x9e3779b1n;
+const PRIME32_2 = 0x85ebca77n;
+const PRIME32_3 = 0xc2b2ae3dn;
+const PRIME64_1 = 0x9e3779b185ebca87n;
+const PRIME64_2 = 0xc2b2ae3d27d4eb4fn;
+const PRIME64_3 = 0x165667b19e3779f9n;
+const PRIME64_4 = 0x85ebca77c2b2ae63n;
+const PRIME64_5 = 0x27d4eb2f165667c5n;
+
+const SECRET = Buffer.from(
+  'b8fe6c3923a44bbe7c01812cf721ad1cded46de9839097db7240a4a4b7b3671f' +
+    'cb79e64eccc0e578825ad07dccff7221b8084674f743248ee03590e6813a264c' +
+    '3c2852bb91c300cb88d0658b1b532ea371644897a20df94e3819ef46a9deacd8' +
+    'a8fa763fe39c343ff9dcbbc7c70b4f1d8a51e04bcdb45931c89f7ec9d9787364' +
+    'eac5ac8334d3ebc3c581a0fffa1363eb170ddd51b7f0da49d316552629d4689e' +
+    '2b16be587d47a1fc8ff8b8d17ad031ce45cb3a8f95160428afd7fbcabb4b407e',
+  'hex',
+);
+const SECRET_LEN = SECRET.length;
+const STRIPE_LEN = 64;
+const SECRET_CONSUME_RATE = 8;
+const SECRET_MERGEACCS_START = 11;
+const SECRET_LASTACC_START = 7;
+const SECRET_SIZE_MIN = 136;
+
+const add = (a: bigint, b: bigint) => (a + b) & MASK_64;
+const mul = (a: bigint, b: bigint) => (a * b) & MASK_64;
+const neg = (a: bigint) => -a & MASK_64;
+const mul128 = (a: bigint, b: bigint): [bigint, bigint] => {
+  const product = a * b;
+  return [product & MASK_64, product >> 64n];
+};
+const mul128Fold64 = (a: bigint, b: bigint) => {
+  const [lo, hi] = mul128(a, b);
+  return lo ^ hi;
+};
+const xorshift64 = (value: bigint, shift: bigint) => value ^ (value >> shift);
+const swap32 = (value: bigint) =>
+  ((value & 0xffn) << 24n) |
+  ((value & 0xff00n) << 8n) |
+  ((value >> 8n) & 0xff00n) |
+  (value >> 24n);
+const swap64 = (value: bigint) =>
+  (swap32(value & MASK_32) << 32n) | swap32(value >> 32n);
+const rotl32 = (value: bigint, r: bigint) =>
+  ((value << r) | (value >> (32n - r))) & MASK_32;
+
+const avalanche = (value: bigint) =>
+  xorshift64(mul(xorshift64(value, 37n), 0x165667919e3779f9n), 32n);
+const xxh64Avalanche = (value: bigint) => {
+  let v = mul(xorshift64(value, 33n), PRIME64_2);
+  v = mul(xorshift64(v, 29n), PRIME64_3);
+  return xorshift64(v, 32n);
+};
+
+const readU32 = (data: Buffer, offset: number) =>
+  BigInt(data.readUInt32LE(offset));
+const readU64 = (data: Buffer, offset: number) => data.readBigUInt64LE(offset);
+
+const mix16B = (input: Buffer, inputOffset: number, secretOffset: number) =>
+  mul128Fold64(
+    readU64(input, inputOffset) ^ readU64(SECRET, secretOffset),
+    readU64(input, inputOffset + 8) ^ readU64(SECRET, secretOffset + 8),
+  );
+
+const mix32B = (
+  acc: [bigint, bigint],
+  input: Buffer,
+  input1Offset: number,
+  input2Offset: number,
+  secretOffset: number,
+): [bigint, bigint] => {
+  const lo =
+    add(acc[0], mix16B(input, input1Offset, secretOffset)) ^
+    add(readU64(input, input2Offset), readU64(input, input2Offset + 8));
+  const hi =
+    add(acc[1], mix16B(input, input2Offset, secretOffset + 16)) ^
+    add(readU64(input, input1Offset), readU64(input, input1Offset + 8));
+  return [lo, hi];
+};
+
+const finalizeMid = (acc: [bigint, bigint], len: number): [bigint, bigint] => {
+  const lo = add(acc[0], acc[1]);
+  const hi = add(
+    add(mul(acc[0], PRIME64_1), mul(acc[1], PRIME64_4)),
+    mul(BigInt(len), PRIME64_2),
+  );
+  return [avalanche(lo), neg(avalanche(hi))];
+};
+
+const hash1To3 = (input: Buffer): [bigint, bigint] => {
+  const len = input.length;
+  const inputLo =
+    (BigInt(input[0]) << 16n) |
+    (BigInt(input[len >> 1]) << 24n) |
+    BigInt(input[len - 1]) |
+    (BigInt(len) << 8n);
+  const inputHi = rotl32(swap32(inputLo), 13n);
+  const flipLo = readU32(SECRET, 0) ^ readU32(SECRET, 4);
+  const flipHi = readU32(SECRET, 8) ^ readU32(SECRET, 12);
+  return [xxh64Avalanche(inputLo ^ flipLo), xxh64Avalanche(inputHi ^ flipHi)];
+};
+
+const hash4To8 = (input: Buffer): [bigint, bigint] => {
+  const len = input.length;
+  const input64 = add(readU32(input, 0), readU32(input, len - 4) << 32n);
+  const flip = readU64(SECRET, 16) ^ readU64(SECRET, 24);
+  let [lo, hi] = mul128(input64 ^ flip, add(PRIME64_1, BigInt(len) << 2n));
+  hi = add(hi, (lo << 1n) & MASK_64);
+  lo ^= hi >> 3n;
+  lo = xorshift64(mul(xorshift64(lo, 35n), 0x9fb21c651e98df25n), 28n);
+  return [lo, avalanche(hi)];
+};
+
+const hash9To16 = (input: Buffer): [bigint, bigint] => {
+  const len = input.length;
+  const flipLo = readU64(SECRET, 32) ^ readU64(SECRET, 40);
+  const flipHi = readU64(SECRET, 48) ^ readU64(SECRET, 56);
+  const inputLo = readU64(input, 0);
+  let inputHi = readU64(input, len - 8);
+  let [mulLow, mulHigh] = mul128(inputLo ^ inputHi ^ flipLo, PRIME64_1);
+  mulLow = add(mulLow, BigInt(len - 1) << 54n);
+  inputHi ^= flipHi;
+  mulHigh = add(mulHigh, add(inputHi, (inputHi & MASK_32) * (PRIME32_2 - 1n)));
+  mulLow ^= swap64(mulHigh);
+  const [resultLo, resultHi] = mul128(mulLow, PRIME64_2);
+  const hi = add(resultHi, mul(mulHigh, PRIME64_2));
+  return [avalanche(resultLo), avalanche(hi)];
+};
+
+const hash17To128 = (input: Buffer): [bigint, bigint] => {
+  const len = input.length;
+  let acc: [bigint, bigint] = [mul(BigInt(len), PRIME64_1), 0n];
+  if (len > 32) {
+    if (len > 64) {
+      if (len > 96) {
+        acc = mix32B(acc, input, 48, len - 64, 96);
+      }
+      acc = mix32B(acc, input, 32, len - 48, 64);
+    }
+    acc = mix32B(acc, input, 16, len - 32, 32);
+  }
+  acc = mix32B(acc, input, 0, len - 16, 0);
+  return finalizeMid(acc, len);
+};
+
+const hash129To240 = (input: Buffer): [bigint, bigint] => {
+  const len = input.length;
+  const nbRounds = Math.floor(len / 32);
+  let acc: [bigint, bigint] = [mul(BigInt(len), PRIME64_1), 0n];
+  for (let i = 0; i < 4; i++) {
+    acc = mix32B(acc, input, 32 * i, 32 * i + 16, 32 * i);
+  }
+  acc = [avalanche(acc[0]), avalanche(acc[1])];
+  for (let i = 4; i < nbRounds; i++) {
+    acc = mix32B(acc, input, 32 * i, 32 * i + 16, 3 + 32 * (i - 4));
+  }
+  acc = mix32B(acc, input, len - 16, len - 32, SECRET_SIZE_MIN - 17 - 16);
+  return finalizeMid(acc, len);
+};
+
+const accumulate512 = (
+  acc: bigint[],
+  input: Buffer,
+  inputOffset: number,
+  secretOffset: number,
+) => {
+  for (let i = 0; i < 8; i++) {
+    const dataVal = readU64(input, inputOffset + 8 * i);
+    const dataKey = dataVal ^ readU64(SECRET, secretOffset + 8 * i);
+    acc[i ^ 1] = add(acc[i ^ 1], dataVal);
+    acc[i] = add(acc[i], (dataKey & MASK_32) * (dataKey >> 32n));
+  }
+};
+
+const accumulateLoop = (
+  acc: bigint[],
+  input: Buffer,
+  inputOffset: number,
+  nbStripes: number,
+) => {
+  for (let i = 0; i < nbStripes; i++) {
+    accumulate512(
+      acc,
+      input,
+      inputOffset + i * STRIPE_LEN,
+      i * SECRET_CONSUME_RATE,
+    );
+  }
+};
+
+const scramble = (acc: bigint[]) => {
+  for (let i = 0; i < 8; i++) {
+    const key = readU64(SECRET, SECRET_LEN - STRIPE_LEN + 8 * i);
+    acc[i] = mul(xorshift64(acc[i], 47n) ^ key, PRIME32_1);
+  }
+};
+
+const mergeAccs = (acc: bigint[], secretOffset: number, start: bigint) => {
+  let result = start;
+  for (let i = 0; i < 4; i++) {
+    result = add(
+      result,
+      mul128Fold64(
+        acc[2 * i] ^ readU64(SECRET, secretOffset + 16 * i),
+        acc[2 * i + 1] ^ readU64(SECRET, secretOffset + 16 * i + 8),
+      ),
+    );
+  }
+  return avalanche(result);
+};
+
+const hashLong = (input: Buffer): [bigint, bigint] => {
+  const len = input.length;
+  const acc = [
+    PRIME32_3,
+    PRIME64_1,
+    PRIME64_2,
+    PRIME64_3,
+    PRIME64_4,
+    PRIME32_2,
+    PRIME64_5,
+    PRIME32_1,
+  ];
+  const nbStripes = (SECRET_LEN - STRIPE_LEN) / SECRET_CONSUME_RATE;
+  const blockLen = STRIPE_LEN * nbStripes;
+  const nbBlocks = Math.floor((len - 1) / blockLen);
+  for (let i = 0; i < nbBlocks; i++) {
+    accumulateLoop(acc, input, i * blockLen, nbStripes);
+    scramble(acc);
+  }
+  accumulateLoop(
+    acc,
+    input,
+    nbBlocks * blockLen,
+    Math.floor((len - 1 - blockLen * nbBlocks) / STRIPE_LEN),
+  );
+  accumulate512(
+    acc,
+    input,
+    len - STRIPE_LEN,
+    SECRET_LEN - STRIPE_LEN - SECRET_LASTACC_START,
+  );
+  const lenBig = BigInt(len);
+  return [
+    mergeAccs(acc, SECRET_MERGEACCS_START, mul(lenBig, PRIME64_1)),
+    mergeAccs(
+      acc,
+      SECRET_LEN - STRIPE_LEN - SECRET_MERGEACCS_START,
+      ~mul(lenBig, PRIME64_2) & MASK_64,
+    ),
+  ];
+};
+
+const xxh3128 = (input: Buffer): Buffer => {
+  const len = input.length;
+  let lo: bigint;
+  let hi: bigint;
+  if (len > 240) {
+    [lo, hi] = hashLong(input);
+  } else if (len > 128) {
+    [lo, hi] = hash129To240(input);
+  } else if (len > 16) {
+    [lo, hi] = hash17To128(input);
+  } else if (len > 8) {
+    [lo, hi] = hash9To16(input);
+  } else if (len >= 4) {
+    [lo, hi] = hash4To8(input);
+  } else if (len > 0) {
+    [lo, hi] = hash1To3(input);
+  } else {
+    lo = xxh64Avalanche(readU64(SECRET, 64) ^ readU64(SECRET, 72));
+    hi = xxh64Avalanche(readU64(SECRET, 80) ^ readU64(SECRET, 88));
+  }
+  const result = Buffer.alloc(16);
+  result.writeBigUInt64BE(hi, 0);
+  result.writeBigUInt64BE(lo, 8);
+  return result;
+};
+
+export default () => new OneShotHash(xxh3128);
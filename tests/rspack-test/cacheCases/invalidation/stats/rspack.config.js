@@ -0,0 +1,47 @@
+const path = require("path");
+
+/** @type {import("@rspack/core").Configuration} */
+module.exports = {
+	context: __dirname,
+	cache: {
+		type: "persistent"
+	},
+	plugins: [
+		{
+			buildIndex: 0,
+			apply(compiler) {
+				compiler.hooks.done.tap("Test", stats => {
+					const { cacheInvalidation } = stats.toJson({
+						all: false,
+						cacheInvalidation: true
+					});
+					if (this.buildIndex == 0) {
+						// nothing is cached before the first start
+						expect(cacheInvalidation).toEqual({
+							buildDependencies: [],
+							paths: []
+						});
+					}
+					if (this.buildIndex == 1) {
+						// the report only describes the cold start
+						expect(cacheInvalidation).toBeUndefined();
+					}
+					if (this.buildIndex == 2) {
+						expect(cacheInvalidation.buildDependencies).toEqual([]);
+						const file = cacheInvalidation.paths.find(
+							item => item.path === path.join(__dirname, "file.js")
+						);
+						expect(file).toMatchObject({
+							scope: "file",
+							kind: "modified"
+						});
+						expect(typeof file.strategy).toBe("string");
+						expect(file.modules).toHaveLength(1);
+						expect(file.modules[0]).toMatch(/file\.js$/);
+					}
+					this.buildIndex++;
+				});
+			}
+		}
+	]
+};
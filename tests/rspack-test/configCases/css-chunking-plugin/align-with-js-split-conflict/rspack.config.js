@@ -0,0 +1,53 @@
+const rspack = require("@rspack/core");
+
+/** @type {import("@rspack/core").Configuration} */
+module.exports = {
+	mode: "development",
+	module: {
+		rules: [
+			{
+				test: /\.css$/,
+				type: "css/auto"
+			}
+		]
+	},
+	optimization: {
+		splitChunks: {
+			cacheGroups: {
+				shared: {
+					test: /shared/,
+					enforce: true,
+					name: "shared"
+				}
+			}
+		}
+	},
+	plugins: [
+		new rspack.experiments.CssChunkingPlugin({
+			alignWithJs: true,
+			// every css module gets its own css chunk
+			maxSize: 1
+		}),
+		{
+			/**
+			 * @param {import("@rspack/core").Compiler} compiler
+			 */
+			apply(compiler) {
+				compiler.hooks.done.tap("PLUGIN", stats => {
+					const json = stats.toJson({ all: false, chunks: true, chunkModules: true });
+					const cssChunks = json.chunks
+						.map(chunk => chunk.modules.map(module => module.name))
+						.filter(names => names.length > 0 && names.every(name => name.endsWith(".css")))
+						.map(names => names.join(","))
+						.sort();
+					expect(cssChunks).toEqual([
+						"./page1.css",
+						"./page2.css",
+						"./shared/a.css",
+						"./shared/b.css"
+					]);
+				});
+			}
+		}
+	]
+};
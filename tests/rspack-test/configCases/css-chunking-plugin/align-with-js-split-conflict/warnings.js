@@ -0,0 +1,7 @@
+// the conflicting modules are split into different css chunks, so only the css chunking plugin can report it
+module.exports = [
+	[
+		/chunk shared \[rspack\.CssChunkingPlugin\]/,
+		/Conflicting order between \.\/shared\/[ab]\.css and \.\/shared\/[ab]\.css/
+	]
+];
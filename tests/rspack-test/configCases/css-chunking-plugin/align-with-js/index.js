@@ -0,0 +1,2 @@
+import("./page1");
+import("./page2");
@@ -0,0 +1,3 @@
+import "./shared/a.css";
+import "./shared/b.css";
+import "./page1.css";
@@ -0,0 +1,3 @@
+import "./shared/b.css";
+import "./shared/a.css";
+import "./page2.css";
@@ -0,0 +1,55 @@
+const rspack = require("@rspack/core");
+
+/** @type {import("@rspack/core").Configuration} */
+module.exports = {
+	mode: "development",
+	module: {
+		rules: [
+			{
+				test: /\.css$/,
+				type: "css/auto"
+			}
+		]
+	},
+	optimization: {
+		splitChunks: {
+			cacheGroups: {
+				shared: {
+					test: /shared/,
+					enforce: true,
+					name: "shared"
+				}
+			}
+		}
+	},
+	plugins: [
+		new rspack.experiments.CssChunkingPlugin({
+			alignWithJs: true
+		}),
+		{
+			/**
+			 * @param {import("@rspack/core").Compiler} compiler
+			 */
+			apply(compiler) {
+				compiler.hooks.done.tap("PLUGIN", stats => {
+					const json = stats.toJson({ all: false, chunks: true, chunkModules: true });
+					// Test scenario:
+					// - `page1` and `page2` load the `shared` chunk, which imports `a.css` and
+					//   `b.css` in a different order for each page
+					// - The css modules are only grouped with the ones loaded by the same js chunks,
+					//   so the css of the pages is never merged into the css of the `shared` chunk
+					const cssChunks = json.chunks
+						.map(chunk => chunk.modules.map(module => module.name))
+						.filter(names => names.length > 0 && names.every(name => name.endsWith(".css")))
+						.map(names => names.sort().join(","))
+						.sort();
+					expect(cssChunks).toEqual([
+						"./page1.css",
+						"./page2.css",
+						"./shared/a.css,./shared/b.css"
+					]);
+				});
+			}
+		}
+	]
+};
@@ -0,0 +1,4 @@
+// the conflict in the css chunk grouping both modules is only reported once, by the css plugin
+module.exports = [
+	[/Conflicting order between \.\/shared\/[ab]\.css and \.\/shared\/[ab]\.css/]
+];
@@ -0,0 +1,12 @@
+it("should hash with blake3 and xxhash3-128 in js", () => {
+	expect(BLAKE3_HEX).toBe(
+		"6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+	);
+	expect(BLAKE3_BASE62).toBe("nLppJEN38CzScp5Ie5aFYmWDBupROOlRDnfe1FeGYVn");
+	expect(XXHASH3_128_HEX).toBe("06b05ab6733a618578af5f94892f3950");
+	expect(XXHASH3_128_BASE62).toBe("0cCxYZbb4E9heiwwcdgmWs");
+});
+
+it("should use the base62 digest in the chunk hash hooks", () => {
+	expect(__STATS__.hash).toMatch(/^[0-9a-zA-Z]{20}$/);
+});
@@ -0,0 +1,38 @@
+"use strict";
+
+const {
+	DefinePlugin,
+	util: { createHash }
+} = require("@rspack/core");
+
+/** @type {import("@rspack/core").Configuration} */
+module.exports = {
+	output: {
+		hashDigest: "base62",
+		hashDigestLength: 20,
+		hashFunction: "blake3"
+	},
+	plugins: [
+		new DefinePlugin({
+			BLAKE3_HEX: JSON.stringify(
+				createHash("blake3").update("abc").digest("hex")
+			),
+			BLAKE3_BASE62: JSON.stringify(
+				createHash("blake3").update("abc").digest("base62")
+			),
+			XXHASH3_128_HEX: JSON.stringify(
+				createHash("xxhash3-128").update("abc").digest("hex")
+			),
+			XXHASH3_128_BASE62: JSON.stringify(
+				createHash("xxhash3-128").update("abc").digest("base62")
+			)
+		}),
+		compiler => {
+			compiler.hooks.compilation.tap("Test", compilation => {
+				compilation.hooks.chunkHash.tap("Test", (_chunk, hash) => {
+					hash.update("js chunk hash");
+				});
+			});
+		}
+	]
+};
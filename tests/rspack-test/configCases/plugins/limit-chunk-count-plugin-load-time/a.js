@@ -0,0 +1,3 @@
+const value = (await import("./b")).default;
+
+export { value };
@@ -0,0 +1,3 @@
+const value = (await import("./c")).default;
+
+export default value;
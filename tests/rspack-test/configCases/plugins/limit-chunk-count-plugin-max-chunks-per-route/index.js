@@ -0,0 +1,7 @@
+import fs from "fs";
+import path from "path";
+
+it("should merge the runtime chunk into the entry chunk", () => {
+	expect(fs.existsSync(path.join(__dirname, "main.js"))).toBe(true);
+	expect(fs.existsSync(path.join(__dirname, "runtime.js"))).toBe(false);
+});
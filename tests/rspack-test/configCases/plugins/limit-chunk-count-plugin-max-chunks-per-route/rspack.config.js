@@ -0,0 +1,21 @@
+var webpack = require("@rspack/core");
+/** @type {import("@rspack/core").Configuration} */
+module.exports = {
+	node: {
+		__dirname: false,
+		__filename: false
+	},
+	entry: "./index.js",
+	output: {
+		filename: "[name].js"
+	},
+	optimization: {
+		runtimeChunk: "single"
+	},
+	plugins: [
+		new webpack.optimize.LimitChunkCountPlugin({
+			maxChunks: 10,
+			maxChunksPerRoute: 1
+		})
+	]
+};
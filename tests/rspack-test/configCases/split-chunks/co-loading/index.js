@@ -0,0 +1,11 @@
+it("should load the chunks adjusted by the co-loading profile", async () => {
+	const [home, search, settings, profile] = await Promise.all([
+		import(/* webpackChunkName: "home" */ "./home"),
+		import(/* webpackChunkName: "search" */ "./search"),
+		import(/* webpackChunkName: "settings" */ "./settings"),
+		import(/* webpackChunkName: "profile" */ "./profile")
+	]);
+	expect(home.default).toBe("home");
+	expect(search.default).toBe("search");
+	expect(settings.default[1]).toBe(profile.default[1]);
+});
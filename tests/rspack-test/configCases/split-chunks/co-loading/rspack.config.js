@@ -0,0 +1,51 @@
+/** @type {import("@rspack/core").Configuration} */
+module.exports = {
+	mode: "development",
+	entry: "./index.js",
+	output: {
+		filename: "[name].js"
+	},
+	optimization: {
+		splitChunks: {
+			minSize: 0,
+			cacheGroups: {
+				default: false,
+				defaultVendors: false
+			},
+			coLoading: {
+				requestCost: 10,
+				loadSets: [
+					// `home` and `search` are always loaded together, so they are merged
+					{ chunks: ["home", "search"], frequency: 100 },
+					// `settings` and `profile` are mostly loaded apart, so only the module
+					// they share is split into a new chunk
+					{ chunks: ["settings", "profile"], frequency: 10 },
+					{ chunks: ["settings"], frequency: 10 },
+					{ chunks: ["profile"], frequency: 10 }
+				]
+			}
+		}
+	},
+	plugins: [
+		{
+			/**
+			 * @param {import("@rspack/core").Compiler} compiler
+			 */
+			apply(compiler) {
+				compiler.hooks.done.tap("PLUGIN", stats => {
+					const json = stats.toJson({ all: false, chunks: true, chunkModules: true });
+					const asyncChunks = json.chunks
+						.filter(chunk => !chunk.initial)
+						.map(chunk => chunk.modules.map(module => module.name).sort().join(","))
+						.sort();
+					expect(asyncChunks).toEqual([
+						"./home.js,./search.js",
+						"./profile.js",
+						"./settings.js",
+						"./shared.js"
+					]);
+				});
+			}
+		}
+	]
+};
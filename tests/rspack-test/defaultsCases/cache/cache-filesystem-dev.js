@@ -13,6 +13,7 @@ module.exports = {
 			+     "buildDependencies": Array [],
 			+     "portable": undefined,
 			+     "snapshot": Object {
+			+       "contentHashOnly": false,
 			+       "immutablePaths": Array [],
 			+       "managedPaths": Array [
 			+         /[\\\\/]node_modules[\\\\/][^.]/,
@@ -19,6 +19,7 @@ module.exports = {
 			+     "buildDependencies": Array [],
 			+     "portable": undefined,
 			+     "snapshot": Object {
+			+       "contentHashOnly": false,
 			+       "immutablePaths": Array [],
 			+       "managedPaths": Array [
 			+         /[\\\\/]node_modules[\\\\/][^.]/,
@@ -343,6 +343,7 @@ module.exports = {
 			  output: Object {
 			    assetModuleFilename: [hash][ext][query],
 			    asyncChunks: true,
+			    atomicEmit: false,
 			    bundlerInfo: Object {
 			      bundler: rspack,
 			      force: true,
@@ -0,0 +1 @@
+export default __webpack_module__.id;
@@ -0,0 +1,7 @@
+import a from "./a";
+import b from "./b";
+
+it("should revive the recorded module id", () => {
+	expect(String(b)).toBe("777");
+	expect(String(a)).not.toBe("777");
+});
@@ -0,0 +1,7 @@
+import a from "./a";
+import c from "./c";
+
+it("should not reuse the id recorded for the removed module", () => {
+	expect(String(a)).not.toBe("777");
+	expect(String(c)).not.toBe("777");
+});
@@ -0,0 +1,9 @@
+import a from "./a";
+import b from "./b";
+import c from "./c";
+
+it("should keep the id of the module added again", () => {
+	expect(String(b)).toBe("777");
+	expect(String(a)).not.toBe("777");
+	expect(String(c)).not.toBe("777");
+});
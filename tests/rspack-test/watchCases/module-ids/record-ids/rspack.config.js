@@ -0,0 +1,6 @@
+const { experiments } = require("@rspack/core");
+
+/** @type {import('@rspack/core').Configuration} */
+module.exports = {
+	plugins: [new experiments.RecordIdsPlugin({ path: "records.json" })]
+};